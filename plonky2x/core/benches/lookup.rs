@@ -0,0 +1,119 @@
+//! Compares the size of circuits doing byte decompositions with bits and with lookup tables.
+//!
+//! Run with `cargo bench --bench lookup`. Each case builds the same gadget `NUM_OPS` times, once
+//! with the bit decomposition and once with the lookup-based gadget, and reports the degree of
+//! both circuits. The full SHA-256 and RLP circuits, which now use the lookup gadgets, are
+//! reported on their own.
+
+use std::time::Instant;
+
+use plonky2x::prelude::*;
+use plonky2x::utils::setup_logger;
+
+const NUM_OPS: usize = 1024;
+
+/// Builds a circuit with `gadget` applied to `NUM_OPS` fresh inputs and returns its degree bits.
+fn degree_bits(gadget: impl Fn(&mut DefaultBuilder)) -> usize {
+    let mut builder = DefaultBuilder::new();
+    for _ in 0..NUM_OPS {
+        gadget(&mut builder);
+    }
+    let circuit = builder.build();
+    circuit.data.common.degree_bits()
+}
+
+fn compare(name: &str, bits: impl Fn(&mut DefaultBuilder), lookup: impl Fn(&mut DefaultBuilder)) {
+    let start = Instant::now();
+    let bits_degree = degree_bits(bits);
+    let lookup_degree = degree_bits(lookup);
+    println!(
+        "{:<32} bits: 2^{:<3} lookups: 2^{:<3} ({:?})",
+        name,
+        bits_degree,
+        lookup_degree,
+        start.elapsed()
+    );
+}
+
+fn main() {
+    setup_logger();
+    println!("{} operations per circuit", NUM_OPS);
+
+    compare(
+        "32-bit range check",
+        |builder| {
+            let x = builder.init::<Variable>();
+            builder.api.range_check(x.0, 32);
+        },
+        |builder| {
+            let x = builder.init::<Variable>();
+            builder.range_check(x, 32);
+        },
+    );
+
+    compare(
+        "byte xor",
+        |builder| {
+            let a = builder.init::<ByteVariable>();
+            let b = builder.init::<ByteVariable>();
+            builder.xor(a, b);
+        },
+        |builder| {
+            let a = builder.init::<CompactByteVariable>();
+            let b = builder.init::<CompactByteVariable>();
+            builder.xor(a, b);
+        },
+    );
+
+    compare(
+        "byte to nibbles",
+        |builder| {
+            let a = builder.init::<ByteVariable>();
+            a.to_nibbles(builder);
+        },
+        |builder| {
+            let a = builder.init::<CompactByteVariable>();
+            a.to_nibbles(builder);
+        },
+    );
+
+    compare(
+        "SHA-256 last chunk index",
+        |builder| {
+            let length = builder.init::<U32Variable>();
+            let bits = length.to_be_bits(builder);
+            U32Variable::from_be_bits(&bits[..26], builder);
+        },
+        |builder| {
+            let length = builder.init::<U32Variable>();
+            builder.split_low_bits(length.variable, 6, 32);
+        },
+    );
+
+    let start = Instant::now();
+    let mut builder = DefaultBuilder::new();
+    let message = builder.init::<ArrayVariable<ByteVariable, 256>>();
+    let length = builder.init::<U32Variable>();
+    builder.sha256_variable(message.as_slice(), length);
+    let circuit = builder.build();
+    println!(
+        "{:<32} 2^{} ({:?})",
+        "sha256_variable, 256 bytes",
+        circuit.data.common.degree_bits(),
+        start.elapsed()
+    );
+
+    let start = Instant::now();
+    let mut builder = DefaultBuilder::new();
+    let encoding = builder.init::<ArrayVariable<ByteVariable, 600>>();
+    let length = builder.init::<Variable>();
+    let finish = builder.init::<BoolVariable>();
+    builder.decode_element_as_list::<600, 17, 34>(encoding, length, finish);
+    let circuit = builder.build();
+    println!(
+        "{:<32} 2^{} ({:?})",
+        "decode_element_as_list, 17 x 34",
+        circuit.data.common.degree_bits(),
+        start.elapsed()
+    );
+}
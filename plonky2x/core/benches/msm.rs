@@ -0,0 +1,135 @@
+//! Compares the number of gates of the multi-scalar multiplication gadgets.
+//!
+//! Run with `cargo bench --bench msm`. For each curve and number of points, the same sum of
+//! scalar multiplications is computed with one double-and-add per point (before), with Straus'
+//! method and with Pippenger's bucket method, and the fixed-base multiplication of the generator
+//! is compared to a double-and-add of the generator.
+
+use std::time::Instant;
+
+use plonky2::iop::target::BoolTarget;
+use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2x::frontend::ecc::ed25519::curve::curve_types::Curve;
+use plonky2x::frontend::ecc::ed25519::curve::ed25519::Ed25519;
+use plonky2x::frontend::ecc::ed25519::gadgets::curve::CircuitBuilderCurve;
+use plonky2x::frontend::ecc::msm::edwards::TwistedEdwards;
+use plonky2x::frontend::ecc::msm::weierstrass::{
+    ShortWeierstrass, WeierstrassCurve, WeierstrassPointTarget,
+};
+use plonky2x::frontend::ecc::msm::{CircuitBuilderMsm, CircuitGroupLaw};
+use plonky2x::frontend::ecc::secp256k1::Secp256K1;
+use plonky2x::frontend::num::nonnative::nonnative::CircuitBuilderNonNative;
+use plonky2x::prelude::GoldilocksField;
+
+const D: usize = 2;
+type F = GoldilocksField;
+
+const NB_BITS: usize = 256;
+const NB_POINTS: [usize; 4] = [1, 2, 4, 8];
+
+/// Computes `sum_i scalars[i] * points[i]` with one double-and-add per point.
+fn double_and_add<G: CircuitGroupLaw<F, D>>(
+    builder: &mut BaseCircuitBuilder<F, D>,
+    points: &[G::PointTarget],
+    scalars_bits_le: &[Vec<BoolTarget>],
+) -> G::PointTarget {
+    let mut result = G::constant_point(builder, G::neutral());
+    for (p, bits) in points.iter().zip(scalars_bits_le.iter()) {
+        let mut doubled = p.clone();
+        for (i, bit) in bits.iter().enumerate() {
+            let sum = G::add_points(builder, &result, &doubled);
+            result = G::select_point(builder, *bit, &sum, &result);
+            if i + 1 < bits.len() {
+                doubled = G::double_point(builder, &doubled);
+            }
+        }
+    }
+    result
+}
+
+/// Returns the number of gates added by `gadget`.
+fn count_gates<T>(
+    builder: &mut BaseCircuitBuilder<F, D>,
+    gadget: impl FnOnce(&mut BaseCircuitBuilder<F, D>) -> T,
+) -> usize {
+    let gates_before = builder.num_gates();
+    gadget(builder);
+    builder.num_gates() - gates_before
+}
+
+fn scalars_bits(builder: &mut BaseCircuitBuilder<F, D>, nb_points: usize) -> Vec<Vec<BoolTarget>> {
+    (0..nb_points)
+        .map(|_| {
+            (0..NB_BITS)
+                .map(|_| builder.add_virtual_bool_target_safe())
+                .collect()
+        })
+        .collect()
+}
+
+fn compare<G: CircuitGroupLaw<F, D>>(
+    name: &str,
+    virtual_point: impl Fn(&mut BaseCircuitBuilder<F, D>) -> G::PointTarget,
+    generator: G::Point,
+) {
+    for nb_points in NB_POINTS {
+        let start = Instant::now();
+        let mut builder = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
+        let points = (0..nb_points)
+            .map(|_| virtual_point(&mut builder))
+            .collect::<Vec<_>>();
+        let bits = scalars_bits(&mut builder, nb_points);
+
+        let naive = count_gates(&mut builder, |b| double_and_add::<G>(b, &points, &bits));
+        let straus = count_gates(&mut builder, |b| b.curve_msm::<G>(&points, &bits));
+        let pippenger = count_gates(&mut builder, |b| b.curve_msm_pippenger::<G>(&points, &bits));
+        println!(
+            "{} msm of {:>2} points: {:>8} double-and-add, {:>8} straus, {:>8} pippenger ({:?})",
+            name,
+            nb_points,
+            naive,
+            straus,
+            pippenger,
+            start.elapsed()
+        );
+    }
+
+    let start = Instant::now();
+    let mut builder = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
+    let bits = scalars_bits(&mut builder, 1);
+    let naive = count_gates(&mut builder, |b| {
+        let g = G::constant_point(b, generator);
+        double_and_add::<G>(b, &[g], &bits)
+    });
+    let fixed_base = count_gates(&mut builder, |b| {
+        b.curve_fixed_base_scalar_mul::<G>(generator, &bits[0])
+    });
+    println!(
+        "{} generator multiplication: {:>8} double-and-add, {:>8} fixed base ({:?})",
+        name,
+        naive,
+        fixed_base,
+        start.elapsed()
+    );
+}
+
+fn main() {
+    println!("{} bit scalars", NB_BITS);
+
+    compare::<TwistedEdwards<Ed25519>>(
+        "ed25519",
+        |builder| builder.add_virtual_affine_point_target(),
+        Ed25519::GENERATOR_PROJECTIVE,
+    );
+
+    compare::<ShortWeierstrass<Secp256K1>>(
+        "secp256k1",
+        |builder| WeierstrassPointTarget {
+            x: builder.add_virtual_nonnative_target(),
+            y: builder.add_virtual_nonnative_target(),
+            z: builder.add_virtual_nonnative_target(),
+        },
+        Secp256K1::GENERATOR,
+    );
+}
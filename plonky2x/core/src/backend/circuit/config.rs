@@ -9,6 +9,7 @@ use plonky2::hash::hash_types::RichField;
 use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
 use serde::{Deserialize, Serialize};
 
+use crate::backend::circuit::poseidon2_config::Poseidon2GoldilocksConfig;
 use crate::backend::wrapper::plonky2_config::PoseidonBN128GoldilocksConfig;
 
 /// Parameters such as the field, hash function, etc. used for the circuit.
@@ -56,3 +57,18 @@ impl PlonkParameters<2> for Groth16WrapperParameters {
 
     type CurtaConfig = CurtaPoseidonGoldilocksConfig;
 }
+
+/// Parameters using Poseidon2 for the FRI Merkle trees and Fiat-Shamir. Uses the
+/// `Poseidon2GoldilocksConfig`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Poseidon2Parameters;
+
+impl PlonkParameters<2> for Poseidon2Parameters {
+    type Field = GoldilocksField;
+
+    type CubicParams = GoldilocksCubicParameters;
+
+    type Config = Poseidon2GoldilocksConfig;
+
+    type CurtaConfig = CurtaPoseidonGoldilocksConfig;
+}
@@ -3,6 +3,7 @@ pub mod config;
 mod input;
 mod mock;
 mod output;
+pub mod poseidon2_config;
 mod serialization;
 mod witness;
 
@@ -11,7 +12,9 @@ use core::fmt::Debug;
 use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 
 pub use self::build::CircuitBuild;
-pub use self::config::{DefaultParameters, Groth16WrapperParameters, PlonkParameters};
+pub use self::config::{
+    DefaultParameters, Groth16WrapperParameters, PlonkParameters, Poseidon2Parameters,
+};
 pub use self::input::PublicInput;
 pub use self::mock::MockCircuitBuild;
 pub use self::output::PublicOutput;
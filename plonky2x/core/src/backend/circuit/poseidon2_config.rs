@@ -0,0 +1,201 @@
+use core::fmt::Debug;
+
+use plonky2::field::extension::quadratic::QuadraticExtension;
+use plonky2::field::extension::Extendable;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::hash::hash_types::{HashOut, RichField};
+use plonky2::hash::hashing::{compress, hash_n_to_hash_no_pad, PlonkyPermutation};
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig, Hasher};
+use serde::Serialize;
+
+use crate::frontend::hash::poseidon::poseidon2::{
+    poseidon2_permute_circuit, POSEIDON2_PARAMS, POSEIDON2_RATE, POSEIDON2_WIDTH,
+};
+
+/// Configuration using Poseidon2 over the Goldilocks field for both the FRI Merkle trees and the
+/// Fiat-Shamir transcript.
+///
+/// The in-circuit permutation is built from arithmetic gates rather than a dedicated gate, so
+/// recursive verification is more expensive than with Poseidon.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct Poseidon2GoldilocksConfig;
+impl GenericConfig<2> for Poseidon2GoldilocksConfig {
+    type F = GoldilocksField;
+    type FE = QuadraticExtension<Self::F>;
+    type Hasher = Poseidon2Hash;
+    type InnerHasher = Poseidon2Hash;
+}
+
+/// A type that the Poseidon2 permutation can be applied to natively.
+pub trait Poseidon2Permuter: Sized {
+    fn permute(input: [Self; POSEIDON2_WIDTH]) -> [Self; POSEIDON2_WIDTH];
+}
+
+impl Poseidon2Permuter for GoldilocksField {
+    fn permute(mut input: [Self; POSEIDON2_WIDTH]) -> [Self; POSEIDON2_WIDTH] {
+        POSEIDON2_PARAMS.permute(&mut input);
+        input
+    }
+}
+
+impl Poseidon2Permuter for Target {
+    fn permute(_input: [Self; POSEIDON2_WIDTH]) -> [Self; POSEIDON2_WIDTH] {
+        panic!("Call `permute_swapped()` on `Poseidon2Hash` instead");
+    }
+}
+
+/// The Poseidon2 sponge state.
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct Poseidon2Permutation<T> {
+    state: [T; POSEIDON2_WIDTH],
+}
+
+impl<T: Eq> Eq for Poseidon2Permutation<T> {}
+
+impl<T> AsRef<[T]> for Poseidon2Permutation<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.state
+    }
+}
+
+impl<T: Copy + Debug + Default + Eq + Poseidon2Permuter + Send + Sync> PlonkyPermutation<T>
+    for Poseidon2Permutation<T>
+{
+    const RATE: usize = POSEIDON2_RATE;
+    const WIDTH: usize = POSEIDON2_WIDTH;
+
+    fn new<I: IntoIterator<Item = T>>(elts: I) -> Self {
+        let mut perm = Self {
+            state: [T::default(); POSEIDON2_WIDTH],
+        };
+        perm.set_from_iter(elts, 0);
+        perm
+    }
+
+    fn set_elt(&mut self, elt: T, idx: usize) {
+        self.state[idx] = elt;
+    }
+
+    fn set_from_iter<I: IntoIterator<Item = T>>(&mut self, elts: I, start_idx: usize) {
+        for (s, e) in self.state[start_idx..].iter_mut().zip(elts) {
+            *s = e;
+        }
+    }
+
+    fn set_from_slice(&mut self, elts: &[T], start_idx: usize) {
+        let begin = start_idx;
+        let end = start_idx + elts.len();
+        self.state[begin..end].copy_from_slice(elts);
+    }
+
+    fn permute(&mut self) {
+        self.state = T::permute(self.state);
+    }
+
+    fn squeeze(&self) -> &[T] {
+        &self.state[..Self::RATE]
+    }
+}
+
+/// Poseidon2 hash function over the Goldilocks field.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Poseidon2Hash;
+impl Hasher<GoldilocksField> for Poseidon2Hash {
+    const HASH_SIZE: usize = 4 * 8;
+    type Hash = HashOut<GoldilocksField>;
+    type Permutation = Poseidon2Permutation<GoldilocksField>;
+
+    fn hash_no_pad(input: &[GoldilocksField]) -> Self::Hash {
+        hash_n_to_hash_no_pad::<GoldilocksField, Self::Permutation>(input)
+    }
+
+    fn two_to_one(left: Self::Hash, right: Self::Hash) -> Self::Hash {
+        compress::<GoldilocksField, Self::Permutation>(left, right)
+    }
+}
+
+impl AlgebraicHasher<GoldilocksField> for Poseidon2Hash {
+    type AlgebraicPermutation = Poseidon2Permutation<Target>;
+
+    fn permute_swapped<const D: usize>(
+        inputs: Self::AlgebraicPermutation,
+        swap: BoolTarget,
+        builder: &mut BaseCircuitBuilder<GoldilocksField, D>,
+    ) -> Self::AlgebraicPermutation
+    where
+        GoldilocksField: RichField + Extendable<D>,
+    {
+        // Swapping the first two quarters of the state puts a Merkle sibling on the correct side.
+        let mut state: [Target; POSEIDON2_WIDTH] = inputs.as_ref().try_into().unwrap();
+        let (left, right) = (state[..4].to_vec(), state[4..8].to_vec());
+        for i in 0..4 {
+            state[i] = builder.select(swap, right[i], left[i]);
+            state[i + 4] = builder.select(swap, left[i], right[i]);
+        }
+
+        let output = poseidon2_permute_circuit(builder, &POSEIDON2_PARAMS, state);
+        Self::AlgebraicPermutation::new(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::{Field, Sample};
+    use plonky2::plonk::config::Hasher;
+
+    use super::Poseidon2Hash;
+    use crate::backend::circuit::Poseidon2Parameters;
+    use crate::frontend::hash::poseidon::poseidon2::POSEIDON2_PARAMS;
+    use crate::prelude::{CircuitBuilder, GoldilocksField, Variable};
+    use crate::utils;
+    use crate::utils::proof::ProofWithPublicInputsTargetUtils;
+
+    type F = GoldilocksField;
+    type L = Poseidon2Parameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_poseidon2_hash_no_pad() {
+        let inputs = F::rand_vec(11);
+        assert_eq!(
+            Poseidon2Hash::hash_no_pad(&inputs).elements,
+            POSEIDON2_PARAMS.hash_no_pad(&inputs)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_poseidon2_parameters_recursion() {
+        utils::setup_logger();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let a = builder.read::<Variable>();
+        let b = builder.read::<Variable>();
+        let c = builder.add(a, b);
+        builder.write(c);
+        let inner_circuit = builder.build();
+
+        let mut input = inner_circuit.input();
+        input.write::<Variable>(F::from_canonical_u64(3));
+        input.write::<Variable>(F::from_canonical_u64(4));
+        let (inner_proof, output) = inner_circuit.prove(&input);
+        inner_circuit.verify(&inner_proof, &input, &output);
+
+        // The recursive verifier hashes with the in-circuit Poseidon2 permutation.
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let verifier_data = builder.constant_verifier_data::<L>(&inner_circuit.data);
+        let proof = builder.proof_read(&inner_circuit);
+        builder.verify_proof::<L>(&proof, &verifier_data, &inner_circuit.data.common);
+        let c = proof.read_end_from_pis::<Variable>();
+        builder.proof_write(c);
+        let outer_circuit = builder.build();
+
+        let mut input = outer_circuit.input();
+        input.proof_write(inner_proof);
+        let (proof, mut output) = outer_circuit.prove(&input);
+        outer_circuit.verify(&proof, &input, &output);
+        assert_eq!(output.proof_read::<Variable>(), F::from_canonical_u64(7));
+    }
+}
@@ -42,8 +42,12 @@ use plonky2::util::serialization::{Buffer, IoResult, Read, WitnessGeneratorSeria
 
 use super::registry::{SerializationRegistry, Serializer};
 use super::PlonkParameters;
+use crate::frontend::builder::lookup::{ByteDecompositionHint, LowBitsDecompositionHint};
 use crate::frontend::builder::watch::WatchGenerator;
+use crate::frontend::curta::ec::accelerator::hint::EcHint;
+use crate::frontend::ecc::ed25519::curve::ed25519::Ed25519;
 use crate::frontend::ecc::ed25519::field::ed25519_base::Ed25519Base;
+use crate::frontend::ecc::ed25519::gadgets::curve::DecompressPointHint;
 use crate::frontend::eth::beacon::generators::{
     BeaconAllWithdrawalsHint, BeaconBalanceBatchWitnessHint, BeaconBalanceGenerator,
     BeaconBalanceWitnessHint, BeaconBalancesGenerator, BeaconBlockRootsHint,
@@ -63,8 +67,7 @@ use crate::frontend::eth::storage::generators::{
 };
 use crate::frontend::hash::blake2::curta::MAX_NUM_CURTA_CHUNKS;
 use crate::frontend::hash::deprecated::bit_operations::XOR3Generator;
-use crate::frontend::hash::keccak::keccak256::Keccak256Generator;
-use crate::frontend::hash::sha::curta::digest_hint::SHADigestHint;
+use crate::frontend::hash::sha::curta::digest_hint::{SHAChainingValueHint, SHADigestHint};
 use crate::frontend::hash::sha::curta::proof_hint::SHAProofHint;
 use crate::frontend::hint::asynchronous::generator::{AsyncHintDataRef, AsyncHintRef};
 use crate::frontend::hint::asynchronous::hint::AsyncHint;
@@ -82,6 +85,8 @@ use crate::frontend::num::u32::gates::arithmetic_u32::U32ArithmeticGenerator;
 use crate::frontend::num::u32::gates::comparison::ComparisonGenerator;
 use crate::frontend::num::u32::gates::range_check_u32::U32RangeCheckGenerator;
 use crate::frontend::num::u32::gates::subtraction_u32::U32SubtractionGenerator;
+use crate::frontend::uint::modular::InvModHint;
+use crate::frontend::uint::sqrt::BigUintSqrtHint;
 use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::{Bytes32Variable, SubArrayExtractorHint, U256Variable};
 use crate::prelude::{ArrayVariable, BoolVariable, U32Variable, Variable};
@@ -315,9 +320,6 @@ where
         let eth_storage_key_generator_id = EthStorageKeyGenerator::<L, D>::id();
         r.register_simple::<EthStorageKeyGenerator<L, D>>(eth_storage_key_generator_id);
 
-        let keccak256_generator_id = Keccak256Generator::<L, D>::id();
-        r.register_simple::<Keccak256Generator<L, D>>(keccak256_generator_id);
-
         let beacon_balance_generator_id = BeaconBalanceGenerator::<L, D>::id();
         r.register_simple::<BeaconBalanceGenerator<L, D>>(beacon_balance_generator_id);
 
@@ -441,6 +443,14 @@ where
 
         r.register_hint::<SubArrayExtractorHint>();
 
+        r.register_hint::<BigUintSqrtHint>();
+        r.register_hint::<ByteDecompositionHint>();
+        r.register_hint::<LowBitsDecompositionHint>();
+        r.register_hint::<InvModHint>();
+
+        r.register_hint::<EcHint>();
+        r.register_hint::<DecompressPointHint<Ed25519>>();
+
         r.register_hint::<BeaconBlockRootsHint>();
 
         r.register_hint::<BeaconGraffitiHint>();
@@ -448,6 +458,9 @@ where
         r.register_hint::<SHADigestHint<SHA256, 64>>();
         r.register_async_hint::<Async<SHADigestHint<SHA256, 64>>>();
 
+        r.register_hint::<SHAChainingValueHint<SHA256, 64>>();
+        r.register_async_hint::<Async<SHAChainingValueHint<SHA256, 64>>>();
+
         r.register_hint::<SHAProofHint<SHA256, 64>>();
         r.register_async_hint::<Async<SHAProofHint<SHA256, 64>>>();
 
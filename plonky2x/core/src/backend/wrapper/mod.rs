@@ -1,5 +1,5 @@
 pub mod plonky2_config;
 pub mod poseidon_bn128;
-mod poseidon_bn128_constants;
+pub(crate) mod poseidon_bn128_constants;
 pub mod utils;
 pub mod wrap;
@@ -0,0 +1,321 @@
+//! Range checks and byte operations backed by plonky2 lookup tables.
+//!
+//! Every table is added to the circuit the first time it is used, so a circuit only pays for the
+//! tables it needs. The range tables have at most 256 rows, while the XOR and AND tables have
+//! 65536 rows, a fixed cost of a few thousand gates that is amortized over many byte operations.
+
+use alloc::sync::Arc;
+
+use plonky2::field::types::{Field, PrimeField64};
+use plonky2::gates::lookup_table::LookupTable;
+use plonky2::iop::target::Target;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::vars::{CompactByteVariable, ValueStream, Variable, VariableStream};
+use crate::prelude::CircuitVariable;
+
+/// The lookup tables used by the byte gadgets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ByteTable {
+    /// The identity on `[0, 2^bits)`, for `bits <= 8`.
+    Range(usize),
+    /// Maps `256 * a + b` to `a ^ b`.
+    Xor,
+    /// Maps `256 * a + b` to `a & b`.
+    And,
+    /// Maps a byte to its high nibble.
+    HighNibble,
+}
+
+impl ByteTable {
+    fn pairs(&self) -> LookupTable {
+        let pairs = match self {
+            ByteTable::Range(bits) => (0..1u16 << bits).map(|x| (x, x)).collect(),
+            ByteTable::Xor => (0..=u16::MAX).map(|x| (x, (x >> 8) ^ (x & 0xff))).collect(),
+            ByteTable::And => (0..=u16::MAX).map(|x| (x, (x >> 8) & (x & 0xff))).collect(),
+            ByteTable::HighNibble => (0..256u16).map(|x| (x, x >> 4)).collect(),
+        };
+        Arc::new(pairs)
+    }
+}
+
+/// Splits a field element into little-endian bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ByteDecompositionHint {
+    num_bytes: usize,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for ByteDecompositionHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let value = input_stream.read_value::<Variable>().to_canonical_u64();
+        for i in 0..self.num_bytes {
+            let byte = (value >> (8 * i)) & 0xff;
+            output_stream.write_value::<Variable>(L::Field::from_canonical_u64(byte));
+        }
+    }
+}
+
+/// Splits a field element into its value shifted right by `low_bits` and its low `low_bits` bits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LowBitsDecompositionHint {
+    low_bits: usize,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for LowBitsDecompositionHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let value = input_stream.read_value::<Variable>().to_canonical_u64();
+        let high = value >> self.low_bits;
+        let low = value & ((1 << self.low_bits) - 1);
+        output_stream.write_value::<Variable>(L::Field::from_canonical_u64(high));
+        output_stream.write_value::<Variable>(L::Field::from_canonical_u64(low));
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Looks up `input` in `table`, adding the table to the circuit if it is not there yet.
+    pub(crate) fn lookup(&mut self, table: ByteTable, input: Target) -> Target {
+        let index = match self.lookup_tables.get(&table) {
+            Some(index) => *index,
+            None => {
+                let index = self.api.add_lookup_table_from_pairs(table.pairs());
+                self.lookup_tables.insert(table, index);
+                index
+            }
+        };
+        self.api.add_lookup_from_index(input, index)
+    }
+
+    /// Looks up the pair `(a, b)` of bytes in a two-input byte table.
+    pub(crate) fn lookup_byte_pair(
+        &mut self,
+        table: ByteTable,
+        a: CompactByteVariable,
+        b: CompactByteVariable,
+    ) -> CompactByteVariable {
+        let input = self
+            .api
+            .mul_const_add(L::Field::from_canonical_u64(256), a.0 .0, b.0 .0);
+        CompactByteVariable(Variable(self.lookup(table, input)))
+    }
+
+    /// Decomposes `variable` into little-endian bytes, where the last byte holds the top
+    /// `bits % 8` bits, and checks that the value fits in `bits` bits.
+    fn decompose_bytes_le(&mut self, variable: Variable, bits: usize) -> Vec<Variable> {
+        assert!(bits < 64, "the decomposition is only unique below 64 bits");
+        let num_bytes = bits.div_ceil(8);
+
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&variable);
+        let output_stream = self.hint(input_stream, ByteDecompositionHint { num_bytes });
+        let bytes = output_stream.read_vec::<Variable>(self, num_bytes);
+
+        let mut acc = self.api.zero();
+        for (i, byte) in bytes.iter().enumerate() {
+            let width = (bits - 8 * i).min(8);
+            self.lookup(ByteTable::Range(width), byte.0);
+            acc = self
+                .api
+                .mul_const_add(L::Field::from_canonical_u64(1 << (8 * i)), byte.0, acc);
+        }
+        self.api.connect(acc, variable.0);
+
+        bytes
+    }
+
+    /// Checks that `variable` is less than `2^bits` using byte lookups, for `bits < 64`.
+    pub fn range_check(&mut self, variable: Variable, bits: usize) {
+        if bits <= 8 {
+            self.lookup(ByteTable::Range(bits), variable.0);
+        } else {
+            self.decompose_bytes_le(variable, bits);
+        }
+    }
+
+    /// Splits `variable`, which is checked to be less than `2^bits`, into `variable >> low_bits`
+    /// and `variable mod 2^low_bits`, range-checking both halves with byte lookups.
+    pub fn split_low_bits(
+        &mut self,
+        variable: Variable,
+        low_bits: usize,
+        bits: usize,
+    ) -> (Variable, Variable) {
+        assert!(
+            0 < low_bits && low_bits < bits && bits < 64,
+            "the split is only unique below 64 bits"
+        );
+
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&variable);
+        let output_stream = self.hint(input_stream, LowBitsDecompositionHint { low_bits });
+        let high = output_stream.read::<Variable>(self);
+        let low = output_stream.read::<Variable>(self);
+
+        self.range_check(high, bits - low_bits);
+        self.range_check(low, low_bits);
+        let acc =
+            self.api
+                .mul_const_add(L::Field::from_canonical_u64(1 << low_bits), high.0, low.0);
+        self.api.connect(acc, variable.0);
+
+        (high, low)
+    }
+
+    /// Packs at most seven little-endian bytes into a single field element.
+    pub fn pack_bytes_le(&mut self, bytes: &[CompactByteVariable]) -> Variable {
+        assert!(
+            bytes.len() < 8,
+            "at most seven bytes fit in a field element"
+        );
+        let mut acc = self.api.zero();
+        for (i, byte) in bytes.iter().enumerate() {
+            acc =
+                self.api
+                    .mul_const_add(L::Field::from_canonical_u64(1 << (8 * i)), byte.0 .0, acc);
+        }
+        Variable(acc)
+    }
+
+    /// Unpacks a field element into `num_bytes < 8` little-endian bytes, checking that it fits.
+    pub fn unpack_bytes_le(
+        &mut self,
+        variable: Variable,
+        num_bytes: usize,
+    ) -> Vec<CompactByteVariable> {
+        assert!(num_bytes < 8, "at most seven bytes fit in a field element");
+        self.decompose_bytes_le(variable, 8 * num_bytes)
+            .into_iter()
+            .map(CompactByteVariable)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::debug;
+
+    use crate::prelude::*;
+    use crate::utils;
+
+    #[test]
+    fn test_range_check() {
+        let cases = [
+            (0u64, 0usize),
+            (1, 1),
+            (200, 8),
+            (256, 9),
+            (4095, 12),
+            (65535, 16),
+            (u32::MAX as u64, 32),
+            ((1 << 63) - 1, 63),
+        ];
+
+        let mut builder = DefaultBuilder::new();
+        for (_, bits) in cases.iter() {
+            let variable = builder.read::<Variable>();
+            builder.range_check(variable, *bits);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for (value, _) in cases.iter() {
+            input.write::<Variable>(GoldilocksField::from_canonical_u64(*value));
+        }
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_check_out_of_range() {
+        let mut builder = DefaultBuilder::new();
+        let variable = builder.read::<Variable>();
+        builder.range_check(variable, 12);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Variable>(GoldilocksField::from_canonical_u64(5000));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_split_low_bits() {
+        let value = 0xdead_beefu64;
+
+        let mut builder = DefaultBuilder::new();
+        let variable = builder.read::<Variable>();
+        let (high, low) = builder.split_low_bits(variable, 6, 32);
+        builder.write(high);
+        builder.write(low);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Variable>(GoldilocksField::from_canonical_u64(value));
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<Variable>(),
+            GoldilocksField::from_canonical_u64(value >> 6)
+        );
+        assert_eq!(
+            output.read::<Variable>(),
+            GoldilocksField::from_canonical_u64(value & 0x3f)
+        );
+    }
+
+    #[test]
+    fn test_pack_unpack_bytes() {
+        let value = 0x0011_2233_4455_6677u64;
+
+        let mut builder = DefaultBuilder::new();
+        let variable = builder.read::<Variable>();
+        let bytes = builder.unpack_bytes_le(variable, 7);
+        let packed = builder.pack_bytes_le(&bytes);
+        builder.assert_is_equal(packed, variable);
+        for byte in bytes {
+            builder.write(byte);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Variable>(GoldilocksField::from_canonical_u64(value));
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for i in 0..7 {
+            let byte = output.read::<CompactByteVariable>();
+            assert_eq!(byte, (value >> (8 * i)) as u8);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_compact_bytes_degree() {
+        utils::setup_logger();
+        const NUM_BYTES: usize = 4096;
+
+        let mut builder = DefaultBuilder::new();
+        for _ in 0..NUM_BYTES {
+            builder.init::<ByteVariable>();
+        }
+        let bits_circuit = builder.build();
+
+        let mut builder = DefaultBuilder::new();
+        for _ in 0..NUM_BYTES {
+            builder.init::<CompactByteVariable>();
+        }
+        let compact_circuit = builder.build();
+
+        let bits_degree = bits_circuit.data.common.degree_bits();
+        let compact_degree = compact_circuit.data.common.degree_bits();
+        debug!(
+            "degree bits for {} bytes: {} as bits, {} as compact bytes",
+            NUM_BYTES, bits_degree, compact_degree
+        );
+        assert!(compact_degree < bits_degree);
+    }
+}
@@ -1,5 +1,6 @@
 mod boolean;
 pub mod io;
+pub mod lookup;
 pub mod permutation;
 mod proof;
 pub mod watch;
@@ -21,7 +22,11 @@ use plonky2::plonk::circuit_data::CircuitConfig;
 use tokio::runtime::Runtime;
 
 pub use self::io::CircuitIO;
+use self::lookup::ByteTable;
+use super::curta::ec::accelerator::EcAccelerator;
+use super::hash::blake2::blake2s::Blake2sAccelerator;
 use super::hash::blake2::curta::Blake2bAccelerator;
+use super::hash::blake3::Blake3Accelerator;
 use super::hash::sha::sha256::curta::SHA256Accelerator;
 use super::hash::sha::sha512::curta::SHA512Accelerator;
 use super::hint::HintGenerator;
@@ -46,10 +51,14 @@ pub struct CircuitBuilder<L: PlonkParameters<D>, const D: usize> {
     pub(crate) hints: Vec<Box<dyn HintGenerator<L, D>>>,
     pub(crate) async_hints: Vec<AsyncHintDataRef<L, D>>,
     pub(crate) async_hints_indices: Vec<usize>,
+    pub(crate) lookup_tables: BTreeMap<ByteTable, usize>,
 
     pub blake2b_accelerator: Option<Blake2bAccelerator<L, D>>,
+    pub blake2s_accelerator: Option<Blake2sAccelerator<L, D>>,
+    pub blake3_accelerator: Option<Blake3Accelerator<L, D>>,
     pub sha256_accelerator: Option<SHA256Accelerator>,
     pub sha512_accelerator: Option<SHA512Accelerator>,
+    pub ec_accelerator: Option<EcAccelerator>,
 }
 
 /// The universal api for building circuits using `plonky2x` with default parameters.
@@ -79,9 +88,13 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             hints: Vec::new(),
             async_hints: Vec::new(),
             async_hints_indices: Vec::new(),
+            lookup_tables: BTreeMap::new(),
             blake2b_accelerator: None,
+            blake2s_accelerator: None,
+            blake3_accelerator: None,
             sha256_accelerator: None,
             sha512_accelerator: None,
+            ec_accelerator: None,
         };
 
         if let Ok(rpc_url) = env::var("CONSENSUS_RPC_1") {
@@ -148,6 +161,16 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             accelerator.build(self);
         }
 
+        let blake2s_accelerator = self.blake2s_accelerator.clone();
+        if let Some(accelerator) = blake2s_accelerator {
+            accelerator.build(self);
+        }
+
+        let blake3_accelerator = self.blake3_accelerator.clone();
+        if let Some(accelerator) = blake3_accelerator {
+            accelerator.build(self);
+        }
+
         let sha256_accelerator = self.sha256_accelerator.clone();
         if let Some(accelerator) = sha256_accelerator {
             self.curta_constrain_sha::<SHA256, 64>(accelerator);
@@ -158,6 +181,11 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             self.curta_constrain_sha::<SHA512, 80>(accelerator);
         }
 
+        let ec_accelerator = self.ec_accelerator.clone();
+        if let Some(accelerator) = ec_accelerator {
+            self.curta_constrain_ec(accelerator);
+        }
+
         for (index, gen_ref) in self
             .async_hints_indices
             .iter()
@@ -0,0 +1,431 @@
+use core::marker::PhantomData;
+
+use curta::chip::arithmetic::expression::ArithmeticExpression;
+use curta::chip::builder::AirBuilder;
+use curta::chip::ec::edwards::ed25519::Ed25519;
+use curta::chip::ec::point::AffinePointRegister;
+use curta::chip::ec::weierstrass::bn254::Bn254;
+use curta::chip::field::instruction::FromFieldInstruction;
+use curta::chip::field::parameters::FieldParameters;
+use curta::chip::field::register::FieldRegister;
+use curta::chip::register::array::ArrayRegister;
+use curta::chip::register::bit::BitRegister;
+use curta::chip::register::cubic::CubicRegister;
+use curta::chip::register::element::ElementRegister;
+use curta::chip::register::{Register, RegisterSized};
+use curta::chip::trace::data::AirTraceData;
+use curta::chip::{AirParameters, Chip};
+use curta::math::prelude::{CubicParameters, *};
+use serde::{Deserialize, Serialize};
+
+use super::curve::EcAirCurve;
+use super::instruction::EcInstruction;
+use crate::frontend::curta::ec::secp256k1::Secp256k1;
+
+/// The minimum number of rows of the EC STARK, which is the size of the table range checking the
+/// 16-bit limbs of the field elements.
+pub const EC_AIR_MIN_NUM_ROWS_DEGREE: usize = 16;
+
+/// The parameters of an [`EcAir`] proving `NUM_CURVES` curves. The columns are upper bounds for
+/// the registers and field operations of each curve: about 50 field operations per row for the
+/// short Weierstrass curves and 35 for ed25519.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcAirParameters<F: Field, R: CubicParameters<F>, const NUM_CURVES: usize>(
+    pub PhantomData<(F, R)>,
+);
+
+impl<F: PrimeField64, R: CubicParameters<F>, const NUM_CURVES: usize> AirParameters
+    for EcAirParameters<F, R, NUM_CURVES>
+{
+    type Field = F;
+    type CubicParams = R;
+
+    const NUM_ARITHMETIC_COLUMNS: usize = 5500 * NUM_CURVES;
+    const NUM_FREE_COLUMNS: usize = 4 + 6 * NUM_CURVES;
+    const EXTENDED_COLUMNS: usize = 8400 * NUM_CURVES;
+
+    type Instruction = EcInstruction;
+}
+
+/// An operation of the [`EcAir`], with the only information the AIR needs about it: the number
+/// of rows it takes.
+///
+/// Each row of a curve holds an accumulator `acc`, a point `m` and a bit `b`, and computes
+/// `acc + b * m` and `2 * m`, which become the accumulator and point of the next row. An operation
+/// sets the accumulator and point of its first row, and its result is the accumulator after its
+/// last row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EcOp {
+    /// `a + b`, in one row with `acc = a`, `m = b` and `b = 1`.
+    Add,
+    /// `2 * p`, in two rows starting from the neutral element and `m = p`, with bits `0, 1`.
+    Double,
+    /// `k * p`, in one row per bit of `k`, starting from the neutral element and `m = p`.
+    ScalarMul { num_bits: usize },
+    /// `sum_i b_i * p_i`, in one row per point. The point of every row after the first is loaded
+    /// from the public inputs instead of doubling the previous one.
+    Sum { num_points: usize },
+}
+
+impl EcOp {
+    pub fn num_rows(&self) -> usize {
+        match *self {
+            EcOp::Add => 1,
+            EcOp::Double => 2,
+            EcOp::ScalarMul { num_bits } => num_bits,
+            EcOp::Sum { num_points } => num_points,
+        }
+    }
+
+    /// The number of points loaded from the public inputs after the first row.
+    pub fn num_loads(&self) -> usize {
+        match *self {
+            EcOp::Sum { num_points } => num_points - 1,
+            _ => 0,
+        }
+    }
+}
+
+/// The operations of each curve in an [`EcAir`]. A curve without operations is left out of the
+/// AIR.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EcAirShape {
+    pub ed25519: Vec<EcOp>,
+    pub secp256k1: Vec<EcOp>,
+    pub bn254: Vec<EcOp>,
+}
+
+impl EcAirShape {
+    pub fn num_curves(&self) -> usize {
+        [&self.ed25519, &self.secp256k1, &self.bn254]
+            .iter()
+            .filter(|ops| !ops.is_empty())
+            .count()
+    }
+
+    /// The number of rows of the AIR: enough for the curve with the most rows, and at least
+    /// `2^EC_AIR_MIN_NUM_ROWS_DEGREE`.
+    pub fn num_rows(&self) -> usize {
+        [&self.ed25519, &self.secp256k1, &self.bn254]
+            .iter()
+            .map(|ops| ops.iter().map(EcOp::num_rows).sum::<usize>())
+            .max()
+            .unwrap()
+            .next_power_of_two()
+            .max(1 << EC_AIR_MIN_NUM_ROWS_DEGREE)
+    }
+}
+
+/// A point in projective coordinates `(x : y : z)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ProjectivePointRegister<P: FieldParameters> {
+    pub x: FieldRegister<P>,
+    pub y: FieldRegister<P>,
+    pub z: FieldRegister<P>,
+}
+
+impl<P: FieldParameters> ProjectivePointRegister<P> {
+    pub fn new(x: FieldRegister<P>, y: FieldRegister<P>, z: FieldRegister<P>) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn coordinates(&self) -> [&FieldRegister<P>; 3] {
+        [&self.x, &self.y, &self.z]
+    }
+
+    fn alloc<L: AirParameters>(builder: &mut AirBuilder<L>) -> Self {
+        Self::new(builder.alloc(), builder.alloc(), builder.alloc())
+    }
+
+    fn alloc_public<L: AirParameters>(builder: &mut AirBuilder<L>) -> Self {
+        Self::new(
+            builder.alloc_public(),
+            builder.alloc_public(),
+            builder.alloc_public(),
+        )
+    }
+
+    fn expressions<F: Field>(&self) -> [ArithmeticExpression<F>; 3] {
+        [self.x.expr(), self.y.expr(), self.z.expr()]
+    }
+}
+
+/// The registers of one curve in the [`EcAir`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct EcSection<E: EcAirCurve> {
+    pub ops: Vec<EcOp>,
+
+    /// The accumulator and point of the first row of each operation.
+    pub inputs: Vec<[ProjectivePointRegister<E::BaseField>; 2]>,
+    /// The points loaded by `EcOp::Sum`, in order.
+    pub loads: Vec<ProjectivePointRegister<E::BaseField>>,
+    /// The bit of every row used by an operation.
+    pub bits: ArrayRegister<BitRegister>,
+    /// The affine result of each operation.
+    pub results: Vec<AffinePointRegister<E>>,
+
+    pub acc: ProjectivePointRegister<E::BaseField>,
+    pub multiple: ProjectivePointRegister<E::BaseField>,
+    pub sum: ProjectivePointRegister<E::BaseField>,
+    pub multiple_double: ProjectivePointRegister<E::BaseField>,
+    /// `acc + bit * multiple`.
+    pub acc_out: ProjectivePointRegister<E::BaseField>,
+    /// The affine coordinates of `acc_out`, which are only constrained in the last row of an
+    /// operation.
+    pub result: AffinePointRegister<E>,
+    pub bit: BitRegister,
+    /// Set in the first row of an operation.
+    pub start: BitRegister,
+    /// Set in the rows whose point is loaded from the public inputs.
+    pub load: BitRegister,
+    /// Set in the last row of an operation.
+    pub end: BitRegister,
+    /// Set in every row used by an operation.
+    pub active: BitRegister,
+}
+
+impl<E: EcAirCurve> EcSection<E> {
+    /// Allocates the registers and constraints of the curve, and returns the digests of its
+    /// public inputs, which the caller inserts into the bus.
+    fn new<L: AirParameters>(
+        builder: &mut AirBuilder<L>,
+        ops: &[EcOp],
+        clk: &ElementRegister,
+        channel_idx: usize,
+    ) -> (Self, Vec<CubicRegister>)
+    where
+        L::Instruction: FromFieldInstruction<E::BaseField>,
+    {
+        let num_rows = ops.iter().map(EcOp::num_rows).sum::<usize>();
+        let num_loads = ops.iter().map(EcOp::num_loads).sum::<usize>();
+        let size = FieldRegister::<E::BaseField>::size_of();
+
+        // Allocate public inputs.
+        let inputs = ops
+            .iter()
+            .map(|_| {
+                [
+                    ProjectivePointRegister::alloc_public(builder),
+                    ProjectivePointRegister::alloc_public(builder),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let loads = (0..num_loads)
+            .map(|_| ProjectivePointRegister::alloc_public(builder))
+            .collect::<Vec<_>>();
+        let bits = builder.alloc_array_public::<BitRegister>(num_rows);
+        let results = ops
+            .iter()
+            .map(|_| AffinePointRegister::new(builder.alloc_public(), builder.alloc_public()))
+            .collect::<Vec<_>>();
+
+        // The row registers and the group law.
+        let acc = ProjectivePointRegister::alloc(builder);
+        let multiple = ProjectivePointRegister::alloc(builder);
+        let bit = builder.alloc::<BitRegister>();
+        let start = builder.alloc::<BitRegister>();
+        let load = builder.alloc::<BitRegister>();
+        let end = builder.alloc::<BitRegister>();
+        let active = builder.alloc::<BitRegister>();
+
+        let sum = E::add_air(builder, &acc, &multiple);
+        let multiple_double = E::double_air(builder, &multiple);
+
+        let acc_out = ProjectivePointRegister::alloc(builder);
+        for ((out, sum), acc) in acc_out
+            .coordinates()
+            .into_iter()
+            .zip(sum.coordinates())
+            .zip(acc.coordinates())
+        {
+            builder.set_to_expression(out, bit.expr() * sum.expr() + bit.not_expr() * acc.expr());
+        }
+
+        // Inside an operation, the accumulator of the next row is `acc_out`, and its point is the
+        // double of the current one unless it is loaded.
+        for (acc, out) in acc.coordinates().into_iter().zip(acc_out.coordinates()) {
+            builder.assert_expression_zero_transition(
+                end.not_expr() * (acc.next().expr() - out.expr()),
+            );
+        }
+        for (multiple, double) in multiple
+            .coordinates()
+            .into_iter()
+            .zip(multiple_double.coordinates())
+        {
+            builder.assert_expression_zero_transition(
+                (end.not_expr() - load.next().expr()) * (multiple.next().expr() - double.expr()),
+            );
+        }
+
+        // In the last row of an operation, `result` are the affine coordinates of `acc_out`. Since
+        // `acc_out` is a valid point, it is not `(0 : 0 : 0)`, so this also constrains `acc_out`
+        // not to be the neutral element of a short Weierstrass curve, `(0 : 1 : 0)`.
+        let result: AffinePointRegister<E> =
+            AffinePointRegister::new(builder.alloc(), builder.alloc());
+        let result_x_z = builder.fp_mul(&result.x, &acc_out.z);
+        let result_y_z = builder.fp_mul(&result.y, &acc_out.z);
+        builder.assert_expression_zero(end.expr() * (result_x_z.expr() - acc_out.x.expr()));
+        builder.assert_expression_zero(end.expr() * (result_y_z.expr() - acc_out.y.expr()));
+
+        // Send the inputs, loaded points, bits and results of the operations to the bus.
+        let start_challenges = builder.alloc_challenge_array::<CubicRegister>(1 + 6 * size);
+        let load_challenges = builder.alloc_challenge_array::<CubicRegister>(1 + 3 * size);
+        let bit_challenges =
+            builder.alloc_challenge_array::<CubicRegister>(1 + BitRegister::size_of());
+        let end_challenges = builder.alloc_challenge_array::<CubicRegister>(1 + 2 * size);
+
+        let mut start_values = vec![clk.expr()];
+        start_values.extend(acc.expressions());
+        start_values.extend(multiple.expressions());
+        let start_digest = builder.accumulate_expressions(&start_challenges, &start_values);
+        builder.output_from_bus_filtered(channel_idx, start_digest, start);
+
+        let mut load_values = vec![clk.expr()];
+        load_values.extend(multiple.expressions());
+        let load_digest = builder.accumulate_expressions(&load_challenges, &load_values);
+        builder.output_from_bus_filtered(channel_idx, load_digest, load);
+
+        let bit_digest = builder.accumulate_expressions(&bit_challenges, &[clk.expr(), bit.expr()]);
+        builder.output_from_bus_filtered(channel_idx, bit_digest, active);
+
+        let end_digest = builder.accumulate_expressions(
+            &end_challenges,
+            &[clk.expr(), result.x.expr(), result.y.expr()],
+        );
+        builder.output_from_bus_filtered(channel_idx, end_digest, end);
+
+        // The digests of the public inputs, at the rows of the operations.
+        let row_expr =
+            |row: usize| ArithmeticExpression::from_constant(L::Field::from_canonical_usize(row));
+        let mut digests = Vec::new();
+        let mut row = 0;
+        let mut loads_iter = loads.iter();
+        for (op, (input, op_result)) in ops.iter().zip(inputs.iter().zip(results.iter())) {
+            let mut start_values = vec![row_expr(row)];
+            start_values.extend(input[0].expressions());
+            start_values.extend(input[1].expressions());
+            digests.push(builder.accumulate_public_expressions(&start_challenges, &start_values));
+
+            for load_row in row + 1..row + 1 + op.num_loads() {
+                let mut load_values = vec![row_expr(load_row)];
+                load_values.extend(loads_iter.next().unwrap().expressions());
+                digests.push(builder.accumulate_public_expressions(&load_challenges, &load_values));
+            }
+
+            for bit_row in row..row + op.num_rows() {
+                digests.push(builder.accumulate_public_expressions(
+                    &bit_challenges,
+                    &[row_expr(bit_row), bits.get(bit_row).expr()],
+                ));
+            }
+
+            row += op.num_rows();
+            digests.push(builder.accumulate_public_expressions(
+                &end_challenges,
+                &[row_expr(row - 1), op_result.x.expr(), op_result.y.expr()],
+            ));
+        }
+
+        let section = Self {
+            ops: ops.to_vec(),
+            inputs,
+            loads,
+            bits,
+            results,
+            acc,
+            multiple,
+            sum,
+            multiple_double,
+            acc_out,
+            result,
+            bit,
+            start,
+            load,
+            end,
+            active,
+        };
+        (section, digests)
+    }
+}
+
+/// A STARK proving the elliptic curve operations of ed25519, secp256k1 and BN254 G1 requested
+/// from the [`EcAccelerator`](super::EcAccelerator).
+///
+/// The curves use disjoint columns of the same rows, so the operations of all curves are proven
+/// together. The inputs, bits and results of the operations are public inputs, which are matched
+/// to the rows with a bus.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct EcAir<F: PrimeField64, R: CubicParameters<F>, const NUM_CURVES: usize> {
+    pub air: Chip<EcAirParameters<F, R, NUM_CURVES>>,
+    pub trace_data: AirTraceData<EcAirParameters<F, R, NUM_CURVES>>,
+    pub ed25519: Option<EcSection<Ed25519>>,
+    pub secp256k1: Option<EcSection<Secp256k1>>,
+    pub bn254: Option<EcSection<Bn254>>,
+}
+
+impl<F: PrimeField64, R: CubicParameters<F>, const NUM_CURVES: usize> EcAir<F, R, NUM_CURVES> {
+    pub fn new(shape: &EcAirShape) -> Self {
+        assert_eq!(shape.num_curves(), NUM_CURVES);
+        let mut builder = AirBuilder::new();
+
+        // Initialize clock and bus channel.
+        let clk = builder.clock();
+        let mut bus = builder.new_bus();
+        let channel_idx = bus.new_channel(&mut builder);
+
+        let mut digests = Vec::new();
+        let ed25519 = Self::section(
+            &mut builder,
+            &shape.ed25519,
+            &clk,
+            channel_idx,
+            &mut digests,
+        );
+        let secp256k1 = Self::section(
+            &mut builder,
+            &shape.secp256k1,
+            &clk,
+            channel_idx,
+            &mut digests,
+        );
+        let bn254 = Self::section(&mut builder, &shape.bn254, &clk, channel_idx, &mut digests);
+
+        // Insert the public operations into the bus.
+        for digest in digests.iter() {
+            bus.insert_global_value(digest);
+        }
+
+        builder.constrain_bus(bus);
+        let (air, trace_data) = builder.build();
+
+        Self {
+            air,
+            trace_data,
+            ed25519,
+            secp256k1,
+            bn254,
+        }
+    }
+
+    fn section<E: EcAirCurve>(
+        builder: &mut AirBuilder<EcAirParameters<F, R, NUM_CURVES>>,
+        ops: &[EcOp],
+        clk: &ElementRegister,
+        channel_idx: usize,
+        digests: &mut Vec<CubicRegister>,
+    ) -> Option<EcSection<E>>
+    where
+        EcInstruction: FromFieldInstruction<E::BaseField>,
+    {
+        if ops.is_empty() {
+            return None;
+        }
+        let (section, section_digests) = EcSection::new(builder, ops, clk, channel_idx);
+        digests.extend(section_digests);
+        Some(section)
+    }
+}
@@ -0,0 +1,301 @@
+use curta::chip::builder::AirBuilder;
+use curta::chip::ec::edwards::ed25519::Ed25519;
+use curta::chip::ec::weierstrass::bn254::{Bn254, Bn254Parameters};
+use curta::chip::ec::weierstrass::WeierstrassParameters;
+use curta::chip::ec::EllipticCurve;
+use curta::chip::field::instruction::FromFieldInstruction;
+use curta::chip::field::parameters::MAX_NB_LIMBS;
+use curta::chip::AirParameters;
+use num::{BigUint, Num, One, Zero};
+
+use super::air::ProjectivePointRegister;
+use super::{EcAccelerator, EcRequest};
+use crate::frontend::curta::ec::secp256k1::{Secp256k1, Secp256k1Parameters};
+
+/// A curve proven by the [`EcAir`](super::air::EcAir).
+///
+/// Points are in projective coordinates and the group law uses complete formulas, so additions and
+/// doublings have no exceptional cases and the neutral element needs no special handling.
+pub trait EcAirCurve: EllipticCurve {
+    /// The projective coordinates of the neutral element.
+    fn neutral() -> [BigUint; 3];
+
+    fn add_air<L: AirParameters>(
+        builder: &mut AirBuilder<L>,
+        p: &ProjectivePointRegister<Self::BaseField>,
+        q: &ProjectivePointRegister<Self::BaseField>,
+    ) -> ProjectivePointRegister<Self::BaseField>
+    where
+        L::Instruction: FromFieldInstruction<Self::BaseField>;
+
+    fn double_air<L: AirParameters>(
+        builder: &mut AirBuilder<L>,
+        p: &ProjectivePointRegister<Self::BaseField>,
+    ) -> ProjectivePointRegister<Self::BaseField>
+    where
+        L::Instruction: FromFieldInstruction<Self::BaseField>;
+
+    /// The requests of this curve in the accelerator.
+    fn requests(accelerator: &mut EcAccelerator) -> &mut Vec<EcRequest<Self>>;
+}
+
+/// The little-endian 16-bit limbs of a constant, as taken by `fp_mul_const`.
+fn constant_limbs(value: &BigUint) -> [u16; MAX_NB_LIMBS] {
+    let mut limbs = [0u16; MAX_NB_LIMBS];
+    let digits = value
+        .to_u32_digits()
+        .into_iter()
+        .flat_map(|digit| [digit as u16, (digit >> 16) as u16]);
+    for (limb, digit) in limbs.iter_mut().zip(digits) {
+        *limb = digit;
+    }
+    limbs
+}
+
+/// The complete formulas for `a = 0` of Renes, Costello and Batina, "Complete addition formulas
+/// for prime order elliptic curves" (algorithms 7 and 9), as in `ecc::msm::weierstrass`.
+fn weierstrass_add_air<L: AirParameters, W: WeierstrassParameters>(
+    builder: &mut AirBuilder<L>,
+    p: &ProjectivePointRegister<W::BaseField>,
+    q: &ProjectivePointRegister<W::BaseField>,
+) -> ProjectivePointRegister<W::BaseField>
+where
+    L::Instruction: FromFieldInstruction<W::BaseField>,
+{
+    let b3 = constant_limbs(&(W::b_int() * 3u32));
+    let ProjectivePointRegister {
+        x: x1,
+        y: y1,
+        z: z1,
+    } = p;
+    let ProjectivePointRegister {
+        x: x2,
+        y: y2,
+        z: z2,
+    } = q;
+
+    let t0 = builder.fp_mul(x1, x2);
+    let t1 = builder.fp_mul(y1, y2);
+    let t2 = builder.fp_mul(z1, z2);
+
+    let x1_plus_y1 = builder.fp_add(x1, y1);
+    let x2_plus_y2 = builder.fp_add(x2, y2);
+    let t3 = builder.fp_mul(&x1_plus_y1, &x2_plus_y2);
+    let t0_plus_t1 = builder.fp_add(&t0, &t1);
+    let t3 = builder.fp_sub(&t3, &t0_plus_t1);
+
+    let y1_plus_z1 = builder.fp_add(y1, z1);
+    let y2_plus_z2 = builder.fp_add(y2, z2);
+    let t4 = builder.fp_mul(&y1_plus_z1, &y2_plus_z2);
+    let t1_plus_t2 = builder.fp_add(&t1, &t2);
+    let t4 = builder.fp_sub(&t4, &t1_plus_t2);
+
+    let x1_plus_z1 = builder.fp_add(x1, z1);
+    let x2_plus_z2 = builder.fp_add(x2, z2);
+    let y3 = builder.fp_mul(&x1_plus_z1, &x2_plus_z2);
+    let t0_plus_t2 = builder.fp_add(&t0, &t2);
+    let y3 = builder.fp_sub(&y3, &t0_plus_t2);
+
+    let t0_double = builder.fp_add(&t0, &t0);
+    let t0 = builder.fp_add(&t0_double, &t0);
+    let t2 = builder.fp_mul_const(&t2, b3);
+    let z3 = builder.fp_add(&t1, &t2);
+    let t1 = builder.fp_sub(&t1, &t2);
+    let y3 = builder.fp_mul_const(&y3, b3);
+
+    let t3_t1 = builder.fp_mul(&t3, &t1);
+    let t4_y3 = builder.fp_mul(&t4, &y3);
+    let x3 = builder.fp_sub(&t3_t1, &t4_y3);
+
+    let t1_z3 = builder.fp_mul(&t1, &z3);
+    let y3_t0 = builder.fp_mul(&y3, &t0);
+    let y3 = builder.fp_add(&t1_z3, &y3_t0);
+
+    let z3_t4 = builder.fp_mul(&z3, &t4);
+    let t0_t3 = builder.fp_mul(&t0, &t3);
+    let z3 = builder.fp_add(&z3_t4, &t0_t3);
+
+    ProjectivePointRegister::new(x3, y3, z3)
+}
+
+fn weierstrass_double_air<L: AirParameters, W: WeierstrassParameters>(
+    builder: &mut AirBuilder<L>,
+    p: &ProjectivePointRegister<W::BaseField>,
+) -> ProjectivePointRegister<W::BaseField>
+where
+    L::Instruction: FromFieldInstruction<W::BaseField>,
+{
+    let b3 = constant_limbs(&(W::b_int() * 3u32));
+    let ProjectivePointRegister { x, y, z } = p;
+
+    let t0 = builder.fp_mul(y, y);
+    let t0_2 = builder.fp_add(&t0, &t0);
+    let t0_4 = builder.fp_add(&t0_2, &t0_2);
+    let z3 = builder.fp_add(&t0_4, &t0_4);
+    let t1 = builder.fp_mul(y, z);
+    let z_squared = builder.fp_mul(z, z);
+    let t2 = builder.fp_mul_const(&z_squared, b3);
+    let x3 = builder.fp_mul(&t2, &z3);
+    let y3 = builder.fp_add(&t0, &t2);
+    let z3 = builder.fp_mul(&t1, &z3);
+
+    let t2_double = builder.fp_add(&t2, &t2);
+    let t2_triple = builder.fp_add(&t2_double, &t2);
+    let t0 = builder.fp_sub(&t0, &t2_triple);
+    let t0_y3 = builder.fp_mul(&t0, &y3);
+    let y3 = builder.fp_add(&x3, &t0_y3);
+
+    let t0_x = builder.fp_mul(&t0, x);
+    let t0_x_y = builder.fp_mul(&t0_x, y);
+    let x3 = builder.fp_add(&t0_x_y, &t0_x_y);
+
+    ProjectivePointRegister::new(x3, y3, z3)
+}
+
+macro_rules! impl_weierstrass_ec_air_curve {
+    ($curve:ty, $parameters:ty, $requests:ident) => {
+        impl EcAirCurve for $curve {
+            fn neutral() -> [BigUint; 3] {
+                [BigUint::zero(), BigUint::one(), BigUint::zero()]
+            }
+
+            fn add_air<L: AirParameters>(
+                builder: &mut AirBuilder<L>,
+                p: &ProjectivePointRegister<Self::BaseField>,
+                q: &ProjectivePointRegister<Self::BaseField>,
+            ) -> ProjectivePointRegister<Self::BaseField>
+            where
+                L::Instruction: FromFieldInstruction<Self::BaseField>,
+            {
+                weierstrass_add_air::<L, $parameters>(builder, p, q)
+            }
+
+            fn double_air<L: AirParameters>(
+                builder: &mut AirBuilder<L>,
+                p: &ProjectivePointRegister<Self::BaseField>,
+            ) -> ProjectivePointRegister<Self::BaseField>
+            where
+                L::Instruction: FromFieldInstruction<Self::BaseField>,
+            {
+                weierstrass_double_air::<L, $parameters>(builder, p)
+            }
+
+            fn requests(accelerator: &mut EcAccelerator) -> &mut Vec<EcRequest<Self>> {
+                &mut accelerator.$requests
+            }
+        }
+    };
+}
+
+impl_weierstrass_ec_air_curve!(Secp256k1, Secp256k1Parameters, secp256k1);
+impl_weierstrass_ec_air_curve!(Bn254, Bn254Parameters, bn254);
+
+/// The `d` parameter of ed25519, `-121665 / 121666`.
+fn ed25519_d() -> BigUint {
+    BigUint::from_str_radix(
+        "52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3",
+        16,
+    )
+    .unwrap()
+}
+
+/// The complete formulas for twisted Edwards curves with `a = -1` of Bernstein, Birkner, Joye,
+/// Lange and Peters, "Twisted Edwards curves" (`add-2008-bbjlp` and `dbl-2008-bbjlp`). They are
+/// complete on ed25519 since `a` is a square and `d` is not.
+impl EcAirCurve for Ed25519 {
+    fn neutral() -> [BigUint; 3] {
+        [BigUint::zero(), BigUint::one(), BigUint::one()]
+    }
+
+    fn add_air<L: AirParameters>(
+        builder: &mut AirBuilder<L>,
+        p: &ProjectivePointRegister<Self::BaseField>,
+        q: &ProjectivePointRegister<Self::BaseField>,
+    ) -> ProjectivePointRegister<Self::BaseField>
+    where
+        L::Instruction: FromFieldInstruction<Self::BaseField>,
+    {
+        let ProjectivePointRegister {
+            x: x1,
+            y: y1,
+            z: z1,
+        } = p;
+        let ProjectivePointRegister {
+            x: x2,
+            y: y2,
+            z: z2,
+        } = q;
+
+        let a = builder.fp_mul(z1, z2);
+        let b = builder.fp_mul(&a, &a);
+        let c = builder.fp_mul(x1, x2);
+        let d = builder.fp_mul(y1, y2);
+        let c_d = builder.fp_mul(&c, &d);
+        let e = builder.fp_mul_const(&c_d, constant_limbs(&ed25519_d()));
+        let f = builder.fp_sub(&b, &e);
+        let g = builder.fp_add(&b, &e);
+
+        let x1_plus_y1 = builder.fp_add(x1, y1);
+        let x2_plus_y2 = builder.fp_add(x2, y2);
+        let cross = builder.fp_mul(&x1_plus_y1, &x2_plus_y2);
+        let c_plus_d = builder.fp_add(&c, &d);
+        let cross = builder.fp_sub(&cross, &c_plus_d);
+
+        let a_f = builder.fp_mul(&a, &f);
+        let x3 = builder.fp_mul(&a_f, &cross);
+        // D - aC with a = -1.
+        let a_g = builder.fp_mul(&a, &g);
+        let y3 = builder.fp_mul(&a_g, &c_plus_d);
+        let z3 = builder.fp_mul(&f, &g);
+
+        ProjectivePointRegister::new(x3, y3, z3)
+    }
+
+    fn double_air<L: AirParameters>(
+        builder: &mut AirBuilder<L>,
+        p: &ProjectivePointRegister<Self::BaseField>,
+    ) -> ProjectivePointRegister<Self::BaseField>
+    where
+        L::Instruction: FromFieldInstruction<Self::BaseField>,
+    {
+        let ProjectivePointRegister { x, y, z } = p;
+
+        let x_plus_y = builder.fp_add(x, y);
+        let b = builder.fp_mul(&x_plus_y, &x_plus_y);
+        let c = builder.fp_mul(x, x);
+        let d = builder.fp_mul(y, y);
+        // F = aC + D with a = -1.
+        let f = builder.fp_sub(&d, &c);
+        let h = builder.fp_mul(z, z);
+        let h_double = builder.fp_add(&h, &h);
+
+        // The formula computes (X3 : Y3 : Z3) with J = F - 2H. This computes (-X3 : -Y3 : -Z3),
+        // the same point, with -J = 2H - F, which saves negating E - D = -(C + D).
+        let minus_j = builder.fp_sub(&h_double, &f);
+        let c_plus_d = builder.fp_add(&c, &d);
+        let b_minus_c_d = builder.fp_sub(&b, &c_plus_d);
+        let x3 = builder.fp_mul(&b_minus_c_d, &minus_j);
+        let y3 = builder.fp_mul(&f, &c_plus_d);
+        let z3 = builder.fp_mul(&f, &minus_j);
+
+        ProjectivePointRegister::new(x3, y3, z3)
+    }
+
+    fn requests(accelerator: &mut EcAccelerator) -> &mut Vec<EcRequest<Self>> {
+        &mut accelerator.ed25519
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use super::constant_limbs;
+
+    #[test]
+    fn test_constant_limbs() {
+        let value = BigUint::from(0x1234_5678_9abc_def0u64);
+        let limbs = constant_limbs(&value);
+        assert_eq!(limbs[..5], [0xdef0, 0x9abc, 0x5678, 0x1234, 0]);
+    }
+}
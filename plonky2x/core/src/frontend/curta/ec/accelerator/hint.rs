@@ -0,0 +1,296 @@
+use curta::chip::ec::gadget::EllipticCurveWriter;
+use curta::chip::ec::point::AffinePoint;
+use curta::chip::field::parameters::FieldParameters;
+use curta::chip::field::register::FieldRegister;
+use curta::chip::trace::generator::ArithmeticGenerator;
+use curta::chip::trace::writer::TraceWriter;
+use curta::chip::utils::digits_to_biguint;
+use curta::plonky2::stark::config::StarkyConfig;
+use curta::plonky2::stark::prover::StarkyProver;
+use curta::plonky2::stark::verifier::StarkyVerifier;
+use curta::plonky2::stark::Starky;
+use curta::polynomial::to_u16_le_limbs_polynomial;
+use log::debug;
+use num::{BigUint, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::air::{EcAir, EcAirShape, EcSection, ProjectivePointRegister};
+use super::curve::EcAirCurve;
+use crate::frontend::curta::ec::point::ProjectivePointVariable;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::prelude::{PlonkParameters, ValueStream, *};
+
+/// Generates the proof of the operations of an [`EcAir`].
+///
+/// The input stream holds, for each curve of the shape and each of its operations, the
+/// accumulator and point of its first row, the points it loads and the bit of each of its rows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcHint {
+    pub shape: EcAirShape,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for EcHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        match self.shape.num_curves() {
+            1 => self.prove::<L, D, 1>(input_stream, output_stream),
+            2 => self.prove::<L, D, 2>(input_stream, output_stream),
+            3 => self.prove::<L, D, 3>(input_stream, output_stream),
+            _ => panic!("the EC accelerator has no operations"),
+        }
+    }
+}
+
+impl EcHint {
+    fn prove<L: PlonkParameters<D>, const D: usize, const NUM_CURVES: usize>(
+        &self,
+        input_stream: &mut ValueStream<L, D>,
+        output_stream: &mut ValueStream<L, D>,
+    ) {
+        let num_rows = self.shape.num_rows();
+        let EcAir {
+            air,
+            trace_data,
+            ed25519,
+            secp256k1,
+            bn254,
+        } = EcAir::<L::Field, L::CubicParams, NUM_CURVES>::new(&self.shape);
+
+        let mut ed25519 = ed25519.map(|section| EcSectionTrace::read(section, input_stream));
+        let mut secp256k1 = secp256k1.map(|section| EcSectionTrace::read(section, input_stream));
+        let mut bn254 = bn254.map(|section| EcSectionTrace::read(section, input_stream));
+
+        let trace_generator = ArithmeticGenerator::new(trace_data, num_rows);
+        let writer = trace_generator.new_writer();
+
+        for row in 0..num_rows {
+            ed25519.iter_mut().for_each(|s| s.write_row(&writer, row));
+            secp256k1.iter_mut().for_each(|s| s.write_row(&writer, row));
+            bn254.iter_mut().for_each(|s| s.write_row(&writer, row));
+            writer.write_row_instructions(&trace_generator.air_data, row);
+
+            // The affine result depends on `acc_out`, so the row is written again once it is set.
+            ed25519
+                .iter_mut()
+                .for_each(|s| s.write_result(&writer, row));
+            secp256k1
+                .iter_mut()
+                .for_each(|s| s.write_result(&writer, row));
+            bn254.iter_mut().for_each(|s| s.write_result(&writer, row));
+            writer.write_row_instructions(&trace_generator.air_data, row);
+        }
+
+        ed25519.iter().for_each(|s| s.write_public_inputs(&writer));
+        secp256k1
+            .iter()
+            .for_each(|s| s.write_public_inputs(&writer));
+        bn254.iter().for_each(|s| s.write_public_inputs(&writer));
+        writer.write_global_instructions(&trace_generator.air_data);
+
+        let stark = Starky::new(air);
+        let config = StarkyConfig::standard_fast_config(num_rows);
+
+        let public_inputs: Vec<L::Field> = writer.public().unwrap().clone();
+
+        let proof = StarkyProver::<L::Field, L::CurtaConfig, D>::prove(
+            &config,
+            &stark,
+            &trace_generator,
+            &public_inputs,
+        )
+        .unwrap();
+        debug!("Generated EC proof");
+
+        // Verify the proof to make sure it's valid.
+        StarkyVerifier::verify(&config, &stark, proof.clone(), &public_inputs).unwrap();
+
+        output_stream.write_stark_proof(proof);
+        output_stream.write_slice(&public_inputs);
+    }
+}
+
+/// The values of a row of one curve, set by the operation it belongs to.
+#[derive(Debug, Clone, Default)]
+struct EcRow {
+    start: Option<[[BigUint; 3]; 2]>,
+    load: Option<[BigUint; 3]>,
+    bit: bool,
+    end: bool,
+    active: bool,
+}
+
+/// Writes the rows of one curve of the [`EcAir`].
+struct EcSectionTrace<E: EcAirCurve> {
+    section: EcSection<E>,
+    rows: Vec<EcRow>,
+    /// The accumulator and point of the current row.
+    acc: [BigUint; 3],
+    multiple: [BigUint; 3],
+    results: Vec<AffinePoint<E>>,
+}
+
+impl<E: EcAirCurve> EcSectionTrace<E> {
+    fn read<L: PlonkParameters<D>, const D: usize>(
+        section: EcSection<E>,
+        input_stream: &mut ValueStream<L, D>,
+    ) -> Self {
+        let mut rows = Vec::new();
+        for op in section.ops.iter() {
+            let acc = read_point_value::<L, D, E>(input_stream);
+            let multiple = read_point_value::<L, D, E>(input_stream);
+            let loads = (0..op.num_loads())
+                .map(|_| read_point_value::<L, D, E>(input_stream))
+                .collect::<Vec<_>>();
+
+            for (i, load) in core::iter::once(None)
+                .chain(loads.into_iter().map(Some))
+                .chain(core::iter::repeat(None))
+                .take(op.num_rows())
+                .enumerate()
+            {
+                rows.push(EcRow {
+                    start: (i == 0).then(|| [acc.clone(), multiple.clone()]),
+                    load,
+                    bit: input_stream.read_value::<BoolVariable>(),
+                    end: i + 1 == op.num_rows(),
+                    active: true,
+                });
+            }
+        }
+
+        Self {
+            section,
+            rows,
+            acc: E::neutral(),
+            multiple: E::neutral(),
+            results: Vec::new(),
+        }
+    }
+
+    /// Writes the accumulator, point and flags of a row.
+    fn write_row<F: PrimeField64>(&mut self, writer: &TraceWriter<F>, row: usize) {
+        let values = self.rows.get(row).cloned().unwrap_or_default();
+        if let Some([acc, multiple]) = &values.start {
+            self.acc = acc.clone();
+            self.multiple = multiple.clone();
+        }
+        if let Some(multiple) = &values.load {
+            self.multiple = multiple.clone();
+        }
+
+        let section = &self.section;
+        write_point(writer, &section.acc, &self.acc, row);
+        write_point(writer, &section.multiple, &self.multiple, row);
+        for (register, value) in [
+            (&section.bit, values.bit),
+            (&section.start, values.start.is_some()),
+            (&section.load, values.load.is_some()),
+            (&section.end, values.end),
+            (&section.active, values.active),
+        ] {
+            writer.write(register, &F::from_canonical_u8(value as u8), row);
+        }
+        write_field(writer, &section.result.x, &BigUint::zero(), row);
+        write_field(writer, &section.result.y, &BigUint::zero(), row);
+    }
+
+    /// Writes `acc_out` and, in the last row of an operation, its affine coordinates. Sets the
+    /// accumulator and point of the next row.
+    fn write_result<F: PrimeField64>(&mut self, writer: &TraceWriter<F>, row: usize) {
+        let values = self.rows.get(row).cloned().unwrap_or_default();
+        let section = &self.section;
+
+        let acc_out = if values.bit {
+            read_point(writer, &section.sum, row)
+        } else {
+            self.acc.clone()
+        };
+        write_point(writer, &section.acc_out, &acc_out, row);
+
+        if values.end {
+            let modulus = E::BaseField::modulus();
+            let [x, y, z] = &acc_out;
+            assert!(
+                !z.is_zero(),
+                "the result of an EC accelerator operation is the neutral element"
+            );
+            let z_inv = z.modpow(&(&modulus - 2u32), &modulus);
+            let result = AffinePoint::<E>::new(x * &z_inv % &modulus, y * &z_inv % &modulus);
+            write_field(writer, &section.result.x, &result.x, row);
+            write_field(writer, &section.result.y, &result.y, row);
+            self.results.push(result);
+
+            self.acc = E::neutral();
+            self.multiple = E::neutral();
+        } else {
+            self.multiple = read_point(writer, &section.multiple_double, row);
+            self.acc = acc_out;
+        }
+    }
+
+    /// Writes the inputs, loaded points, bits and results of the operations.
+    fn write_public_inputs<F: PrimeField64>(&self, writer: &TraceWriter<F>) {
+        let section = &self.section;
+        let starts = self.rows.iter().filter_map(|row| row.start.as_ref());
+        for (input, [acc, multiple]) in section.inputs.iter().zip(starts) {
+            write_point(writer, &input[0], acc, 0);
+            write_point(writer, &input[1], multiple, 0);
+        }
+        let loads = self.rows.iter().filter_map(|row| row.load.as_ref());
+        for (register, load) in section.loads.iter().zip(loads) {
+            write_point(writer, register, load, 0);
+        }
+        for (i, row) in self.rows.iter().enumerate() {
+            writer.write(
+                &section.bits.get(i),
+                &F::from_canonical_u8(row.bit as u8),
+                0,
+            );
+        }
+        for (register, result) in section.results.iter().zip(self.results.iter()) {
+            writer.write_ec_point(register, result, 0);
+        }
+    }
+}
+
+fn read_point_value<L: PlonkParameters<D>, const D: usize, E: EcAirCurve>(
+    input_stream: &mut ValueStream<L, D>,
+) -> [BigUint; 3] {
+    let point = input_stream.read_value::<ProjectivePointVariable<E>>();
+    [point.x, point.y, point.z]
+}
+
+fn write_field<F: PrimeField64, P: FieldParameters>(
+    writer: &TraceWriter<F>,
+    register: &FieldRegister<P>,
+    value: &BigUint,
+    row: usize,
+) {
+    writer.write(register, &to_u16_le_limbs_polynomial::<F, P>(value), row);
+}
+
+fn write_point<F: PrimeField64, P: FieldParameters>(
+    writer: &TraceWriter<F>,
+    register: &ProjectivePointRegister<P>,
+    value: &[BigUint; 3],
+    row: usize,
+) {
+    for (coordinate, value) in register.coordinates().into_iter().zip(value.iter()) {
+        write_field(writer, coordinate, value, row);
+    }
+}
+
+fn read_point<F: PrimeField64, P: FieldParameters>(
+    writer: &TraceWriter<F>,
+    register: &ProjectivePointRegister<P>,
+    row: usize,
+) -> [BigUint; 3] {
+    register.coordinates().map(|coordinate| {
+        let limbs = writer
+            .read(coordinate, row)
+            .as_coefficients()
+            .iter()
+            .map(|limb| limb.as_canonical_u64() as u16)
+            .collect::<Vec<_>>();
+        digits_to_biguint(&limbs)
+    })
+}
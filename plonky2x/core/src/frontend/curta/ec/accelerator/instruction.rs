@@ -0,0 +1,101 @@
+use curta::air::parser::AirParser;
+use curta::air::AirConstraint;
+use curta::chip::ec::edwards::ed25519::Ed25519BaseField;
+use curta::chip::ec::weierstrass::bn254::Bn254BaseField;
+use curta::chip::field::add::FpAddInstruction;
+use curta::chip::field::den::FpDenInstruction;
+use curta::chip::field::div::FpDivInstruction;
+use curta::chip::field::inner_product::FpInnerProductInstruction;
+use curta::chip::field::instruction::{FpInstruction, FromFieldInstruction};
+use curta::chip::field::mul::FpMulInstruction;
+use curta::chip::field::mul_const::FpMulConstInstruction;
+use curta::chip::field::sub::FpSubInstruction;
+use curta::chip::instruction::Instruction;
+use curta::chip::trace::writer::TraceWriter;
+use curta::math::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::frontend::curta::ec::secp256k1::Secp256k1BaseField;
+
+/// The instructions of the [`EcAir`](super::air::EcAir): the field arithmetic of the base field
+/// of every curve it supports.
+///
+/// A Curta AIR has a single instruction type, so the field instructions of the three base fields
+/// are wrapped into one enum to prove the curves in the same STARK.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EcInstruction {
+    Ed25519(FpInstruction<Ed25519BaseField>),
+    Secp256k1(FpInstruction<Secp256k1BaseField>),
+    Bn254(FpInstruction<Bn254BaseField>),
+}
+
+macro_rules! impl_from_field_instruction {
+    ($variant:ident, $field:ty, [$($instruction:ident),*]) => {
+        impl From<FpInstruction<$field>> for EcInstruction {
+            fn from(instruction: FpInstruction<$field>) -> Self {
+                Self::$variant(instruction)
+            }
+        }
+
+        $(
+            impl From<$instruction<$field>> for EcInstruction {
+                fn from(instruction: $instruction<$field>) -> Self {
+                    Self::$variant(instruction.into())
+                }
+            }
+        )*
+
+        impl FromFieldInstruction<$field> for EcInstruction {}
+    };
+}
+
+macro_rules! impl_from_field_instructions {
+    ($($variant:ident => $field:ty),*) => {
+        $(
+            impl_from_field_instruction!(
+                $variant,
+                $field,
+                [
+                    FpAddInstruction,
+                    FpSubInstruction,
+                    FpMulInstruction,
+                    FpMulConstInstruction,
+                    FpInnerProductInstruction,
+                    FpDenInstruction,
+                    FpDivInstruction
+                ]
+            );
+        )*
+    };
+}
+
+impl_from_field_instructions!(
+    Ed25519 => Ed25519BaseField,
+    Secp256k1 => Secp256k1BaseField,
+    Bn254 => Bn254BaseField
+);
+
+impl<F: PrimeField64> Instruction<F> for EcInstruction {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        match self {
+            Self::Ed25519(instruction) => Instruction::<F>::write(instruction, writer, row_index),
+            Self::Secp256k1(instruction) => Instruction::<F>::write(instruction, writer, row_index),
+            Self::Bn254(instruction) => Instruction::<F>::write(instruction, writer, row_index),
+        }
+    }
+}
+
+impl<AP: AirParser> AirConstraint<AP> for EcInstruction
+where
+    FpInstruction<Ed25519BaseField>: AirConstraint<AP>,
+    FpInstruction<Secp256k1BaseField>: AirConstraint<AP>,
+    FpInstruction<Bn254BaseField>: AirConstraint<AP>,
+{
+    fn eval(&self, parser: &mut AP) {
+        match self {
+            Self::Ed25519(instruction) => instruction.eval(parser),
+            Self::Secp256k1(instruction) => instruction.eval(parser),
+            Self::Bn254(instruction) => instruction.eval(parser),
+        }
+    }
+}
@@ -0,0 +1,480 @@
+//! A shared Curta accelerator for elliptic curve operations.
+//!
+//! Gadgets register their curve operations on the builder during `define`, and the accelerator
+//! proves all of them in `pre_build` with a single [`EcAir`] STARK covering ed25519, secp256k1
+//! and BN254 G1. Additions, doublings, scalar multiplications and masked sums use complete
+//! formulas, and the size of the STARK follows the number of requested operations.
+
+pub mod air;
+pub mod curve;
+pub mod hint;
+pub mod instruction;
+
+use curta::air::RAirData;
+use curta::chip::ec::edwards::ed25519::Ed25519 as CurtaEd25519;
+use curta::chip::ec::edwards::scalar_mul::generator::AffinePointTarget as CurtaAffinePointTarget;
+use curta::chip::ec::weierstrass::bn254::Bn254;
+use curta::chip::register::Register;
+use curta::plonky2::stark::config::StarkyConfig;
+use curta::plonky2::stark::Starky;
+
+use self::air::{EcAir, EcAirShape, EcOp, EcSection};
+use self::curve::EcAirCurve;
+use self::hint::EcHint;
+use crate::frontend::curta::ec::point::{
+    AffinePointVariable, ProjectivePointVariable, ProjectivePointVariableValue,
+};
+use crate::frontend::curta::ec::secp256k1::Secp256k1;
+use crate::frontend::curta::field::variable::FieldVariable;
+use crate::frontend::ecc::ed25519::curve::ed25519::Ed25519;
+use crate::frontend::ecc::ed25519::field::ed25519_scalar::Ed25519Scalar;
+use crate::frontend::ecc::ed25519::gadgets::curve::{AffinePointTarget, CircuitBuilderCurve};
+use crate::frontend::num::nonnative::nonnative::NonNativeTarget;
+use crate::prelude::*;
+
+/// A curve operation requested from the [`EcAccelerator`].
+#[derive(Debug, Clone)]
+pub enum EcRequest<E: EcAirCurve> {
+    Add {
+        a: AffinePointVariable<E>,
+        b: AffinePointVariable<E>,
+        result: AffinePointVariable<E>,
+    },
+    Double {
+        point: AffinePointVariable<E>,
+        result: AffinePointVariable<E>,
+    },
+    ScalarMul {
+        point: AffinePointVariable<E>,
+        scalar_bits_le: Vec<BoolVariable>,
+        result: AffinePointVariable<E>,
+    },
+    Sum {
+        points: Vec<AffinePointVariable<E>>,
+        selectors: Vec<BoolVariable>,
+        result: AffinePointVariable<E>,
+    },
+}
+
+impl<E: EcAirCurve> EcRequest<E> {
+    pub fn op(&self) -> EcOp {
+        match self {
+            EcRequest::Add { .. } => EcOp::Add,
+            EcRequest::Double { .. } => EcOp::Double,
+            EcRequest::ScalarMul { scalar_bits_le, .. } => EcOp::ScalarMul {
+                num_bits: scalar_bits_le.len(),
+            },
+            EcRequest::Sum { points, .. } => EcOp::Sum {
+                num_points: points.len(),
+            },
+        }
+    }
+
+    pub fn result(&self) -> &AffinePointVariable<E> {
+        match self {
+            EcRequest::Add { result, .. }
+            | EcRequest::Double { result, .. }
+            | EcRequest::ScalarMul { result, .. }
+            | EcRequest::Sum { result, .. } => result,
+        }
+    }
+}
+
+/// The values of the rows of an operation in the [`EcAir`]: the accumulator and point of its
+/// first row, the points loaded in the next rows and the bit of every row.
+struct EcOpInputs<E: EcAirCurve> {
+    acc: ProjectivePointVariable<E>,
+    multiple: ProjectivePointVariable<E>,
+    loads: Vec<ProjectivePointVariable<E>>,
+    bits: Vec<BoolVariable>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EcAccelerator {
+    pub ed25519: Vec<EcRequest<CurtaEd25519>>,
+    pub secp256k1: Vec<EcRequest<Secp256k1>>,
+    pub bn254: Vec<EcRequest<Bn254>>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    fn ec_accelerator(&mut self) -> &mut EcAccelerator {
+        self.ec_accelerator
+            .get_or_insert_with(EcAccelerator::default)
+    }
+
+    fn curta_ec_request<E: EcAirCurve>(
+        &mut self,
+        request: impl FnOnce(AffinePointVariable<E>) -> EcRequest<E>,
+    ) -> AffinePointVariable<E> {
+        let result = self.init_unsafe::<AffinePointVariable<E>>();
+        E::requests(self.ec_accelerator()).push(request(result.clone()));
+        result
+    }
+
+    /// Computes `a + b`. The result is constrained by the EC accelerator.
+    ///
+    /// On short Weierstrass curves, the result must not be the point at infinity, which has no
+    /// affine coordinates.
+    pub fn curta_ec_add<E: EcAirCurve>(
+        &mut self,
+        a: &AffinePointVariable<E>,
+        b: &AffinePointVariable<E>,
+    ) -> AffinePointVariable<E> {
+        self.curta_ec_request(|result| EcRequest::Add {
+            a: a.clone(),
+            b: b.clone(),
+            result,
+        })
+    }
+
+    /// Computes `2 * point`. The result is constrained by the EC accelerator.
+    ///
+    /// On short Weierstrass curves, the result must not be the point at infinity.
+    pub fn curta_ec_double<E: EcAirCurve>(
+        &mut self,
+        point: &AffinePointVariable<E>,
+    ) -> AffinePointVariable<E> {
+        self.curta_ec_request(|result| EcRequest::Double {
+            point: point.clone(),
+            result,
+        })
+    }
+
+    /// Computes `scalar * point` with the little-endian bits of the scalar, using one row of the
+    /// EC accelerator per bit.
+    ///
+    /// On short Weierstrass curves, the result must not be the point at infinity, so the scalar
+    /// must not be a multiple of the order of the point.
+    pub fn curta_ec_scalar_mul<E: EcAirCurve>(
+        &mut self,
+        point: &AffinePointVariable<E>,
+        scalar_bits_le: &[BoolVariable],
+    ) -> AffinePointVariable<E> {
+        assert!(!scalar_bits_le.is_empty(), "the scalar has no bits");
+        self.curta_ec_request(|result| EcRequest::ScalarMul {
+            point: point.clone(),
+            scalar_bits_le: scalar_bits_le.to_vec(),
+            result,
+        })
+    }
+
+    /// Computes the sum of the points whose selector is set, using one row of the EC accelerator
+    /// per point.
+    ///
+    /// On short Weierstrass curves, the sum must not be the point at infinity, so at least one
+    /// selector must be set.
+    pub fn curta_ec_sum<E: EcAirCurve>(
+        &mut self,
+        points: &[AffinePointVariable<E>],
+        selectors: &[BoolVariable],
+    ) -> AffinePointVariable<E> {
+        assert_eq!(points.len(), selectors.len());
+        assert!(!points.is_empty(), "there are no points to sum");
+        self.curta_ec_request(|result| EcRequest::Sum {
+            points: points.to_vec(),
+            selectors: selectors.to_vec(),
+            result,
+        })
+    }
+
+    /// Computes `scalar * point` on ed25519 with the EC accelerator.
+    pub fn curta_ed25519_scalar_mul(
+        &mut self,
+        point: &AffinePointTarget<Ed25519>,
+        scalar: &NonNativeTarget<Ed25519Scalar>,
+    ) -> AffinePointTarget<Ed25519> {
+        let CurtaAffinePointTarget { x, y } = self.api.convert_to_curta_affine_point_target(point);
+        let point = AffinePointVariable::<CurtaEd25519> {
+            x: FieldVariable::new(x.into_iter().map(Variable).collect()),
+            y: FieldVariable::new(y.into_iter().map(Variable).collect()),
+        };
+        let scalar_bits_le = scalar
+            .value
+            .limbs
+            .iter()
+            .flat_map(|limb| self.api.split_le(limb.target, 32))
+            .map(BoolVariable::from)
+            .collect::<Vec<_>>();
+
+        let result = self.curta_ec_scalar_mul(&point, &scalar_bits_le);
+
+        let [x, y] = [&result.x, &result.y].map(|coordinate| {
+            coordinate
+                .variables()
+                .iter()
+                .map(|v| v.0)
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap()
+        });
+        let result = CurtaAffinePointTarget { x, y };
+        self.api.convert_from_curta_affine_point_target(&result)
+    }
+
+    /// Proves all the curve operations requested from the accelerator.
+    pub(crate) fn curta_constrain_ec(&mut self, accelerator: EcAccelerator) {
+        let shape = EcAirShape {
+            ed25519: accelerator.ed25519.iter().map(EcRequest::op).collect(),
+            secp256k1: accelerator.secp256k1.iter().map(EcRequest::op).collect(),
+            bn254: accelerator.bn254.iter().map(EcRequest::op).collect(),
+        };
+        match shape.num_curves() {
+            0 => {}
+            1 => self.curta_constrain_ec_air::<1>(&accelerator, shape),
+            2 => self.curta_constrain_ec_air::<2>(&accelerator, shape),
+            3 => self.curta_constrain_ec_air::<3>(&accelerator, shape),
+            _ => unreachable!(),
+        }
+    }
+
+    fn curta_constrain_ec_air<const NUM_CURVES: usize>(
+        &mut self,
+        accelerator: &EcAccelerator,
+        shape: EcAirShape,
+    ) {
+        let num_rows = shape.num_rows();
+        let EcAir {
+            air,
+            ed25519,
+            secp256k1,
+            bn254,
+            ..
+        } = EcAir::<L::Field, L::CubicParams, NUM_CURVES>::new(&shape);
+
+        let stark = Starky::new(air);
+        let config = StarkyConfig::<L::CurtaConfig, D>::standard_fast_config(num_rows);
+
+        let mut input_stream = VariableStream::new();
+        let ed25519_inputs = self.curta_ec_inputs(&accelerator.ed25519, &mut input_stream);
+        let secp256k1_inputs = self.curta_ec_inputs(&accelerator.secp256k1, &mut input_stream);
+        let bn254_inputs = self.curta_ec_inputs(&accelerator.bn254, &mut input_stream);
+
+        let hint = EcHint { shape };
+        let outputs = self.hint(input_stream, hint);
+
+        // The stark proof will verify that the public inputs are range checked.
+        let proof = outputs.read_stark_proof(self, &stark, &config);
+        let public_inputs = outputs.read_exact_unsafe(self, stark.air.num_public_inputs());
+        self.verify_stark_proof(&config, &stark, proof, &public_inputs);
+
+        // Bind the proven operations to the requested ones.
+        if let Some(section) = ed25519 {
+            self.curta_bind_ec_section(
+                &section,
+                &accelerator.ed25519,
+                ed25519_inputs,
+                &public_inputs,
+            );
+        }
+        if let Some(section) = secp256k1 {
+            self.curta_bind_ec_section(
+                &section,
+                &accelerator.secp256k1,
+                secp256k1_inputs,
+                &public_inputs,
+            );
+        }
+        if let Some(section) = bn254 {
+            self.curta_bind_ec_section(&section, &accelerator.bn254, bn254_inputs, &public_inputs);
+        }
+    }
+
+    /// Computes the row values of the requested operations and writes them to the input stream of
+    /// the [`EcHint`].
+    fn curta_ec_inputs<E: EcAirCurve>(
+        &mut self,
+        requests: &[EcRequest<E>],
+        input_stream: &mut VariableStream,
+    ) -> Vec<EcOpInputs<E>> {
+        let neutral = E::neutral();
+        let neutral = self.constant::<ProjectivePointVariable<E>>(ProjectivePointVariableValue {
+            x: neutral[0].clone(),
+            y: neutral[1].clone(),
+            z: neutral[2].clone(),
+        });
+        let true_var = self._true();
+        let false_var = self._false();
+
+        let mut inputs = Vec::with_capacity(requests.len());
+        for request in requests.iter() {
+            let op_inputs = match request {
+                EcRequest::Add { a, b, .. } => EcOpInputs {
+                    acc: ProjectivePointVariable::from_affine(self, a),
+                    multiple: ProjectivePointVariable::from_affine(self, b),
+                    loads: vec![],
+                    bits: vec![true_var],
+                },
+                EcRequest::Double { point, .. } => EcOpInputs {
+                    acc: neutral.clone(),
+                    multiple: ProjectivePointVariable::from_affine(self, point),
+                    loads: vec![],
+                    bits: vec![false_var, true_var],
+                },
+                EcRequest::ScalarMul {
+                    point,
+                    scalar_bits_le,
+                    ..
+                } => EcOpInputs {
+                    acc: neutral.clone(),
+                    multiple: ProjectivePointVariable::from_affine(self, point),
+                    loads: vec![],
+                    bits: scalar_bits_le.clone(),
+                },
+                EcRequest::Sum {
+                    points, selectors, ..
+                } => EcOpInputs {
+                    acc: neutral.clone(),
+                    multiple: ProjectivePointVariable::from_affine(self, &points[0]),
+                    loads: points[1..]
+                        .iter()
+                        .map(|point| ProjectivePointVariable::from_affine(self, point))
+                        .collect(),
+                    bits: selectors.clone(),
+                },
+            };
+
+            input_stream.write::<ProjectivePointVariable<E>>(&op_inputs.acc);
+            input_stream.write::<ProjectivePointVariable<E>>(&op_inputs.multiple);
+            for load in op_inputs.loads.iter() {
+                input_stream.write::<ProjectivePointVariable<E>>(load);
+            }
+            for bit in op_inputs.bits.iter() {
+                input_stream.write::<BoolVariable>(bit);
+            }
+            inputs.push(op_inputs);
+        }
+        inputs
+    }
+
+    /// Connects the public inputs of a curve of the [`EcAir`] to the requested operations.
+    fn curta_bind_ec_section<E: EcAirCurve>(
+        &mut self,
+        section: &EcSection<E>,
+        requests: &[EcRequest<E>],
+        inputs: Vec<EcOpInputs<E>>,
+        public_inputs: &[Variable],
+    ) {
+        let mut loads = section.loads.iter();
+        let mut bits = section.bits.iter();
+        for (((request, op_inputs), registers), result) in requests
+            .iter()
+            .zip(inputs)
+            .zip(section.inputs.iter())
+            .zip(section.results.iter())
+        {
+            let acc = ProjectivePointVariable::read_from_stark(&registers[0], public_inputs);
+            let multiple = ProjectivePointVariable::read_from_stark(&registers[1], public_inputs);
+            self.assert_is_equal(acc, op_inputs.acc);
+            self.assert_is_equal(multiple, op_inputs.multiple);
+
+            for load in op_inputs.loads {
+                let register = loads.next().unwrap();
+                let value = ProjectivePointVariable::read_from_stark(register, public_inputs);
+                self.assert_is_equal(value, load);
+            }
+
+            for bit in op_inputs.bits {
+                let value = bits.next().unwrap().read_from_slice(public_inputs);
+                self.assert_is_equal(bit.variable, value);
+            }
+
+            let result = AffinePointVariable::read_from_stark(result, public_inputs);
+            self.assert_is_equal(result, request.result().clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curta::chip::ec::point::AffinePoint;
+    use curta::chip::ec::weierstrass::bn254::Bn254;
+    use num::BigUint;
+    use plonky2::field::types::Sample;
+
+    use crate::frontend::curta::ec::point::AffinePointVariable;
+    use crate::frontend::curta::ec::secp256k1::Secp256k1;
+    use crate::frontend::ecc::ed25519::curve::curve_types::{Curve, CurveScalar};
+    use crate::frontend::ecc::ed25519::curve::ed25519::Ed25519;
+    use crate::frontend::ecc::ed25519::field::ed25519_scalar::Ed25519Scalar;
+    use crate::frontend::ecc::ed25519::gadgets::curve::CircuitBuilderCurve;
+    use crate::frontend::num::nonnative::nonnative::CircuitBuilderNonNative;
+    use crate::prelude::*;
+    use crate::utils::setup_logger;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_ec_accelerator() {
+        setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        // Two ed25519 scalar multiplications.
+        let ed_scalars = [Ed25519Scalar::rand(), Ed25519Scalar::rand()];
+        for scalar in ed_scalars {
+            let g = builder.api.constant_affine_point(Ed25519::GENERATOR_AFFINE);
+            let s = builder.api.constant_nonnative(scalar);
+            let result = builder.curta_ed25519_scalar_mul(&g, &s);
+            let expected = builder.api.constant_affine_point(
+                (CurveScalar(scalar) * Ed25519::GENERATOR_PROJECTIVE).to_affine(),
+            );
+            builder.api.connect_affine_point(&result, &expected);
+        }
+
+        // An addition, a doubling and a scalar multiplication on secp256k1, and the same on BN254.
+        let scalar = 0xdead_beefu32;
+        let bits = (0..32)
+            .map(|i| builder.constant::<BoolVariable>((scalar >> i) & 1 == 1))
+            .collect::<Vec<_>>();
+
+        let secp_generator = Secp256k1::generator();
+        let secp_a_value = secp_generator.sw_scalar_mul(&BigUint::from(3u32));
+        let secp_b_value = secp_generator.sw_scalar_mul(&BigUint::from(5u32));
+        let secp_a =
+            builder.constant::<AffinePointVariable<Secp256k1>>(secp_a_value.clone().into());
+        let secp_b =
+            builder.constant::<AffinePointVariable<Secp256k1>>(secp_b_value.clone().into());
+        let sum = builder.curta_ec_add(&secp_a, &secp_b);
+        builder.write(sum);
+        let double = builder.curta_ec_double(&secp_a);
+        builder.write(double);
+        let product = builder.curta_ec_scalar_mul(&secp_a, &bits);
+        builder.write(product);
+
+        let generator = Bn254::generator();
+        let a_value = generator.sw_scalar_mul(&BigUint::from(3u32));
+        let b_value = generator.sw_scalar_mul(&BigUint::from(5u32));
+        let a = builder.constant::<AffinePointVariable<Bn254>>(a_value.clone().into());
+        let b = builder.constant::<AffinePointVariable<Bn254>>(b_value.clone().into());
+        let sum = builder.curta_ec_add(&a, &b);
+        builder.write(sum);
+        let double = builder.curta_ec_double(&a);
+        builder.write(double);
+        let product = builder.curta_ec_scalar_mul(&a, &bits);
+        builder.write(product);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        circuit.test_default_serializers();
+
+        let sum_value = AffinePoint::from(output.read::<AffinePointVariable<Secp256k1>>());
+        assert_eq!(sum_value, secp_a_value.sw_add(&secp_b_value));
+        let double_value = AffinePoint::from(output.read::<AffinePointVariable<Secp256k1>>());
+        assert_eq!(
+            double_value,
+            secp_a_value.sw_scalar_mul(&BigUint::from(2u32))
+        );
+        let product_value = AffinePoint::from(output.read::<AffinePointVariable<Secp256k1>>());
+        assert_eq!(
+            product_value,
+            secp_a_value.sw_scalar_mul(&BigUint::from(scalar))
+        );
+
+        let sum_value = AffinePoint::from(output.read::<AffinePointVariable<Bn254>>());
+        assert_eq!(sum_value, a_value.sw_add(&b_value));
+        let double_value = AffinePoint::from(output.read::<AffinePointVariable<Bn254>>());
+        assert_eq!(double_value, a_value.sw_scalar_mul(&BigUint::from(2u32)));
+        let product_value = AffinePoint::from(output.read::<AffinePointVariable<Bn254>>());
+        assert_eq!(product_value, a_value.sw_scalar_mul(&BigUint::from(scalar)));
+    }
+}
@@ -1,3 +1 @@
-pub mod air;
 pub mod builder;
-pub mod hint;
@@ -1,2 +1,4 @@
+pub mod accelerator;
 pub mod aggregate;
 pub mod point;
+pub mod secp256k1;
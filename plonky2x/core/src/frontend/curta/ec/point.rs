@@ -1,7 +1,9 @@
 use curta::chip::ec::point::{AffinePoint, AffinePointRegister};
 use curta::chip::ec::EllipticCurve;
 use curta::chip::register::Register;
+use num::{BigUint, One};
 
+use super::accelerator::air::ProjectivePointRegister;
 use crate::frontend::curta::field::variable::FieldVariable;
 use crate::prelude::*;
 
@@ -20,6 +22,41 @@ impl<E: EllipticCurve> AffinePointVariable<E> {
     }
 }
 
+/// A point in projective coordinates `(x : y : z)`, as used by the
+/// [`EcAir`](super::accelerator::air::EcAir).
+#[derive(Debug, Clone, CircuitVariable)]
+pub struct ProjectivePointVariable<E: EllipticCurve> {
+    pub x: FieldVariable<E::BaseField>,
+    pub y: FieldVariable<E::BaseField>,
+    pub z: FieldVariable<E::BaseField>,
+}
+
+impl<E: EllipticCurve> ProjectivePointVariable<E> {
+    pub fn read_from_stark(
+        register: &ProjectivePointRegister<E::BaseField>,
+        public_inputs: &[Variable],
+    ) -> Self {
+        ProjectivePointVariable {
+            x: FieldVariable::new(register.x.read_from_slice(public_inputs).as_coefficients()),
+            y: FieldVariable::new(register.y.read_from_slice(public_inputs).as_coefficients()),
+            z: FieldVariable::new(register.z.read_from_slice(public_inputs).as_coefficients()),
+        }
+    }
+
+    /// The point `(x : y : 1)`.
+    pub fn from_affine<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        point: &AffinePointVariable<E>,
+    ) -> Self {
+        let one = builder.constant::<FieldVariable<E::BaseField>>(BigUint::one());
+        ProjectivePointVariable {
+            x: point.x.clone(),
+            y: point.y.clone(),
+            z: one,
+        }
+    }
+}
+
 impl<F: RichField, E: EllipticCurve> From<AffinePoint<E>> for AffinePointVariableValue<E, F> {
     fn from(value: AffinePoint<E>) -> Self {
         Self {
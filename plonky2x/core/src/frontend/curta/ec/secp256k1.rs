@@ -0,0 +1,107 @@
+//! The Curta parameters of secp256k1, which Curta does not provide, defined the same way as its
+//! BN254 parameters.
+
+use curta::chip::ec::weierstrass::{SWCurve, WeierstrassParameters};
+use curta::chip::ec::EllipticCurveParameters;
+use curta::chip::field::parameters::{FieldParameters, MAX_NB_LIMBS};
+use num::{BigUint, Num, Zero};
+use serde::{Deserialize, Serialize};
+
+/// The secp256k1 curve `y^2 = x^3 + 7`, see https://www.secg.org/sec2-v2.pdf.
+pub type Secp256k1 = SWCurve<Secp256k1Parameters>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Secp256k1Parameters;
+
+/// The base field of secp256k1, with modulus `2^256 - 2^32 - 977`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Secp256k1BaseField;
+
+impl FieldParameters for Secp256k1BaseField {
+    const NB_BITS_PER_LIMB: usize = 16;
+    const NB_LIMBS: usize = 16;
+    const NB_WITNESS_LIMBS: usize = 2 * Self::NB_LIMBS - 2;
+    const MODULUS: [u16; MAX_NB_LIMBS] = [
+        64559, 65535, 65534, 65535, 65535, 65535, 65535, 65535, 65535, 65535, 65535, 65535, 65535,
+        65535, 65535, 65535, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    const WITNESS_OFFSET: usize = 1usize << 20;
+
+    fn modulus() -> BigUint {
+        BigUint::from_str_radix(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            16,
+        )
+        .unwrap()
+    }
+}
+
+impl EllipticCurveParameters for Secp256k1Parameters {
+    type BaseField = Secp256k1BaseField;
+}
+
+impl WeierstrassParameters for Secp256k1Parameters {
+    const A: [u16; MAX_NB_LIMBS] = [0; MAX_NB_LIMBS];
+    const B: [u16; MAX_NB_LIMBS] = {
+        let mut b = [0; MAX_NB_LIMBS];
+        b[0] = 7;
+        b
+    };
+
+    fn generator() -> (BigUint, BigUint) {
+        let x = BigUint::from_str_radix(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            16,
+        )
+        .unwrap();
+        let y = BigUint::from_str_radix(
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            16,
+        )
+        .unwrap();
+        (x, y)
+    }
+
+    fn prime_group_order() -> BigUint {
+        BigUint::from_str_radix(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )
+        .unwrap()
+    }
+
+    fn a_int() -> BigUint {
+        BigUint::zero()
+    }
+
+    fn b_int() -> BigUint {
+        BigUint::from(7u32)
+    }
+
+    fn nb_scalar_bits() -> usize {
+        256
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curta::chip::ec::weierstrass::WeierstrassParameters;
+    use curta::chip::field::parameters::FieldParameters;
+    use num::BigUint;
+
+    use super::{Secp256k1BaseField, Secp256k1Parameters};
+
+    #[test]
+    fn test_secp256k1_parameters() {
+        let modulus = Secp256k1BaseField::modulus();
+        let limbs = (0..Secp256k1BaseField::NB_LIMBS)
+            .map(|i| BigUint::from(Secp256k1BaseField::MODULUS[i]) << (16 * i))
+            .sum::<BigUint>();
+        assert_eq!(limbs, modulus);
+
+        // The generator is on the curve.
+        let (x, y) = Secp256k1Parameters::generator();
+        let b = Secp256k1Parameters::b_int();
+        assert_eq!((&y * &y) % &modulus, (&x * &x * &x + b) % &modulus);
+    }
+}
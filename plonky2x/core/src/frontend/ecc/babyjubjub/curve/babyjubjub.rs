@@ -0,0 +1,118 @@
+use plonky2::field::types::Field;
+use serde::{Deserialize, Serialize};
+
+use crate::frontend::ecc::babyjubjub::field::babyjubjub_scalar::BabyJubJubScalar;
+use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+use crate::frontend::ecc::ed25519::curve::curve_types::{AffinePoint, Curve};
+
+/// The BabyJubJub curve used across the circom ecosystem (circomlib, Semaphore, Tornado).
+///
+/// The curve is defined over the BN254 scalar field and the generator is `Base8`, which spans the
+/// prime order subgroup. Parameters are taken from EIP-2494.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct BabyJubJub;
+
+impl Curve for BabyJubJub {
+    type BaseField = Bn254Scalar;
+    type ScalarField = BabyJubJubScalar;
+
+    const A: Bn254Scalar = Bn254Scalar([168700, 0, 0, 0]);
+    const D: Bn254Scalar = Bn254Scalar([168696, 0, 0, 0]);
+    const GENERATOR_AFFINE: AffinePoint<Self> = AffinePoint {
+        x: BABYJUBJUB_BASE8_X,
+        y: BABYJUBJUB_BASE8_Y,
+        zero: false,
+    };
+}
+
+/// 5299619240641551281634865583518297030282874472190772894086521144482721001553
+const BABYJUBJUB_BASE8_X: Bn254Scalar = Bn254Scalar([
+    0x2893F3F6BB957051,
+    0x2AB8D8010534E0B6,
+    0x4EACB2E09D6277C1,
+    0x0BB77A6AD63E739B,
+]);
+
+/// 16950150798460657717958625567821834550301663161624707787222815936182638968203
+const BABYJUBJUB_BASE8_Y: Bn254Scalar = Bn254Scalar([
+    0x4B3C257A872D7D8B,
+    0xFCE0051FB9E13377,
+    0x25572E1CD16BF9ED,
+    0x25797203F7A0B249,
+]);
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use plonky2::field::types::{Field, PrimeField};
+
+    use crate::frontend::ecc::babyjubjub::curve::babyjubjub::BabyJubJub;
+    use crate::frontend::ecc::babyjubjub::field::babyjubjub_scalar::BabyJubJubScalar;
+    use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+    use crate::frontend::ecc::ed25519::curve::curve_types::{AffinePoint, Curve, ProjectivePoint};
+
+    #[test]
+    fn test_generator() {
+        let g = BabyJubJub::GENERATOR_AFFINE;
+        assert!(g.is_valid());
+        assert!(g.to_projective().is_valid());
+        assert!(BabyJubJub::is_safe_curve());
+    }
+
+    #[test]
+    fn test_generator_order() {
+        // The order of `Base8` is the order of the scalar field, so `(l - 1) * G = -G`.
+        let g = BabyJubJub::GENERATOR_PROJECTIVE;
+        let minus_one = BabyJubJub::convert(BabyJubJubScalar::NEG_ONE);
+        assert_eq!(minus_one * g, g.neg());
+    }
+
+    fn point(x: &[u8], y: &[u8]) -> AffinePoint<BabyJubJub> {
+        AffinePoint::nonzero(
+            Bn254Scalar::from_noncanonical_biguint(BigUint::parse_bytes(x, 10).unwrap()),
+            Bn254Scalar::from_noncanonical_biguint(BigUint::parse_bytes(y, 10).unwrap()),
+        )
+    }
+
+    #[test]
+    fn test_eip2494_addition() {
+        // Test vector from EIP-2494.
+        let p1 = point(
+            b"17777552123799933955779906779655732241715742912184938656739573121738514868268",
+            b"2626589144620713026669568689430873010625803728049924121243784502389097019475",
+        );
+        let p2 = point(
+            b"16540640123574156134436876038791482806971768689494387082833631921987005038935",
+            b"20819045374670962167435360035096875258406992893633759881276124905556507972311",
+        );
+        let expected = point(
+            b"7916061937171219682591368294088513039687205273691143098332585753343424131937",
+            b"14035240266687799601661095864649209771790948434046947201833777492504781204499",
+        );
+        assert_eq!(p1 + p2, expected);
+        assert_eq!(
+            (p1.to_projective() + p2.to_projective()).to_affine(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_naive_multiplication() {
+        let g = BabyJubJub::GENERATOR_PROJECTIVE;
+        let lhs = BabyJubJubScalar::from_noncanonical_biguint(BigUint::from_slice(&[
+            1111, 2222, 3333, 4444, 5555, 6666, 7777, 888,
+        ]));
+        let mut sum = ProjectivePoint::<BabyJubJub>::ZERO;
+        let mut doubled = g;
+        for limb in lhs.to_canonical_biguint().to_u64_digits().iter() {
+            for j in 0..64 {
+                if (limb >> j & 1u64) != 0u64 {
+                    sum = sum + doubled;
+                }
+                doubled = doubled.double();
+            }
+        }
+        assert!(sum.is_valid());
+        assert_eq!(BabyJubJub::convert(lhs) * g, sum);
+    }
+}
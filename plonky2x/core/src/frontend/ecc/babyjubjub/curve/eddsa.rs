@@ -0,0 +1,82 @@
+use plonky2::field::types::{Field, PrimeField};
+
+use crate::frontend::ecc::babyjubjub::curve::babyjubjub::BabyJubJub;
+use crate::frontend::ecc::babyjubjub::field::babyjubjub_scalar::BabyJubJubScalar;
+use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+use crate::frontend::ecc::ed25519::curve::curve_types::{AffinePoint, Curve, CurveScalar};
+use crate::frontend::ecc::ed25519::curve::eddsa::{EDDSAPublicKey, EDDSASignature};
+use crate::frontend::hash::poseidon::poseidon_bn254::PoseidonBn254Params;
+
+/// Computes the EdDSA-Poseidon challenge `poseidon(R.x, R.y, A.x, A.y, msg)`.
+pub fn challenge(
+    r: &AffinePoint<BabyJubJub>,
+    pk: &EDDSAPublicKey<BabyJubJub>,
+    msg: Bn254Scalar,
+) -> Bn254Scalar {
+    PoseidonBn254Params::new(6).hash(&[r.x, r.y, pk.0.x, pk.0.y, msg])
+}
+
+/// Signs `msg` with the secret scalar `sk` and the nonce `r`, such that the public key is
+/// `sk * Base8`. This follows circomlib's `signPoseidon` once the secret scalar and nonce have been
+/// derived from the private key.
+pub fn sign_message(
+    msg: Bn254Scalar,
+    sk: BabyJubJubScalar,
+    r: BabyJubJubScalar,
+) -> (EDDSASignature<BabyJubJub>, EDDSAPublicKey<BabyJubJub>) {
+    let pk = EDDSAPublicKey((CurveScalar(sk) * BabyJubJub::GENERATOR_PROJECTIVE).to_affine());
+    let r_point = (CurveScalar(r) * BabyJubJub::GENERATOR_PROJECTIVE).to_affine();
+    let h = challenge(&r_point, &pk, msg);
+    let h = BabyJubJubScalar::from_noncanonical_biguint(
+        h.to_canonical_biguint() % BabyJubJubScalar::order(),
+    );
+    let eight = BabyJubJubScalar::from_canonical_u64(8);
+    let s = r + eight * h * sk;
+    (EDDSASignature { r: r_point, s }, pk)
+}
+
+/// Verifies an EdDSA-Poseidon signature as circomlib's `EdDSAPoseidonVerifier` does, by checking
+/// that `S * Base8 = R + 8 * h * A`.
+pub fn verify_message(
+    msg: Bn254Scalar,
+    sig: &EDDSASignature<BabyJubJub>,
+    pk: &EDDSAPublicKey<BabyJubJub>,
+) -> bool {
+    assert!(pk.0.is_valid());
+    assert!(sig.r.is_valid());
+
+    let h = challenge(&sig.r, pk, msg);
+    let h = BabyJubJubScalar::from_noncanonical_biguint(
+        h.to_canonical_biguint() % BabyJubJubScalar::order(),
+    );
+
+    // Multiplying by the cofactor first maps the public key into the prime order subgroup, where
+    // the challenge can be reduced modulo the subgroup order.
+    let pk_8 = pk.0.double().double().double();
+    let s_g = (CurveScalar(sig.s) * BabyJubJub::GENERATOR_PROJECTIVE).to_affine();
+    let h_pk = (CurveScalar(h) * pk_8.to_projective()).to_affine();
+    let rhs = sig.r + h_pk;
+
+    s_g == rhs
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::{Field, Sample};
+
+    use crate::frontend::ecc::babyjubjub::curve::eddsa::{sign_message, verify_message};
+    use crate::frontend::ecc::babyjubjub::field::babyjubjub_scalar::BabyJubJubScalar;
+    use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+
+    #[test]
+    fn test_eddsa_poseidon() {
+        let msg = Bn254Scalar::from_canonical_u64(1234);
+        let sk = BabyJubJubScalar::rand();
+        let r = BabyJubJubScalar::rand();
+        let (sig, pk) = sign_message(msg, sk, r);
+        assert!(verify_message(msg, &sig, &pk));
+
+        let wrong_msg = Bn254Scalar::from_canonical_u64(1235);
+        assert!(!verify_message(wrong_msg, &sig, &pk));
+    }
+}
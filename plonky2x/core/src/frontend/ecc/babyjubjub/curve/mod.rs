@@ -0,0 +1,2 @@
+pub mod babyjubjub;
+pub mod eddsa;
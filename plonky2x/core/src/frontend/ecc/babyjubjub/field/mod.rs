@@ -0,0 +1,2 @@
+pub mod babyjubjub_scalar;
+pub mod bn254_scalar;
@@ -0,0 +1,102 @@
+use crate::frontend::ecc::babyjubjub::curve::babyjubjub::BabyJubJub;
+use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+use crate::frontend::ecc::ed25519::curve::curve_types::Curve;
+use crate::frontend::ecc::ed25519::gadgets::curve::CircuitBuilderCurve;
+use crate::frontend::ecc::ed25519::gadgets::eddsa::{
+    assert_signature_scalar_canonical, EDDSAPublicKeyTarget, EDDSASignatureTarget,
+};
+use crate::frontend::ecc::msm::edwards::TwistedEdwards;
+use crate::frontend::ecc::msm::CircuitBuilderMsm;
+use crate::frontend::num::nonnative::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use crate::prelude::{CircuitBuilder, PlonkParameters};
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Verifies an EdDSA-Poseidon signature over BabyJubJub, matching circomlib's
+    /// `EdDSAPoseidonVerifier`: checks that `S * Base8 = R + 8 * h * A` where
+    /// `h = poseidon(R.x, R.y, A.x, A.y, msg)` and that `S` is less than the subgroup order.
+    pub fn babyjubjub_eddsa_poseidon_verify(
+        &mut self,
+        msg: &NonNativeTarget<Bn254Scalar>,
+        sig: &EDDSASignatureTarget<BabyJubJub>,
+        pk: &EDDSAPublicKeyTarget<BabyJubJub>,
+    ) {
+        self.api.curve_assert_valid(&pk.0);
+        self.api.curve_assert_valid(&sig.r);
+        assert_signature_scalar_canonical(&mut self.api, &sig.s);
+
+        let h = self.poseidon_bn254(&[
+            sig.r.x.clone(),
+            sig.r.y.clone(),
+            pk.0.x.clone(),
+            pk.0.y.clone(),
+            msg.clone(),
+        ]);
+        let h_bits = self.api.split_nonnative_to_bits(&h);
+
+        let pk_2 = self.api.curve_add(&pk.0, &pk.0);
+        let pk_4 = self.api.curve_add(&pk_2, &pk_2);
+        let pk_8 = self.api.curve_add(&pk_4, &pk_4);
+        let h_pk = self
+            .api
+            .curve_msm::<TwistedEdwards<BabyJubJub>>(&[pk_8], &[h_bits]);
+        let rhs = self.api.curve_add(&sig.r, &h_pk);
+
+        let s_bits = self.api.split_nonnative_to_bits(&sig.s);
+        let lhs = self
+            .api
+            .curve_fixed_base_scalar_mul::<TwistedEdwards<BabyJubJub>>(
+                BabyJubJub::GENERATOR_PROJECTIVE,
+                &s_bits,
+            );
+
+        self.api.connect_affine_point(&lhs, &rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::{Field, Sample};
+
+    use crate::frontend::ecc::babyjubjub::curve::babyjubjub::BabyJubJub;
+    use crate::frontend::ecc::babyjubjub::curve::eddsa::sign_message;
+    use crate::frontend::ecc::babyjubjub::field::babyjubjub_scalar::BabyJubJubScalar;
+    use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+    use crate::frontend::ecc::ed25519::gadgets::curve::AffinePointTarget;
+    use crate::frontend::ecc::ed25519::gadgets::eddsa::{
+        EDDSAPublicKeyTarget, EDDSASignatureTarget, EDDSASignatureTargetValue,
+    };
+    use crate::frontend::num::nonnative::nonnative::NonNativeTarget;
+    use crate::prelude::DefaultBuilder;
+    use crate::utils;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_babyjubjub_eddsa_poseidon_verify() {
+        utils::setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        let msg = builder.read::<NonNativeTarget<Bn254Scalar>>();
+        let sig = builder.read::<EDDSASignatureTarget<BabyJubJub>>();
+        let pk = EDDSAPublicKeyTarget(builder.read::<AffinePointTarget<BabyJubJub>>());
+        builder.babyjubjub_eddsa_poseidon_verify(&msg, &sig, &pk);
+
+        let circuit = builder.build();
+
+        let msg_value = Bn254Scalar::from_canonical_u64(1234);
+        let (sig_value, pk_value) = sign_message(
+            msg_value,
+            BabyJubJubScalar::rand(),
+            BabyJubJubScalar::rand(),
+        );
+
+        let mut input = circuit.input();
+        input.write::<NonNativeTarget<Bn254Scalar>>(msg_value);
+        input.write::<EDDSASignatureTarget<BabyJubJub>>(EDDSASignatureTargetValue {
+            r: sig_value.r,
+            s: sig_value.s,
+        });
+        input.write::<AffinePointTarget<BabyJubJub>>(pk_value.0);
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
@@ -0,0 +1,118 @@
+use plonky2::field::types::Field;
+
+use crate::frontend::ecc::bn254::field::bn254_base::Bn254Base;
+
+/// The coefficient `b` of the BN254 curve `y^2 = x^3 + b` over the base field.
+pub const G1_B: u64 = 3;
+
+/// A point of the BN254 curve `E: y^2 = x^3 + 3` over `Fp`, in affine coordinates.
+///
+/// The point at infinity is not representable. This is enough for verifying keys and other
+/// constants, which are never the neutral element.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct G1Affine {
+    pub x: Bn254Base,
+    pub y: Bn254Base,
+}
+
+/// A point of the sextic twist `E': y^2 = x^3 + 3 / (9 + u)` over `Fp2`, in affine coordinates.
+/// Each coordinate is stored as `[c0, c1]` for `c0 + c1 * u`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct G2Affine {
+    pub x: [Bn254Base; 2],
+    pub y: [Bn254Base; 2],
+}
+
+impl G1Affine {
+    pub fn generator() -> Self {
+        Self {
+            x: Bn254Base::ONE,
+            y: Bn254Base::TWO,
+        }
+    }
+
+    pub fn from_decimal_strs(x: &str, y: &str) -> Self {
+        Self {
+            x: Bn254Base::from_decimal_str(x),
+            y: Bn254Base::from_decimal_str(y),
+        }
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        self.y.square() == self.x.cube() + Bn254Base::from_canonical_u64(G1_B)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self {
+            x: self.x,
+            y: -self.y,
+        }
+    }
+
+    pub fn double(&self) -> Self {
+        let lambda = (self.x.square() * Bn254Base::from_canonical_u64(3)) / self.y.double();
+        self.with_slope(lambda, self.x)
+    }
+
+    /// Adds two points with distinct `x` coordinates.
+    pub fn add(&self, other: &Self) -> Self {
+        assert_ne!(
+            self.x, other.x,
+            "incomplete addition of points with equal x"
+        );
+        let lambda = (other.y - self.y) / (other.x - self.x);
+        self.with_slope(lambda, other.x)
+    }
+
+    fn with_slope(&self, lambda: Bn254Base, other_x: Bn254Base) -> Self {
+        let x = lambda.square() - self.x - other_x;
+        let y = lambda * (self.x - x) - self.y;
+        Self { x, y }
+    }
+}
+
+impl G2Affine {
+    pub fn generator() -> Self {
+        Self::from_decimal_strs(
+            (
+                "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+                "11559732032986387107991004021392285783925812861821192530917403151452391805634",
+            ),
+            (
+                "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+                "4082367875863433681332203403145435568316851327593401208105741076214120093531",
+            ),
+        )
+    }
+
+    pub fn from_decimal_strs(x: (&str, &str), y: (&str, &str)) -> Self {
+        Self {
+            x: [
+                Bn254Base::from_decimal_str(x.0),
+                Bn254Base::from_decimal_str(x.1),
+            ],
+            y: [
+                Bn254Base::from_decimal_str(y.0),
+                Bn254Base::from_decimal_str(y.1),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::G1Affine;
+
+    #[test]
+    fn test_g1_arithmetic() {
+        let g = G1Affine::generator();
+        assert!(g.is_on_curve());
+
+        let two_g = g.double();
+        let three_g = two_g.add(&g);
+        assert!(two_g.is_on_curve());
+        assert!(three_g.is_on_curve());
+        assert_eq!(three_g.add(&g), two_g.double());
+        assert_eq!(three_g.add(&g.neg()), two_g);
+    }
+}
@@ -0,0 +1,315 @@
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::BoolTarget;
+
+use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+use crate::frontend::ecc::bn254::curve::bn254::{G1Affine, G2Affine, G1_B};
+use crate::frontend::ecc::bn254::field::bn254_base::Bn254Base;
+use crate::frontend::ecc::bn254::gadgets::fp2::Fp2Variable;
+use crate::frontend::num::nonnative::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use crate::prelude::{CircuitBuilder, CircuitVariable, PlonkParameters, Variable};
+
+/// A point of the BN254 curve over `Fp` in affine coordinates.
+#[derive(Clone, Debug, CircuitVariable)]
+pub struct G1AffineVariable {
+    pub x: NonNativeTarget<Bn254Base>,
+    pub y: NonNativeTarget<Bn254Base>,
+}
+
+/// A point of the BN254 sextic twist over `Fp2` in affine coordinates.
+#[derive(Clone, Debug, CircuitVariable)]
+pub struct G2AffineVariable {
+    pub x: Fp2Variable,
+    pub y: Fp2Variable,
+}
+
+/// The coefficient `3 / (9 + u)` of the twist, as `(c0, c1)`.
+const G2_B: (&str, &str) = (
+    "19485874751759354771024239261021720505790618469301721065564631296452457478373",
+    "266929791119991161246907387137283842545076965332900288569378510910307636690",
+);
+
+/// The scalar `6x^2`, by which the endomorphism `psi` acts on G2, for the BN254 parameter `x`.
+const SIX_X_SQUARED: u128 = 147946756881789318990833708069417712966;
+
+/// A G1 point with no known relation to the generator, used to keep the accumulator of
+/// `g1_linear_combination` away from the neutral element. It is `k * G` for `k` the SHA-256 digest
+/// of `"plonky2x bn254 g1 offset"` reduced modulo the group order.
+const G1_OFFSET: (&str, &str) = (
+    "10154191288374019521965406363986564697448946972921859290360988593560619009280",
+    "10834380341940984477294921341981540470212302847045379633080371549413418889377",
+);
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    pub fn g1_constant(&mut self, point: G1Affine) -> G1AffineVariable {
+        G1AffineVariable {
+            x: self.api.constant_nonnative(point.x),
+            y: self.api.constant_nonnative(point.y),
+        }
+    }
+
+    pub fn g2_constant(&mut self, point: G2Affine) -> G2AffineVariable {
+        G2AffineVariable {
+            x: self.fp2_constant(point.x[0], point.x[1]),
+            y: self.fp2_constant(point.y[0], point.y[1]),
+        }
+    }
+
+    pub fn g1_connect(&mut self, a: &G1AffineVariable, b: &G1AffineVariable) {
+        self.api.connect_nonnative(&a.x, &b.x);
+        self.api.connect_nonnative(&a.y, &b.y);
+    }
+
+    pub fn g2_connect(&mut self, a: &G2AffineVariable, b: &G2AffineVariable) {
+        self.fp2_connect(&a.x, &b.x);
+        self.fp2_connect(&a.y, &b.y);
+    }
+
+    pub fn g1_neg(&mut self, a: &G1AffineVariable) -> G1AffineVariable {
+        G1AffineVariable {
+            x: a.x.clone(),
+            y: self.api.neg_nonnative(&a.y),
+        }
+    }
+
+    pub fn g2_neg(&mut self, a: &G2AffineVariable) -> G2AffineVariable {
+        G2AffineVariable {
+            x: a.x.clone(),
+            y: self.fp2_neg(&a.y),
+        }
+    }
+
+    /// Asserts that `a` satisfies `y^2 = x^3 + 3`. Since G1 has cofactor one, this also places `a`
+    /// in the prime order subgroup.
+    pub fn g1_assert_on_curve(&mut self, a: &G1AffineVariable) {
+        let y_squared = self.api.mul_nonnative(&a.y, &a.y);
+        let x_squared = self.api.mul_nonnative(&a.x, &a.x);
+        let x_cubed = self.api.mul_nonnative(&x_squared, &a.x);
+        let b = self
+            .api
+            .constant_nonnative(Bn254Base::from_canonical_u64(G1_B));
+        let rhs = self.api.add_nonnative(&x_cubed, &b);
+        self.api.connect_nonnative(&y_squared, &rhs);
+    }
+
+    /// Asserts that `a` satisfies `y^2 = x^3 + 3 / (9 + u)`. This does not check membership in the
+    /// prime order subgroup.
+    pub fn g2_assert_on_curve(&mut self, a: &G2AffineVariable) {
+        let y_squared = self.fp2_square(&a.y);
+        let x_squared = self.fp2_square(&a.x);
+        let x_cubed = self.fp2_mul(&x_squared, &a.x);
+        let b = self.fp2_constant(
+            Bn254Base::from_decimal_str(G2_B.0),
+            Bn254Base::from_decimal_str(G2_B.1),
+        );
+        let rhs = self.fp2_add(&x_cubed, &b);
+        self.fp2_connect(&y_squared, &rhs);
+    }
+
+    /// Asserts that `a` lies in the prime order subgroup G2 of the twist by checking
+    /// `psi(a) = [6x^2] a`, following El Housni, Guillevic and Piellard (2022).
+    ///
+    /// The scalar multiplication uses incomplete formulas, which can only fail for points outside
+    /// G2, so every such point makes the circuit unsatisfiable.
+    pub fn g2_assert_in_subgroup(&mut self, a: &G2AffineVariable) {
+        let psi = self.g2_psi(a);
+        let mut acc = a.clone();
+        for i in (0..127 - SIX_X_SQUARED.leading_zeros() as usize).rev() {
+            acc = self.g2_double(&acc);
+            if (SIX_X_SQUARED >> i) & 1 == 1 {
+                acc = self.g2_add(&acc, a);
+            }
+        }
+        self.g2_connect(&acc, &psi);
+    }
+
+    /// The endomorphism `psi = phi^-1 * pi * phi` of the twist, where `phi` maps the twist to the
+    /// curve over `Fp12` and `pi` is the Frobenius endomorphism. It acts on G2 as multiplication by
+    /// `p`.
+    pub fn g2_psi(&mut self, a: &G2AffineVariable) -> G2AffineVariable {
+        let x_conjugate = self.fp2_conjugate(&a.x);
+        let y_conjugate = self.fp2_conjugate(&a.y);
+        let gamma_x = self.fp12_frobenius_coeff(1, 2);
+        let gamma_y = self.fp12_frobenius_coeff(1, 3);
+        G2AffineVariable {
+            x: self.fp2_mul(&x_conjugate, &gamma_x),
+            y: self.fp2_mul(&y_conjugate, &gamma_y),
+        }
+    }
+
+    /// Doubles a G2 point with a nonzero `y` coordinate. The circuit is unsatisfiable otherwise.
+    pub fn g2_double(&mut self, a: &G2AffineVariable) -> G2AffineVariable {
+        let x_squared = self.fp2_square(&a.x);
+        let numerator = self.fp2_mul_by_u64(&x_squared, 3);
+        let denominator = self.fp2_add(&a.y, &a.y);
+        let denominator_inv = self.fp2_inverse(&denominator);
+        let lambda = self.fp2_mul(&numerator, &denominator_inv);
+        self.g2_with_slope(a, &a.x, &lambda)
+    }
+
+    /// Adds two G2 points with distinct `x` coordinates. The circuit is unsatisfiable otherwise.
+    pub fn g2_add(&mut self, a: &G2AffineVariable, b: &G2AffineVariable) -> G2AffineVariable {
+        let dy = self.fp2_sub(&b.y, &a.y);
+        let dx = self.fp2_sub(&b.x, &a.x);
+        let dx_inv = self.fp2_inverse(&dx);
+        let lambda = self.fp2_mul(&dy, &dx_inv);
+        self.g2_with_slope(a, &b.x, &lambda)
+    }
+
+    /// Returns the third intersection of the line of slope `lambda` through `a`, negated.
+    fn g2_with_slope(
+        &mut self,
+        a: &G2AffineVariable,
+        other_x: &Fp2Variable,
+        lambda: &Fp2Variable,
+    ) -> G2AffineVariable {
+        let lambda_squared = self.fp2_square(lambda);
+        let x_sum = self.fp2_add(&a.x, other_x);
+        let x = self.fp2_sub(&lambda_squared, &x_sum);
+        let x_diff = self.fp2_sub(&a.x, &x);
+        let lambda_x_diff = self.fp2_mul(lambda, &x_diff);
+        let y = self.fp2_sub(&lambda_x_diff, &a.y);
+        G2AffineVariable { x, y }
+    }
+
+    /// Adds two G1 points with distinct `x` coordinates. The circuit is unsatisfiable otherwise.
+    pub fn g1_add(&mut self, a: &G1AffineVariable, b: &G1AffineVariable) -> G1AffineVariable {
+        let dy = self.api.sub_nonnative(&b.y, &a.y);
+        let dx = self.api.sub_nonnative(&b.x, &a.x);
+        let dx_inv = self.api.inv_nonnative(&dx);
+        let lambda = self.api.mul_nonnative(&dy, &dx_inv);
+
+        let lambda_squared = self.api.mul_nonnative(&lambda, &lambda);
+        let x_sum = self.api.add_nonnative(&a.x, &b.x);
+        let x = self.api.sub_nonnative(&lambda_squared, &x_sum);
+        let x_diff = self.api.sub_nonnative(&a.x, &x);
+        let lambda_x_diff = self.api.mul_nonnative(&lambda, &x_diff);
+        let y = self.api.sub_nonnative(&lambda_x_diff, &a.y);
+        G1AffineVariable { x, y }
+    }
+
+    /// Returns `a` if `b` is true and `c` otherwise.
+    pub fn g1_select(
+        &mut self,
+        b: BoolTarget,
+        a: &G1AffineVariable,
+        c: &G1AffineVariable,
+    ) -> G1AffineVariable {
+        G1AffineVariable {
+            x: self.api.if_nonnative(b, &a.x, &c.x),
+            y: self.api.if_nonnative(b, &a.y, &c.y),
+        }
+    }
+
+    /// Computes `base + sum_i scalars[i] * points[i]` for points known at circuit build time.
+    ///
+    /// The multiples `2^j * points[i]` are computed natively, so the circuit only performs one
+    /// conditional addition per scalar bit. The accumulator starts at a fixed offset point, which
+    /// is subtracted at the end, so that the incomplete addition formulas never meet the neutral
+    /// element for honest inputs.
+    pub fn g1_linear_combination(
+        &mut self,
+        base: G1Affine,
+        points: &[G1Affine],
+        scalars: &[NonNativeTarget<Bn254Scalar>],
+    ) -> G1AffineVariable {
+        assert_eq!(points.len(), scalars.len());
+        let offset = G1Affine::from_decimal_strs(G1_OFFSET.0, G1_OFFSET.1);
+
+        let mut acc = self.g1_constant(base.add(&offset));
+        for (point, scalar) in points.iter().zip(scalars.iter()) {
+            let bits = self.api.split_nonnative_to_bits(scalar);
+            let mut multiple = *point;
+            for bit in bits {
+                let multiple_target = self.g1_constant(multiple);
+                let sum = self.g1_add(&acc, &multiple_target);
+                acc = self.g1_select(bit, &sum, &acc);
+                multiple = multiple.double();
+            }
+        }
+
+        let neg_offset = self.g1_constant(offset.neg());
+        self.g1_add(&acc, &neg_offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::Field;
+
+    use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+    use crate::frontend::ecc::bn254::curve::bn254::{G1Affine, G2Affine};
+    use crate::frontend::num::nonnative::nonnative::CircuitBuilderNonNative;
+    use crate::prelude::DefaultBuilder;
+    use crate::utils;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_g1_linear_combination() {
+        utils::setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        let g = G1Affine::generator();
+        let two_g = g.double();
+        let scalars = [
+            builder
+                .api
+                .constant_nonnative(Bn254Scalar::from_canonical_u64(5)),
+            builder
+                .api
+                .constant_nonnative(Bn254Scalar::from_canonical_u64(3)),
+        ];
+
+        // g + 5 * (2 * g) + 3 * g = 14 * g
+        let result = builder.g1_linear_combination(g, &[two_g, g], &scalars);
+        let seven_g = two_g.double().add(&two_g).add(&g);
+        let expected = builder.g1_constant(seven_g.double());
+        builder.g1_connect(&result, &expected);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_g2_assert_in_subgroup() {
+        utils::setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        let g = builder.g2_constant(G2Affine::generator());
+        builder.g2_assert_on_curve(&g);
+        builder.g2_assert_in_subgroup(&g);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_g2_assert_in_subgroup_outside() {
+        utils::setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        // A point of the twist with x = 1, whose order is not the group order.
+        let point = G2Affine::from_decimal_strs(
+            ("1", "0"),
+            (
+                "18278151005453108793778860132295291098363647455926340152056652516292830556603",
+                "5912654199736721486680175016176231956195085055698687135131307249486702594212",
+            ),
+        );
+        let point = builder.g2_constant(point);
+        builder.g2_assert_on_curve(&point);
+        builder.g2_assert_in_subgroup(&point);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
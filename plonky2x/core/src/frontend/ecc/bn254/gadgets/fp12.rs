@@ -0,0 +1,283 @@
+use plonky2::hash::hash_types::RichField;
+
+use crate::frontend::ecc::bn254::field::bn254_base::Bn254Base;
+use crate::frontend::ecc::bn254::gadgets::fp2::Fp2Variable;
+use crate::frontend::ecc::bn254::gadgets::fp6::Fp6Variable;
+use crate::frontend::num::nonnative::nonnative::NonNativeTarget;
+use crate::prelude::{CircuitBuilder, CircuitVariable, PlonkParameters, Variable};
+
+/// An element `c0 + c1 * w` of `Fp12 = Fp6[w] / (w^2 - v)`.
+#[derive(Clone, Debug, CircuitVariable)]
+pub struct Fp12Variable {
+    pub c0: Fp6Variable,
+    pub c1: Fp6Variable,
+}
+
+/// The coefficients `xi^(i * (p^k - 1) / 6)` for `k = 1, 2, 3` and `i = 0..6`, as decimal strings.
+const FROBENIUS_COEFFS: [[(&str, &str); 6]; 3] = [
+    [
+        ("1", "0"),
+        (
+            "8376118865763821496583973867626364092589906065868298776909617916018768340080",
+            "16469823323077808223889137241176536799009286646108169935659301613961712198316",
+        ),
+        (
+            "21575463638280843010398324269430826099269044274347216827212613867836435027261",
+            "10307601595873709700152284273816112264069230130616436755625194854815875713954",
+        ),
+        (
+            "2821565182194536844548159561693502659359617185244120367078079554186484126554",
+            "3505843767911556378687030309984248845540243509899259641013678093033130930403",
+        ),
+        (
+            "2581911344467009335267311115468803099551665605076196740867805258568234346338",
+            "19937756971775647987995932169929341994314640652964949448313374472400716661030",
+        ),
+        (
+            "685108087231508774477564247770172212460312782337200605669322048753928464687",
+            "8447204650696766136447902020341177575205426561248465145919723016860428151883",
+        ),
+    ],
+    [
+        ("1", "0"),
+        (
+            "21888242871839275220042445260109153167277707414472061641714758635765020556617",
+            "0",
+        ),
+        (
+            "21888242871839275220042445260109153167277707414472061641714758635765020556616",
+            "0",
+        ),
+        (
+            "21888242871839275222246405745257275088696311157297823662689037894645226208582",
+            "0",
+        ),
+        (
+            "2203960485148121921418603742825762020974279258880205651966",
+            "0",
+        ),
+        (
+            "2203960485148121921418603742825762020974279258880205651967",
+            "0",
+        ),
+    ],
+    [
+        ("1", "0"),
+        (
+            "11697423496358154304825782922584725312912383441159505038794027105778954184319",
+            "303847389135065887422783454877609941456349188919719272345083954437860409601",
+        ),
+        (
+            "3772000881919853776433695186713858239009073593817195771773381919316419345261",
+            "2236595495967245188281701248203181795121068902605861227855261137820944008926",
+        ),
+        (
+            "19066677689644738377698246183563772429336693972053703295610958340458742082029",
+            "18382399103927718843559375435273026243156067647398564021675359801612095278180",
+        ),
+        (
+            "5324479202449903542726783395506214481928257762400643279780343368557297135718",
+            "16208900380737693084919495127334387981393726419856888799917914180988844123039",
+        ),
+        (
+            "8941241848238582420466759817324047081148088512956452953208002715982955420483",
+            "10338197737521362862238855242243140895517409139741313354160881284257516364953",
+        ),
+    ],
+];
+
+impl Fp12Variable {
+    /// The coefficients of `self` in the basis `1, w, ..., w^5` of `Fp12` over `Fp2`.
+    fn to_w_basis(&self) -> [Fp2Variable; 6] {
+        [
+            self.c0.c0.clone(),
+            self.c1.c0.clone(),
+            self.c0.c1.clone(),
+            self.c1.c1.clone(),
+            self.c0.c2.clone(),
+            self.c1.c2.clone(),
+        ]
+    }
+
+    fn from_w_basis(coeffs: [Fp2Variable; 6]) -> Self {
+        let [g0, g1, g2, g3, g4, g5] = coeffs;
+        Self {
+            c0: Fp6Variable {
+                c0: g0,
+                c1: g2,
+                c2: g4,
+            },
+            c1: Fp6Variable {
+                c0: g1,
+                c1: g3,
+                c2: g5,
+            },
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Creates a constant from its twelve base field coefficients, ordered as
+    /// `c0.c0.c0, c0.c0.c1, c0.c1.c0, ..., c1.c2.c1`.
+    pub fn fp12_constant(&mut self, coeffs: &[Bn254Base; 12]) -> Fp12Variable {
+        let mut fp2s = coeffs
+            .chunks(2)
+            .map(|c| self.fp2_constant(c[0], c[1]))
+            .collect::<Vec<_>>()
+            .into_iter();
+        let mut fp6 = || Fp6Variable {
+            c0: fp2s.next().unwrap(),
+            c1: fp2s.next().unwrap(),
+            c2: fp2s.next().unwrap(),
+        };
+        Fp12Variable {
+            c0: fp6(),
+            c1: fp6(),
+        }
+    }
+
+    pub fn fp12_one(&mut self) -> Fp12Variable {
+        Fp12Variable {
+            c0: self.fp6_one(),
+            c1: self.fp6_zero(),
+        }
+    }
+
+    pub fn fp12_connect(&mut self, a: &Fp12Variable, b: &Fp12Variable) {
+        self.fp6_connect(&a.c0, &b.c0);
+        self.fp6_connect(&a.c1, &b.c1);
+    }
+
+    pub fn fp12_mul(&mut self, a: &Fp12Variable, b: &Fp12Variable) -> Fp12Variable {
+        let t0 = self.fp6_mul(&a.c0, &b.c0);
+        let t1 = self.fp6_mul(&a.c1, &b.c1);
+        let v_t1 = self.fp6_mul_by_nonresidue(&t1);
+        let c0 = self.fp6_add(&t0, &v_t1);
+
+        let a_sum = self.fp6_add(&a.c0, &a.c1);
+        let b_sum = self.fp6_add(&b.c0, &b.c1);
+        let cross = self.fp6_mul(&a_sum, &b_sum);
+        let cross = self.fp6_sub(&cross, &t0);
+        let c1 = self.fp6_sub(&cross, &t1);
+
+        Fp12Variable { c0, c1 }
+    }
+
+    pub fn fp12_square(&mut self, a: &Fp12Variable) -> Fp12Variable {
+        self.fp12_mul(a, a)
+    }
+
+    /// Multiplies `a` by the sparse element `(y, 0, 0) + (b0, b1, 0) * w` with `y` in the base
+    /// field, which is the shape of the line functions evaluated in the Miller loop.
+    pub fn fp12_mul_by_line(
+        &mut self,
+        a: &Fp12Variable,
+        y: &NonNativeTarget<Bn254Base>,
+        b0: &Fp2Variable,
+        b1: &Fp2Variable,
+    ) -> Fp12Variable {
+        let a0_y = Fp6Variable {
+            c0: self.fp2_mul_by_fp(&a.c0.c0, y),
+            c1: self.fp2_mul_by_fp(&a.c0.c1, y),
+            c2: self.fp2_mul_by_fp(&a.c0.c2, y),
+        };
+        let a1_y = Fp6Variable {
+            c0: self.fp2_mul_by_fp(&a.c1.c0, y),
+            c1: self.fp2_mul_by_fp(&a.c1.c1, y),
+            c2: self.fp2_mul_by_fp(&a.c1.c2, y),
+        };
+        let a1_b = self.fp6_mul_by_01(&a.c1, b0, b1);
+        let v_a1_b = self.fp6_mul_by_nonresidue(&a1_b);
+        let a0_b = self.fp6_mul_by_01(&a.c0, b0, b1);
+        Fp12Variable {
+            c0: self.fp6_add(&a0_y, &v_a1_b),
+            c1: self.fp6_add(&a0_b, &a1_y),
+        }
+    }
+
+    /// Computes `a^(p^6)`, which is also the inverse of `a` in the cyclotomic subgroup.
+    pub fn fp12_conjugate(&mut self, a: &Fp12Variable) -> Fp12Variable {
+        Fp12Variable {
+            c0: a.c0.clone(),
+            c1: self.fp6_neg(&a.c1),
+        }
+    }
+
+    pub fn fp12_inverse(&mut self, a: &Fp12Variable) -> Fp12Variable {
+        // (c0 + c1 * w)^-1 = (c0 - c1 * w) / (c0^2 - v * c1^2)
+        let c0_squared = self.fp6_mul(&a.c0, &a.c0);
+        let c1_squared = self.fp6_mul(&a.c1, &a.c1);
+        let v_c1_squared = self.fp6_mul_by_nonresidue(&c1_squared);
+        let norm = self.fp6_sub(&c0_squared, &v_c1_squared);
+        let norm_inv = self.fp6_inverse(&norm);
+        let c0 = self.fp6_mul(&a.c0, &norm_inv);
+        let c1 = self.fp6_mul(&a.c1, &norm_inv);
+        Fp12Variable {
+            c0,
+            c1: self.fp6_neg(&c1),
+        }
+    }
+
+    /// Computes `a^(p^power)` for `power` in `1..=3`.
+    pub fn fp12_frobenius(&mut self, a: &Fp12Variable, power: usize) -> Fp12Variable {
+        assert!((1..=3).contains(&power));
+        let mut mapped = Vec::with_capacity(6);
+        for (i, coeff) in a.to_w_basis().iter().enumerate() {
+            let coeff = if power % 2 == 1 {
+                self.fp2_conjugate(coeff)
+            } else {
+                coeff.clone()
+            };
+            let gamma = self.fp12_frobenius_coeff(power, i);
+            mapped.push(self.fp2_mul(&coeff, &gamma));
+        }
+        Fp12Variable::from_w_basis(mapped.try_into().unwrap())
+    }
+
+    /// Returns the coefficient `xi^(i * (p^power - 1) / 6)` used by the Frobenius endomorphism.
+    pub fn fp12_frobenius_coeff(&mut self, power: usize, i: usize) -> Fp2Variable {
+        let (c0, c1) = FROBENIUS_COEFFS[power - 1][i];
+        self.fp2_constant(
+            Bn254Base::from_decimal_str(c0),
+            Bn254Base::from_decimal_str(c1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::Sample;
+
+    use crate::frontend::ecc::bn254::field::bn254_base::Bn254Base;
+    use crate::prelude::DefaultBuilder;
+    use crate::utils;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_fp12_arithmetic() {
+        utils::setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        let coeffs = [(); 12].map(|_| Bn254Base::rand());
+        let a = builder.fp12_constant(&coeffs);
+
+        let a_inv = builder.fp12_inverse(&a);
+        let one = builder.fp12_mul(&a, &a_inv);
+        let expected_one = builder.fp12_one();
+        builder.fp12_connect(&one, &expected_one);
+
+        let frob_1 = builder.fp12_frobenius(&a, 1);
+        let frob_1_1 = builder.fp12_frobenius(&frob_1, 1);
+        let frob_2 = builder.fp12_frobenius(&a, 2);
+        builder.fp12_connect(&frob_1_1, &frob_2);
+
+        let frob_2_1 = builder.fp12_frobenius(&frob_2, 1);
+        let frob_3 = builder.fp12_frobenius(&a, 3);
+        builder.fp12_connect(&frob_2_1, &frob_3);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
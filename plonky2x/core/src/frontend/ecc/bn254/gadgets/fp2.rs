@@ -0,0 +1,179 @@
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+
+use crate::frontend::ecc::bn254::field::bn254_base::Bn254Base;
+use crate::frontend::num::nonnative::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use crate::prelude::{CircuitBuilder, CircuitVariable, PlonkParameters, Variable};
+
+/// An element `c0 + c1 * u` of `Fp2 = Fp[u] / (u^2 + 1)`, where `Fp` is the BN254 base field.
+#[derive(Clone, Debug, CircuitVariable)]
+pub struct Fp2Variable {
+    pub c0: NonNativeTarget<Bn254Base>,
+    pub c1: NonNativeTarget<Bn254Base>,
+}
+
+/// The non-residue `xi = 9 + u` used to build `Fp6` and `Fp12` on top of `Fp2`.
+const XI_C0: u64 = 9;
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    pub fn fp2_constant(&mut self, c0: Bn254Base, c1: Bn254Base) -> Fp2Variable {
+        Fp2Variable {
+            c0: self.api.constant_nonnative(c0),
+            c1: self.api.constant_nonnative(c1),
+        }
+    }
+
+    pub fn fp2_zero(&mut self) -> Fp2Variable {
+        self.fp2_constant(Bn254Base::ZERO, Bn254Base::ZERO)
+    }
+
+    pub fn fp2_one(&mut self) -> Fp2Variable {
+        self.fp2_constant(Bn254Base::ONE, Bn254Base::ZERO)
+    }
+
+    pub fn fp2_connect(&mut self, a: &Fp2Variable, b: &Fp2Variable) {
+        self.api.connect_nonnative(&a.c0, &b.c0);
+        self.api.connect_nonnative(&a.c1, &b.c1);
+    }
+
+    pub fn fp2_add(&mut self, a: &Fp2Variable, b: &Fp2Variable) -> Fp2Variable {
+        Fp2Variable {
+            c0: self.api.add_nonnative(&a.c0, &b.c0),
+            c1: self.api.add_nonnative(&a.c1, &b.c1),
+        }
+    }
+
+    pub fn fp2_sub(&mut self, a: &Fp2Variable, b: &Fp2Variable) -> Fp2Variable {
+        Fp2Variable {
+            c0: self.api.sub_nonnative(&a.c0, &b.c0),
+            c1: self.api.sub_nonnative(&a.c1, &b.c1),
+        }
+    }
+
+    pub fn fp2_neg(&mut self, a: &Fp2Variable) -> Fp2Variable {
+        Fp2Variable {
+            c0: self.api.neg_nonnative(&a.c0),
+            c1: self.api.neg_nonnative(&a.c1),
+        }
+    }
+
+    pub fn fp2_conjugate(&mut self, a: &Fp2Variable) -> Fp2Variable {
+        Fp2Variable {
+            c0: a.c0.clone(),
+            c1: self.api.neg_nonnative(&a.c1),
+        }
+    }
+
+    /// Karatsuba multiplication, using three base field multiplications.
+    pub fn fp2_mul(&mut self, a: &Fp2Variable, b: &Fp2Variable) -> Fp2Variable {
+        let v0 = self.api.mul_nonnative(&a.c0, &b.c0);
+        let v1 = self.api.mul_nonnative(&a.c1, &b.c1);
+        let a_sum = self.api.add_nonnative(&a.c0, &a.c1);
+        let b_sum = self.api.add_nonnative(&b.c0, &b.c1);
+        let sum_product = self.api.mul_nonnative(&a_sum, &b_sum);
+        let v0_plus_v1 = self.api.add_nonnative(&v0, &v1);
+        Fp2Variable {
+            c0: self.api.sub_nonnative(&v0, &v1),
+            c1: self.api.sub_nonnative(&sum_product, &v0_plus_v1),
+        }
+    }
+
+    pub fn fp2_square(&mut self, a: &Fp2Variable) -> Fp2Variable {
+        // (c0 + c1 * u)^2 = (c0 + c1) * (c0 - c1) + 2 * c0 * c1 * u
+        let sum = self.api.add_nonnative(&a.c0, &a.c1);
+        let diff = self.api.sub_nonnative(&a.c0, &a.c1);
+        let c0_c1 = self.api.mul_nonnative(&a.c0, &a.c1);
+        Fp2Variable {
+            c0: self.api.mul_nonnative(&sum, &diff),
+            c1: self.api.add_nonnative(&c0_c1, &c0_c1),
+        }
+    }
+
+    /// Multiplies an `Fp2` element by a base field element.
+    pub fn fp2_mul_by_fp(
+        &mut self,
+        a: &Fp2Variable,
+        b: &NonNativeTarget<Bn254Base>,
+    ) -> Fp2Variable {
+        Fp2Variable {
+            c0: self.api.mul_nonnative(&a.c0, b),
+            c1: self.api.mul_nonnative(&a.c1, b),
+        }
+    }
+
+    /// Multiplies an `Fp2` element by a small base field constant.
+    pub fn fp2_mul_by_u64(&mut self, a: &Fp2Variable, b: u64) -> Fp2Variable {
+        let b = self
+            .api
+            .constant_nonnative(Bn254Base::from_canonical_u64(b));
+        self.fp2_mul_by_fp(a, &b)
+    }
+
+    /// Multiplies an `Fp2` element by the non-residue `xi = 9 + u`.
+    pub fn fp2_mul_by_nonresidue(&mut self, a: &Fp2Variable) -> Fp2Variable {
+        // (c0 + c1 * u) * (9 + u) = (9 * c0 - c1) + (9 * c1 + c0) * u
+        let nine = self
+            .api
+            .constant_nonnative(Bn254Base::from_canonical_u64(XI_C0));
+        let nine_c0 = self.api.mul_nonnative(&a.c0, &nine);
+        let nine_c1 = self.api.mul_nonnative(&a.c1, &nine);
+        Fp2Variable {
+            c0: self.api.sub_nonnative(&nine_c0, &a.c1),
+            c1: self.api.add_nonnative(&nine_c1, &a.c0),
+        }
+    }
+
+    pub fn fp2_inverse(&mut self, a: &Fp2Variable) -> Fp2Variable {
+        // (c0 + c1 * u)^-1 = (c0 - c1 * u) / (c0^2 + c1^2)
+        let c0_squared = self.api.mul_nonnative(&a.c0, &a.c0);
+        let c1_squared = self.api.mul_nonnative(&a.c1, &a.c1);
+        let norm = self.api.add_nonnative(&c0_squared, &c1_squared);
+        let norm_inv = self.api.inv_nonnative(&norm);
+        let conjugate = self.fp2_conjugate(a);
+        self.fp2_mul_by_fp(&conjugate, &norm_inv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::{Field, Sample};
+
+    use crate::frontend::ecc::bn254::field::bn254_base::Bn254Base;
+    use crate::prelude::DefaultBuilder;
+    use crate::utils;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_fp2_arithmetic() {
+        utils::setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        let (a0, a1) = (Bn254Base::rand(), Bn254Base::rand());
+        let (b0, b1) = (Bn254Base::rand(), Bn254Base::rand());
+        let a = builder.fp2_constant(a0, a1);
+        let b = builder.fp2_constant(b0, b1);
+
+        let product = builder.fp2_mul(&a, &b);
+        let expected = builder.fp2_constant(a0 * b0 - a1 * b1, a0 * b1 + a1 * b0);
+        builder.fp2_connect(&product, &expected);
+
+        let square = builder.fp2_square(&a);
+        let a_times_a = builder.fp2_mul(&a, &a);
+        builder.fp2_connect(&square, &a_times_a);
+
+        let a_inv = builder.fp2_inverse(&a);
+        let one = builder.fp2_mul(&a, &a_inv);
+        let expected_one = builder.fp2_one();
+        builder.fp2_connect(&one, &expected_one);
+
+        let nine = Bn254Base::from_canonical_u64(9);
+        let xi_a = builder.fp2_mul_by_nonresidue(&a);
+        let expected = builder.fp2_constant(nine * a0 - a1, nine * a1 + a0);
+        builder.fp2_connect(&xi_a, &expected);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
@@ -0,0 +1,159 @@
+use plonky2::hash::hash_types::RichField;
+
+use crate::frontend::ecc::bn254::gadgets::fp2::Fp2Variable;
+use crate::prelude::{CircuitBuilder, CircuitVariable, PlonkParameters, Variable};
+
+/// An element `c0 + c1 * v + c2 * v^2` of `Fp6 = Fp2[v] / (v^3 - xi)` with `xi = 9 + u`.
+#[derive(Clone, Debug, CircuitVariable)]
+pub struct Fp6Variable {
+    pub c0: Fp2Variable,
+    pub c1: Fp2Variable,
+    pub c2: Fp2Variable,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    pub fn fp6_zero(&mut self) -> Fp6Variable {
+        Fp6Variable {
+            c0: self.fp2_zero(),
+            c1: self.fp2_zero(),
+            c2: self.fp2_zero(),
+        }
+    }
+
+    pub fn fp6_one(&mut self) -> Fp6Variable {
+        Fp6Variable {
+            c0: self.fp2_one(),
+            c1: self.fp2_zero(),
+            c2: self.fp2_zero(),
+        }
+    }
+
+    pub fn fp6_connect(&mut self, a: &Fp6Variable, b: &Fp6Variable) {
+        self.fp2_connect(&a.c0, &b.c0);
+        self.fp2_connect(&a.c1, &b.c1);
+        self.fp2_connect(&a.c2, &b.c2);
+    }
+
+    pub fn fp6_add(&mut self, a: &Fp6Variable, b: &Fp6Variable) -> Fp6Variable {
+        Fp6Variable {
+            c0: self.fp2_add(&a.c0, &b.c0),
+            c1: self.fp2_add(&a.c1, &b.c1),
+            c2: self.fp2_add(&a.c2, &b.c2),
+        }
+    }
+
+    pub fn fp6_sub(&mut self, a: &Fp6Variable, b: &Fp6Variable) -> Fp6Variable {
+        Fp6Variable {
+            c0: self.fp2_sub(&a.c0, &b.c0),
+            c1: self.fp2_sub(&a.c1, &b.c1),
+            c2: self.fp2_sub(&a.c2, &b.c2),
+        }
+    }
+
+    pub fn fp6_neg(&mut self, a: &Fp6Variable) -> Fp6Variable {
+        Fp6Variable {
+            c0: self.fp2_neg(&a.c0),
+            c1: self.fp2_neg(&a.c1),
+            c2: self.fp2_neg(&a.c2),
+        }
+    }
+
+    /// Karatsuba multiplication, using six `Fp2` multiplications.
+    pub fn fp6_mul(&mut self, a: &Fp6Variable, b: &Fp6Variable) -> Fp6Variable {
+        let t0 = self.fp2_mul(&a.c0, &b.c0);
+        let t1 = self.fp2_mul(&a.c1, &b.c1);
+        let t2 = self.fp2_mul(&a.c2, &b.c2);
+
+        // c0 = t0 + xi * ((a1 + a2) * (b1 + b2) - t1 - t2)
+        let a12 = self.fp2_add(&a.c1, &a.c2);
+        let b12 = self.fp2_add(&b.c1, &b.c2);
+        let cross = self.fp2_mul(&a12, &b12);
+        let cross = self.fp2_sub(&cross, &t1);
+        let cross = self.fp2_sub(&cross, &t2);
+        let cross = self.fp2_mul_by_nonresidue(&cross);
+        let c0 = self.fp2_add(&t0, &cross);
+
+        // c1 = (a0 + a1) * (b0 + b1) - t0 - t1 + xi * t2
+        let a01 = self.fp2_add(&a.c0, &a.c1);
+        let b01 = self.fp2_add(&b.c0, &b.c1);
+        let cross = self.fp2_mul(&a01, &b01);
+        let cross = self.fp2_sub(&cross, &t0);
+        let cross = self.fp2_sub(&cross, &t1);
+        let xi_t2 = self.fp2_mul_by_nonresidue(&t2);
+        let c1 = self.fp2_add(&cross, &xi_t2);
+
+        // c2 = (a0 + a2) * (b0 + b2) - t0 - t2 + t1
+        let a02 = self.fp2_add(&a.c0, &a.c2);
+        let b02 = self.fp2_add(&b.c0, &b.c2);
+        let cross = self.fp2_mul(&a02, &b02);
+        let cross = self.fp2_sub(&cross, &t0);
+        let cross = self.fp2_sub(&cross, &t2);
+        let c2 = self.fp2_add(&cross, &t1);
+
+        Fp6Variable { c0, c1, c2 }
+    }
+
+    /// Multiplies `a` by the sparse element `b0 + b1 * v`.
+    pub fn fp6_mul_by_01(
+        &mut self,
+        a: &Fp6Variable,
+        b0: &Fp2Variable,
+        b1: &Fp2Variable,
+    ) -> Fp6Variable {
+        let a0_b0 = self.fp2_mul(&a.c0, b0);
+        let a2_b1 = self.fp2_mul(&a.c2, b1);
+        let xi_a2_b1 = self.fp2_mul_by_nonresidue(&a2_b1);
+        let a0_b1 = self.fp2_mul(&a.c0, b1);
+        let a1_b0 = self.fp2_mul(&a.c1, b0);
+        let a1_b1 = self.fp2_mul(&a.c1, b1);
+        let a2_b0 = self.fp2_mul(&a.c2, b0);
+        Fp6Variable {
+            c0: self.fp2_add(&a0_b0, &xi_a2_b1),
+            c1: self.fp2_add(&a0_b1, &a1_b0),
+            c2: self.fp2_add(&a1_b1, &a2_b0),
+        }
+    }
+
+    /// Multiplies an `Fp6` element by `v`.
+    pub fn fp6_mul_by_nonresidue(&mut self, a: &Fp6Variable) -> Fp6Variable {
+        Fp6Variable {
+            c0: self.fp2_mul_by_nonresidue(&a.c2),
+            c1: a.c0.clone(),
+            c2: a.c1.clone(),
+        }
+    }
+
+    pub fn fp6_inverse(&mut self, a: &Fp6Variable) -> Fp6Variable {
+        // t0 = a0^2 - xi * a1 * a2
+        let a0_squared = self.fp2_square(&a.c0);
+        let a1_a2 = self.fp2_mul(&a.c1, &a.c2);
+        let xi_a1_a2 = self.fp2_mul_by_nonresidue(&a1_a2);
+        let t0 = self.fp2_sub(&a0_squared, &xi_a1_a2);
+
+        // t1 = xi * a2^2 - a0 * a1
+        let a2_squared = self.fp2_square(&a.c2);
+        let xi_a2_squared = self.fp2_mul_by_nonresidue(&a2_squared);
+        let a0_a1 = self.fp2_mul(&a.c0, &a.c1);
+        let t1 = self.fp2_sub(&xi_a2_squared, &a0_a1);
+
+        // t2 = a1^2 - a0 * a2
+        let a1_squared = self.fp2_square(&a.c1);
+        let a0_a2 = self.fp2_mul(&a.c0, &a.c2);
+        let t2 = self.fp2_sub(&a1_squared, &a0_a2);
+
+        // The norm a0 * t0 + xi * (a2 * t1 + a1 * t2) lies in Fp2.
+        let a0_t0 = self.fp2_mul(&a.c0, &t0);
+        let a2_t1 = self.fp2_mul(&a.c2, &t1);
+        let a1_t2 = self.fp2_mul(&a.c1, &t2);
+        let sum = self.fp2_add(&a2_t1, &a1_t2);
+        let xi_sum = self.fp2_mul_by_nonresidue(&sum);
+        let norm = self.fp2_add(&a0_t0, &xi_sum);
+        let norm_inv = self.fp2_inverse(&norm);
+
+        Fp6Variable {
+            c0: self.fp2_mul(&t0, &norm_inv),
+            c1: self.fp2_mul(&t1, &norm_inv),
+            c2: self.fp2_mul(&t2, &norm_inv),
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use plonky2::hash::hash_types::RichField;
+
+use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+use crate::frontend::ecc::bn254::curve::bn254::{G1Affine, G2Affine};
+use crate::frontend::ecc::bn254::gadgets::curve::{G1AffineVariable, G2AffineVariable};
+use crate::frontend::num::nonnative::nonnative::NonNativeTarget;
+use crate::prelude::{CircuitBuilder, CircuitVariable, PlonkParameters, Variable};
+
+/// A Groth16 verifying key over BN254, as exported by circom (snarkjs) or gnark.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Groth16VerifyingKey {
+    pub alpha_g1: G1Affine,
+    pub beta_g2: G2Affine,
+    pub gamma_g2: G2Affine,
+    pub delta_g2: G2Affine,
+    /// The commitments to the public input polynomials. `ic[0]` is the constant term, so there
+    /// is one more element than public inputs.
+    pub ic: Vec<G1Affine>,
+}
+
+/// A Groth16 proof over BN254.
+#[derive(Clone, Debug, CircuitVariable)]
+pub struct Groth16ProofVariable {
+    pub a: G1AffineVariable,
+    pub b: G2AffineVariable,
+    pub c: G1AffineVariable,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Verifies a Groth16 proof against a verifying key known at circuit build time by checking
+    /// `e(-A, B) * e(alpha, beta) * e(L, gamma) * e(C, delta) = 1`, where
+    /// `L = ic[0] + sum_i public_inputs[i] * ic[i + 1]`.
+    ///
+    /// The proof points are checked to lie on their curves, and `B` to lie in the prime order
+    /// subgroup of the twist. G1 has cofactor one, so `A` and `C` need no subgroup check.
+    pub fn verify_groth16(
+        &mut self,
+        vk: &Groth16VerifyingKey,
+        proof: &Groth16ProofVariable,
+        public_inputs: &[NonNativeTarget<Bn254Scalar>],
+    ) {
+        assert_eq!(vk.ic.len(), public_inputs.len() + 1);
+        self.g1_assert_on_curve(&proof.a);
+        self.g2_assert_on_curve(&proof.b);
+        self.g2_assert_in_subgroup(&proof.b);
+        self.g1_assert_on_curve(&proof.c);
+
+        let l = self.g1_linear_combination(vk.ic[0], &vk.ic[1..], public_inputs);
+        let neg_a = self.g1_neg(&proof.a);
+        let alpha = self.g1_constant(vk.alpha_g1);
+        let beta = self.g2_constant(vk.beta_g2);
+        let gamma = self.g2_constant(vk.gamma_g2);
+        let delta = self.g2_constant(vk.delta_g2);
+
+        self.bn254_pairing_check(&[
+            (neg_a, proof.b.clone()),
+            (alpha, beta),
+            (l, gamma),
+            (proof.c.clone(), delta),
+        ]);
+    }
+
+    /// Verifies several Groth16 proofs against the same verifying key, so that a single proof of
+    /// this circuit attests to all of them. `public_inputs[i]` are the public inputs of
+    /// `proofs[i]`.
+    ///
+    /// Every proof gets its own pairing check. Multiplying the checks together would be cheaper,
+    /// but would let a prover cancel an invalid proof against another one.
+    pub fn verify_groth16_batch(
+        &mut self,
+        vk: &Groth16VerifyingKey,
+        proofs: &[Groth16ProofVariable],
+        public_inputs: &[Vec<NonNativeTarget<Bn254Scalar>>],
+    ) {
+        assert_eq!(proofs.len(), public_inputs.len());
+        for (proof, inputs) in proofs.iter().zip(public_inputs.iter()) {
+            self.verify_groth16(vk, proof, inputs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Field;
+
+    use super::{Groth16ProofVariable, Groth16ProofVariableValue, Groth16VerifyingKey};
+    use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+    use crate::frontend::ecc::bn254::curve::bn254::{G1Affine, G2Affine};
+    use crate::frontend::ecc::bn254::gadgets::curve::{
+        G1AffineVariableValue, G2AffineVariableValue,
+    };
+    use crate::frontend::ecc::bn254::gadgets::fp2::Fp2VariableValue;
+    use crate::frontend::num::nonnative::nonnative::NonNativeTarget;
+    use crate::prelude::DefaultBuilder;
+    use crate::utils;
+
+    type ProofValue = Groth16ProofVariableValue<GoldilocksField>;
+
+    fn g1(x: &str, y: &str) -> G1Affine {
+        G1Affine::from_decimal_strs(x, y)
+    }
+
+    fn g2(x: (&str, &str), y: (&str, &str)) -> G2Affine {
+        G2Affine::from_decimal_strs(x, y)
+    }
+
+    fn proof_value(a: G1Affine, b: G2Affine, c: G1Affine) -> ProofValue {
+        Groth16ProofVariableValue {
+            a: G1AffineVariableValue { x: a.x, y: a.y },
+            b: G2AffineVariableValue {
+                x: Fp2VariableValue {
+                    c0: b.x[0],
+                    c1: b.x[1],
+                },
+                y: Fp2VariableValue {
+                    c0: b.y[0],
+                    c1: b.y[1],
+                },
+            },
+            c: G1AffineVariableValue { x: c.x, y: c.y },
+        }
+    }
+
+    /// A verifying key for two public inputs, built from random trapdoors so that the Groth16
+    /// equation holds for the proof of `test_proof`.
+    fn test_vk() -> Groth16VerifyingKey {
+        Groth16VerifyingKey {
+            alpha_g1: g1(
+                "11286697761739527281421408564496938605620283743468565727615499152348860485880",
+                "18938608374515726207494399788417037443382555337182015891599766063745529185136",
+            ),
+            beta_g2: g2(
+                (
+                    "11185681809194118876277609859981742911568625240323080357981727913509424769833",
+                    "8427845444927549862217343918598410516744663669343470524340601862263432518688",
+                ),
+                (
+                    "3088438971663656547970369867460070261429180039508850566773369926264686927043",
+                    "19855086565277502692409734050082548516016951736406763116552325228314794396352",
+                ),
+            ),
+            gamma_g2: g2(
+                (
+                    "15141876578127195733335022317146106951702460099039050865995512424481226856955",
+                    "20450249181069477143914131498898291566221699609403788390344961701935885444533",
+                ),
+                (
+                    "7454084748086502704199527039759427525255867878141590254314146066777083602409",
+                    "10555366934696887064748076439521831495878438806835563443438023387416310457263",
+                ),
+            ),
+            delta_g2: g2(
+                (
+                    "18330073223748406408078063823045474175696151432838662739377824100077405279649",
+                    "6383021415820694495951963615932490421704077637573602617043166525977996026245",
+                ),
+                (
+                    "13329326691053160200298217318382848170400035140109781574192898077228510067996",
+                    "18050411998169739105606661660836616613891602779559460161481382602690370326648",
+                ),
+            ),
+            ic: vec![
+                g1(
+                    "7262693398820120022143940685800118090167915689844250571663594938651261460607",
+                    "4205970214660912380217428005877095066660045236210999763269367654077216012026",
+                ),
+                g1(
+                    "4727909561748220289032558616870171144960312406232117863439362472741040238187",
+                    "11163478255751274320559743114262723499626490563295534607379416133951220688905",
+                ),
+                g1(
+                    "87898739334510405749765465499512290230501494624894078803551638125307396395",
+                    "18383099465750948781882104517733302400960769302503338402549383405710906166203",
+                ),
+            ],
+        }
+    }
+
+    fn test_proof() -> (G1Affine, G2Affine, G1Affine, [Bn254Scalar; 2]) {
+        let a = g1(
+            "18554089310594471034407928698429311618396488996643322059263826742275773018194",
+            "14892058308792173840298491623473906338837688589530625679087722279954253974434",
+        );
+        let b = g2(
+            (
+                "17808706539060678920858538977299474318497191694089094664268269434593674240136",
+                "5299769426076328834537668921876609143850116123219543292016931836258874636238",
+            ),
+            (
+                "20474225826033864375774732634228223539772121581384053347704189240749179462689",
+                "6555464917670745849889270007516446878526889662609503274036582136573896369922",
+            ),
+        );
+        let c = g1(
+            "1388214099227289100339231203107828838252799709331103600594103537381438136571",
+            "11191445136333558006090346856043136531185951278061554990463813596094690928126",
+        );
+        let public_inputs = [
+            "3076986173373736189858978072589605493845280646133659618551191098016269762923",
+            "5964766786552181528313732493298845589549983942602568518166673116629960143794",
+        ]
+        .map(|s| {
+            Bn254Scalar::from_noncanonical_biguint(BigUint::parse_bytes(s.as_bytes(), 10).unwrap())
+        });
+        (a, b, c, public_inputs)
+    }
+
+    /// Builds a circuit verifying `proofs` against `test_vk`, then proves and verifies it.
+    fn prove_groth16(proofs: &[(ProofValue, [Bn254Scalar; 2])]) {
+        let vk = test_vk();
+        let mut builder = DefaultBuilder::new();
+        let mut proof_variables = Vec::new();
+        let mut input_variables = Vec::new();
+        for _ in proofs.iter() {
+            proof_variables.push(builder.read::<Groth16ProofVariable>());
+            input_variables.push(vec![
+                builder.read::<NonNativeTarget<Bn254Scalar>>(),
+                builder.read::<NonNativeTarget<Bn254Scalar>>(),
+            ]);
+        }
+        builder.verify_groth16_batch(&vk, &proof_variables, &input_variables);
+        let circuit = builder.build();
+
+        let mut input = circuit.input();
+        for (proof, public_inputs) in proofs.iter() {
+            input.write::<Groth16ProofVariable>(proof.clone());
+            for public_input in public_inputs.iter() {
+                input.write::<NonNativeTarget<Bn254Scalar>>(*public_input);
+            }
+        }
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_groth16() {
+        utils::setup_logger();
+        let (a, b, c, public_inputs) = test_proof();
+        prove_groth16(&[(proof_value(a, b, c), public_inputs)]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_groth16_batch() {
+        utils::setup_logger();
+        let (a, b, c, public_inputs) = test_proof();
+
+        // The same statement, rerandomized as (A, B + delta, C + A).
+        let b_rerandomized = g2(
+            (
+                "31624582004639348499598453489728874975303927945783340110506112065896013765",
+                "5154535923346370835348671617453938190386976643909169606522518005859876070043",
+            ),
+            (
+                "15179862169164861927174033105323239312777159079038947022995525357083640962883",
+                "6102262921972459897085065305223329002228259202434986413483438847034834638270",
+            ),
+        );
+        let c_rerandomized = c.add(&a);
+
+        prove_groth16(&[
+            (proof_value(a, b, c), public_inputs),
+            (
+                proof_value(a, b_rerandomized, c_rerandomized),
+                public_inputs,
+            ),
+        ]);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_groth16_wrong_public_input() {
+        utils::setup_logger();
+        let (a, b, c, mut public_inputs) = test_proof();
+        public_inputs[1] += Bn254Scalar::ONE;
+        prove_groth16(&[(proof_value(a, b, c), public_inputs)]);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_groth16_tampered_proof() {
+        utils::setup_logger();
+        let (a, b, c, public_inputs) = test_proof();
+        prove_groth16(&[(proof_value(a, b, c.neg()), public_inputs)]);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod curve;
+pub mod fp12;
+pub mod fp2;
+pub mod fp6;
+pub mod groth16;
+pub mod pairing;
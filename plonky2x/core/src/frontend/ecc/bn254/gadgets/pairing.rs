@@ -0,0 +1,243 @@
+use crate::frontend::ecc::bn254::gadgets::curve::{G1AffineVariable, G2AffineVariable};
+use crate::frontend::ecc::bn254::gadgets::fp12::Fp12Variable;
+use crate::frontend::ecc::bn254::gadgets::fp2::Fp2Variable;
+use crate::prelude::{CircuitBuilder, PlonkParameters};
+
+/// The BN254 curve parameter `x`, with `p = 36x^4 + 36x^3 + 24x^2 + 6x + 1`.
+const BN_X: u64 = 4965661367192848881;
+
+/// The non-adjacent form of `6x + 2`, least significant digit first.
+const ATE_LOOP_NAF: [i8; 66] = [
+    0, 0, 0, 1, 0, 1, 0, -1, 0, 0, -1, 0, 0, 0, 1, 0, 0, -1, 0, -1, 0, 0, 0, 1, 0, -1, 0, 0, 0, 0,
+    -1, 0, 0, 1, 0, -1, 0, 0, 1, 0, 0, 0, 0, 0, -1, 0, 0, -1, 0, 1, 0, -1, 0, 0, 0, -1, 0, -1, 0,
+    0, 0, 1, 0, -1, 0, 1,
+];
+
+/// The coefficients `(b0, b1)` of a line evaluated at a G1 point, as consumed by
+/// `fp12_mul_by_line` together with the `y` coordinate of that point.
+type LineCoeffs = (Fp2Variable, Fp2Variable);
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Evaluates the line of slope `lambda` through `t` at `p`, and returns the point
+    /// `t + other` where `other` is the second intersection with `x` coordinate `other_x`.
+    fn bn254_line_step(
+        &mut self,
+        t: &G2AffineVariable,
+        other_x: &Fp2Variable,
+        lambda: &Fp2Variable,
+        p: &G1AffineVariable,
+    ) -> (G2AffineVariable, LineCoeffs) {
+        // l(P) = yP - lambda * xP * w + (lambda * xT - yT) * w^3
+        let neg_lambda = self.fp2_neg(lambda);
+        let b0 = self.fp2_mul_by_fp(&neg_lambda, &p.x);
+        let lambda_xt = self.fp2_mul(lambda, &t.x);
+        let b1 = self.fp2_sub(&lambda_xt, &t.y);
+
+        let lambda_squared = self.fp2_square(lambda);
+        let x_sum = self.fp2_add(&t.x, other_x);
+        let x = self.fp2_sub(&lambda_squared, &x_sum);
+        let x_diff = self.fp2_sub(&t.x, &x);
+        let lambda_x_diff = self.fp2_mul(lambda, &x_diff);
+        let y = self.fp2_sub(&lambda_x_diff, &t.y);
+
+        (G2AffineVariable { x, y }, (b0, b1))
+    }
+
+    fn bn254_double_step(
+        &mut self,
+        t: &G2AffineVariable,
+        p: &G1AffineVariable,
+    ) -> (G2AffineVariable, LineCoeffs) {
+        // lambda = 3 * xT^2 / (2 * yT)
+        let xt_squared = self.fp2_square(&t.x);
+        let numerator = self.fp2_mul_by_u64(&xt_squared, 3);
+        let denominator = self.fp2_add(&t.y, &t.y);
+        let denominator_inv = self.fp2_inverse(&denominator);
+        let lambda = self.fp2_mul(&numerator, &denominator_inv);
+        self.bn254_line_step(t, &t.x, &lambda, p)
+    }
+
+    fn bn254_add_step(
+        &mut self,
+        t: &G2AffineVariable,
+        q: &G2AffineVariable,
+        p: &G1AffineVariable,
+    ) -> (G2AffineVariable, LineCoeffs) {
+        // lambda = (yQ - yT) / (xQ - xT)
+        let numerator = self.fp2_sub(&q.y, &t.y);
+        let denominator = self.fp2_sub(&q.x, &t.x);
+        let denominator_inv = self.fp2_inverse(&denominator);
+        let lambda = self.fp2_mul(&numerator, &denominator_inv);
+        self.bn254_line_step(t, &q.x, &lambda, p)
+    }
+
+    /// Computes the product of the optimal ate Miller loops of all `(P, Q)` pairs, sharing the
+    /// squarings of the accumulator between pairs.
+    ///
+    /// The steps use affine formulas, so every `Q` must be a non-zero point of the prime order
+    /// subgroup. The circuit is unsatisfiable otherwise.
+    pub fn bn254_multi_miller_loop(
+        &mut self,
+        pairs: &[(G1AffineVariable, G2AffineVariable)],
+    ) -> Fp12Variable {
+        let mut f = self.fp12_one();
+        let mut ts = pairs.iter().map(|(_, q)| q.clone()).collect::<Vec<_>>();
+        let neg_qs = pairs
+            .iter()
+            .map(|(_, q)| self.g2_neg(q))
+            .collect::<Vec<_>>();
+
+        for i in (0..ATE_LOOP_NAF.len() - 1).rev() {
+            f = self.fp12_square(&f);
+            for (t, (p, _)) in ts.iter_mut().zip(pairs.iter()) {
+                let (next, (b0, b1)) = self.bn254_double_step(t, p);
+                f = self.fp12_mul_by_line(&f, &p.y, &b0, &b1);
+                *t = next;
+            }
+
+            if ATE_LOOP_NAF[i] != 0 {
+                for (j, (p, q)) in pairs.iter().enumerate() {
+                    let q = if ATE_LOOP_NAF[i] == 1 { q } else { &neg_qs[j] };
+                    let (next, (b0, b1)) = self.bn254_add_step(&ts[j], q, p);
+                    f = self.fp12_mul_by_line(&f, &p.y, &b0, &b1);
+                    ts[j] = next;
+                }
+            }
+        }
+
+        for (j, (p, q)) in pairs.iter().enumerate() {
+            // Q1 = psi(Q) and Q2 = -psi^2(Q), where psi is the twisted Frobenius endomorphism.
+            let q1 = self.g2_psi(q);
+            let gamma_x = self.fp12_frobenius_coeff(2, 2);
+            let q2 = G2AffineVariable {
+                x: self.fp2_mul(&q.x, &gamma_x),
+                y: q.y.clone(),
+            };
+
+            let (next, (b0, b1)) = self.bn254_add_step(&ts[j], &q1, p);
+            f = self.fp12_mul_by_line(&f, &p.y, &b0, &b1);
+            let (next, (b0, b1)) = self.bn254_add_step(&next, &q2, p);
+            f = self.fp12_mul_by_line(&f, &p.y, &b0, &b1);
+            ts[j] = next;
+        }
+
+        f
+    }
+
+    /// Computes `a^(-x)` for the BN254 parameter `x`, assuming `a` is in the cyclotomic subgroup.
+    fn bn254_exp_by_neg_x(&mut self, a: &Fp12Variable) -> Fp12Variable {
+        let mut result = a.clone();
+        for i in (0..63 - BN_X.leading_zeros() as usize).rev() {
+            result = self.fp12_square(&result);
+            if (BN_X >> i) & 1 == 1 {
+                result = self.fp12_mul(&result, a);
+            }
+        }
+        self.fp12_conjugate(&result)
+    }
+
+    /// Raises `f` to the power `(p^12 - 1) / r`, following Fuentes-Castañeda et al. for the hard
+    /// part of the exponent.
+    pub fn bn254_final_exponentiation(&mut self, f: &Fp12Variable) -> Fp12Variable {
+        // Easy part: f^((p^6 - 1) * (p^2 + 1)).
+        let f_conjugate = self.fp12_conjugate(f);
+        let f_inv = self.fp12_inverse(f);
+        let f1 = self.fp12_mul(&f_conjugate, &f_inv);
+        let f1_frob = self.fp12_frobenius(&f1, 2);
+        let r = self.fp12_mul(&f1_frob, &f1);
+
+        // Hard part: r^((p^4 - p^2 + 1) / r), up to a power coprime to r.
+        let y0 = self.bn254_exp_by_neg_x(&r);
+        let y1 = self.fp12_square(&y0);
+        let y2 = self.fp12_square(&y1);
+        let y3 = self.fp12_mul(&y2, &y1);
+        let y4 = self.bn254_exp_by_neg_x(&y3);
+        let y5 = self.fp12_square(&y4);
+        let y6 = self.bn254_exp_by_neg_x(&y5);
+        let y3 = self.fp12_conjugate(&y3);
+        let y6 = self.fp12_conjugate(&y6);
+        let y7 = self.fp12_mul(&y6, &y4);
+        let y8 = self.fp12_mul(&y7, &y3);
+        let y9 = self.fp12_mul(&y8, &y1);
+        let y10 = self.fp12_mul(&y8, &y4);
+        let y11 = self.fp12_mul(&y10, &r);
+        let y12 = self.fp12_frobenius(&y9, 1);
+        let y13 = self.fp12_mul(&y12, &y11);
+        let y8 = self.fp12_frobenius(&y8, 2);
+        let y14 = self.fp12_mul(&y8, &y13);
+        let r_conjugate = self.fp12_conjugate(&r);
+        let y15 = self.fp12_mul(&r_conjugate, &y9);
+        let y15 = self.fp12_frobenius(&y15, 3);
+        self.fp12_mul(&y15, &y14)
+    }
+
+    /// Asserts that `prod_i e(P_i, Q_i) = 1`.
+    pub fn bn254_pairing_check(&mut self, pairs: &[(G1AffineVariable, G2AffineVariable)]) {
+        let f = self.bn254_multi_miller_loop(pairs);
+        let result = self.bn254_final_exponentiation(&f);
+        let one = self.fp12_one();
+        self.fp12_connect(&result, &one);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frontend::ecc::bn254::curve::bn254::{G1Affine, G2Affine};
+    use crate::prelude::DefaultBuilder;
+    use crate::utils;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bn254_pairing_check() {
+        utils::setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        // e(2 * G1, G2) * e(-G1, 2 * G2) = 1
+        let g1 = G1Affine::generator();
+        let two_g2 = G2Affine::from_decimal_strs(
+            (
+                "18029695676650738226693292988307914797657423701064905010927197838374790804409",
+                "14583779054894525174450323658765874724019480979794335525732096752006891875705",
+            ),
+            (
+                "2140229616977736810657479771656733941598412651537078903776637920509952744750",
+                "11474861747383700316476719153975578001603231366361248090558603872215261634898",
+            ),
+        );
+        let pairs = [
+            (
+                builder.g1_constant(g1.double()),
+                builder.g2_constant(G2Affine::generator()),
+            ),
+            (builder.g1_constant(g1.neg()), builder.g2_constant(two_g2)),
+        ];
+        builder.bn254_pairing_check(&pairs);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bn254_pairing_check_unbalanced() {
+        utils::setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        // e(2 * G1, G2) * e(-G1, G2) = e(G1, G2) != 1
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let pairs = [
+            (builder.g1_constant(g1.double()), builder.g2_constant(g2)),
+            (builder.g1_constant(g1.neg()), builder.g2_constant(g2)),
+        ];
+        builder.bn254_pairing_check(&pairs);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
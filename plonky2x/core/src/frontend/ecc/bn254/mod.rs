@@ -0,0 +1,3 @@
+pub mod curve;
+pub mod field;
+pub mod gadgets;
@@ -1,19 +1,29 @@
+use core::marker::PhantomData;
+
 use curta::chip::ec::edwards::scalar_mul::generator::AffinePointTarget as CurtaAffinePointTarget;
+use num::{BigUint, Integer};
 use plonky2::field::extension::Extendable;
 use plonky2::field::types::{Field, PrimeField, PrimeField64};
 use plonky2::hash::hash_types::RichField;
 use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::iop::witness::Witness;
 use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
+use plonky2::util::ceil_div_usize;
 use plonky2::util::serialization::{Buffer, IoResult};
+use serde::{Deserialize, Serialize};
 
 use crate::frontend::ecc::ed25519::curve::curve_types::{AffinePoint, Curve};
-use crate::frontend::hash::deprecated::bit_operations::util::biguint_to_bits_target;
-use crate::frontend::num::biguint::{CircuitBuilderBiguint, WitnessBigUint};
+use crate::frontend::hash::deprecated::bit_operations::util::{
+    biguint_to_bits_target, bits_to_biguint_target,
+};
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::num::biguint::{BigUintTarget, CircuitBuilderBiguint, WitnessBigUint};
 use crate::frontend::num::nonnative::nonnative::{
     CircuitBuilderNonNative, NonNativeTarget, ReadNonNativeTarget, WriteNonNativeTarget,
 };
 use crate::frontend::num::nonnative::split_nonnative::CircuitBuilderSplit;
+use crate::frontend::num::u32::gadgets::arithmetic_u32::U32Target;
+use crate::frontend::vars::{ValueStream, VariableStream};
 use crate::prelude::{
     BoolVariable, ByteVariable, Bytes32Variable, CircuitBuilder, CircuitVariable, PlonkParameters,
     Variable,
@@ -94,6 +104,9 @@ pub trait CircuitBuilderCurve<F: RichField + Extendable<D>, const D: usize> {
 
     fn add_virtual_affine_point_target<C: Curve>(&mut self) -> AffinePointTarget<C>;
 
+    /// The neutral element of the curve, which is (0, 1) for twisted Edwards curves.
+    fn curve_zero<C: Curve>(&mut self) -> AffinePointTarget<C>;
+
     fn curve_assert_valid<C: Curve>(&mut self, p: &AffinePointTarget<C>);
 
     fn curve_neg<C: Curve>(&mut self, p: &AffinePointTarget<C>) -> AffinePointTarget<C>;
@@ -111,6 +124,15 @@ pub trait CircuitBuilderCurve<F: RichField + Extendable<D>, const D: usize> {
         p2: &AffinePointTarget<C>,
     ) -> AffinePointTarget<C>;
 
+    /// Multiply a point by a scalar given as little-endian bits, using double-and-add.
+    ///
+    /// This relies on the addition law being complete, which holds for ed25519 and BabyJubJub.
+    fn curve_scalar_mul<C: Curve>(
+        &mut self,
+        p: &AffinePointTarget<C>,
+        scalar_bits_le: &[BoolTarget],
+    ) -> AffinePointTarget<C>;
+
     fn compress_point<C: Curve>(&mut self, p: &AffinePointTarget<C>) -> CompressedPointTarget;
 
     fn random_access_affine_point<C: Curve>(
@@ -163,10 +185,17 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderCurve<F, D>
         AffinePointTarget { x, y }
     }
 
+    fn curve_zero<C: Curve>(&mut self) -> AffinePointTarget<C> {
+        AffinePointTarget {
+            x: self.zero_nonnative(),
+            y: self.constant_nonnative(C::BaseField::ONE),
+        }
+    }
+
     fn curve_assert_valid<C: Curve>(&mut self, p: &AffinePointTarget<C>) {
-        // ed25519 has the following parameters
-        // Equation: a * x ** 2 + y ** 2 = 1 + d * x ** 2 * y ** 2
-        // a is -1, so the above equation can be rewritten as
+        // Twisted Edwards curves have the following equation
+        // a * x ** 2 + y ** 2 = 1 + d * x ** 2 * y ** 2
+        // When a is -1 (e.g. ed25519), the above equation can be rewritten as
         // y ** 2 = 1 + d * x ** 2 * y ** 2 + x ** 2
         let d = self.constant_nonnative(C::D);
         let one = self.constant_nonnative(C::BaseField::ONE);
@@ -175,11 +204,17 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderCurve<F, D>
         let x_squared = self.mul_nonnative(&p.x, &p.x);
         let x_squared_times_y_squared = self.mul_nonnative(&x_squared, &y_squared);
         let d_x_squared_times_y_squared = self.mul_nonnative(&d, &x_squared_times_y_squared);
-        let d_x_squared_times_y_squared_plus_x_sqaured =
-            self.add_nonnative(&d_x_squared_times_y_squared, &x_squared);
-        let rhs = self.add_nonnative(&one, &d_x_squared_times_y_squared_plus_x_sqaured);
-
-        self.connect_nonnative(&y_squared, &rhs);
+        let rhs = self.add_nonnative(&one, &d_x_squared_times_y_squared);
+
+        if C::A == C::BaseField::NEG_ONE {
+            let rhs = self.add_nonnative(&rhs, &x_squared);
+            self.connect_nonnative(&y_squared, &rhs);
+        } else {
+            let a = self.constant_nonnative(C::A);
+            let a_x_squared = self.mul_nonnative(&a, &x_squared);
+            let lhs = self.add_nonnative(&a_x_squared, &y_squared);
+            self.connect_nonnative(&lhs, &rhs);
+        }
     }
 
     fn curve_neg<C: Curve>(&mut self, p: &AffinePointTarget<C>) -> AffinePointTarget<C> {
@@ -224,9 +259,14 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderCurve<F, D>
         let x3_den_inv = self.inv_nonnative(&x3_den);
         let x3 = self.mul_nonnative(&x3_num, &x3_den_inv);
 
-        // y3 numerator is y1y2 - ax1x2 where a = -1
-        // can be rewritten as y1y2 + x1x2
-        let y3_num = self.add_nonnative(&y1y2, &x1x2);
+        // y3 numerator is y1y2 - ax1x2, which can be rewritten as y1y2 + x1x2 when a = -1
+        let y3_num = if C::A == C::BaseField::NEG_ONE {
+            self.add_nonnative(&y1y2, &x1x2)
+        } else {
+            let a = self.constant_nonnative(C::A);
+            let ax1x2 = self.mul_nonnative(&a, &x1x2);
+            self.sub_nonnative(&y1y2, &ax1x2)
+        };
         let y3_den = self.sub_nonnative(&one, &dx1x2y1y2);
         let y3_den_inv = self.inv_nonnative(&y3_den);
         let y3 = self.mul_nonnative(&y3_num, &y3_den_inv);
@@ -234,6 +274,26 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderCurve<F, D>
         AffinePointTarget { x: x3, y: y3 }
     }
 
+    fn curve_scalar_mul<C: Curve>(
+        &mut self,
+        p: &AffinePointTarget<C>,
+        scalar_bits_le: &[BoolTarget],
+    ) -> AffinePointTarget<C> {
+        let mut result = self.curve_zero();
+        let mut doubled = p.clone();
+        for (i, bit) in scalar_bits_le.iter().enumerate() {
+            let sum = self.curve_add(&result, &doubled);
+            result = AffinePointTarget {
+                x: self.if_nonnative(*bit, &sum.x, &result.x),
+                y: self.if_nonnative(*bit, &sum.y, &result.y),
+            };
+            if i + 1 < scalar_bits_le.len() {
+                doubled = self.curve_add(&doubled, &doubled);
+            }
+        }
+        result
+    }
+
     // This function will accept an affine point target and return
     // the point in compressed form (bit vector).
     fn compress_point<C: Curve>(&mut self, p: &AffinePointTarget<C>) -> CompressedPointTarget {
@@ -315,6 +375,11 @@ pub struct CompressedPointVariable(pub Bytes32Variable);
 
 pub trait CircuitBuilderCurveGadget<L: PlonkParameters<D>, const D: usize> {
     fn compress_point<C: Curve>(&mut self, p: &AffinePointTarget<C>) -> CompressedPointVariable;
+
+    /// Decompresses a point encoded as in RFC 8032: the little endian `y` coordinate with the
+    /// parity of `x` in the most significant bit. Non-canonical encodings (`y >= p`) and encodings
+    /// of points that are not on the curve are rejected.
+    fn decompress_point<C: Curve>(&mut self, p: &CompressedPointVariable) -> AffinePointTarget<C>;
 }
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilderCurveGadget<L, D>
@@ -334,6 +399,110 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilderCurveGadget<L, D>
         byte_variables.reverse();
         CompressedPointVariable(Bytes32Variable::from(byte_variables.as_slice()))
     }
+
+    fn decompress_point<C: Curve>(&mut self, p: &CompressedPointVariable) -> AffinePointTarget<C> {
+        // Flip from big endian back to little endian, which gives the bits of the encoding from
+        // the most significant one down.
+        let mut bits =
+            p.0.as_bytes()
+                .iter()
+                .rev()
+                .flat_map(|byte| byte.0.map(|bit| -> BoolTarget { bit.into() }))
+                .collect::<Vec<_>>();
+        let x_is_odd = bits[0];
+        bits[0] = self.api._false();
+
+        let y_biguint = bits_to_biguint_target(&mut self.api, bits);
+        let y = self.api.biguint_to_nonnative::<C::BaseField>(&y_biguint);
+        y.assert_is_valid(self);
+
+        let mut input_stream = VariableStream::new();
+        input_stream.write(&y);
+        input_stream.write(&BoolVariable::from(x_is_odd));
+        let output_stream = self.hint(input_stream, DecompressPointHint::<C>::new());
+        let x_limbs = (0..y.value.num_limbs())
+            .map(|_| {
+                let limb = output_stream.read::<Variable>(self);
+                self.api.range_check(limb.0, 32);
+                U32Target::from_target_unsafe(limb.0)
+            })
+            .collect::<Vec<_>>();
+        let x = self
+            .api
+            .biguint_to_nonnative::<C::BaseField>(&BigUintTarget { limbs: x_limbs });
+        x.assert_is_valid(self);
+
+        // Since `x` is canonical, its parity is the lowest bit of its first limb.
+        let x_bits_low_32 = self.api.split_le(x.value.get_limb(0).target, 32);
+        self.api.connect(x_bits_low_32[0].target, x_is_odd.target);
+
+        let point = AffinePointTarget { x, y };
+        self.api.curve_assert_valid(&point);
+        point
+    }
+}
+
+/// Recovers the `x` coordinate of a point from its `y` coordinate and the parity of `x`.
+///
+/// The `x` coordinate is written out limb by limb, padded to the full number of limbs. If there is
+/// no such point, zero is written and the constraints of `decompress_point` fail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DecompressPointHint<C: Curve> {
+    _phantom: PhantomData<fn() -> C>,
+}
+
+impl<C: Curve> DecompressPointHint<C> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Solves `a * x ** 2 + y ** 2 = 1 + d * x ** 2 * y ** 2` for `x`. The square root is taken
+    /// as in RFC 8032, which requires `p = 5 mod 8`.
+    fn recover_x(y: C::BaseField, x_is_odd: bool) -> Option<C::BaseField> {
+        let p = C::BaseField::order();
+        debug_assert_eq!(&p % 8u32, BigUint::from(5u32));
+
+        let y_squared = y.square();
+        let u = y_squared - C::BaseField::ONE;
+        let v = C::D * y_squared - C::A;
+        let w = u * v.try_inverse()?;
+
+        let mut x = w.exp_biguint(&((&p + 3u32) >> 3));
+        if x.square() != w {
+            let sqrt_neg_one = C::BaseField::TWO.exp_biguint(&((&p - 1u32) >> 2));
+            x *= sqrt_neg_one;
+        }
+        if x.square() != w || (x == C::BaseField::ZERO && x_is_odd) {
+            return None;
+        }
+        if x.to_canonical_biguint().is_odd() != x_is_odd {
+            x = -x;
+        }
+        Some(x)
+    }
+}
+
+impl<C: Curve> Default for DecompressPointHint<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, C: Curve> Hint<L, D> for DecompressPointHint<C> {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let y = input_stream.read_value::<NonNativeTarget<C::BaseField>>();
+        let x_is_odd = input_stream.read_value::<BoolVariable>();
+
+        let x = Self::recover_x(y, x_is_odd).unwrap_or(C::BaseField::ZERO);
+        let mut limbs = x.to_canonical_biguint().to_u32_digits();
+        limbs.resize(ceil_div_usize(C::BaseField::BITS, 32), 0);
+        for limb in limbs {
+            output_stream.write_value::<Variable>(L::Field::from_canonical_u32(limb));
+        }
+    }
 }
 
 pub trait WitnessAffinePoint<F: PrimeField64>: Witness<F> {
@@ -395,6 +564,7 @@ impl ReadAffinePoint for Buffer<'_> {
 
 #[cfg(test)]
 mod tests {
+    use ethers::types::H256;
     use plonky2::field::types::{Field, Sample};
     use plonky2::iop::witness::{PartialWitness, WitnessWrite};
     use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
@@ -406,7 +576,7 @@ mod tests {
     use crate::frontend::ecc::ed25519::field::ed25519_base::Ed25519Base;
     use crate::frontend::ecc::ed25519::field::ed25519_scalar::Ed25519Scalar;
     use crate::frontend::ecc::ed25519::gadgets::curve::{
-        AffinePointTarget, CircuitBuilderCurve, CircuitBuilderCurveGadget,
+        AffinePointTarget, CircuitBuilderCurve, CircuitBuilderCurveGadget, CompressedPointVariable,
     };
     use crate::frontend::hash::deprecated::bit_operations::util::biguint_to_bits_target;
     use crate::frontend::num::biguint::CircuitBuilderBiguint;
@@ -542,6 +712,38 @@ mod tests {
         data.verify(proof).unwrap();
     }
 
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_curve_scalar_mul() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+
+        let pw = PartialWitness::new();
+        let mut builder = BaseCircuitBuilder::<F, D>::new(config);
+
+        let scalar = 0b1011_0110_1100_0101u64;
+        let g = Ed25519::GENERATOR_AFFINE;
+        let expected = (CurveScalar(Ed25519Scalar::from_canonical_u64(scalar))
+            * Ed25519::GENERATOR_PROJECTIVE)
+            .to_affine();
+        let expected_target = builder.constant_affine_point(expected);
+
+        let g_target = builder.constant_affine_point(g);
+        let bits = (0..16)
+            .map(|i| builder.constant_bool((scalar >> i) & 1 == 1))
+            .collect::<Vec<_>>();
+        let actual_target = builder.curve_scalar_mul(&g_target, &bits);
+        builder.connect_affine_point(&expected_target, &actual_target);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof).unwrap();
+    }
+
     #[test]
     fn test_compress_point() {
         const D: usize = 2;
@@ -606,4 +808,55 @@ mod tests {
 
         assert_eq!(computed_pubkey.0.to_vec(), pubkey_bytes);
     }
+
+    #[test]
+    fn test_decompress_point_variable() {
+        type Curve = Ed25519;
+
+        let mut builder = DefaultBuilder::new();
+
+        let compressed = builder.read::<Bytes32Variable>();
+        let point = builder.decompress_point::<Curve>(&CompressedPointVariable(compressed));
+        builder.write(point);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+
+        let pubkey = "de25aec935b10f657b43fa97e5a8d4e523bdb0f9972605f0b064eff7b17048ba";
+        let pubkey_bytes = hex::decode(pubkey).unwrap();
+        input.write::<Bytes32Variable>(H256::from_slice(&pubkey_bytes));
+
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        circuit.test_default_serializers();
+
+        let point = output.read::<AffinePointTarget<Curve>>();
+        assert_eq!(
+            point,
+            AffinePoint::<Curve>::new_from_compressed_point(&pubkey_bytes)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_decompress_point_non_canonical() {
+        type Curve = Ed25519;
+
+        let mut builder = DefaultBuilder::new();
+
+        let compressed = builder.read::<Bytes32Variable>();
+        builder.decompress_point::<Curve>(&CompressedPointVariable(compressed));
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+
+        // The encoding of y = p + 1, which is a non-canonical encoding of the point (0, 1).
+        let mut bytes = [0xffu8; 32];
+        bytes[0] = 0xee;
+        bytes[31] = 0x7f;
+        input.write::<Bytes32Variable>(H256::from(bytes));
+
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
 }
@@ -1,27 +1,24 @@
 use core::fmt::Debug;
 
-use curta::chip::ec::edwards::ed25519::Ed25519 as CurtaEd25519;
-use curta::chip::ec::edwards::scalar_mul::generator::ScalarMulEd25519Gadget;
-use curta::chip::ec::EllipticCurve;
-use curta::math::extension::cubic::parameters::CubicParameters;
-use curta::plonky2::stark::config::CurtaConfig;
+use num::{BigUint, One};
 use plonky2::field::extension::Extendable;
+use plonky2::field::types::PrimeField;
 use plonky2::hash::hash_types::RichField;
 use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
 
 use crate::frontend::ecc::ed25519::curve::curve_types::Curve;
+use crate::frontend::ecc::ed25519::curve::ed25519::Ed25519;
 use crate::frontend::ecc::ed25519::field::ed25519_scalar::Ed25519Scalar;
 use crate::frontend::ecc::ed25519::gadgets::curve::{AffinePointTarget, CircuitBuilderCurve};
 use crate::frontend::hash::deprecated::sha512::{
     sha512, sha512_variable, CHUNK_BITS_1024, LENGTH_BITS_128,
 };
-use crate::frontend::num::biguint::BigUintTarget;
+use crate::frontend::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
 use crate::frontend::num::nonnative::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
 use crate::frontend::num::u32::gadgets::arithmetic_u32::U32Target;
 use crate::prelude::{CircuitBuilder, CircuitVariable, PlonkParameters, Variable};
 
-const MAX_NUM_SIGS: usize = 256;
 const COMPRESSED_SIG_AND_PK_LEN_BITS: usize = 512;
 
 #[derive(Clone, Debug)]
@@ -91,6 +88,21 @@ fn biguint_from_le_bytes<F: RichField + Extendable<D>, const D: usize>(
     BigUintTarget { limbs: u32_targets }
 }
 
+/// Checks that the `S` part of a signature is less than the order of the subgroup. Otherwise
+/// `S + l` would verify as well, making signatures malleable.
+pub(crate) fn assert_signature_scalar_canonical<
+    F: RichField + Extendable<D>,
+    FF: PrimeField,
+    const D: usize,
+>(
+    builder: &mut BaseCircuitBuilder<F, D>,
+    s: &NonNativeTarget<FF>,
+) {
+    let max = builder.constant_biguint(&(FF::order() - BigUint::one()));
+    let is_canonical = builder.cmp_biguint(&s.value, &max);
+    builder.assert_one(is_canonical.target);
+}
+
 pub const fn calculate_eddsa_num_chunks(msg_len_bits: usize) -> usize {
     ((msg_len_bits + COMPRESSED_SIG_AND_PK_LEN_BITS + LENGTH_BITS_128 + 1) / CHUNK_BITS_1024) + 1
 }
@@ -98,18 +110,15 @@ pub const fn calculate_eddsa_num_chunks(msg_len_bits: usize) -> usize {
 // Note: This function should not be used outside of succinctx.
 // TODO: Migrate to CircuitVariable
 pub fn curta_batch_eddsa_verify_variable<
-    F: RichField + Extendable<D>,
-    C: Curve,
-    E: CubicParameters<F>,
-    Config: CurtaConfig<D, F = F, FE = F::Extension>,
+    L: PlonkParameters<D>,
     const D: usize,
     // Maximum length of a signed message in bytes.
     const MAX_MSG_LENGTH_BYTES: usize,
 >(
-    builder: &mut BaseCircuitBuilder<F, D>,
+    builder: &mut CircuitBuilder<L, D>,
     num_sigs: usize,
-) -> EDDSAVariableTargets<C> {
-    assert!(num_sigs > 0 && num_sigs <= MAX_NUM_SIGS);
+) -> EDDSAVariableTargets<Ed25519> {
+    assert!(num_sigs > 0);
 
     let max_msg_len_bits = MAX_MSG_LENGTH_BYTES * 8;
 
@@ -121,45 +130,42 @@ pub fn curta_batch_eddsa_verify_variable<
     let mut msgs_bit_lengths = Vec::new();
     let mut sigs = Vec::new();
     let mut pub_keys = Vec::new();
-    let mut curta_pub_keys = Vec::new();
-    let mut h_scalars_limbs = Vec::new();
-    let mut generators = Vec::new();
-    let mut sigs_s_limbs = Vec::new();
 
     for _i in 0..num_sigs {
         let mut msg = Vec::new();
         for _ in 0..max_msg_len_bits {
             // Note that add_virtual_bool_target_safe will do a range check to verify each element is 0 or 1.
-            msg.push(builder.add_virtual_bool_target_safe());
+            msg.push(builder.api.add_virtual_bool_target_safe());
         }
 
         // Targets for the message length and number of chunks
         // TODO: Should we range check that msg_length is less than MAX_MSG_LEN * 8?
-        let msg_bit_length = builder.add_virtual_target();
+        let msg_bit_length = builder.api.add_virtual_target();
         msgs_bit_lengths.push(msg_bit_length);
 
         // Note: Add 512 bits for the sig.r and pk_compressed
-        let compressed_sig_and_pk_bit_length =
-            builder.constant(F::from_canonical_usize(COMPRESSED_SIG_AND_PK_LEN_BITS));
-        let hash_msg_bit_length = builder.add(msg_bit_length, compressed_sig_and_pk_bit_length);
+        let compressed_sig_and_pk_bit_length = builder.api.constant(
+            L::Field::from_canonical_usize(COMPRESSED_SIG_AND_PK_LEN_BITS),
+        );
+        let hash_msg_bit_length = builder
+            .api
+            .add(msg_bit_length, compressed_sig_and_pk_bit_length);
 
         // There is already a calculation for the number of limbs needed for the underlying biguint targets.
         let sig = EDDSASignatureTarget {
-            r: builder.add_virtual_affine_point_target(),
-            s: builder.add_virtual_nonnative_target(),
+            r: builder.api.add_virtual_affine_point_target(),
+            s: builder.api.add_virtual_nonnative_target(),
         };
-        let pub_key = EDDSAPublicKeyTarget(builder.add_virtual_affine_point_target());
-        builder.curve_assert_valid(&pub_key.0);
-        builder.curve_assert_valid(&sig.r);
-
-        // Convert into format for the curta scalar mul
-        curta_pub_keys.push(builder.convert_to_curta_affine_point_target(&pub_key.0));
+        let pub_key = EDDSAPublicKeyTarget(builder.api.add_virtual_affine_point_target());
+        builder.api.curve_assert_valid(&pub_key.0);
+        builder.api.curve_assert_valid(&sig.r);
+        assert_signature_scalar_canonical(&mut builder.api, &sig.s);
 
         // Calculate h = hash(sig.r + pk + msg) mod q
         let mut hash_msg = Vec::new();
-        let a = builder.compress_point(&sig.r);
+        let a = builder.api.compress_point(&sig.r);
         let r_compressed = reverse_byte_ordering(a.bit_targets.to_vec());
-        let b = builder.compress_point(&pub_key.0);
+        let b = builder.api.compress_point(&pub_key.0);
         let pk_compressed = reverse_byte_ordering(b.bit_targets.to_vec());
 
         for i in 0..256 {
@@ -177,87 +183,34 @@ pub fn curta_batch_eddsa_verify_variable<
         for _ in
             (max_msg_len_bits + COMPRESSED_SIG_AND_PK_LEN_BITS)..(max_num_chunks * CHUNK_BITS_1024)
         {
-            hash_msg.push(builder._false());
+            hash_msg.push(builder.api._false());
         }
 
         msgs.push(msg);
 
-        let sha512_targets = sha512_variable::<F, D>(builder, max_num_chunks);
-        builder.connect(sha512_targets.hash_msg_length_bits, hash_msg_bit_length);
+        let sha512_targets = sha512_variable::<L::Field, D>(&mut builder.api, max_num_chunks);
+        builder
+            .api
+            .connect(sha512_targets.hash_msg_length_bits, hash_msg_bit_length);
 
         for i in 0..max_num_chunks * CHUNK_BITS_1024 {
-            builder.connect(sha512_targets.message[i].target, hash_msg[i].target);
+            builder
+                .api
+                .connect(sha512_targets.message[i].target, hash_msg[i].target);
         }
 
-        let digest = biguint_from_le_bytes(builder, sha512_targets.digest);
-        let h_scalar = builder.reduce::<Ed25519Scalar>(&digest);
-
-        let h_scalar_limbs = h_scalar
-            .value
-            .limbs
-            .iter()
-            .map(|x| x.target)
-            .collect::<Vec<_>>();
-        h_scalars_limbs.push(h_scalar_limbs);
-
-        let sig_s_limbs = sig
-            .s
-            .value
-            .limbs
-            .iter()
-            .map(|x| x.target)
-            .collect::<Vec<_>>();
-        sigs_s_limbs.push(sig_s_limbs);
-
-        let generator =
-            ScalarMulEd25519Gadget::constant_affine_point(builder, CurtaEd25519::ec_generator());
+        let digest = biguint_from_le_bytes(&mut builder.api, sha512_targets.digest);
+        let h_scalar = builder.api.reduce::<Ed25519Scalar>(&digest);
+
+        // Verify that s * G = R + h * A with the EC accelerator.
+        let generator = builder.api.constant_affine_point(Ed25519::GENERATOR_AFFINE);
+        let pk_times_h = builder.curta_ed25519_scalar_mul(&pub_key.0, &h_scalar);
+        let s_times_g = builder.curta_ed25519_scalar_mul(&generator, &sig.s);
+        let rhs = builder.api.curve_add(&sig.r, &pk_times_h);
+        CircuitBuilderCurve::connect_affine_point(&mut builder.api, &s_times_g, &rhs);
 
         pub_keys.push(pub_key);
         sigs.push(sig);
-        generators.push(generator);
-    }
-
-    // "Pad" the rest of the scalar mul inputs with dummy operands
-    for _i in num_sigs..MAX_NUM_SIGS {
-        curta_pub_keys.push(ScalarMulEd25519Gadget::constant_affine_point(
-            builder,
-            CurtaEd25519::ec_generator(),
-        ));
-        h_scalars_limbs.push([builder.zero(); 8].to_vec());
-
-        generators.push(ScalarMulEd25519Gadget::constant_affine_point(
-            builder,
-            CurtaEd25519::ec_generator(),
-        ));
-        sigs_s_limbs.push([builder.zero(); 8].to_vec());
-    }
-
-    // Now do the batch scalar mul verification
-    let pk_times_h_witnesses = builder.ed_scalar_mul_batch_hint(&curta_pub_keys, &h_scalars_limbs);
-    let pk_times_h_results =
-        builder.ed_scalar_mul_batch::<E, Config>(&curta_pub_keys, &h_scalars_limbs);
-
-    let s_times_g_witnesses = builder.ed_scalar_mul_batch_hint(&generators, &sigs_s_limbs);
-    let s_times_g_results = builder.ed_scalar_mul_batch::<E, Config>(&generators, &sigs_s_limbs);
-
-    for i in 0..num_sigs {
-        // Verify the scalar muls
-        ScalarMulEd25519Gadget::connect_affine_point(
-            builder,
-            &pk_times_h_witnesses[i],
-            &pk_times_h_results[i],
-        );
-        ScalarMulEd25519Gadget::connect_affine_point(
-            builder,
-            &s_times_g_witnesses[i],
-            &s_times_g_results[i],
-        );
-
-        // Complete the signature verification
-        let pk_times_h = builder.convert_from_curta_affine_point_target(&pk_times_h_results[i]);
-        let rhs = builder.curve_add(&sigs[i].r, &pk_times_h);
-        let s_times_g = builder.convert_from_curta_affine_point_target(&s_times_g_results[i]);
-        CircuitBuilderCurve::connect_affine_point(builder, &s_times_g, &rhs);
     }
 
     EDDSAVariableTargets {
@@ -272,52 +225,40 @@ pub fn curta_batch_eddsa_verify_variable<
 // TODO: Migrate to CircuitVariable
 // TODO: If there is one shared message length for all signed messages, then we can optimize this function with sha512, instead of variable_sha512.
 // TODO: If there is one shared message for all signed messages, then we can optimize this function by computing the sha512 once.
-pub fn curta_batch_eddsa_verify<
-    F: RichField + Extendable<D>,
-    C: Curve,
-    E: CubicParameters<F>,
-    Config: CurtaConfig<D, F = F, FE = F::Extension>,
-    const D: usize,
->(
-    builder: &mut BaseCircuitBuilder<F, D>,
+pub fn curta_batch_eddsa_verify<L: PlonkParameters<D>, const D: usize>(
+    builder: &mut CircuitBuilder<L, D>,
     num_sigs: usize,
     msg_len: u128, // message length in bytes
-) -> EDDSATargets<C> {
-    assert!(num_sigs > 0 && num_sigs <= MAX_NUM_SIGS);
+) -> EDDSATargets<Ed25519> {
+    assert!(num_sigs > 0);
 
     // Create the eddsa circuit's virtual targets.
     let mut msgs = Vec::new();
     let mut sigs = Vec::new();
     let mut pub_keys = Vec::new();
-    let mut curta_pub_keys = Vec::new();
-    let mut h_scalars_limbs = Vec::new();
-    let mut generators = Vec::new();
-    let mut sigs_s_limbs = Vec::new();
 
     for _i in 0..num_sigs {
         let mut msg = Vec::new();
         for _ in 0..msg_len * 8 {
             // Note that add_virtual_bool_target_safe will do a range check to verify each element is 0 or 1.
-            msg.push(builder.add_virtual_bool_target_safe());
+            msg.push(builder.api.add_virtual_bool_target_safe());
         }
 
         // There is already a calculation for the number of limbs needed for the underlying biguint targets.
         let sig = EDDSASignatureTarget {
-            r: builder.add_virtual_affine_point_target(),
-            s: builder.add_virtual_nonnative_target(),
+            r: builder.api.add_virtual_affine_point_target(),
+            s: builder.api.add_virtual_nonnative_target(),
         };
-        let pub_key = EDDSAPublicKeyTarget(builder.add_virtual_affine_point_target());
-        builder.curve_assert_valid(&pub_key.0);
-        builder.curve_assert_valid(&sig.r);
-
-        // Convert into format for the curta scalar mul
-        curta_pub_keys.push(builder.convert_to_curta_affine_point_target(&pub_key.0));
+        let pub_key = EDDSAPublicKeyTarget(builder.api.add_virtual_affine_point_target());
+        builder.api.curve_assert_valid(&pub_key.0);
+        builder.api.curve_assert_valid(&sig.r);
+        assert_signature_scalar_canonical(&mut builder.api, &sig.s);
 
         // Calculate h = hash(sig.r + pk + msg) mod q
         let mut hash_msg = Vec::new();
-        let a = builder.compress_point(&sig.r);
+        let a = builder.api.compress_point(&sig.r);
         let r_compressed = reverse_byte_ordering(a.bit_targets.to_vec());
-        let b = builder.compress_point(&pub_key.0);
+        let b = builder.api.compress_point(&pub_key.0);
         let pk_compressed = reverse_byte_ordering(b.bit_targets.to_vec());
 
         for i in 0..r_compressed.len() {
@@ -333,76 +274,19 @@ pub fn curta_batch_eddsa_verify<
         }
         msgs.push(msg);
 
-        let digest_bits_target = sha512(builder, &hash_msg);
-        let digest = biguint_from_le_bytes(builder, digest_bits_target);
-        let h_scalar = builder.reduce::<Ed25519Scalar>(&digest);
-
-        let h_scalar_limbs = h_scalar
-            .value
-            .limbs
-            .iter()
-            .map(|x| x.target)
-            .collect::<Vec<_>>();
-        h_scalars_limbs.push(h_scalar_limbs);
-
-        let sig_s_limbs = sig
-            .s
-            .value
-            .limbs
-            .iter()
-            .map(|x| x.target)
-            .collect::<Vec<_>>();
-        sigs_s_limbs.push(sig_s_limbs);
-
-        let generator =
-            ScalarMulEd25519Gadget::constant_affine_point(builder, CurtaEd25519::ec_generator());
+        let digest_bits_target = sha512(&mut builder.api, &hash_msg);
+        let digest = biguint_from_le_bytes(&mut builder.api, digest_bits_target);
+        let h_scalar = builder.api.reduce::<Ed25519Scalar>(&digest);
+
+        // Verify that s * G = R + h * A with the EC accelerator.
+        let generator = builder.api.constant_affine_point(Ed25519::GENERATOR_AFFINE);
+        let pk_times_h = builder.curta_ed25519_scalar_mul(&pub_key.0, &h_scalar);
+        let s_times_g = builder.curta_ed25519_scalar_mul(&generator, &sig.s);
+        let rhs = builder.api.curve_add(&sig.r, &pk_times_h);
+        CircuitBuilderCurve::connect_affine_point(&mut builder.api, &s_times_g, &rhs);
 
         pub_keys.push(pub_key);
         sigs.push(sig);
-        generators.push(generator);
-    }
-
-    // "Pad" the rest of the scalar mul inputs with dummy operands
-    for _i in num_sigs..MAX_NUM_SIGS {
-        curta_pub_keys.push(ScalarMulEd25519Gadget::constant_affine_point(
-            builder,
-            CurtaEd25519::ec_generator(),
-        ));
-        h_scalars_limbs.push([builder.zero(); 8].to_vec());
-
-        generators.push(ScalarMulEd25519Gadget::constant_affine_point(
-            builder,
-            CurtaEd25519::ec_generator(),
-        ));
-        sigs_s_limbs.push([builder.zero(); 8].to_vec());
-    }
-
-    // Now do the batch scalar mul verification
-    let pk_times_h_witnesses = builder.ed_scalar_mul_batch_hint(&curta_pub_keys, &h_scalars_limbs);
-    let pk_times_h_results =
-        builder.ed_scalar_mul_batch::<E, Config>(&curta_pub_keys, &h_scalars_limbs);
-
-    let s_times_g_witnesses = builder.ed_scalar_mul_batch_hint(&generators, &sigs_s_limbs);
-    let s_times_g_results = builder.ed_scalar_mul_batch::<E, Config>(&generators, &sigs_s_limbs);
-
-    for i in 0..num_sigs {
-        // Verify the scalar muls
-        ScalarMulEd25519Gadget::connect_affine_point(
-            builder,
-            &pk_times_h_witnesses[i],
-            &pk_times_h_results[i],
-        );
-        ScalarMulEd25519Gadget::connect_affine_point(
-            builder,
-            &s_times_g_witnesses[i],
-            &s_times_g_results[i],
-        );
-
-        // Complete the signature verification
-        let pk_times_h = builder.convert_from_curta_affine_point_target(&pk_times_h_results[i]);
-        let rhs = builder.curve_add(&sigs[i].r, &pk_times_h);
-        let s_times_g = builder.convert_from_curta_affine_point_target(&s_times_g_results[i]);
-        CircuitBuilderCurve::connect_affine_point(builder, &s_times_g, &rhs);
     }
 
     EDDSATargets {
@@ -416,8 +300,6 @@ pub fn curta_batch_eddsa_verify<
 mod tests {
     use std::time::SystemTime;
 
-    use curta::math::goldilocks::cubic::GoldilocksCubicParameters;
-    use curta::plonky2::stark::config::CurtaPoseidonGoldilocksConfig;
     use log::debug;
     use num::BigUint;
     use plonky2::field::goldilocks_field::GoldilocksField;
@@ -438,6 +320,7 @@ mod tests {
         curta_batch_eddsa_verify, curta_batch_eddsa_verify_variable,
     };
     use crate::frontend::num::biguint::WitnessBigUint;
+    use crate::prelude::DefaultBuilder;
     use crate::utils;
 
     fn to_bits(msg: Vec<u8>) -> Vec<bool> {
@@ -455,17 +338,16 @@ mod tests {
         res
     }
 
-    fn test_eddsa_circuit_with_config(config: CircuitConfig) {
+    /// Verifies a signature, with `S + l` in place of `S` if `malleate_s` is set.
+    fn test_eddsa_circuit_with_config(config: CircuitConfig, malleate_s: bool) {
         utils::setup_logger();
         type F = GoldilocksField;
-        type E = GoldilocksCubicParameters;
-        type SC = CurtaPoseidonGoldilocksConfig;
-        type C = PoseidonGoldilocksConfig;
         type Curve = Ed25519;
         const D: usize = 2;
 
         let mut pw = PartialWitness::new();
-        let mut builder = BaseCircuitBuilder::<F, D>::new(config);
+        let mut builder = DefaultBuilder::new();
+        builder.api = BaseCircuitBuilder::<F, D>::new(config);
 
         let msg = b"plonky2";
         let msg_bits = to_bits(msg.to_vec());
@@ -512,11 +394,7 @@ mod tests {
 
         assert!(verify_message(&msg_bits, &sig, &EDDSAPublicKey(pub_key)));
 
-        let eddsa_target = curta_batch_eddsa_verify::<F, Curve, E, SC, D>(
-            &mut builder,
-            1,
-            msg.len().try_into().unwrap(),
-        );
+        let eddsa_target = curta_batch_eddsa_verify(&mut builder, 1, msg.len().try_into().unwrap());
         for i in 0..msg_bits.len() {
             pw.set_bool_target(eddsa_target.msgs[0][i], msg_bits[i]);
         }
@@ -539,12 +417,16 @@ mod tests {
             &sig_r.y.to_canonical_biguint(),
         );
 
-        pw.set_biguint_target(&eddsa_target.sigs[0].s.value, &sig_s.to_canonical_biguint());
+        let mut sig_s_value = sig_s.to_canonical_biguint();
+        if malleate_s {
+            sig_s_value += Ed25519Scalar::order();
+        }
+        pw.set_biguint_target(&eddsa_target.sigs[0].s.value, &sig_s_value);
 
-        dbg!(builder.num_gates());
+        dbg!(builder.api.num_gates());
 
         let circuit_builder_start_time = SystemTime::now();
-        let data = builder.build::<C>();
+        let data = builder.build().data;
         let circuit_builder_time = circuit_builder_start_time.elapsed().unwrap();
 
         let proof_start_time = SystemTime::now();
@@ -577,20 +459,15 @@ mod tests {
         let msg_len = msgs[0].len();
 
         type F = GoldilocksField;
-        type E = GoldilocksCubicParameters;
-        type SC = CurtaPoseidonGoldilocksConfig;
         type C = PoseidonGoldilocksConfig;
-        type Curve = Ed25519;
         const D: usize = 2;
 
         let mut pw = PartialWitness::new();
-        let mut builder = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
+        let mut builder = DefaultBuilder::new();
+        builder.api = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
 
-        let eddsa_target = curta_batch_eddsa_verify::<F, Curve, E, SC, D>(
-            &mut builder,
-            msgs.len(),
-            msg_len.try_into().unwrap(),
-        );
+        let eddsa_target =
+            curta_batch_eddsa_verify(&mut builder, msgs.len(), msg_len.try_into().unwrap());
 
         for i in 0..msgs.len() {
             let msg_bits = to_bits(msgs[i].to_vec());
@@ -632,7 +509,7 @@ mod tests {
             pw.set_biguint_target(&eddsa_target.sigs[i].s.value, &sig_s.to_canonical_biguint());
         }
 
-        let inner_data = builder.build::<C>();
+        let inner_data = builder.build().data;
         let inner_proof = inner_data.prove(pw).unwrap();
         inner_data.verify(inner_proof.clone()).unwrap();
 
@@ -647,7 +524,7 @@ mod tests {
             &inner_data.common,
         );
 
-        let outer_data = outer_builder.build::<C>();
+        let outer_data = outer_builder.build().data;
         for gate in outer_data.common.gates.iter() {
             debug!("ecddsa verify recursive gate: {:?}", gate);
         }
@@ -672,21 +549,17 @@ mod tests {
         assert!(pub_keys.len() == sigs.len());
 
         type F = GoldilocksField;
-        type E = GoldilocksCubicParameters;
-        type SC = CurtaPoseidonGoldilocksConfig;
         type C = PoseidonGoldilocksConfig;
-        type Curve = Ed25519;
         const D: usize = 2;
 
         let mut pw = PartialWitness::new();
-        let mut builder = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
+        let mut builder = DefaultBuilder::new();
+        builder.api = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
         const MAX_MSG_LEN_BYTES: usize = 128;
         const MAX_MSG_LEN_BITS: usize = MAX_MSG_LEN_BYTES * 8;
         // Length of sig.r and pk_compressed in hash_msg
-        let eddsa_target = curta_batch_eddsa_verify_variable::<F, Curve, E, SC, D, MAX_MSG_LEN_BYTES>(
-            &mut builder,
-            msgs.len(),
-        );
+        let eddsa_target =
+            curta_batch_eddsa_verify_variable::<_, D, MAX_MSG_LEN_BYTES>(&mut builder, msgs.len());
 
         for i in 0..msgs.len() {
             let msg_bits = to_bits(msgs[i].to_vec());
@@ -739,7 +612,7 @@ mod tests {
             pw.set_biguint_target(&eddsa_target.sigs[i].s.value, &sig_s.to_canonical_biguint());
         }
 
-        let inner_data = builder.build::<C>();
+        let inner_data = builder.build().data;
         let circuit_digest = inner_data.verifier_only.circuit_digest;
         debug!("circuit_digest: {:?}", circuit_digest);
 
@@ -757,7 +630,7 @@ mod tests {
             &inner_data.common,
         );
 
-        let outer_data = outer_builder.build::<C>();
+        let outer_data = outer_builder.build().data;
         for gate in outer_data.common.gates.iter() {
             debug!("ecddsa verify recursive gate: {:?}", gate);
         }
@@ -774,13 +647,20 @@ mod tests {
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_eddsa_circuit_narrow() {
-        test_eddsa_circuit_with_config(CircuitConfig::standard_ecc_config());
+        test_eddsa_circuit_with_config(CircuitConfig::standard_ecc_config(), false);
     }
 
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_eddsa_circuit_wide() {
-        test_eddsa_circuit_with_config(CircuitConfig::wide_ecc_config());
+        test_eddsa_circuit_with_config(CircuitConfig::wide_ecc_config(), false);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_eddsa_circuit_malleable_s() {
+        test_eddsa_circuit_with_config(CircuitConfig::standard_ecc_config(), true);
     }
 
     #[test]
@@ -107,14 +107,8 @@ impl<L: PlonkParameters<D>, const D: usize> EDDSABatchVerify<L, D> for CircuitBu
         signatures: ArrayVariable<EDDSASignatureTarget<Self::Curve>, NUM_SIGS>,
         pubkeys: ArrayVariable<AffinePointTarget<Self::Curve>, NUM_SIGS>,
     ) {
-        let eddsa_target = curta_batch_eddsa_verify_variable::<
-            L::Field,
-            Self::Curve,
-            L::CubicParams,
-            L::CurtaConfig,
-            D,
-            MESSAGE_BYTE_LENGTH,
-        >(&mut self.api, NUM_SIGS);
+        let eddsa_target =
+            curta_batch_eddsa_verify_variable::<L, D, MESSAGE_BYTE_LENGTH>(self, NUM_SIGS);
 
         for i in 0..NUM_SIGS {
             let byte_length = self.constant::<U32Variable>(MESSAGE_BYTE_LENGTH as u32);
@@ -158,14 +152,8 @@ impl<L: PlonkParameters<D>, const D: usize> EDDSABatchVerify<L, D> for CircuitBu
     ) {
         let dummy_target = self.get_dummy_targets();
 
-        let eddsa_target = curta_batch_eddsa_verify_variable::<
-            L::Field,
-            Self::Curve,
-            L::CubicParams,
-            L::CurtaConfig,
-            D,
-            MAX_MESSAGE_BYTE_LENGTH,
-        >(&mut self.api, NUM_SIGS);
+        let eddsa_target =
+            curta_batch_eddsa_verify_variable::<L, D, MAX_MESSAGE_BYTE_LENGTH>(self, NUM_SIGS);
 
         // If the validator is active, use the corresponding signature and public key. Otherwise, use the dummy signature and public key.
         for i in 0..NUM_SIGS {
@@ -1 +1,5 @@
+pub mod babyjubjub;
+pub mod bn254;
 pub mod ed25519;
+pub mod msm;
+pub mod secp256k1;
@@ -0,0 +1,208 @@
+use core::marker::PhantomData;
+
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
+
+use super::CircuitGroupLaw;
+use crate::frontend::ecc::ed25519::curve::curve_types::{Curve, ProjectivePoint};
+use crate::frontend::ecc::ed25519::gadgets::curve::{AffinePointTarget, CircuitBuilderCurve};
+use crate::frontend::num::nonnative::nonnative::CircuitBuilderNonNative;
+
+/// The group law of the twisted Edwards curve `C`, which is complete.
+#[derive(Debug, Clone, Copy)]
+pub struct TwistedEdwards<C: Curve>(PhantomData<C>);
+
+impl<F: RichField + Extendable<D>, const D: usize, C: Curve> CircuitGroupLaw<F, D>
+    for TwistedEdwards<C>
+{
+    type Point = ProjectivePoint<C>;
+    type PointTarget = AffinePointTarget<C>;
+
+    fn neutral() -> Self::Point {
+        ProjectivePoint::ZERO
+    }
+
+    fn add(p1: &Self::Point, p2: &Self::Point) -> Self::Point {
+        *p1 + *p2
+    }
+
+    fn double(p: &Self::Point) -> Self::Point {
+        p.double()
+    }
+
+    fn constant_point(builder: &mut BaseCircuitBuilder<F, D>, p: Self::Point) -> Self::PointTarget {
+        let p = p.to_affine();
+        if p.zero {
+            builder.curve_zero()
+        } else {
+            builder.constant_affine_point(p)
+        }
+    }
+
+    fn add_points(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        p1: &Self::PointTarget,
+        p2: &Self::PointTarget,
+    ) -> Self::PointTarget {
+        builder.curve_add(p1, p2)
+    }
+
+    fn double_point(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        p: &Self::PointTarget,
+    ) -> Self::PointTarget {
+        builder.curve_add(p, p)
+    }
+
+    fn select_point(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        b: BoolTarget,
+        p1: &Self::PointTarget,
+        p2: &Self::PointTarget,
+    ) -> Self::PointTarget {
+        AffinePointTarget {
+            x: builder.if_nonnative(b, &p1.x, &p2.x),
+            y: builder.if_nonnative(b, &p1.y, &p2.y),
+        }
+    }
+
+    fn random_access_point(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        index: Target,
+        table: Vec<Self::PointTarget>,
+    ) -> Self::PointTarget {
+        builder.random_access_affine_point(index, table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::debug;
+    use plonky2::field::types::{Field, Sample};
+    use plonky2::iop::target::BoolTarget;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use super::TwistedEdwards;
+    use crate::frontend::ecc::ed25519::curve::curve_types::{Curve, CurveScalar};
+    use crate::frontend::ecc::ed25519::curve::ed25519::Ed25519;
+    use crate::frontend::ecc::ed25519::field::ed25519_scalar::Ed25519Scalar;
+    use crate::frontend::ecc::ed25519::gadgets::curve::CircuitBuilderCurve;
+    use crate::frontend::ecc::msm::CircuitBuilderMsm;
+    use crate::utils;
+
+    const D: usize = 2;
+    type C = PoseidonGoldilocksConfig;
+    type F = <C as GenericConfig<D>>::F;
+    type G = TwistedEdwards<Ed25519>;
+
+    const NB_BITS: usize = 32;
+
+    fn scalar_bits(builder: &mut BaseCircuitBuilder<F, D>, scalar: u64) -> Vec<BoolTarget> {
+        (0..NB_BITS)
+            .map(|i| builder.constant_bool((scalar >> i) & 1 == 1))
+            .collect()
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_curve_msm_ed25519() {
+        utils::setup_logger();
+        let mut builder = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
+
+        let scalars = [rand::random::<u32>(), rand::random::<u32>()];
+        let points = [
+            (CurveScalar(Ed25519Scalar::rand()) * Ed25519::GENERATOR_PROJECTIVE).to_affine(),
+            (CurveScalar(Ed25519Scalar::rand()) * Ed25519::GENERATOR_PROJECTIVE).to_affine(),
+        ];
+        let expected = (CurveScalar(Ed25519Scalar::from_canonical_u32(scalars[0]))
+            * points[0].to_projective()
+            + CurveScalar(Ed25519Scalar::from_canonical_u32(scalars[1]))
+                * points[1].to_projective())
+        .to_affine();
+
+        let point_targets = points
+            .iter()
+            .map(|p| builder.constant_affine_point(*p))
+            .collect::<Vec<_>>();
+        let bits = scalars
+            .iter()
+            .map(|s| scalar_bits(&mut builder, *s as u64))
+            .collect::<Vec<_>>();
+
+        let gates_before = builder.num_gates();
+        let naive = bits
+            .iter()
+            .zip(point_targets.iter())
+            .map(|(b, p)| builder.curve_scalar_mul(p, b))
+            .collect::<Vec<_>>();
+        let naive = builder.curve_add(&naive[0], &naive[1]);
+        let gates_naive = builder.num_gates() - gates_before;
+
+        let gates_before = builder.num_gates();
+        let straus = builder.curve_msm::<G>(&point_targets, &bits);
+        let gates_straus = builder.num_gates() - gates_before;
+
+        let gates_before = builder.num_gates();
+        let pippenger = builder.curve_msm_pippenger::<G>(&point_targets, &bits);
+        let gates_pippenger = builder.num_gates() - gates_before;
+
+        debug!(
+            "gates for two {} bit scalar multiplications: {} naive, {} straus, {} pippenger",
+            NB_BITS, gates_naive, gates_straus, gates_pippenger
+        );
+        assert!(gates_straus < gates_naive);
+
+        let expected_target = builder.constant_affine_point(expected);
+        builder.connect_affine_point(&naive, &expected_target);
+        builder.connect_affine_point(&straus, &expected_target);
+        builder.connect_affine_point(&pippenger, &expected_target);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new()).unwrap();
+        data.verify(proof).unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_curve_fixed_base_scalar_mul_ed25519() {
+        utils::setup_logger();
+        let mut builder = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
+
+        let scalar = rand::random::<u32>();
+        let g = Ed25519::GENERATOR_AFFINE;
+        let expected = (CurveScalar(Ed25519Scalar::from_canonical_u32(scalar))
+            * Ed25519::GENERATOR_PROJECTIVE)
+            .to_affine();
+
+        let bits = scalar_bits(&mut builder, scalar as u64);
+
+        let gates_before = builder.num_gates();
+        let g_target = builder.constant_affine_point(g);
+        let naive = builder.curve_scalar_mul(&g_target, &bits);
+        let gates_naive = builder.num_gates() - gates_before;
+
+        let gates_before = builder.num_gates();
+        let fixed_base =
+            builder.curve_fixed_base_scalar_mul::<G>(Ed25519::GENERATOR_PROJECTIVE, &bits);
+        let gates_fixed_base = builder.num_gates() - gates_before;
+
+        debug!(
+            "gates for a {} bit scalar multiplication of the generator: {} naive, {} fixed base",
+            NB_BITS, gates_naive, gates_fixed_base
+        );
+        assert!(gates_fixed_base < gates_naive);
+
+        let expected_target = builder.constant_affine_point(expected);
+        builder.connect_affine_point(&naive, &expected_target);
+        builder.connect_affine_point(&fixed_base, &expected_target);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new()).unwrap();
+        data.verify(proof).unwrap();
+    }
+}
@@ -0,0 +1,234 @@
+//! Multi-scalar multiplication gadgets for any curve whose group law implements
+//! [`CircuitGroupLaw`].
+//!
+//! The gadgets only use the group law through the trait, so the same code serves the twisted
+//! Edwards curves in [`edwards`] (ed25519, BabyJubJub) and the short Weierstrass curves in
+//! [`weierstrass`] (secp256k1). Both laws are complete, so table entries, buckets and
+//! accumulators may be the neutral element.
+
+pub mod edwards;
+pub mod weierstrass;
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
+
+/// The number of scalar bits consumed per window. Tables have `2^MSM_WINDOW_BITS` entries.
+pub const MSM_WINDOW_BITS: usize = 4;
+
+/// The group law of a curve, both on points known at circuit build time and in the circuit.
+///
+/// The law must be complete: adding or doubling any two points, including the neutral element,
+/// must give the correct result.
+pub trait CircuitGroupLaw<F: RichField + Extendable<D>, const D: usize> {
+    /// A point known at circuit build time.
+    type Point: Copy;
+
+    /// A point in the circuit.
+    type PointTarget: Clone;
+
+    fn neutral() -> Self::Point;
+
+    fn add(p1: &Self::Point, p2: &Self::Point) -> Self::Point;
+
+    fn double(p: &Self::Point) -> Self::Point;
+
+    fn constant_point(builder: &mut BaseCircuitBuilder<F, D>, p: Self::Point) -> Self::PointTarget;
+
+    fn add_points(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        p1: &Self::PointTarget,
+        p2: &Self::PointTarget,
+    ) -> Self::PointTarget;
+
+    fn double_point(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        p: &Self::PointTarget,
+    ) -> Self::PointTarget;
+
+    /// Returns `p1` if `b` is true and `p2` otherwise.
+    fn select_point(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        b: BoolTarget,
+        p1: &Self::PointTarget,
+        p2: &Self::PointTarget,
+    ) -> Self::PointTarget;
+
+    /// Returns `table[index]`, where the length of `table` is a power of two.
+    fn random_access_point(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        index: Target,
+        table: Vec<Self::PointTarget>,
+    ) -> Self::PointTarget;
+}
+
+/// Multi-scalar multiplication gadgets. Scalars are given as little-endian bits.
+pub trait CircuitBuilderMsm<F: RichField + Extendable<D>, const D: usize> {
+    /// Computes `sum_i scalars[i] * points[i]` with Straus' method: each point gets a table of its
+    /// small multiples, and the doublings of the accumulator are shared between all points.
+    fn curve_msm<G: CircuitGroupLaw<F, D>>(
+        &mut self,
+        points: &[G::PointTarget],
+        scalars_bits_le: &[Vec<BoolTarget>],
+    ) -> G::PointTarget;
+
+    /// Computes `sum_i scalars[i] * points[i]` with Pippenger's bucket method: in each window,
+    /// every point is added to the bucket of its digit, and the buckets are weighted with running
+    /// sums.
+    ///
+    /// A circuit cannot index the buckets by a witness digit, so each point is conditionally added
+    /// to every bucket. This costs `2^MSM_WINDOW_BITS` additions per point and window, against one
+    /// addition and one table lookup for `curve_msm`, so Straus' method is cheaper for every
+    /// number of points; see `benches/msm.rs`.
+    fn curve_msm_pippenger<G: CircuitGroupLaw<F, D>>(
+        &mut self,
+        points: &[G::PointTarget],
+        scalars_bits_le: &[Vec<BoolTarget>],
+    ) -> G::PointTarget;
+
+    /// Computes `scalar * base` for a point known at circuit build time. Each window uses a table
+    /// of constants, so no doublings are needed in the circuit.
+    fn curve_fixed_base_scalar_mul<G: CircuitGroupLaw<F, D>>(
+        &mut self,
+        base: G::Point,
+        scalar_bits_le: &[BoolTarget],
+    ) -> G::PointTarget;
+}
+
+/// Pads every scalar to a whole number of windows and returns the scalars and the number of
+/// windows.
+fn pad_to_windows<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut BaseCircuitBuilder<F, D>,
+    scalars_bits_le: &[Vec<BoolTarget>],
+) -> (Vec<Vec<BoolTarget>>, usize) {
+    let nb_bits = scalars_bits_le.iter().map(|s| s.len()).max().unwrap_or(0);
+    let nb_windows = (nb_bits + MSM_WINDOW_BITS - 1) / MSM_WINDOW_BITS;
+
+    let false_target = builder._false();
+    let scalars_bits_le = scalars_bits_le
+        .iter()
+        .map(|bits| {
+            let mut bits = bits.clone();
+            bits.resize(nb_windows * MSM_WINDOW_BITS, false_target);
+            bits
+        })
+        .collect::<Vec<_>>();
+    (scalars_bits_le, nb_windows)
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderMsm<F, D>
+    for BaseCircuitBuilder<F, D>
+{
+    fn curve_msm<G: CircuitGroupLaw<F, D>>(
+        &mut self,
+        points: &[G::PointTarget],
+        scalars_bits_le: &[Vec<BoolTarget>],
+    ) -> G::PointTarget {
+        assert_eq!(points.len(), scalars_bits_le.len());
+        let (scalars_bits_le, nb_windows) = pad_to_windows(self, scalars_bits_le);
+
+        // tables[i][j] = j * points[i]
+        let tables = points
+            .iter()
+            .map(|p| {
+                let mut table = vec![G::constant_point(self, G::neutral()), p.clone()];
+                for j in 2..1 << MSM_WINDOW_BITS {
+                    let entry = if j % 2 == 0 {
+                        G::double_point(self, &table[j / 2])
+                    } else {
+                        G::add_points(self, &table[j - 1], p)
+                    };
+                    table.push(entry);
+                }
+                table
+            })
+            .collect::<Vec<_>>();
+
+        let mut result = G::constant_point(self, G::neutral());
+        for window in (0..nb_windows).rev() {
+            if window + 1 < nb_windows {
+                for _ in 0..MSM_WINDOW_BITS {
+                    result = G::double_point(self, &result);
+                }
+            }
+            for (table, bits) in tables.iter().zip(scalars_bits_le.iter()) {
+                let digit = self
+                    .le_sum(bits[window * MSM_WINDOW_BITS..(window + 1) * MSM_WINDOW_BITS].iter());
+                let entry = G::random_access_point(self, digit, table.clone());
+                result = G::add_points(self, &result, &entry);
+            }
+        }
+        result
+    }
+
+    fn curve_msm_pippenger<G: CircuitGroupLaw<F, D>>(
+        &mut self,
+        points: &[G::PointTarget],
+        scalars_bits_le: &[Vec<BoolTarget>],
+    ) -> G::PointTarget {
+        assert_eq!(points.len(), scalars_bits_le.len());
+        let (scalars_bits_le, nb_windows) = pad_to_windows(self, scalars_bits_le);
+
+        let mut result = G::constant_point(self, G::neutral());
+        for window in (0..nb_windows).rev() {
+            if window + 1 < nb_windows {
+                for _ in 0..MSM_WINDOW_BITS {
+                    result = G::double_point(self, &result);
+                }
+            }
+
+            // buckets[j - 1] is the sum of the points whose digit is j.
+            let mut buckets =
+                vec![G::constant_point(self, G::neutral()); (1 << MSM_WINDOW_BITS) - 1];
+            for (p, bits) in points.iter().zip(scalars_bits_le.iter()) {
+                let digit = self
+                    .le_sum(bits[window * MSM_WINDOW_BITS..(window + 1) * MSM_WINDOW_BITS].iter());
+                for (j, bucket) in buckets.iter_mut().enumerate() {
+                    let j = self.constant(F::from_canonical_usize(j + 1));
+                    let in_bucket = self.is_equal(digit, j);
+                    let sum = G::add_points(self, bucket, p);
+                    *bucket = G::select_point(self, in_bucket, &sum, bucket);
+                }
+            }
+
+            // sum_j j * buckets[j - 1], as the sum of the running sums from the highest bucket.
+            let mut running_sum = G::constant_point(self, G::neutral());
+            let mut window_sum = G::constant_point(self, G::neutral());
+            for bucket in buckets.iter().rev() {
+                running_sum = G::add_points(self, &running_sum, bucket);
+                window_sum = G::add_points(self, &window_sum, &running_sum);
+            }
+            result = G::add_points(self, &result, &window_sum);
+        }
+        result
+    }
+
+    fn curve_fixed_base_scalar_mul<G: CircuitGroupLaw<F, D>>(
+        &mut self,
+        base: G::Point,
+        scalar_bits_le: &[BoolTarget],
+    ) -> G::PointTarget {
+        let mut window_base = base;
+        let mut result = G::constant_point(self, G::neutral());
+        for chunk in scalar_bits_le.chunks(MSM_WINDOW_BITS) {
+            // table[j] = j * 2^(MSM_WINDOW_BITS * window) * base
+            let mut multiple = G::neutral();
+            let mut table = vec![G::constant_point(self, multiple)];
+            for _ in 1..1 << chunk.len() {
+                multiple = G::add(&multiple, &window_base);
+                table.push(G::constant_point(self, multiple));
+            }
+
+            let digit = self.le_sum(chunk.iter());
+            let entry = G::random_access_point(self, digit, table);
+            result = G::add_points(self, &result, &entry);
+
+            for _ in 0..MSM_WINDOW_BITS {
+                window_base = G::double(&window_base);
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,513 @@
+use core::fmt::Debug;
+use core::marker::PhantomData;
+
+use num::BigUint;
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::{Field, PrimeField};
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
+
+use super::CircuitGroupLaw;
+use crate::frontend::num::nonnative::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+
+/// A short Weierstrass curve `y^2 = x^3 + b`, i.e. with `a = 0`, of prime order.
+pub trait WeierstrassCurve: 'static + Sync + Sized + Copy + Debug {
+    type BaseField: PrimeField;
+    type ScalarField: PrimeField;
+
+    const B: Self::BaseField;
+
+    const GENERATOR: WeierstrassPoint<Self>;
+}
+
+/// A point on a short Weierstrass curve in projective coordinates, where the neutral element is
+/// `(0 : 1 : 0)`.
+///
+/// Additions and doublings use the complete formulas for `a = 0` of Renes, Costello and Batina,
+/// "Complete addition formulas for prime order elliptic curves" (algorithms 7 and 9), so they have
+/// no exceptional cases.
+#[derive(Copy, Clone, Debug)]
+pub struct WeierstrassPoint<C: WeierstrassCurve> {
+    pub x: C::BaseField,
+    pub y: C::BaseField,
+    pub z: C::BaseField,
+}
+
+impl<C: WeierstrassCurve> WeierstrassPoint<C> {
+    pub const ZERO: Self = Self {
+        x: C::BaseField::ZERO,
+        y: C::BaseField::ONE,
+        z: C::BaseField::ZERO,
+    };
+
+    pub fn from_affine(x: C::BaseField, y: C::BaseField) -> Self {
+        let point = Self {
+            x,
+            y,
+            z: C::BaseField::ONE,
+        };
+        debug_assert!(point.is_valid());
+        point
+    }
+
+    /// Returns the affine coordinates, or `None` for the neutral element.
+    pub fn to_affine(&self) -> Option<(C::BaseField, C::BaseField)> {
+        if self.z == C::BaseField::ZERO {
+            None
+        } else {
+            let z_inv = self.z.inverse();
+            Some((self.x * z_inv, self.y * z_inv))
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let Self { x, y, z } = *self;
+        y.square() * z == x.cube() + C::B * z.cube()
+    }
+
+    #[must_use]
+    pub fn add(&self, other: &Self) -> Self {
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (other.x, other.y, other.z);
+        let b3 = C::B.triple();
+
+        let t0 = x1 * x2;
+        let t1 = y1 * y2;
+        let t2 = z1 * z2;
+        let t3 = (x1 + y1) * (x2 + y2) - (t0 + t1);
+        let t4 = (y1 + z1) * (y2 + z2) - (t1 + t2);
+        let y3 = (x1 + z1) * (x2 + z2) - (t0 + t2);
+        let t0 = t0.triple();
+        let t2 = b3 * t2;
+        let z3 = t1 + t2;
+        let t1 = t1 - t2;
+        let y3 = b3 * y3;
+        let x3 = t3 * t1 - t4 * y3;
+        let y3 = t1 * z3 + y3 * t0;
+        let z3 = z3 * t4 + t0 * t3;
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    #[must_use]
+    pub fn double(&self) -> Self {
+        let Self { x, y, z } = *self;
+        let b3 = C::B.triple();
+
+        let t0 = y.square();
+        let z3 = t0.double().double().double();
+        let t1 = y * z;
+        let t2 = b3 * z.square();
+        let x3 = t2 * z3;
+        let y3 = t0 + t2;
+        let z3 = t1 * z3;
+        let t0 = t0 - t2.triple();
+        let y3 = x3 + t0 * y3;
+        let x3 = (t0 * x * y).double();
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// Computes `scalar * self` with double-and-add.
+    #[must_use]
+    pub fn scalar_mul(&self, scalar: &BigUint) -> Self {
+        let mut result = Self::ZERO;
+        for i in (0..scalar.bits()).rev() {
+            result = result.double();
+            if scalar.bit(i) {
+                result = result.add(self);
+            }
+        }
+        result
+    }
+}
+
+impl<C: WeierstrassCurve> PartialEq for WeierstrassPoint<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+    }
+}
+
+impl<C: WeierstrassCurve> Eq for WeierstrassPoint<C> {}
+
+/// A target representing a point on a short Weierstrass curve in projective coordinates.
+#[derive(Clone, Debug)]
+pub struct WeierstrassPointTarget<C: WeierstrassCurve> {
+    pub x: NonNativeTarget<C::BaseField>,
+    pub y: NonNativeTarget<C::BaseField>,
+    pub z: NonNativeTarget<C::BaseField>,
+}
+
+pub trait CircuitBuilderWeierstrass<F: RichField + Extendable<D>, const D: usize> {
+    fn constant_weierstrass_point<C: WeierstrassCurve>(
+        &mut self,
+        p: WeierstrassPoint<C>,
+    ) -> WeierstrassPointTarget<C>;
+
+    /// Constrains `p` to be on the curve, i.e. `y^2 z = x^3 + b z^3`, and not to be `(0 : 0 : 0)`.
+    fn weierstrass_assert_valid<C: WeierstrassCurve>(&mut self, p: &WeierstrassPointTarget<C>);
+
+    /// Constrains `lhs` and `rhs` to be the same point, which may have different coordinates.
+    fn connect_weierstrass_point<C: WeierstrassCurve>(
+        &mut self,
+        lhs: &WeierstrassPointTarget<C>,
+        rhs: &WeierstrassPointTarget<C>,
+    );
+
+    /// Adds two points with the complete formulas, so the points may be equal or neutral.
+    fn weierstrass_add<C: WeierstrassCurve>(
+        &mut self,
+        p1: &WeierstrassPointTarget<C>,
+        p2: &WeierstrassPointTarget<C>,
+    ) -> WeierstrassPointTarget<C>;
+
+    fn weierstrass_double<C: WeierstrassCurve>(
+        &mut self,
+        p: &WeierstrassPointTarget<C>,
+    ) -> WeierstrassPointTarget<C>;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderWeierstrass<F, D>
+    for BaseCircuitBuilder<F, D>
+{
+    fn constant_weierstrass_point<C: WeierstrassCurve>(
+        &mut self,
+        p: WeierstrassPoint<C>,
+    ) -> WeierstrassPointTarget<C> {
+        WeierstrassPointTarget {
+            x: self.constant_nonnative(p.x),
+            y: self.constant_nonnative(p.y),
+            z: self.constant_nonnative(p.z),
+        }
+    }
+
+    fn weierstrass_assert_valid<C: WeierstrassCurve>(&mut self, p: &WeierstrassPointTarget<C>) {
+        let b = self.constant_nonnative(C::B);
+        let y_squared = self.mul_nonnative(&p.y, &p.y);
+        let lhs = self.mul_nonnative(&y_squared, &p.z);
+        let x_cubed = self.mul_many_nonnative(&[p.x.clone(), p.x.clone(), p.x.clone()]);
+        let z_cubed = self.mul_many_nonnative(&[p.z.clone(), p.z.clone(), p.z.clone()]);
+        let b_z_cubed = self.mul_nonnative(&b, &z_cubed);
+        let rhs = self.add_nonnative(&x_cubed, &b_z_cubed);
+        self.connect_nonnative(&lhs, &rhs);
+
+        // A curve of prime order has no point of order two, so y is only zero for (0 : 0 : 0).
+        let y_inv = self.inv_nonnative(&p.y);
+        let y_y_inv = self.mul_nonnative(&p.y, &y_inv);
+        let one = self.constant_nonnative(C::BaseField::ONE);
+        self.connect_nonnative(&y_y_inv, &one);
+    }
+
+    fn connect_weierstrass_point<C: WeierstrassCurve>(
+        &mut self,
+        lhs: &WeierstrassPointTarget<C>,
+        rhs: &WeierstrassPointTarget<C>,
+    ) {
+        let x1_z2 = self.mul_nonnative(&lhs.x, &rhs.z);
+        let x2_z1 = self.mul_nonnative(&rhs.x, &lhs.z);
+        self.connect_nonnative(&x1_z2, &x2_z1);
+        let y1_z2 = self.mul_nonnative(&lhs.y, &rhs.z);
+        let y2_z1 = self.mul_nonnative(&rhs.y, &lhs.z);
+        self.connect_nonnative(&y1_z2, &y2_z1);
+    }
+
+    fn weierstrass_add<C: WeierstrassCurve>(
+        &mut self,
+        p1: &WeierstrassPointTarget<C>,
+        p2: &WeierstrassPointTarget<C>,
+    ) -> WeierstrassPointTarget<C> {
+        let WeierstrassPointTarget {
+            x: x1,
+            y: y1,
+            z: z1,
+        } = p1;
+        let WeierstrassPointTarget {
+            x: x2,
+            y: y2,
+            z: z2,
+        } = p2;
+        let b3 = self.constant_nonnative(C::B.triple());
+
+        let t0 = self.mul_nonnative(x1, x2);
+        let t1 = self.mul_nonnative(y1, y2);
+        let t2 = self.mul_nonnative(z1, z2);
+
+        // t3 = (x1 + y1) * (x2 + y2) - (t0 + t1)
+        let x1_y1 = self.add_nonnative(x1, y1);
+        let x2_y2 = self.add_nonnative(x2, y2);
+        let t3 = self.mul_nonnative(&x1_y1, &x2_y2);
+        let t0_t1 = self.add_nonnative(&t0, &t1);
+        let t3 = self.sub_nonnative(&t3, &t0_t1);
+
+        // t4 = (y1 + z1) * (y2 + z2) - (t1 + t2)
+        let y1_z1 = self.add_nonnative(y1, z1);
+        let y2_z2 = self.add_nonnative(y2, z2);
+        let t4 = self.mul_nonnative(&y1_z1, &y2_z2);
+        let t1_t2 = self.add_nonnative(&t1, &t2);
+        let t4 = self.sub_nonnative(&t4, &t1_t2);
+
+        // y3 = (x1 + z1) * (x2 + z2) - (t0 + t2)
+        let x1_z1 = self.add_nonnative(x1, z1);
+        let x2_z2 = self.add_nonnative(x2, z2);
+        let y3 = self.mul_nonnative(&x1_z1, &x2_z2);
+        let t0_t2 = self.add_nonnative(&t0, &t2);
+        let y3 = self.sub_nonnative(&y3, &t0_t2);
+
+        let t0 = self.add_many_nonnative(&[t0.clone(), t0.clone(), t0]);
+        let t2 = self.mul_nonnative(&b3, &t2);
+        let z3 = self.add_nonnative(&t1, &t2);
+        let t1 = self.sub_nonnative(&t1, &t2);
+        let y3 = self.mul_nonnative(&b3, &y3);
+
+        // x3 = t3 * t1 - t4 * y3
+        let t3_t1 = self.mul_nonnative(&t3, &t1);
+        let t4_y3 = self.mul_nonnative(&t4, &y3);
+        let x3 = self.sub_nonnative(&t3_t1, &t4_y3);
+
+        // y3 = t1 * z3 + y3 * t0
+        let t1_z3 = self.mul_nonnative(&t1, &z3);
+        let y3_t0 = self.mul_nonnative(&y3, &t0);
+        let y3 = self.add_nonnative(&t1_z3, &y3_t0);
+
+        // z3 = z3 * t4 + t0 * t3
+        let z3_t4 = self.mul_nonnative(&z3, &t4);
+        let t0_t3 = self.mul_nonnative(&t0, &t3);
+        let z3 = self.add_nonnative(&z3_t4, &t0_t3);
+
+        WeierstrassPointTarget {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    fn weierstrass_double<C: WeierstrassCurve>(
+        &mut self,
+        p: &WeierstrassPointTarget<C>,
+    ) -> WeierstrassPointTarget<C> {
+        let WeierstrassPointTarget { x, y, z } = p;
+        let b3 = self.constant_nonnative(C::B.triple());
+        let eight = self.constant_nonnative(C::BaseField::from_canonical_u64(8));
+
+        let t0 = self.mul_nonnative(y, y);
+        let z3 = self.mul_nonnative(&eight, &t0);
+        let t1 = self.mul_nonnative(y, z);
+        let z_squared = self.mul_nonnative(z, z);
+        let t2 = self.mul_nonnative(&b3, &z_squared);
+        let x3 = self.mul_nonnative(&t2, &z3);
+        let y3 = self.add_nonnative(&t0, &t2);
+        let z3 = self.mul_nonnative(&t1, &z3);
+
+        // t0 = t0 - 3 * t2
+        let t2_tripled = self.add_many_nonnative(&[t2.clone(), t2.clone(), t2]);
+        let t0 = self.sub_nonnative(&t0, &t2_tripled);
+
+        // y3 = x3 + t0 * y3
+        let t0_y3 = self.mul_nonnative(&t0, &y3);
+        let y3 = self.add_nonnative(&x3, &t0_y3);
+
+        // x3 = 2 * t0 * x * y
+        let x_y = self.mul_nonnative(x, y);
+        let t0_x_y = self.mul_nonnative(&t0, &x_y);
+        let x3 = self.add_nonnative(&t0_x_y, &t0_x_y);
+
+        WeierstrassPointTarget {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+}
+
+/// The group law of the short Weierstrass curve `C`, with complete projective formulas.
+#[derive(Debug, Clone, Copy)]
+pub struct ShortWeierstrass<C: WeierstrassCurve>(PhantomData<C>);
+
+impl<F: RichField + Extendable<D>, const D: usize, C: WeierstrassCurve> CircuitGroupLaw<F, D>
+    for ShortWeierstrass<C>
+{
+    type Point = WeierstrassPoint<C>;
+    type PointTarget = WeierstrassPointTarget<C>;
+
+    fn neutral() -> Self::Point {
+        WeierstrassPoint::ZERO
+    }
+
+    fn add(p1: &Self::Point, p2: &Self::Point) -> Self::Point {
+        p1.add(p2)
+    }
+
+    fn double(p: &Self::Point) -> Self::Point {
+        p.double()
+    }
+
+    fn constant_point(builder: &mut BaseCircuitBuilder<F, D>, p: Self::Point) -> Self::PointTarget {
+        builder.constant_weierstrass_point(p)
+    }
+
+    fn add_points(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        p1: &Self::PointTarget,
+        p2: &Self::PointTarget,
+    ) -> Self::PointTarget {
+        builder.weierstrass_add(p1, p2)
+    }
+
+    fn double_point(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        p: &Self::PointTarget,
+    ) -> Self::PointTarget {
+        builder.weierstrass_double(p)
+    }
+
+    fn select_point(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        b: BoolTarget,
+        p1: &Self::PointTarget,
+        p2: &Self::PointTarget,
+    ) -> Self::PointTarget {
+        WeierstrassPointTarget {
+            x: builder.if_nonnative(b, &p1.x, &p2.x),
+            y: builder.if_nonnative(b, &p1.y, &p2.y),
+            z: builder.if_nonnative(b, &p1.z, &p2.z),
+        }
+    }
+
+    fn random_access_point(
+        builder: &mut BaseCircuitBuilder<F, D>,
+        index: Target,
+        table: Vec<Self::PointTarget>,
+    ) -> Self::PointTarget {
+        WeierstrassPointTarget {
+            x: builder.random_access_nonnative(index, table.iter().map(|p| p.x.clone()).collect()),
+            y: builder.random_access_nonnative(index, table.iter().map(|p| p.y.clone()).collect()),
+            z: builder.random_access_nonnative(index, table.iter().map(|p| p.z.clone()).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::debug;
+    use num::BigUint;
+    use plonky2::iop::target::BoolTarget;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use super::{CircuitBuilderWeierstrass, ShortWeierstrass, WeierstrassCurve};
+    use crate::frontend::ecc::msm::CircuitBuilderMsm;
+    use crate::frontend::ecc::secp256k1::Secp256K1;
+    use crate::utils;
+
+    const D: usize = 2;
+    type C = PoseidonGoldilocksConfig;
+    type F = <C as GenericConfig<D>>::F;
+    type G = ShortWeierstrass<Secp256K1>;
+
+    const NB_BITS: usize = 32;
+
+    fn scalar_bits(builder: &mut BaseCircuitBuilder<F, D>, scalar: u64) -> Vec<BoolTarget> {
+        (0..NB_BITS)
+            .map(|i| builder.constant_bool((scalar >> i) & 1 == 1))
+            .collect()
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_curve_msm_secp256k1() {
+        utils::setup_logger();
+        let mut builder = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
+
+        // The same point and scalar twice, so the first window adds a table entry to itself, which
+        // is an exceptional case for incomplete addition formulas.
+        let g = Secp256K1::GENERATOR;
+        let scalar = rand::random::<u32>();
+        let point = g.scalar_mul(&BigUint::from(rand::random::<u64>()));
+        let scalars = [scalar, scalar];
+        let points = [point, point];
+        let expected = point.scalar_mul(&(BigUint::from(scalar) * 2u32));
+
+        let point_targets = points
+            .iter()
+            .map(|p| builder.constant_weierstrass_point(*p))
+            .collect::<Vec<_>>();
+        for p in point_targets.iter() {
+            builder.weierstrass_assert_valid(p);
+        }
+        let bits = scalars
+            .iter()
+            .map(|s| scalar_bits(&mut builder, *s as u64))
+            .collect::<Vec<_>>();
+
+        let gates_before = builder.num_gates();
+        let straus = builder.curve_msm::<G>(&point_targets, &bits);
+        let gates_straus = builder.num_gates() - gates_before;
+
+        let gates_before = builder.num_gates();
+        let pippenger = builder.curve_msm_pippenger::<G>(&point_targets, &bits);
+        let gates_pippenger = builder.num_gates() - gates_before;
+
+        debug!(
+            "gates for two {} bit secp256k1 scalar multiplications: {} straus, {} pippenger",
+            NB_BITS, gates_straus, gates_pippenger
+        );
+
+        let expected_target = builder.constant_weierstrass_point(expected);
+        builder.connect_weierstrass_point(&straus, &expected_target);
+        builder.connect_weierstrass_point(&pippenger, &expected_target);
+        builder.weierstrass_assert_valid(&straus);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new()).unwrap();
+        data.verify(proof).unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_curve_fixed_base_scalar_mul_secp256k1() {
+        utils::setup_logger();
+        let mut builder = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
+
+        let scalar = rand::random::<u32>();
+        let g = Secp256K1::GENERATOR;
+        let expected = g.scalar_mul(&BigUint::from(scalar));
+
+        let bits = scalar_bits(&mut builder, scalar as u64);
+        let fixed_base = builder.curve_fixed_base_scalar_mul::<G>(g, &bits);
+
+        let expected_target = builder.constant_weierstrass_point(expected);
+        builder.connect_weierstrass_point(&fixed_base, &expected_target);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new()).unwrap();
+        data.verify(proof).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_curve_msm_secp256k1_wrong_result() {
+        let mut builder = BaseCircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
+
+        let g = Secp256K1::GENERATOR;
+        let g_target = builder.constant_weierstrass_point(g);
+        let bits = scalar_bits(&mut builder, 5);
+        let result = builder.curve_msm::<G>(&[g_target], &[bits]);
+
+        let wrong = builder.constant_weierstrass_point(g.scalar_mul(&BigUint::from(6u32)));
+        builder.connect_weierstrass_point(&result, &wrong);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new()).unwrap();
+        data.verify(proof).unwrap();
+    }
+}
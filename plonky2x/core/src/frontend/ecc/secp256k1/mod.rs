@@ -0,0 +1,80 @@
+use plonky2::field::secp256k1_base::Secp256K1Base;
+use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
+use serde::{Deserialize, Serialize};
+
+use crate::frontend::ecc::msm::weierstrass::{WeierstrassCurve, WeierstrassPoint};
+
+/// The secp256k1 curve `y^2 = x^3 + 7`, see https://www.secg.org/sec2-v2.pdf.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Secp256K1;
+
+impl WeierstrassCurve for Secp256K1 {
+    type BaseField = Secp256K1Base;
+    type ScalarField = Secp256K1Scalar;
+
+    const B: Secp256K1Base = Secp256K1Base([7, 0, 0, 0]);
+
+    const GENERATOR: WeierstrassPoint<Self> = WeierstrassPoint {
+        x: SECP256K1_GENERATOR_X,
+        y: SECP256K1_GENERATOR_Y,
+        z: Secp256K1Base([1, 0, 0, 0]),
+    };
+}
+
+/// 0x79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798
+const SECP256K1_GENERATOR_X: Secp256K1Base = Secp256K1Base([
+    0x59F2815B16F81798,
+    0x029BFCDB2DCE28D9,
+    0x55A06295CE870B07,
+    0x79BE667EF9DCBBAC,
+]);
+
+/// 0x483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8
+const SECP256K1_GENERATOR_Y: Secp256K1Base = Secp256K1Base([
+    0x9C47D08FFB10D4B8,
+    0xFD17B448A6855419,
+    0x5DA4FBFC0E1108A8,
+    0x483ADA7726A3C465,
+]);
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
+    use plonky2::field::types::Field;
+
+    use super::Secp256K1;
+    use crate::frontend::ecc::msm::weierstrass::{WeierstrassCurve, WeierstrassPoint};
+
+    #[test]
+    fn test_secp256k1_group_law() {
+        let g = Secp256K1::GENERATOR;
+        assert!(g.is_valid());
+
+        // The generator has the order of the scalar field.
+        let order = Secp256K1Scalar::order();
+        assert_eq!(g.scalar_mul(&order), WeierstrassPoint::ZERO);
+        assert_eq!(
+            g.scalar_mul(&(order - 1u32)),
+            WeierstrassPoint {
+                x: g.x,
+                y: -g.y,
+                z: g.z,
+            }
+        );
+
+        // The complete formulas handle doublings and the neutral element.
+        let p = g.scalar_mul(&BigUint::from(12345u32));
+        assert_eq!(p.add(&p), p.double());
+        assert_eq!(p.add(&WeierstrassPoint::ZERO), p);
+        assert_eq!(
+            WeierstrassPoint::<Secp256K1>::ZERO.double(),
+            WeierstrassPoint::ZERO
+        );
+        assert!(p.double().is_valid());
+        assert_eq!(
+            p.add(&g.scalar_mul(&BigUint::from(999u32))),
+            g.scalar_mul(&BigUint::from(13344u32))
+        );
+    }
+}
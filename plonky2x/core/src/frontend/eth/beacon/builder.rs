@@ -18,6 +18,7 @@ use super::vars::{
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::eth::vars::BLSPubkeyVariable;
+use crate::frontend::hash::hasher::{CircuitHasher, CurtaSha256Hasher};
 use crate::frontend::uint::uint256::U256Variable;
 use crate::frontend::uint::uint64::U64Variable;
 use crate::frontend::vars::{
@@ -428,7 +429,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             .iter()
             .map(|w| w.hash_tree_root(self))
             .collect::<Vec<_>>();
-        let items_root = self.ssz_hash_leafs(&leafs);
+        let items_root = self.ssz_hash_leafs::<CurtaSha256Hasher>(&leafs);
 
         // SSZ lists encoded as [items_root, list_length]
         // List length is u256 LE
@@ -437,7 +438,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         let list_length_array: [u8; 32] = list_length.try_into().unwrap();
         let list_length_variable = self.constant::<Bytes32Variable>(H256::from(list_length_array));
 
-        let reconstructed_root = self.ssz_hash_leafs(&[items_root, list_length_variable]);
+        let reconstructed_root =
+            self.ssz_hash_leafs::<CurtaSha256Hasher>(&[items_root, list_length_variable]);
         self.assert_is_equal(withdrawals_variable.withdrawals_root, reconstructed_root);
 
         withdrawals
@@ -607,7 +609,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             proof.as_slice(),
             BLOCK_ROOTS_GINDEX as u64,
         );
-        let root = self.ssz_hash_leafs(block_roots.as_slice());
+        let root = self.ssz_hash_leafs::<CurtaSha256Hasher>(block_roots.as_slice());
         self.assert_is_equal(root, block_roots_root);
         block_roots
     }
@@ -720,12 +722,17 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         variable.hash_tree_root(self)
     }
 
-    pub fn ssz_hash_leafs(&mut self, leafs: &[Bytes32Variable]) -> Bytes32Variable {
+    /// Merkleizes `leafs` with the hasher `H`, which is SHA-256 in the SSZ spec.
+    pub fn ssz_hash_leafs<H: CircuitHasher<L, D>>(&mut self, leafs: &[H::Digest]) -> H::Digest {
         let mut leafs = leafs.to_vec();
         while leafs.len() != 1 {
             let mut tmp = Vec::new();
             for i in 0..leafs.len() / 2 {
-                tmp.push(self.curta_sha256_pair(leafs[i * 2], leafs[i * 2 + 1]));
+                tmp.push(H::hash_pair(
+                    self,
+                    leafs[i * 2].clone(),
+                    leafs[i * 2 + 1].clone(),
+                ));
             }
             leafs = tmp;
         }
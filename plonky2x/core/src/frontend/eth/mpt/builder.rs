@@ -3,9 +3,9 @@ use std::marker::PhantomData;
 use curta::math::field::Field;
 
 use super::generators::*;
-use crate::frontend::vars::Nibbles;
 use crate::prelude::{
-    ArrayVariable, ByteVariable, Bytes32Variable, CircuitBuilder, PlonkParameters, Variable,
+    ArrayVariable, ByteVariable, Bytes32Variable, CircuitBuilder, CompactByteVariable,
+    PlonkParameters, Variable,
 };
 
 pub fn transform_proof_to_padded<const ENCODING_LEN: usize, const PROOF_LEN: usize>(
@@ -63,6 +63,15 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         generator.output
     }
 
+    /// Splits `bytes` into nibbles, high nibble first, as compact bytes. A nibble then takes a
+    /// single variable, which keeps the dynamic selections over the key path cheap.
+    pub fn to_compact_nibbles(&mut self, bytes: &[ByteVariable]) -> Vec<CompactByteVariable> {
+        bytes
+            .iter()
+            .flat_map(|byte| CompactByteVariable::from_byte_variable(self, *byte).to_nibbles(self))
+            .collect()
+    }
+
     const PREFIX_EXTENSION_EVEN: u8 = 0;
     const PREFIX_EXTENSION_ODD: u8 = 1;
     const PREFIX_LEAF_EVEN: u8 = 2;
@@ -84,10 +93,11 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         let branch_node_length = self.constant::<Variable>(L::Field::from_canonical_u8(17u8));
         let leaf_or_extension_node_length =
             self.constant::<Variable>(L::Field::from_canonical_u8(2u8));
-        let prefix_leaf_even = self.constant::<ByteVariable>(Self::PREFIX_LEAF_EVEN);
-        let prefix_leaf_odd = self.constant::<ByteVariable>(Self::PREFIX_LEAF_ODD);
-        let prefix_extension_even = self.constant::<ByteVariable>(Self::PREFIX_EXTENSION_EVEN);
-        let prefix_extension_odd = self.constant::<ByteVariable>(Self::PREFIX_EXTENSION_ODD);
+        let prefix_leaf_even = self.constant::<CompactByteVariable>(Self::PREFIX_LEAF_EVEN);
+        let prefix_leaf_odd = self.constant::<CompactByteVariable>(Self::PREFIX_LEAF_ODD);
+        let prefix_extension_even =
+            self.constant::<CompactByteVariable>(Self::PREFIX_EXTENSION_EVEN);
+        let prefix_extension_odd = self.constant::<CompactByteVariable>(Self::PREFIX_EXTENSION_ODD);
         let one: Variable = self.one::<Variable>();
         let two = self.constant::<Variable>(L::Field::from_canonical_u8(2));
         let const_64 = self.constant::<Variable>(L::Field::from_canonical_u8(64));
@@ -103,12 +113,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         }
         let mut current_node_id = ArrayVariable::<ByteVariable, ELEMENT_LEN>::new(padded_root);
         let hash_key = self.keccak256(&key.as_bytes());
-        let key_path: ArrayVariable<ByteVariable, 64> = hash_key
-            .as_bytes()
-            .to_vec()
-            .to_nibbles(self)
-            .try_into()
-            .unwrap();
+        let key_path = self.to_compact_nibbles(&hash_key.as_bytes());
 
         for i in 0..PROOF_LEN {
             let current_node = proof[i].clone();
@@ -145,7 +150,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             let is_branch = self.is_equal(len_decoded_list, branch_node_length);
             let is_leaf = self.is_equal(len_decoded_list, leaf_or_extension_node_length);
             let key_terminated = self.is_equal(current_key_idx, const_64);
-            let path = decoded_list[0].as_slice().to_vec().to_nibbles(self);
+            let path = self.to_compact_nibbles(decoded_list[0].as_slice());
             let prefix = path[0];
             let prefix_leaf_even = self.is_equal(prefix, prefix_leaf_even);
             let prefix_leaf_odd = self.is_equal(prefix, prefix_leaf_odd);
@@ -155,8 +160,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             let offset_even = self.mul(prefix_extension_even.variable, two);
             let offset_odd = self.mul(prefix_extension_odd.variable, one);
             let offset = self.add(offset_even, offset_odd);
-            let branch_key = self.select_array(key_path.clone().as_slice(), current_key_idx);
-            let branch_key_variable: Variable = self.byte_to_variable(branch_key); // can be unsafe since nibbles are checked
+            let branch_key = self.select_array(&key_path, current_key_idx);
+            let branch_key_variable = branch_key.0;
 
             // Case 1
             let is_branch_and_key_terminated = self.and(is_branch, key_terminated);
@@ -190,13 +195,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             let mut check_length = self.sub(e, f);
             check_length = self.mul(check_length, do_path_remainder_check.variable);
 
-            self.assert_subarray_equal(
-                &path,
-                offset,
-                key_path.as_slice(),
-                current_key_idx,
-                check_length,
-            );
+            self.assert_subarray_equal(&path, offset, &key_path, current_key_idx, check_length);
 
             current_key_idx = self.add(current_key_idx, is_branch_and_key_not_terminated.variable);
             let j = self.mul(is_leaf.variable, check_length);
@@ -231,9 +230,46 @@ mod tests {
     use super::super::utils::{read_fixture, EIP1186ProofResponse};
     use super::*;
     use crate::frontend::eth::utils::u256_to_h256_be;
+    use crate::frontend::vars::Nibbles;
     use crate::prelude::{DefaultBuilder, GoldilocksField};
     use crate::utils;
 
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_compact_key_path_degree() {
+        utils::setup_logger();
+        // Each step of `verify_mpt_proof` selects one nibble of the 64 nibble key path.
+        const NUM_SELECTIONS: usize = 256;
+
+        let mut builder = DefaultBuilder::new();
+        let key = builder.read::<Bytes32Variable>();
+        let key_path = key.as_bytes().to_vec().to_nibbles(&mut builder);
+        for _ in 0..NUM_SELECTIONS {
+            let index = builder.read::<Variable>();
+            let nibble = builder.select_array(&key_path, index);
+            builder.write(nibble);
+        }
+        let bits_circuit = builder.build();
+
+        let mut builder = DefaultBuilder::new();
+        let key = builder.read::<Bytes32Variable>();
+        let key_path = builder.to_compact_nibbles(&key.as_bytes());
+        for _ in 0..NUM_SELECTIONS {
+            let index = builder.read::<Variable>();
+            let nibble = builder.select_array(&key_path, index);
+            builder.write(nibble);
+        }
+        let compact_circuit = builder.build();
+
+        let bits_degree = bits_circuit.data.common.degree_bits();
+        let compact_degree = compact_circuit.data.common.degree_bits();
+        debug!(
+            "degree bits for {} key path selections: {} as bits, {} as compact bytes",
+            NUM_SELECTIONS, bits_degree, compact_degree
+        );
+        assert!(compact_degree < bits_degree);
+    }
+
     #[test]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_mpt_circuit() {
@@ -78,17 +78,17 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D> for Lte
 }
 
 #[derive(Debug, Clone)]
-pub struct SubarrayEqualGenerator<L: PlonkParameters<D>, const D: usize> {
-    pub a: Vec<ByteVariable>,
+pub struct SubarrayEqualGenerator<L: PlonkParameters<D>, const D: usize, V: CircuitVariable> {
+    pub a: Vec<V>,
     pub a_offset: Variable,
-    pub b: Vec<ByteVariable>,
+    pub b: Vec<V>,
     pub b_offset: Variable,
     pub len: Variable,
     pub _phantom: PhantomData<L>,
 }
 
-impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
-    for SubarrayEqualGenerator<L, D>
+impl<L: PlonkParameters<D>, const D: usize, V: CircuitVariable> SimpleGenerator<L::Field, D>
+    for SubarrayEqualGenerator<L, D, V>
 {
     fn id(&self) -> String {
         "SubarrayEqualGenerator".to_string()
@@ -114,8 +114,8 @@ impl<L: PlonkParameters<D>, const D: usize> SimpleGenerator<L::Field, D>
         let b_offset = self.b_offset.get(witness).to_canonical_u64() as usize;
         let len = self.len.get(witness).to_canonical_u64() as usize;
         for i in 0..len {
-            let a = self.a[a_offset + i].get(witness);
-            let b = self.b[b_offset + i].get(witness);
+            let a = V::elements(self.a[a_offset + i].get(witness));
+            let b = V::elements(self.b[b_offset + i].get(witness));
             if a != b {
                 panic!("SubarrayEqualGenerator failed at index {}", i);
             }
@@ -1,7 +1,9 @@
 use std::marker::PhantomData;
 
 use super::generators::SubarrayEqualGenerator;
-use crate::prelude::{BoolVariable, ByteVariable, CircuitBuilder, PlonkParameters, Variable};
+use crate::prelude::{
+    BoolVariable, ByteVariable, CircuitBuilder, CircuitVariable, PlonkParameters, Variable,
+};
 
 // Checks that a[a_offset:a_offset+len] = b[b_offset:b_offset+len]
 pub fn subarray_equal(a: &[u8], a_offset: usize, b: &[u8], b_offset: usize, len: usize) -> u8 {
@@ -27,16 +29,16 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     }
 
     #[allow(unused_variables, dead_code)]
-    pub fn assert_subarray_equal(
+    pub fn assert_subarray_equal<V: CircuitVariable>(
         &mut self,
-        a: &[ByteVariable],
+        a: &[V],
         a_offset: Variable,
-        b: &[ByteVariable],
+        b: &[V],
         b_offset: Variable,
         len: Variable,
     ) {
         // TODO: implement
-        let generator: SubarrayEqualGenerator<L, D> = SubarrayEqualGenerator {
+        let generator: SubarrayEqualGenerator<L, D, V> = SubarrayEqualGenerator {
             a: a.to_vec(),
             a_offset,
             b: b.to_vec(),
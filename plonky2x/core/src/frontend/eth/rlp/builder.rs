@@ -307,6 +307,14 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     ) {
         let generator = RLPDecodeListGenerator::new(self, encoded, len, finish);
         self.add_simple_generator(generator.clone());
+
+        // The lengths are range-checked with byte lookups instead of a bit decomposition.
+        let element_len_bits = (usize::BITS - ELEMENT_LEN.leading_zeros()) as usize;
+        for element_len in generator.decoded_element_lens.as_vec() {
+            self.range_check(element_len, element_len_bits);
+        }
+        let list_len_bits = (usize::BITS - LIST_LEN.leading_zeros()) as usize;
+        self.range_check(generator.len_decoded_list, list_len_bits);
         // TODO: here add verification logic constraints using `builder` to check that the decoded list is correct
         (
             generator.decoded_list,
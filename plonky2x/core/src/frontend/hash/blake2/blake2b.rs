@@ -0,0 +1,189 @@
+//! The BLAKE2b compression function (RFC 7693) in a plonky2 circuit.
+//!
+//! Curta's BLAKE2b chip always starts from the initialization vector, so the computations that
+//! resume from an intermediate chaining value are compressed with plonky2 gates instead.
+
+use itertools::Itertools;
+
+use super::blake2s::BLAKE2S_SIGMA;
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::common::xor2_arr;
+use crate::frontend::vars::{BoolVariable, ByteVariable};
+use crate::prelude::{CircuitBuilder, U64Variable};
+
+/// The initialization vector of BLAKE2b.
+pub(crate) const BLAKE2B_IV: [u64; 8] = [
+    0x6A09E667F3BCC908,
+    0xBB67AE8584CAA73B,
+    0x3C6EF372FE94F82B,
+    0xA54FF53A5F1D36F1,
+    0x510E527FADE682D1,
+    0x9B05688C2B3E6C1F,
+    0x1F83D9ABFB41BD6B,
+    0x5BE0CD19137E2179,
+];
+
+/// The parameter block word xored into `h[0]` for an unkeyed hash with a 32 byte digest.
+pub(crate) const BLAKE2B_256_PARAMS: u64 = 0x01010020;
+
+pub(crate) const BLAKE2B_BLOCK_SIZE: usize = 128;
+
+const BLAKE2B_ROUNDS: usize = 12;
+
+/// A 64-bit word, represented by its big-endian bits.
+pub(crate) type Word64Variable = [BoolVariable; 64];
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    pub(crate) fn constant_word64(&mut self, x: u64) -> Word64Variable {
+        (0..64)
+            .map(|i| self.constant::<BoolVariable>((x >> (63 - i)) & 1 == 1))
+            .collect_vec()
+            .try_into()
+            .unwrap()
+    }
+
+    /// Reads `N` little-endian 64-bit words from the given bytes.
+    pub(crate) fn le_words64<const N: usize>(
+        &mut self,
+        bytes: &[ByteVariable],
+    ) -> [Word64Variable; N] {
+        assert_eq!(bytes.len(), 8 * N);
+        bytes
+            .chunks_exact(8)
+            .map(|word| {
+                word.iter()
+                    .rev()
+                    .flat_map(|byte| byte.as_be_bits())
+                    .collect_vec()
+                    .try_into()
+                    .unwrap()
+            })
+            .collect_vec()
+            .try_into()
+            .unwrap()
+    }
+
+    /// Serializes 64-bit words to bytes, each word in little-endian order.
+    pub(crate) fn words64_to_le_bytes(&mut self, words: &[Word64Variable]) -> Vec<ByteVariable> {
+        words
+            .iter()
+            .flat_map(|word| {
+                word.chunks_exact(8)
+                    .rev()
+                    .map(|bits| ByteVariable(bits.try_into().unwrap()))
+                    .collect_vec()
+            })
+            .collect()
+    }
+
+    /// The initial state of an unkeyed BLAKE2b-256 computation.
+    pub(crate) fn blake2b_256_initial_state(&mut self) -> [Word64Variable; 8] {
+        let mut h = BLAKE2B_IV.map(|x| self.constant_word64(x));
+        h[0] = self.constant_word64(BLAKE2B_IV[0] ^ BLAKE2B_256_PARAMS);
+        h
+    }
+
+    /// Compresses a block into the state `h`, where `counter` is the number of message bytes up
+    /// to the end of the block and `is_last` flags the final block.
+    pub(crate) fn blake2b_compress(
+        &mut self,
+        h: &[Word64Variable; 8],
+        block: &[Word64Variable; 16],
+        counter: U64Variable,
+        is_last: BoolVariable,
+    ) -> [Word64Variable; 8] {
+        let mut v = [h[0]; 16];
+        v[..8].copy_from_slice(h);
+        for (i, iv) in BLAKE2B_IV.iter().enumerate() {
+            v[i + 8] = self.constant_word64(*iv);
+        }
+        // The high word of the 128-bit counter is always zero, as messages are shorter than 2^64
+        // bytes.
+        let mut counter_bits = counter.limbs[1].to_be_bits(self).to_vec();
+        counter_bits.extend(counter.limbs[0].to_be_bits(self));
+        v[12] = xor2_arr(v[12], counter_bits.try_into().unwrap(), self);
+        v[14] = xor2_arr(v[14], [is_last; 64], self);
+
+        for round in 0..BLAKE2B_ROUNDS {
+            let s = &BLAKE2S_SIGMA[round % 10];
+            self.blake2b_g(&mut v, [0, 4, 8, 12], block[s[0]], block[s[1]]);
+            self.blake2b_g(&mut v, [1, 5, 9, 13], block[s[2]], block[s[3]]);
+            self.blake2b_g(&mut v, [2, 6, 10, 14], block[s[4]], block[s[5]]);
+            self.blake2b_g(&mut v, [3, 7, 11, 15], block[s[6]], block[s[7]]);
+            self.blake2b_g(&mut v, [0, 5, 10, 15], block[s[8]], block[s[9]]);
+            self.blake2b_g(&mut v, [1, 6, 11, 12], block[s[10]], block[s[11]]);
+            self.blake2b_g(&mut v, [2, 7, 8, 13], block[s[12]], block[s[13]]);
+            self.blake2b_g(&mut v, [3, 4, 9, 14], block[s[14]], block[s[15]]);
+        }
+
+        let mut out = *h;
+        for (i, word) in out.iter_mut().enumerate() {
+            let mixed = xor2_arr(v[i], v[i + 8], self);
+            *word = xor2_arr(*word, mixed, self);
+        }
+        out
+    }
+
+    fn blake2b_g(
+        &mut self,
+        v: &mut [Word64Variable; 16],
+        [a, b, c, d]: [usize; 4],
+        x: Word64Variable,
+        y: Word64Variable,
+    ) {
+        let a_plus_b = self.add_arr(v[a], v[b]);
+        v[a] = self.add_arr(a_plus_b, x);
+        let d_xor_a = xor2_arr(v[d], v[a], self);
+        v[d] = self._right_rotate(d_xor_a, 32);
+        v[c] = self.add_arr(v[c], v[d]);
+        let b_xor_c = xor2_arr(v[b], v[c], self);
+        v[b] = self._right_rotate(b_xor_c, 24);
+
+        let a_plus_b = self.add_arr(v[a], v[b]);
+        v[a] = self.add_arr(a_plus_b, y);
+        let d_xor_a = xor2_arr(v[d], v[a], self);
+        v[d] = self._right_rotate(d_xor_a, 16);
+        v[c] = self.add_arr(v[c], v[d]);
+        let b_xor_c = xor2_arr(v[b], v[c], self);
+        v[b] = self._right_rotate(b_xor_c, 63);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BLAKE2B_BLOCK_SIZE;
+    use crate::prelude::{
+        ByteVariable, Bytes32Variable, BytesVariable, DefaultBuilder, U64Variable,
+    };
+    use crate::utils::{self, bytes32};
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_blake2b_compress_single_block() {
+        utils::setup_logger();
+
+        let mut builder = DefaultBuilder::new();
+        let abc = builder.constant::<BytesVariable<3>>(*b"abc");
+        let zero = builder.constant::<ByteVariable>(0);
+        let mut block = abc.0.to_vec();
+        block.resize(BLAKE2B_BLOCK_SIZE, zero);
+        let block = builder.le_words64::<16>(&block);
+
+        let h = builder.blake2b_256_initial_state();
+        let counter = builder.constant::<U64Variable>(3);
+        let is_last = builder._true();
+        let h = builder.blake2b_compress(&h, &block, counter, is_last);
+        let digest_bytes = builder.words64_to_le_bytes(&h[..4]);
+        let digest = Bytes32Variable::from(digest_bytes.as_slice());
+
+        let expected_digest = builder.constant::<Bytes32Variable>(bytes32!(
+            "0xbddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"
+        ));
+        builder.assert_is_equal(digest, expected_digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
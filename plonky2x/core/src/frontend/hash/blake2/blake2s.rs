@@ -0,0 +1,383 @@
+//! An implementation of the BLAKE2s-256 hash function (RFC 7693) in a plonky2 circuit.
+//!
+//! `blake2s_variable` computes the hash with plonky2 gates where it is called, while
+//! `curta_blake2s_variable` collects the requests in a [`Blake2sAccelerator`], like
+//! `curta_blake2b_variable` does for BLAKE2b, and constrains them all at once when the circuit is
+//! built. Curta has no BLAKE2s chip, so the accelerator constrains the requests with plonky2
+//! gates.
+
+use core::marker::PhantomData;
+
+use ethers::types::H256;
+use itertools::Itertools;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::common::{xor2_arr, WordVariable};
+use crate::frontend::vars::{
+    BoolVariable, ByteVariable, Bytes32Variable, CircuitVariable, U32Variable, Variable,
+};
+use crate::prelude::CircuitBuilder;
+
+/// The initialization vector of BLAKE2s, which BLAKE3 shares.
+pub(crate) const BLAKE2S_IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// The message schedules of the BLAKE2s rounds, which BLAKE2b repeats from its eleventh round.
+pub(crate) const BLAKE2S_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The parameter block word xored into `h[0]` for an unkeyed hash with a 32 byte digest.
+const BLAKE2S_PARAMS: u32 = 0x01010020;
+
+const BLOCK_SIZE: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct Blake2sRequest {
+    message: Vec<ByteVariable>,
+    message_len: U32Variable,
+    digest: Bytes32Variable,
+}
+
+#[derive(Debug, Clone)]
+pub struct Blake2sAccelerator<L: PlonkParameters<D>, const D: usize> {
+    pub requests: Vec<Blake2sRequest>,
+    _marker: PhantomData<L>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Blake2sAccelerator<L, D> {
+    pub fn build(&self, builder: &mut CircuitBuilder<L, D>) {
+        builder.constrain_blake2s(self);
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Pads a BLAKE2s input with zeros to `MAX_NUM_CHUNKS` chunks and range-checks its length.
+    fn pad_message_blake2s<const MAX_NUM_CHUNKS: usize>(
+        &mut self,
+        message: &[ByteVariable],
+        message_len: Variable,
+    ) -> (Vec<ByteVariable>, U32Variable) {
+        assert!(
+            message.len() <= MAX_NUM_CHUNKS * BLOCK_SIZE,
+            "message too long"
+        );
+        let zero = self.constant::<ByteVariable>(0);
+        let mut padded_message = message.to_vec();
+        padded_message.resize(MAX_NUM_CHUNKS * BLOCK_SIZE, zero);
+        self.api.range_check(message_len.0, 32);
+        let message_len = U32Variable::from_variables_unsafe(&[message_len]);
+        (padded_message, message_len)
+    }
+
+    /// Executes a BLAKE2s hash on the first `message_len` bytes of `message`, which can have at
+    /// most `MAX_NUM_CHUNKS` 64 byte chunks.
+    pub fn blake2s_variable<const MAX_NUM_CHUNKS: usize>(
+        &mut self,
+        message: &[ByteVariable],
+        message_len: Variable,
+    ) -> Bytes32Variable {
+        let (padded_message, message_len) =
+            self.pad_message_blake2s::<MAX_NUM_CHUNKS>(message, message_len);
+        self.blake2s_padded(&padded_message, message_len)
+    }
+
+    /// Executes a BLAKE2s hash on the first `message_len` bytes of `message`, which can have at
+    /// most `MAX_NUM_CHUNKS` 64 byte chunks, through the BLAKE2s accelerator.
+    pub fn curta_blake2s_variable<const MAX_NUM_CHUNKS: usize>(
+        &mut self,
+        message: &[ByteVariable],
+        message_len: Variable,
+    ) -> Bytes32Variable {
+        let (padded_message, message_len) =
+            self.pad_message_blake2s::<MAX_NUM_CHUNKS>(message, message_len);
+        let digest = self.init_unsafe::<Bytes32Variable>();
+
+        if self.blake2s_accelerator.is_none() {
+            self.blake2s_accelerator = Some(Blake2sAccelerator::<L, D> {
+                requests: Vec::new(),
+                _marker: PhantomData,
+            });
+        }
+
+        let accelerator = self
+            .blake2s_accelerator
+            .as_mut()
+            .expect("blake2s accelerator should exist");
+        accelerator.requests.push(Blake2sRequest {
+            message: padded_message,
+            message_len,
+            digest,
+        });
+
+        digest
+    }
+
+    /// Constrains the digests of all the BLAKE2s requests.
+    fn constrain_blake2s(&mut self, accelerator: &Blake2sAccelerator<L, D>) {
+        for request in accelerator.requests.iter() {
+            let digest = self.blake2s_padded(&request.message, request.message_len);
+            self.assert_is_equal(request.digest, digest);
+        }
+    }
+
+    fn blake2s_padded(
+        &mut self,
+        message: &[ByteVariable],
+        message_len: U32Variable,
+    ) -> Bytes32Variable {
+        // Zero out the bytes past the end of the message, as required for the last block.
+        let zero = self.constant::<ByteVariable>(0);
+        let mut in_message = self._true();
+        let message = message
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| {
+                let index = self.constant::<U32Variable>(i as u32);
+                let at_end = self.is_equal(index, message_len);
+                let not_at_end = self.not(at_end);
+                in_message = self.and(in_message, not_at_end);
+                self.select(in_message, *byte, zero)
+            })
+            .collect_vec();
+
+        let mut h = BLAKE2S_IV.map(|x| self.constant_word(x));
+        h[0] = self.constant_word(BLAKE2S_IV[0] ^ BLAKE2S_PARAMS);
+
+        let mut digest = self.constant::<Bytes32Variable>(H256::zero());
+        let mut done = self._false();
+        for (i, block) in message.chunks_exact(BLOCK_SIZE).enumerate() {
+            // The last block is the first one which ends at or after the end of the message. Its
+            // byte counter is the message length instead of the number of bytes so far.
+            let block_end = self.constant::<U32Variable>(((i + 1) * BLOCK_SIZE) as u32);
+            let ends_message = self.lte(message_len, block_end);
+            let not_done = self.not(done);
+            let is_last = self.and(ends_message, not_done);
+            let counter = self.select(is_last, message_len, block_end);
+            let counter = counter.to_be_bits(self);
+
+            let block = self.le_words::<16>(block);
+            h = self.blake2s_compress(&h, &block, counter, is_last);
+
+            let h_bytes = self.words_to_le_bytes(&h);
+            let h_digest = Bytes32Variable::from(h_bytes.as_slice());
+            digest = self.select(is_last, h_digest, digest);
+            done = self.or(done, is_last);
+        }
+
+        // Make sure the message fit in the chunks.
+        let true_v = self._true();
+        self.assert_is_equal(done, true_v);
+
+        digest
+    }
+
+    fn blake2s_compress(
+        &mut self,
+        h: &[WordVariable; 8],
+        block: &[WordVariable; 16],
+        counter: WordVariable,
+        is_last: BoolVariable,
+    ) -> [WordVariable; 8] {
+        let mut v = [h[0]; 16];
+        v[..8].copy_from_slice(h);
+        for (i, iv) in BLAKE2S_IV.iter().enumerate() {
+            v[i + 8] = self.constant_word(*iv);
+        }
+        // The high word of the counter is always zero, as messages are shorter than 2^32 bytes.
+        v[12] = xor2_arr(v[12], counter, self);
+        v[14] = xor2_arr(v[14], [is_last; 32], self);
+
+        for sigma in BLAKE2S_SIGMA.iter() {
+            self.blake_round(&mut v, block, sigma);
+        }
+
+        let mut out = *h;
+        for (i, word) in out.iter_mut().enumerate() {
+            let mixed = xor2_arr(v[i], v[i + 8], self);
+            *word = xor2_arr(*word, mixed, self);
+        }
+        out
+    }
+
+    /// A round of the BLAKE2s and BLAKE3 compression functions, with the message words taken in
+    /// the order given by `schedule`.
+    pub(crate) fn blake_round(
+        &mut self,
+        v: &mut [WordVariable; 16],
+        m: &[WordVariable; 16],
+        schedule: &[usize; 16],
+    ) {
+        let s = schedule;
+        self.blake_g(v, [0, 4, 8, 12], m[s[0]], m[s[1]]);
+        self.blake_g(v, [1, 5, 9, 13], m[s[2]], m[s[3]]);
+        self.blake_g(v, [2, 6, 10, 14], m[s[4]], m[s[5]]);
+        self.blake_g(v, [3, 7, 11, 15], m[s[6]], m[s[7]]);
+        self.blake_g(v, [0, 5, 10, 15], m[s[8]], m[s[9]]);
+        self.blake_g(v, [1, 6, 11, 12], m[s[10]], m[s[11]]);
+        self.blake_g(v, [2, 7, 8, 13], m[s[12]], m[s[13]]);
+        self.blake_g(v, [3, 4, 9, 14], m[s[14]], m[s[15]]);
+    }
+
+    fn blake_g(
+        &mut self,
+        v: &mut [WordVariable; 16],
+        [a, b, c, d]: [usize; 4],
+        x: WordVariable,
+        y: WordVariable,
+    ) {
+        let a_plus_b = self.add_arr(v[a], v[b]);
+        v[a] = self.add_arr(a_plus_b, x);
+        let d_xor_a = xor2_arr(v[d], v[a], self);
+        v[d] = self._right_rotate(d_xor_a, 16);
+        v[c] = self.add_arr(v[c], v[d]);
+        let b_xor_c = xor2_arr(v[b], v[c], self);
+        v[b] = self._right_rotate(b_xor_c, 12);
+
+        let a_plus_b = self.add_arr(v[a], v[b]);
+        v[a] = self.add_arr(a_plus_b, y);
+        let d_xor_a = xor2_arr(v[d], v[a], self);
+        v[d] = self._right_rotate(d_xor_a, 8);
+        v[c] = self.add_arr(v[c], v[d]);
+        let b_xor_c = xor2_arr(v[b], v[c], self);
+        v[b] = self._right_rotate(b_xor_c, 7);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use plonky2::field::types::Field;
+
+    use crate::backend::circuit::DefaultParameters;
+    use crate::frontend::vars::Bytes32Variable;
+    use crate::prelude::{BytesVariable, CircuitBuilder, PlonkParameters, Variable};
+    use crate::utils::bytes32;
+
+    type L = DefaultParameters;
+    type F = <L as PlonkParameters<D>>::Field;
+    const D: usize = 2;
+
+    #[test]
+    fn test_blake2s_empty_string() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let zero = builder.zero();
+        let result = builder.blake2s_variable::<1>(&[], zero);
+
+        let expected_digest =
+            bytes32!("0x69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9");
+        let expected_digest = builder.constant::<Bytes32Variable>(expected_digest);
+        builder.assert_is_equal(result, expected_digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_blake2s_variable_length() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        const MSG_LEN: usize = 100;
+        let msg_bytes = (0..MSG_LEN).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let msg = builder.constant::<BytesVariable<MSG_LEN>>(msg_bytes.try_into().unwrap());
+
+        // The whole message, and its first block alone, with room for an extra chunk.
+        let full_length = builder.constant::<Variable>(F::from_canonical_usize(MSG_LEN));
+        let full_digest = builder.blake2s_variable::<3>(&msg.0, full_length);
+        let block_length = builder.constant::<Variable>(F::from_canonical_usize(64));
+        let block_digest = builder.blake2s_variable::<3>(&msg.0, block_length);
+
+        let expected_full_digest =
+            bytes32!("0x81dcc3a505eace3f879d8f702776770f9df50e521d1428a85daf04f9ad2150e0");
+        let expected_full_digest = builder.constant::<Bytes32Variable>(expected_full_digest);
+        builder.assert_is_equal(full_digest, expected_full_digest);
+
+        let expected_block_digest =
+            bytes32!("0x56f34e8b96557e90c1f24b52d0c89d51086acf1b00f634cf1dde9233b8eaaa3e");
+        let expected_block_digest = builder.constant::<Bytes32Variable>(expected_block_digest);
+        builder.assert_is_equal(block_digest, expected_block_digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_curta_blake2s_variable() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        const MSG_LEN: usize = 100;
+        let msg_bytes = (0..MSG_LEN).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let msg = builder.constant::<BytesVariable<MSG_LEN>>(msg_bytes.try_into().unwrap());
+        let length = builder.read::<Variable>();
+        let digest = builder.curta_blake2s_variable::<3>(&msg.0, length);
+        builder.write(digest);
+        let zero = builder.zero();
+        let empty_digest = builder.curta_blake2s_variable::<1>(&[], zero);
+        builder.write(empty_digest);
+
+        let circuit = builder.build();
+        circuit.test_default_serializers();
+
+        for (length, expected_digest) in [
+            (
+                MSG_LEN,
+                bytes32!("0x81dcc3a505eace3f879d8f702776770f9df50e521d1428a85daf04f9ad2150e0"),
+            ),
+            (
+                64,
+                bytes32!("0x56f34e8b96557e90c1f24b52d0c89d51086acf1b00f634cf1dde9233b8eaaa3e"),
+            ),
+        ] {
+            let mut input = circuit.input();
+            input.write::<Variable>(F::from_canonical_usize(length));
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<Bytes32Variable>(), expected_digest);
+            assert_eq!(
+                output.read::<Bytes32Variable>(),
+                bytes32!("0x69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9")
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_curta_blake2s_variable_length_out_of_range() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let length = builder.read::<Variable>();
+        let digest = builder.curta_blake2s_variable::<1>(&[], length);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        // A length which does not fit in 32 bits.
+        input.write::<Variable>(F::from_canonical_u64(1 << 32));
+        circuit.prove(&input);
+    }
+}
@@ -1,3 +1,5 @@
 //! An implementation of the Blake2 hash functions in a plonky2 circuit
 
+pub mod blake2b;
+pub mod blake2s;
 pub mod curta;
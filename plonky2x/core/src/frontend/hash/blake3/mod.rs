@@ -0,0 +1,534 @@
+//! An implementation of the BLAKE3 hash function in a plonky2 circuit.
+//!
+//! Inputs of any length known at circuit build time are supported, as well as the first `length`
+//! bytes of an input with `blake3_variable`. They are split into 1024 byte chunks, whose chaining
+//! values are merged along the BLAKE3 binary tree. Only the default 32 byte output is produced.
+//!
+//! Like BLAKE2s, the requests are collected in a [`Blake3Accelerator`] and constrained all at once
+//! when the circuit is built, with plonky2 gates as Curta has no chip for BLAKE3.
+
+use core::marker::PhantomData;
+
+use itertools::Itertools;
+use plonky2::util::ceil_div_usize;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::blake2::blake2s::BLAKE2S_IV;
+use crate::frontend::hash::common::{xor2_arr, WordVariable};
+use crate::frontend::vars::{
+    BoolVariable, ByteVariable, Bytes32Variable, CircuitVariable, U32Variable, Variable,
+};
+use crate::prelude::CircuitBuilder;
+
+const BLOCK_LEN: usize = 64;
+const CHUNK_LEN: usize = 1024;
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+#[derive(Debug, Clone)]
+pub struct Blake3Request {
+    input: Vec<ByteVariable>,
+    length: Option<U32Variable>,
+    key: Option<Bytes32Variable>,
+    digest: Bytes32Variable,
+}
+
+#[derive(Debug, Clone)]
+pub struct Blake3Accelerator<L: PlonkParameters<D>, const D: usize> {
+    pub requests: Vec<Blake3Request>,
+    _marker: PhantomData<L>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Blake3Accelerator<L, D> {
+    pub fn build(&self, builder: &mut CircuitBuilder<L, D>) {
+        builder.constrain_blake3(self);
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Executes a BLAKE3 hash on the given input.
+    pub fn blake3(&mut self, input: &[ByteVariable]) -> Bytes32Variable {
+        self.add_blake3_request(input, None, None)
+    }
+
+    /// Executes a BLAKE3 hash on the first `length` bytes of the given input, where `length` is
+    /// constrained to be at most `input.len()`.
+    pub fn blake3_variable(&mut self, input: &[ByteVariable], length: Variable) -> Bytes32Variable {
+        self.api.range_check(length.0, 32);
+        let length = U32Variable::from_variables_unsafe(&[length]);
+        let max_length = self.constant::<U32Variable>(input.len() as u32);
+        let in_range = self.lte(length, max_length);
+        let t = self._true();
+        self.assert_is_equal(in_range, t);
+        self.add_blake3_request(input, Some(length), None)
+    }
+
+    /// Executes a BLAKE3 keyed hash on the given input.
+    pub fn blake3_keyed(
+        &mut self,
+        key: Bytes32Variable,
+        input: &[ByteVariable],
+    ) -> Bytes32Variable {
+        self.add_blake3_request(input, None, Some(key))
+    }
+
+    fn add_blake3_request(
+        &mut self,
+        input: &[ByteVariable],
+        length: Option<U32Variable>,
+        key: Option<Bytes32Variable>,
+    ) -> Bytes32Variable {
+        let digest = self.init_unsafe::<Bytes32Variable>();
+
+        if self.blake3_accelerator.is_none() {
+            self.blake3_accelerator = Some(Blake3Accelerator::<L, D> {
+                requests: Vec::new(),
+                _marker: PhantomData,
+            });
+        }
+        let accelerator = self
+            .blake3_accelerator
+            .as_mut()
+            .expect("blake3 accelerator should exist");
+        accelerator.requests.push(Blake3Request {
+            input: input.to_vec(),
+            length,
+            key,
+            digest,
+        });
+
+        digest
+    }
+
+    /// Constrains the digests of all the BLAKE3 requests.
+    fn constrain_blake3(&mut self, accelerator: &Blake3Accelerator<L, D>) {
+        for request in accelerator.requests.iter() {
+            let (key_words, flags) = match request.key {
+                Some(key) => (self.le_words::<8>(&key.as_bytes()), KEYED_HASH),
+                None => (BLAKE2S_IV.map(|x| self.constant_word(x)), 0),
+            };
+
+            let output = match request.length {
+                None => self.blake3_fixed_length(&key_words, &request.input, flags),
+                Some(length) => {
+                    self.blake3_variable_length(&key_words, &request.input, length, flags)
+                }
+            };
+
+            let digest = self.words_to_le_bytes(&output);
+            let digest = Bytes32Variable::from(digest.as_slice());
+            self.assert_is_equal(request.digest, digest);
+        }
+    }
+
+    /// Computes the root output of `input`.
+    fn blake3_fixed_length(
+        &mut self,
+        key_words: &[WordVariable; 8],
+        input: &[ByteVariable],
+        flags: u32,
+    ) -> [WordVariable; 8] {
+        let num_chunks = ceil_div_usize(input.len(), CHUNK_LEN).max(1);
+        if num_chunks == 1 {
+            self.blake3_chunk(key_words, input, 0, flags | ROOT)
+        } else {
+            let chaining_values = input
+                .chunks(CHUNK_LEN)
+                .enumerate()
+                .map(|(i, chunk)| self.blake3_chunk(key_words, chunk, i as u64, flags))
+                .collect_vec();
+            self.blake3_merge(key_words, &chaining_values, flags, true)
+        }
+    }
+
+    /// Computes the root output of the first `length` bytes of `input`.
+    ///
+    /// Every chunk but the last one is full, so the chaining values of the full chunks are
+    /// computed once. For each possible number of chunks, the last chunk is hashed with its
+    /// variable length and the tree is merged, and the root of the actual number of chunks is
+    /// selected.
+    fn blake3_variable_length(
+        &mut self,
+        key_words: &[WordVariable; 8],
+        input: &[ByteVariable],
+        length: U32Variable,
+        flags: u32,
+    ) -> [WordVariable; 8] {
+        let max_num_chunks = ceil_div_usize(input.len(), CHUNK_LEN).max(1);
+
+        // Zero out the bytes past the end of the message, as required for the last block.
+        let zero = self.constant::<ByteVariable>(0);
+        let mut in_message = self._true();
+        let mut message = input
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| {
+                let index = self.constant::<U32Variable>(i as u32);
+                let at_end = self.is_equal(index, length);
+                let not_at_end = self.not(at_end);
+                in_message = self.and(in_message, not_at_end);
+                self.select(in_message, *byte, zero)
+            })
+            .collect_vec();
+        message.resize(max_num_chunks * CHUNK_LEN, zero);
+
+        let full_chaining_values = message
+            .chunks_exact(CHUNK_LEN)
+            .take(max_num_chunks - 1)
+            .enumerate()
+            .map(|(i, chunk)| self.blake3_chunk(key_words, chunk, i as u64, flags))
+            .collect_vec();
+
+        let mut output = *key_words;
+        for (i, chunk) in message.chunks_exact(CHUNK_LEN).enumerate() {
+            // The message has `i + 1` chunks if it ends in `(i * CHUNK_LEN, (i + 1) * CHUNK_LEN]`,
+            // or in the first chunk if it is empty.
+            let chunk_start = self.constant::<U32Variable>((i * CHUNK_LEN) as u32);
+            let chunk_end = self.constant::<U32Variable>(((i + 1) * CHUNK_LEN) as u32);
+            let ends_in_chunk = self.lte(length, chunk_end);
+            let is_last_chunk = if i == 0 {
+                ends_in_chunk
+            } else {
+                let starts_before = self.gt(length, chunk_start);
+                self.and(starts_before, ends_in_chunk)
+            };
+
+            let chunk_len = self.wrapping_sub(length, chunk_start);
+            let root = if i == 0 {
+                self.blake3_chunk_variable(key_words, chunk, i as u64, chunk_len, flags | ROOT)
+            } else {
+                let last_chaining_value =
+                    self.blake3_chunk_variable(key_words, chunk, i as u64, chunk_len, flags);
+                let mut chaining_values = full_chaining_values[..i].to_vec();
+                chaining_values.push(last_chaining_value);
+                self.blake3_merge(key_words, &chaining_values, flags, true)
+            };
+            output = self.select_words(is_last_chunk, root, output);
+        }
+        output
+    }
+
+    /// Computes the chaining value of the first `chunk_len` bytes of a chunk, whose bytes past
+    /// `chunk_len` are zero. The `ROOT` flag in `flags` is only applied to the last block.
+    fn blake3_chunk_variable(
+        &mut self,
+        key_words: &[WordVariable; 8],
+        chunk: &[ByteVariable],
+        counter: u64,
+        chunk_len: U32Variable,
+        flags: u32,
+    ) -> [WordVariable; 8] {
+        let mut chaining_value = *key_words;
+        let mut output = *key_words;
+        let mut done = self._false();
+        for (i, block) in chunk.chunks_exact(BLOCK_LEN).enumerate() {
+            // The last block is the first one which ends at or after the end of the chunk.
+            let block_start = self.constant::<U32Variable>((i * BLOCK_LEN) as u32);
+            let block_end = self.constant::<U32Variable>(((i + 1) * BLOCK_LEN) as u32);
+            let ends_chunk = self.lte(chunk_len, block_end);
+            let not_done = self.not(done);
+            let is_last = self.and(ends_chunk, not_done);
+
+            let last_block_len = self.wrapping_sub(chunk_len, block_start);
+            let full_block_len = self.constant::<U32Variable>(BLOCK_LEN as u32);
+            let block_len = self.select(is_last, last_block_len, full_block_len);
+            let block_len = block_len.to_be_bits(self);
+
+            let mut block_flags = flags & !ROOT;
+            if i == 0 {
+                block_flags |= CHUNK_START;
+            }
+            let last_block_flags = self.constant_word(block_flags | CHUNK_END | (flags & ROOT));
+            let block_flags = self.constant_word(block_flags);
+            let block_flags = self.select_words(is_last, last_block_flags, block_flags);
+
+            let block_words = self.le_words::<16>(block);
+            chaining_value = self.blake3_compress_words(
+                &chaining_value,
+                &block_words,
+                counter,
+                block_len,
+                block_flags,
+            );
+            output = self.select_words(is_last, chaining_value, output);
+            done = self.or(done, is_last);
+        }
+        output
+    }
+
+    /// Selects `a` if `selector` is true and `b` otherwise, word by word.
+    fn select_words<const N: usize>(
+        &mut self,
+        selector: BoolVariable,
+        a: [WordVariable; N],
+        b: [WordVariable; N],
+    ) -> [WordVariable; N] {
+        core::array::from_fn(|i| core::array::from_fn(|j| self.select(selector, a[i][j], b[i][j])))
+    }
+
+    /// Computes the chaining value of a chunk. The `ROOT` flag in `flags` is only applied to the
+    /// last block.
+    fn blake3_chunk(
+        &mut self,
+        key_words: &[WordVariable; 8],
+        chunk: &[ByteVariable],
+        counter: u64,
+        flags: u32,
+    ) -> [WordVariable; 8] {
+        let zero = self.constant::<ByteVariable>(0);
+        let num_blocks = ceil_div_usize(chunk.len(), BLOCK_LEN).max(1);
+
+        let mut chaining_value = *key_words;
+        for i in 0..num_blocks {
+            let block = &chunk[i * BLOCK_LEN..chunk.len().min((i + 1) * BLOCK_LEN)];
+            let mut block_flags = flags & !ROOT;
+            if i == 0 {
+                block_flags |= CHUNK_START;
+            }
+            if i == num_blocks - 1 {
+                block_flags |= CHUNK_END | (flags & ROOT);
+            }
+
+            let mut padded_block = block.to_vec();
+            padded_block.resize(BLOCK_LEN, zero);
+            let block_words = self.le_words::<16>(&padded_block);
+            chaining_value = self.blake3_compress(
+                &chaining_value,
+                &block_words,
+                counter,
+                block.len() as u32,
+                block_flags,
+            );
+        }
+        chaining_value
+    }
+
+    /// Merges chaining values along the BLAKE3 tree, in which the left subtree of every parent
+    /// holds the largest power of two of chunks that leaves at least one chunk on the right.
+    fn blake3_merge(
+        &mut self,
+        key_words: &[WordVariable; 8],
+        chaining_values: &[[WordVariable; 8]],
+        flags: u32,
+        is_root: bool,
+    ) -> [WordVariable; 8] {
+        let n = chaining_values.len();
+        if n == 1 {
+            return chaining_values[0];
+        }
+        let left_len = 1 << (usize::BITS - 1 - (n - 1).leading_zeros());
+        let left = self.blake3_merge(key_words, &chaining_values[..left_len], flags, false);
+        let right = self.blake3_merge(key_words, &chaining_values[left_len..], flags, false);
+
+        let mut block_words = [left[0]; 16];
+        block_words[..8].copy_from_slice(&left);
+        block_words[8..].copy_from_slice(&right);
+        let parent_flags = flags | PARENT | if is_root { ROOT } else { 0 };
+        self.blake3_compress(key_words, &block_words, 0, BLOCK_LEN as u32, parent_flags)
+    }
+
+    /// The BLAKE3 compression function, truncated to the first 8 words of its output.
+    fn blake3_compress(
+        &mut self,
+        chaining_value: &[WordVariable; 8],
+        block_words: &[WordVariable; 16],
+        counter: u64,
+        block_len: u32,
+        flags: u32,
+    ) -> [WordVariable; 8] {
+        let block_len = self.constant_word(block_len);
+        let flags = self.constant_word(flags);
+        self.blake3_compress_words(chaining_value, block_words, counter, block_len, flags)
+    }
+
+    /// The BLAKE3 compression function with the block length and flags given as words.
+    fn blake3_compress_words(
+        &mut self,
+        chaining_value: &[WordVariable; 8],
+        block_words: &[WordVariable; 16],
+        counter: u64,
+        block_len: WordVariable,
+        flags: WordVariable,
+    ) -> [WordVariable; 8] {
+        let mut v = [chaining_value[0]; 16];
+        v[..8].copy_from_slice(chaining_value);
+        for (i, iv) in BLAKE2S_IV[..4].iter().enumerate() {
+            v[i + 8] = self.constant_word(*iv);
+        }
+        v[12] = self.constant_word(counter as u32);
+        v[13] = self.constant_word((counter >> 32) as u32);
+        v[14] = block_len;
+        v[15] = flags;
+
+        let mut schedule: [usize; 16] = core::array::from_fn(|i| i);
+        for _ in 0..7 {
+            self.blake_round(&mut v, block_words, &schedule);
+            schedule = core::array::from_fn(|i| schedule[MSG_PERMUTATION[i]]);
+        }
+
+        core::array::from_fn(|i| xor2_arr(v[i], v[i + 8], self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use plonky2::field::types::Field;
+
+    use crate::backend::circuit::DefaultParameters;
+    use crate::frontend::vars::Bytes32Variable;
+    use crate::prelude::{BytesVariable, CircuitBuilder, PlonkParameters, Variable};
+    use crate::utils::bytes32;
+
+    type L = DefaultParameters;
+    type F = <L as PlonkParameters<D>>::Field;
+    const D: usize = 2;
+
+    #[test]
+    fn test_blake3_short() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let empty_digest = builder.blake3(&[]);
+        let abc = builder.constant::<BytesVariable<3>>(*b"abc");
+        let abc_digest = builder.blake3(&abc.0);
+
+        let expected_empty_digest =
+            bytes32!("0xaf1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262");
+        let expected_empty_digest = builder.constant::<Bytes32Variable>(expected_empty_digest);
+        builder.assert_is_equal(empty_digest, expected_empty_digest);
+
+        let expected_abc_digest =
+            bytes32!("0x6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85");
+        let expected_abc_digest = builder.constant::<Bytes32Variable>(expected_abc_digest);
+        builder.assert_is_equal(abc_digest, expected_abc_digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_blake3_keyed() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let key = builder.read::<Bytes32Variable>();
+        let digest = builder.blake3_keyed(key, &[]);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        // The key "whats the Elvish word for friend" of the official test vectors.
+        input.write::<Bytes32Variable>(bytes32!(
+            "0x77686174732074686520456c7669736820776f726420666f7220667269656e64"
+        ));
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let expected_digest =
+            bytes32!("0x92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26");
+        assert_eq!(output.read::<Bytes32Variable>(), expected_digest);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_blake3_chunk_tree() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        // Three chunks, the last of which has a single byte.
+        const MSG_LEN: usize = 2049;
+        let msg_bytes = (0..MSG_LEN).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let msg = builder.constant::<BytesVariable<MSG_LEN>>(msg_bytes.try_into().unwrap());
+        let digest = builder.blake3(&msg.0);
+
+        let expected_digest =
+            bytes32!("0x5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030");
+        let expected_digest = builder.constant::<Bytes32Variable>(expected_digest);
+        builder.assert_is_equal(digest, expected_digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_blake3_variable() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        const MSG_LEN: usize = 2049;
+        let msg_bytes = (0..MSG_LEN).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let msg = builder.constant::<BytesVariable<MSG_LEN>>(msg_bytes.try_into().unwrap());
+        let length = builder.read::<Variable>();
+        let digest = builder.blake3_variable(&msg.0, length);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        circuit.test_default_serializers();
+
+        // An empty message, a message ending in the last block of the first chunk, exactly one
+        // chunk, one byte into the second chunk and the full input.
+        let test_cases = [
+            (
+                0,
+                bytes32!("0xaf1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"),
+            ),
+            (
+                1000,
+                bytes32!("0xb43670a52d1af24abdac5d2c3ed19ff4e62b60a618e823ad555888b1b0b91cff"),
+            ),
+            (
+                1024,
+                bytes32!("0x42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7"),
+            ),
+            (
+                1025,
+                bytes32!("0xd00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444"),
+            ),
+            (
+                MSG_LEN,
+                bytes32!("0x5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030"),
+            ),
+        ];
+        for (length, expected_digest) in test_cases {
+            let mut input = circuit.input();
+            input.write::<Variable>(F::from_canonical_usize(length));
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<Bytes32Variable>(), expected_digest);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_blake3_variable_length_out_of_range() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let msg = builder.constant::<BytesVariable<3>>(*b"abc");
+        let length = builder.read::<Variable>();
+        let digest = builder.blake3_variable(&msg.0, length);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Variable>(F::from_canonical_usize(4));
+        circuit.prove(&input);
+    }
+}
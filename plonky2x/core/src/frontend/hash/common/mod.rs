@@ -1,5 +1,13 @@
+use itertools::Itertools;
+
 use crate::frontend::builder::CircuitBuilder;
-use crate::prelude::{BoolVariable, CircuitVariable, PlonkParameters, U32Variable, Variable};
+use crate::prelude::{
+    BoolVariable, ByteVariable, CircuitVariable, PlonkParameters, U32Variable, U64Variable,
+    Variable,
+};
+
+/// A 32-bit word, represented by its big-endian bits.
+pub(crate) type WordVariable = [BoolVariable; 32];
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     pub fn reshape(&self, arr: Vec<BoolVariable>) -> Vec<[BoolVariable; 32]> {
@@ -76,6 +84,25 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             let c_u32 = c_u64.limbs[0];
 
             c_u32.to_be_bits(self).to_vec().try_into().unwrap()
+        } else if S == 64 {
+            let a_u64 = U64Variable {
+                limbs: [
+                    U32Variable::from_be_bits(&a[32..], self),
+                    U32Variable::from_be_bits(&a[..32], self),
+                ],
+            };
+            let b_u64 = U64Variable {
+                limbs: [
+                    U32Variable::from_be_bits(&b[32..], self),
+                    U32Variable::from_be_bits(&b[..32], self),
+                ],
+            };
+            // The carry out of the high limb is dropped, so the sum wraps around.
+            let c_u64 = self.add(a_u64, b_u64);
+
+            let mut bits = c_u64.limbs[1].to_be_bits(self).to_vec();
+            bits.extend(c_u64.limbs[0].to_be_bits(self));
+            bits.try_into().unwrap()
         } else {
             todo!();
         }
@@ -93,6 +120,45 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             .try_into()
             .unwrap()
     }
+
+    pub(crate) fn constant_word(&mut self, x: u32) -> WordVariable {
+        (0..32)
+            .map(|i| self.constant::<BoolVariable>((x >> (31 - i)) & 1 == 1))
+            .collect_vec()
+            .try_into()
+            .unwrap()
+    }
+
+    /// Reads `N` little-endian words from the given bytes.
+    pub(crate) fn le_words<const N: usize>(&mut self, bytes: &[ByteVariable]) -> [WordVariable; N] {
+        assert_eq!(bytes.len(), 4 * N);
+        bytes
+            .chunks_exact(4)
+            .map(|word| {
+                word.iter()
+                    .rev()
+                    .flat_map(|byte| byte.as_be_bits())
+                    .collect_vec()
+                    .try_into()
+                    .unwrap()
+            })
+            .collect_vec()
+            .try_into()
+            .unwrap()
+    }
+
+    /// Serializes words to bytes, each word in little-endian order.
+    pub(crate) fn words_to_le_bytes(&mut self, words: &[WordVariable]) -> Vec<ByteVariable> {
+        words
+            .iter()
+            .flat_map(|word| {
+                word.chunks_exact(8)
+                    .rev()
+                    .map(|bits| ByteVariable(bits.try_into().unwrap()))
+                    .collect_vec()
+            })
+            .collect()
+    }
 }
 
 pub fn xor3_arr<const S: usize, L: PlonkParameters<D>, const D: usize>(
@@ -145,3 +211,16 @@ pub fn and_arr<const S: usize, L: PlonkParameters<D>, const D: usize>(
         .try_into()
         .unwrap()
 }
+
+pub fn or_arr<const S: usize, L: PlonkParameters<D>, const D: usize>(
+    a: [BoolVariable; S],
+    b: [BoolVariable; S],
+    builder: &mut CircuitBuilder<L, D>,
+) -> [BoolVariable; S] {
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| builder.or(*a, *b))
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
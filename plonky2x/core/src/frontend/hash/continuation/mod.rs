@@ -0,0 +1,550 @@
+//! Hashing messages across a chain of proofs.
+//!
+//! A message too large for one circuit is split into segments of whole blocks. Each segment is
+//! absorbed in its own proof, starting from the chaining value and processed length of the end of
+//! the previous segment, and two adjacent segments are joined by checking that the first one ends
+//! where the second one starts. Joining is associative, so the segment proofs can be aggregated
+//! with `mapreduce`, with [`CircuitBuilder::hash_chain_join`] as the reduce function, or verified
+//! one after the other with `verify_proof`. A final circuit checks that the joined segment starts
+//! from the initial state, pads the remaining bytes and outputs the digest.
+//!
+//! The Curta accelerators always start hashing from the initialization vector, so the blocks are
+//! compressed with plonky2 gates.
+
+use core::fmt::Debug;
+
+use ::curta::machine::hash::sha::algorithm::SHAPure;
+use ::curta::machine::hash::sha::sha256::SHA256;
+use ethers::types::H256;
+use itertools::Itertools;
+use plonky2::util::ceil_div_usize;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::blake2::blake2b::BLAKE2B_BLOCK_SIZE;
+use crate::frontend::hash::sha::sha256::{sha256_state_from_bytes, sha256_state_to_bytes};
+use crate::frontend::vars::EvmVariable;
+use crate::prelude::{
+    ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder, CircuitVariable, U32Variable,
+    U64Variable,
+};
+
+/// A hash function whose computation can be carried from one proof to the next.
+pub trait ContinuableHasher<L: PlonkParameters<D>, const D: usize>:
+    Debug + Clone + Send + Sync + 'static
+{
+    /// The block size in bytes.
+    const BLOCK_SIZE: usize;
+
+    /// The chaining value carried between proofs.
+    type ChainingValue: CircuitVariable;
+
+    /// The chaining value before any block is absorbed.
+    fn initial_chaining_value(builder: &mut CircuitBuilder<L, D>) -> Self::ChainingValue;
+
+    /// Absorbs whole blocks into `chaining_value`, where `processed_len` is the number of bytes
+    /// absorbed before them.
+    fn absorb_blocks(
+        builder: &mut CircuitBuilder<L, D>,
+        chaining_value: Self::ChainingValue,
+        processed_len: U64Variable,
+        blocks: &[ByteVariable],
+    ) -> Self::ChainingValue;
+
+    /// Pads the first `tail_len` bytes of `tail`, the end of a message whose first
+    /// `processed_len` bytes are absorbed in `chaining_value`, and returns the digest.
+    fn finalize(
+        builder: &mut CircuitBuilder<L, D>,
+        chaining_value: Self::ChainingValue,
+        processed_len: U64Variable,
+        tail: &[ByteVariable],
+        tail_len: U32Variable,
+    ) -> Bytes32Variable;
+}
+
+/// SHA-256, whose chaining value is encoded like a digest.
+#[derive(Debug, Clone, Copy)]
+pub struct Sha256Chain;
+
+/// BLAKE2b-256, whose chaining value is the 64 byte state.
+///
+/// The last block of a BLAKE2b message is compressed differently, so the tail of a non-empty
+/// message can't be empty: a message made of whole blocks must keep its last block for
+/// `finalize`.
+#[derive(Debug, Clone, Copy)]
+pub struct Blake2bChain;
+
+/// The state of a hash computation between two proofs.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(HashChainState)]
+pub struct HashChainStateVariable<CV: CircuitVariable> {
+    pub chaining_value: CV,
+    pub processed_len: U64Variable,
+}
+
+/// A segment of a hash computation, from the state before its blocks to the state after them.
+#[derive(Debug, Clone, CircuitVariable)]
+#[value_name(HashChainSegment)]
+pub struct HashChainSegmentVariable<CV: CircuitVariable> {
+    pub start: HashChainStateVariable<CV>,
+    pub end: HashChainStateVariable<CV>,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// The state of a hash computation before any block is absorbed.
+    pub fn hash_chain_initial_state<H: ContinuableHasher<L, D>>(
+        &mut self,
+    ) -> HashChainStateVariable<H::ChainingValue> {
+        HashChainStateVariable {
+            chaining_value: H::initial_chaining_value(self),
+            processed_len: self.constant::<U64Variable>(0),
+        }
+    }
+
+    /// Absorbs whole blocks into the given state.
+    pub fn hash_chain_absorb<H: ContinuableHasher<L, D>>(
+        &mut self,
+        state: HashChainStateVariable<H::ChainingValue>,
+        blocks: &[ByteVariable],
+    ) -> HashChainStateVariable<H::ChainingValue> {
+        assert_eq!(
+            blocks.len() % H::BLOCK_SIZE,
+            0,
+            "blocks length should be a multiple of the block size"
+        );
+        let chaining_value =
+            H::absorb_blocks(self, state.chaining_value, state.processed_len, blocks);
+        let blocks_len = self.constant::<U64Variable>(blocks.len() as u64);
+        let processed_len = self.add(state.processed_len, blocks_len);
+        HashChainStateVariable {
+            chaining_value,
+            processed_len,
+        }
+    }
+
+    /// Absorbs whole blocks into the state `start`, returning the segment they span.
+    pub fn hash_chain_segment<H: ContinuableHasher<L, D>>(
+        &mut self,
+        start: HashChainStateVariable<H::ChainingValue>,
+        blocks: &[ByteVariable],
+    ) -> HashChainSegmentVariable<H::ChainingValue> {
+        let end = self.hash_chain_absorb::<H>(start.clone(), blocks);
+        HashChainSegmentVariable { start, end }
+    }
+
+    /// Joins two adjacent segments, constraining the first one to end where the second one
+    /// starts.
+    pub fn hash_chain_join<H: ContinuableHasher<L, D>>(
+        &mut self,
+        left: HashChainSegmentVariable<H::ChainingValue>,
+        right: HashChainSegmentVariable<H::ChainingValue>,
+    ) -> HashChainSegmentVariable<H::ChainingValue> {
+        self.assert_is_equal(left.end, right.start);
+        HashChainSegmentVariable {
+            start: left.start,
+            end: right.end,
+        }
+    }
+
+    /// Pads the first `tail_len` bytes of `tail` as the end of the message absorbed in `state`
+    /// and returns the digest of the whole message.
+    pub fn hash_chain_finalize<H: ContinuableHasher<L, D>>(
+        &mut self,
+        state: HashChainStateVariable<H::ChainingValue>,
+        tail: &[ByteVariable],
+        tail_len: U32Variable,
+    ) -> Bytes32Variable {
+        let max_tail_len = self.constant::<U32Variable>(tail.len() as u32);
+        let tail_len_is_valid = self.lte(tail_len, max_tail_len);
+        let true_v = self._true();
+        self.assert_is_equal(tail_len_is_valid, true_v);
+
+        H::finalize(
+            self,
+            state.chaining_value,
+            state.processed_len,
+            tail,
+            tail_len,
+        )
+    }
+
+    /// Computes the 8 big-endian length bytes of SHA-256 padding for a message of
+    /// `processed_len + tail_len` bytes.
+    fn sha256_chain_length_bytes(
+        &mut self,
+        processed_len: U64Variable,
+        tail_len: U32Variable,
+    ) -> Vec<ByteVariable> {
+        let tail_len = tail_len.to_u64(self);
+        let total_len = self.add(processed_len, tail_len);
+        let bits_per_byte = self.constant::<U64Variable>(8);
+        let total_bit_len = self.mul(total_len, bits_per_byte);
+        total_bit_len.encode(self)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> ContinuableHasher<L, D> for Sha256Chain {
+    const BLOCK_SIZE: usize = 64;
+
+    type ChainingValue = Bytes32Variable;
+
+    fn initial_chaining_value(builder: &mut CircuitBuilder<L, D>) -> Self::ChainingValue {
+        let initial_hash = SHA256::INITIAL_HASH
+            .iter()
+            .flat_map(|word| word.to_be_bytes())
+            .collect_vec();
+        builder.constant::<Bytes32Variable>(H256::from_slice(&initial_hash))
+    }
+
+    fn absorb_blocks(
+        builder: &mut CircuitBuilder<L, D>,
+        chaining_value: Self::ChainingValue,
+        _processed_len: U64Variable,
+        blocks: &[ByteVariable],
+    ) -> Self::ChainingValue {
+        let mut state = sha256_state_from_bytes(chaining_value);
+        for block in blocks.chunks_exact(64) {
+            state = builder.sha256_compress(state, block);
+        }
+        sha256_state_to_bytes(&state)
+    }
+
+    fn finalize(
+        builder: &mut CircuitBuilder<L, D>,
+        chaining_value: Self::ChainingValue,
+        processed_len: U64Variable,
+        tail: &[ByteVariable],
+        tail_len: U32Variable,
+    ) -> Bytes32Variable {
+        // Leave room for the padding byte and the 8 length bytes after a tail of full length.
+        let num_chunks = ceil_div_usize(tail.len() + 9, 64);
+        let zero_byte = builder.constant::<ByteVariable>(0);
+        let mut tail = tail.to_vec();
+        tail.resize(num_chunks * 64, zero_byte);
+
+        // The padding ends in the chunk holding byte `tail_len + 8` of the tail.
+        let length_bytes_len = builder.constant::<U32Variable>(8);
+        let padded_tail_len = builder.add(tail_len, length_bytes_len);
+        let padded_tail_len_bits = padded_tail_len.to_be_bits(builder);
+        let last_chunk = U32Variable::from_be_bits(&padded_tail_len_bits[..26], builder);
+
+        let length_bytes = builder.sha256_chain_length_bytes(processed_len, tail_len);
+        let padded = builder.pad_message_sha256_with_length_bytes(
+            &tail,
+            tail_len,
+            last_chunk,
+            &length_bytes,
+        );
+
+        let mut state = sha256_state_from_bytes(chaining_value);
+        let mut digest = chaining_value;
+        for (i, chunk) in padded.chunks_exact(64).enumerate() {
+            state = builder.sha256_compress(state, chunk);
+            let curr_chunk = builder.constant::<U32Variable>(i as u32);
+            let is_last_chunk = builder.is_equal(curr_chunk, last_chunk);
+            let chunk_digest = sha256_state_to_bytes(&state);
+            digest = builder.select(is_last_chunk, chunk_digest, digest);
+        }
+        digest
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> ContinuableHasher<L, D> for Blake2bChain {
+    const BLOCK_SIZE: usize = BLAKE2B_BLOCK_SIZE;
+
+    type ChainingValue = BytesVariable<64>;
+
+    fn initial_chaining_value(builder: &mut CircuitBuilder<L, D>) -> Self::ChainingValue {
+        let h = builder.blake2b_256_initial_state();
+        BytesVariable(builder.words64_to_le_bytes(&h).try_into().unwrap())
+    }
+
+    fn absorb_blocks(
+        builder: &mut CircuitBuilder<L, D>,
+        chaining_value: Self::ChainingValue,
+        processed_len: U64Variable,
+        blocks: &[ByteVariable],
+    ) -> Self::ChainingValue {
+        let mut h = builder.le_words64::<8>(&chaining_value.0);
+        let is_last = builder._false();
+        for (i, block) in blocks.chunks_exact(BLAKE2B_BLOCK_SIZE).enumerate() {
+            let block_end = builder.constant::<U64Variable>(((i + 1) * BLAKE2B_BLOCK_SIZE) as u64);
+            let counter = builder.add(processed_len, block_end);
+            let block = builder.le_words64::<16>(block);
+            h = builder.blake2b_compress(&h, &block, counter, is_last);
+        }
+        BytesVariable(builder.words64_to_le_bytes(&h).try_into().unwrap())
+    }
+
+    fn finalize(
+        builder: &mut CircuitBuilder<L, D>,
+        chaining_value: Self::ChainingValue,
+        processed_len: U64Variable,
+        tail: &[ByteVariable],
+        tail_len: U32Variable,
+    ) -> Bytes32Variable {
+        // Only an empty message has an empty last block.
+        let zero_u32 = builder.constant::<U32Variable>(0);
+        let zero_u64 = builder.constant::<U64Variable>(0);
+        let tail_is_empty = builder.is_equal(tail_len, zero_u32);
+        let tail_is_not_empty = builder.not(tail_is_empty);
+        let nothing_processed = builder.is_equal(processed_len, zero_u64);
+        let tail_is_valid = builder.or(tail_is_not_empty, nothing_processed);
+        let true_v = builder._true();
+        builder.assert_is_equal(tail_is_valid, true_v);
+
+        // Zero out the bytes past the end of the tail, as required for the last block.
+        let zero = builder.constant::<ByteVariable>(0);
+        let mut in_message = builder._true();
+        let mut tail = tail
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| {
+                let index = builder.constant::<U32Variable>(i as u32);
+                let at_end = builder.is_equal(index, tail_len);
+                let not_at_end = builder.not(at_end);
+                in_message = builder.and(in_message, not_at_end);
+                builder.select(in_message, *byte, zero)
+            })
+            .collect_vec();
+        let num_blocks = ceil_div_usize(tail.len(), BLAKE2B_BLOCK_SIZE).max(1);
+        tail.resize(num_blocks * BLAKE2B_BLOCK_SIZE, zero);
+
+        let mut h = builder.le_words64::<8>(&chaining_value.0);
+        let mut digest = builder.constant::<Bytes32Variable>(H256::zero());
+        let mut done = builder._false();
+        for (i, block) in tail.chunks_exact(BLAKE2B_BLOCK_SIZE).enumerate() {
+            // The last block is the first one which ends at or after the end of the tail. Its
+            // byte counter is the message length instead of the number of bytes so far.
+            let block_end = builder.constant::<U32Variable>(((i + 1) * BLAKE2B_BLOCK_SIZE) as u32);
+            let ends_message = builder.lte(tail_len, block_end);
+            let not_done = builder.not(done);
+            let is_last = builder.and(ends_message, not_done);
+            let tail_counter = builder.select(is_last, tail_len, block_end);
+            let tail_counter = tail_counter.to_u64(builder);
+            let counter = builder.add(processed_len, tail_counter);
+
+            let block = builder.le_words64::<16>(block);
+            h = builder.blake2b_compress(&h, &block, counter, is_last);
+
+            let h_bytes = builder.words64_to_le_bytes(&h[..4]);
+            let h_digest = Bytes32Variable::from(h_bytes.as_slice());
+            digest = builder.select(is_last, h_digest, digest);
+            done = builder.or(done, is_last);
+        }
+        digest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::H256;
+
+    use super::{
+        Blake2bChain, HashChainSegmentVariable, HashChainState, HashChainStateVariable, Sha256Chain,
+    };
+    use crate::backend::circuit::DefaultSerializer;
+    use crate::frontend::hash::hasher::PoseidonHasher;
+    use crate::prelude::{
+        Bytes32Variable, BytesVariable, CircuitBuilder, DefaultBuilder, DefaultParameters,
+        U32Variable,
+    };
+    use crate::utils::hash::sha256;
+    use crate::utils::proof::ProofWithPublicInputsTargetUtils;
+    use crate::utils::{self, bytes32};
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    type ShaSegmentInput = (HashChainStateVariable<Bytes32Variable>, BytesVariable<128>);
+
+    fn message() -> [u8; 300] {
+        core::array::from_fn(|i| (i % 251) as u8)
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_hash_chain_segments() {
+        utils::setup_logger();
+
+        let message = message();
+        let mut builder = DefaultBuilder::new();
+        let message_v = builder.constant::<BytesVariable<300>>(message);
+        let (blocks, tail) = message_v.0.split_at(256);
+        let tail_len = builder.read::<U32Variable>();
+
+        // SHA-256 in two segments of two blocks, joined before finalizing.
+        let start = builder.hash_chain_initial_state::<Sha256Chain>();
+        let left = builder.hash_chain_segment::<Sha256Chain>(start.clone(), &blocks[..128]);
+        let right = builder.hash_chain_segment::<Sha256Chain>(left.end.clone(), &blocks[128..]);
+        let joined = builder.hash_chain_join::<Sha256Chain>(left, right);
+        builder.assert_is_equal(joined.start, start);
+        let digest = builder.hash_chain_finalize::<Sha256Chain>(joined.end, tail, tail_len);
+        builder.write(digest);
+
+        // BLAKE2b in one segment of two blocks.
+        let start = builder.hash_chain_initial_state::<Blake2bChain>();
+        let state = builder.hash_chain_absorb::<Blake2bChain>(start, blocks);
+        let digest = builder.hash_chain_finalize::<Blake2bChain>(state, tail, tail_len);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U32Variable>(44);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            H256::from(sha256(&message))
+        );
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            bytes32!("0x940563f11807c8ba3192299e05cf544b82463742c8a5e80c2a5d81751cd8b0ca")
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_hash_chain_blake2b_empty_tail() {
+        utils::setup_logger();
+
+        // A message of whole blocks must keep its last block for the final compression.
+        let mut builder = DefaultBuilder::new();
+        let blocks = builder.constant::<BytesVariable<128>>([0xab; 128]);
+        let start = builder.hash_chain_initial_state::<Blake2bChain>();
+        let state = builder.hash_chain_absorb::<Blake2bChain>(start, &blocks.0);
+        let tail_len = builder.constant::<U32Variable>(0);
+        let digest = builder.hash_chain_finalize::<Blake2bChain>(state, &[], tail_len);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        circuit.prove(&input);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_hash_chain_across_proofs() {
+        utils::setup_logger();
+
+        let message = message();
+
+        // A segment circuit absorbing two blocks from the state it reads.
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let start = builder.read::<HashChainStateVariable<Bytes32Variable>>();
+        let blocks = builder.read::<BytesVariable<128>>();
+        let segment = builder.hash_chain_segment::<Sha256Chain>(start, &blocks.0);
+        builder.write(segment);
+        let segment_circuit = builder.build();
+
+        // The final circuit joins two segment proofs and pads the rest of the message.
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let verifier_data = builder.constant_verifier_data::<L>(&segment_circuit.data);
+        let mut segments: Vec<HashChainSegmentVariable<Bytes32Variable>> = Vec::new();
+        for _ in 0..2 {
+            let proof = builder.proof_read(&segment_circuit);
+            builder.verify_proof::<L>(&proof, &verifier_data, &segment_circuit.data.common);
+            segments.push(proof.read_end_from_pis());
+        }
+        let right = segments.pop().unwrap();
+        let left = segments.pop().unwrap();
+        let joined = builder.hash_chain_join::<Sha256Chain>(left, right);
+        let initial_state = builder.hash_chain_initial_state::<Sha256Chain>();
+        builder.assert_is_equal(joined.start, initial_state);
+        let tail = builder.constant::<BytesVariable<44>>(message[256..].try_into().unwrap());
+        let tail_len = builder.constant::<U32Variable>(44);
+        let digest = builder.hash_chain_finalize::<Sha256Chain>(joined.end, &tail.0, tail_len);
+        builder.proof_write(digest);
+        let final_circuit = builder.build();
+
+        // Prove the segments one after the other, each starting where the previous one ended.
+        let mut state = HashChainState {
+            chaining_value: bytes32!(
+                "0x6a09e667bb67ae853c6ef372a54ff53a510e527f9b05688c1f83d9ab5be0cd19"
+            ),
+            processed_len: 0,
+        };
+        let mut final_input = final_circuit.input();
+        for blocks in message[..256].chunks_exact(128) {
+            let mut input = segment_circuit.input();
+            input.write::<HashChainStateVariable<Bytes32Variable>>(state);
+            input.write::<BytesVariable<128>>(blocks.try_into().unwrap());
+            let (proof, mut output) = segment_circuit.prove(&input);
+            segment_circuit.verify(&proof, &input, &output);
+            let _start = output.read::<HashChainStateVariable<Bytes32Variable>>();
+            state = output.read::<HashChainStateVariable<Bytes32Variable>>();
+            final_input.proof_write(proof);
+        }
+        assert_eq!(state.processed_len, 256);
+
+        let (proof, mut output) = final_circuit.prove(&final_input);
+        final_circuit.verify(&proof, &final_input, &output);
+        assert_eq!(
+            output.proof_read::<Bytes32Variable>(),
+            H256::from(sha256(&message))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_hash_chain_mapreduce() {
+        utils::setup_logger();
+
+        let message = message();
+        let blocks: [[u8; 128]; 2] =
+            core::array::from_fn(|i| message[128 * i..128 * (i + 1)].try_into().unwrap());
+        let starts = [
+            HashChainState {
+                chaining_value: bytes32!(
+                    "0x6a09e667bb67ae853c6ef372a54ff53a510e527f9b05688c1f83d9ab5be0cd19"
+                ),
+                processed_len: 0,
+            },
+            HashChainState {
+                chaining_value: bytes32!(
+                    "0x593253adfb4cc018be611395485e47c15a5b271dfb8da14fe8f77fb4d05eacbc"
+                ),
+                processed_len: 128,
+            },
+        ];
+        let inputs = starts.into_iter().zip(blocks).collect::<Vec<_>>();
+
+        // Each map proof absorbs one segment, and the reduce proofs join adjacent ones.
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let ctx = builder.constant::<U32Variable>(0);
+        let joined = builder.mapreduce::<
+            PoseidonHasher,
+            U32Variable,
+            ShaSegmentInput,
+            HashChainSegmentVariable<Bytes32Variable>,
+            DefaultSerializer,
+            1,
+            _,
+            _,
+        >(
+            ctx,
+            inputs,
+            |_, inputs, builder| {
+                let (start, blocks) = inputs[0].clone();
+                builder.hash_chain_segment::<Sha256Chain>(start, &blocks.0)
+            },
+            |_, left, right, builder| builder.hash_chain_join::<Sha256Chain>(left, right),
+        );
+        let initial_state = builder.hash_chain_initial_state::<Sha256Chain>();
+        builder.assert_is_equal(joined.start, initial_state);
+        let tail = builder.constant::<BytesVariable<44>>(message[256..].try_into().unwrap());
+        let tail_len = builder.constant::<U32Variable>(44);
+        let digest = builder.hash_chain_finalize::<Sha256Chain>(joined.end, &tail.0, tail_len);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            H256::from(sha256(&message))
+        );
+    }
+}
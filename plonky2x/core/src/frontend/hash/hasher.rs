@@ -0,0 +1,402 @@
+//! A common interface over the hash functions that can be computed in a circuit.
+//!
+//! Gadgets that only need to hash bytes and combine digests (Merkle trees, commitments, ...) can
+//! be written generically over [`CircuitHasher`], so that the underlying hash function can be
+//! swapped without rewriting them.
+
+use core::fmt::Debug;
+
+use plonky2::field::types::Field;
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::util::ceil_div_usize;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::vars::EvmVariable;
+use crate::prelude::{
+    ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder, CircuitVariable, U32Variable,
+    Variable,
+};
+
+/// A hash function over bytes that can be computed in a circuit.
+pub trait CircuitHasher<L: PlonkParameters<D>, const D: usize>:
+    Debug + Clone + Send + Sync + 'static
+{
+    type Digest: CircuitVariable;
+
+    /// Hashes `input`.
+    fn hash(builder: &mut CircuitBuilder<L, D>, input: &[ByteVariable]) -> Self::Digest;
+
+    /// Hashes the first `length` bytes of `input`. The remaining bytes are ignored.
+    fn hash_variable(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> Self::Digest;
+
+    /// Hashes the concatenation of two digests.
+    fn hash_pair(
+        builder: &mut CircuitBuilder<L, D>,
+        left: Self::Digest,
+        right: Self::Digest,
+    ) -> Self::Digest;
+}
+
+/// SHA-256, computed with plonky2 gates.
+#[derive(Debug, Clone, Copy)]
+pub struct Sha256Hasher;
+
+/// SHA-256, computed with the Curta accelerator.
+#[derive(Debug, Clone, Copy)]
+pub struct CurtaSha256Hasher;
+
+/// SHA-512, computed with the Curta accelerator.
+#[derive(Debug, Clone, Copy)]
+pub struct CurtaSha512Hasher;
+
+/// BLAKE2b-256 of messages of at most `MAX_NUM_CHUNKS` 128-byte chunks, computed with the Curta
+/// accelerator.
+#[derive(Debug, Clone, Copy)]
+pub struct CurtaBlake2bHasher<const MAX_NUM_CHUNKS: usize>;
+
+/// BLAKE2s-256 of messages of at most `MAX_NUM_CHUNKS` 64-byte chunks.
+#[derive(Debug, Clone, Copy)]
+pub struct Blake2sHasher<const MAX_NUM_CHUNKS: usize>;
+
+/// Keccak-256, computed with the constrained Keccak sponge.
+#[derive(Debug, Clone, Copy)]
+pub struct Keccak256Hasher;
+
+/// The Poseidon hash of the circuit configuration, over bytes packed into 32-bit field elements.
+///
+/// Poseidon has no padding rule of its own, so `hash_variable` zeroes the bytes past `length` and
+/// appends the length, and `hash` does the same with the full length. `length` is constrained to
+/// be at most the length of the input. Digests of the same message
+/// therefore only agree for inputs of the same maximum length.
+#[derive(Debug, Clone, Copy)]
+pub struct PoseidonHasher;
+
+/// Pads `input` with zeros so that it is a whole number of `chunk_size` byte chunks, leaving room
+/// for the SHA padding byte and a `length_size` byte length.
+fn pad_to_sha_chunks<L: PlonkParameters<D>, const D: usize>(
+    builder: &mut CircuitBuilder<L, D>,
+    input: &[ByteVariable],
+    chunk_size: usize,
+    length_size: usize,
+) -> Vec<ByteVariable> {
+    let num_chunks = ceil_div_usize(input.len() + 1 + length_size, chunk_size);
+    let zero = builder.constant::<ByteVariable>(0);
+    let mut padded = input.to_vec();
+    padded.resize(num_chunks * chunk_size, zero);
+    padded
+}
+
+/// Computes the index of the last chunk of a SHA message of `length` bytes, which is
+/// `(length + length_size) / 2^log_chunk_size`.
+fn sha_last_chunk<L: PlonkParameters<D>, const D: usize>(
+    builder: &mut CircuitBuilder<L, D>,
+    length: U32Variable,
+    log_chunk_size: usize,
+    length_size: usize,
+) -> U32Variable {
+    let length_size = builder.constant::<U32Variable>(length_size as u32);
+    let padded_length = builder.add(length, length_size);
+    let bits = padded_length.to_be_bits(builder);
+    U32Variable::from_be_bits(&bits[..32 - log_chunk_size], builder)
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitHasher<L, D> for Sha256Hasher {
+    type Digest = Bytes32Variable;
+
+    fn hash(builder: &mut CircuitBuilder<L, D>, input: &[ByteVariable]) -> Self::Digest {
+        builder.sha256(input)
+    }
+
+    fn hash_variable(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> Self::Digest {
+        builder.sha256_variable(input, length)
+    }
+
+    fn hash_pair(
+        builder: &mut CircuitBuilder<L, D>,
+        left: Self::Digest,
+        right: Self::Digest,
+    ) -> Self::Digest {
+        builder.sha256_pair(left, right)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitHasher<L, D> for CurtaSha256Hasher {
+    type Digest = Bytes32Variable;
+
+    fn hash(builder: &mut CircuitBuilder<L, D>, input: &[ByteVariable]) -> Self::Digest {
+        builder.curta_sha256(input)
+    }
+
+    fn hash_variable(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> Self::Digest {
+        let input = pad_to_sha_chunks(builder, input, 64, 8);
+        let last_chunk = sha_last_chunk(builder, length, 6, 8);
+        builder.curta_sha256_variable(&input, length, last_chunk)
+    }
+
+    fn hash_pair(
+        builder: &mut CircuitBuilder<L, D>,
+        left: Self::Digest,
+        right: Self::Digest,
+    ) -> Self::Digest {
+        builder.curta_sha256_pair(left, right)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitHasher<L, D> for CurtaSha512Hasher {
+    type Digest = BytesVariable<64>;
+
+    fn hash(builder: &mut CircuitBuilder<L, D>, input: &[ByteVariable]) -> Self::Digest {
+        builder.curta_sha512(input)
+    }
+
+    fn hash_variable(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> Self::Digest {
+        let input = pad_to_sha_chunks(builder, input, 128, 16);
+        let last_chunk = sha_last_chunk(builder, length, 7, 16);
+        builder.curta_sha512_variable(&input, length, last_chunk)
+    }
+
+    fn hash_pair(
+        builder: &mut CircuitBuilder<L, D>,
+        left: Self::Digest,
+        right: Self::Digest,
+    ) -> Self::Digest {
+        let mut input = left.0.to_vec();
+        input.extend(right.0);
+        builder.curta_sha512(&input)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const MAX_NUM_CHUNKS: usize> CircuitHasher<L, D>
+    for CurtaBlake2bHasher<MAX_NUM_CHUNKS>
+{
+    type Digest = Bytes32Variable;
+
+    fn hash(builder: &mut CircuitBuilder<L, D>, input: &[ByteVariable]) -> Self::Digest {
+        let length = builder.constant::<Variable>(L::Field::from_canonical_usize(input.len()));
+        builder.curta_blake2b_variable::<MAX_NUM_CHUNKS>(input, length)
+    }
+
+    fn hash_variable(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> Self::Digest {
+        builder.curta_blake2b_variable::<MAX_NUM_CHUNKS>(input, length.variable)
+    }
+
+    fn hash_pair(
+        builder: &mut CircuitBuilder<L, D>,
+        left: Self::Digest,
+        right: Self::Digest,
+    ) -> Self::Digest {
+        let mut input = left.as_bytes().to_vec();
+        input.extend(right.as_bytes());
+        Self::hash(builder, &input)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const MAX_NUM_CHUNKS: usize> CircuitHasher<L, D>
+    for Blake2sHasher<MAX_NUM_CHUNKS>
+{
+    type Digest = Bytes32Variable;
+
+    fn hash(builder: &mut CircuitBuilder<L, D>, input: &[ByteVariable]) -> Self::Digest {
+        let length = builder.constant::<Variable>(L::Field::from_canonical_usize(input.len()));
+        builder.blake2s_variable::<MAX_NUM_CHUNKS>(input, length)
+    }
+
+    fn hash_variable(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> Self::Digest {
+        builder.blake2s_variable::<MAX_NUM_CHUNKS>(input, length.variable)
+    }
+
+    fn hash_pair(
+        builder: &mut CircuitBuilder<L, D>,
+        left: Self::Digest,
+        right: Self::Digest,
+    ) -> Self::Digest {
+        let mut input = left.as_bytes().to_vec();
+        input.extend(right.as_bytes());
+        Self::hash(builder, &input)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitHasher<L, D> for Keccak256Hasher {
+    type Digest = Bytes32Variable;
+
+    fn hash(builder: &mut CircuitBuilder<L, D>, input: &[ByteVariable]) -> Self::Digest {
+        builder.keccak256(input)
+    }
+
+    fn hash_variable(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> Self::Digest {
+        builder.keccak256_variable(input, length.variable)
+    }
+
+    fn hash_pair(
+        builder: &mut CircuitBuilder<L, D>,
+        left: Self::Digest,
+        right: Self::Digest,
+    ) -> Self::Digest {
+        let mut input = left.as_bytes().to_vec();
+        input.extend(right.as_bytes());
+        builder.keccak256(&input)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitHasher<L, D> for PoseidonHasher
+where
+    <<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher:
+        AlgebraicHasher<<L as PlonkParameters<D>>::Field>,
+{
+    type Digest = Bytes32Variable;
+
+    fn hash(builder: &mut CircuitBuilder<L, D>, input: &[ByteVariable]) -> Self::Digest {
+        let length = builder.constant::<U32Variable>(input.len() as u32);
+        Self::hash_variable(builder, input, length)
+    }
+
+    fn hash_variable(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> Self::Digest {
+        let zero = builder.constant::<ByteVariable>(0);
+        let mut in_message = builder._true();
+        let mut message = Vec::with_capacity(input.len() + 4);
+        for (i, byte) in input.iter().enumerate() {
+            let index = builder.constant::<Variable>(L::Field::from_canonical_usize(i));
+            let at_length = builder.is_equal(index, length.variable);
+            let not_at_length = builder.not(at_length);
+            in_message = builder.and(in_message, not_at_length);
+            message.push(builder.select(in_message, *byte, zero));
+        }
+        // The message ends at the only index equal to `length`, which must have been met by the end
+        // of the input.
+        let end = builder.constant::<Variable>(L::Field::from_canonical_usize(input.len()));
+        let at_end = builder.is_equal(end, length.variable);
+        let not_at_end = builder.not(at_end);
+        in_message = builder.and(in_message, not_at_end);
+        let f = builder._false();
+        builder.assert_is_equal(in_message, f);
+        message.extend(length.encode(builder));
+        builder
+            .poseidon::<<<L as PlonkParameters<D>>::Config as GenericConfig<D>>::Hasher>(&message)
+    }
+
+    fn hash_pair(
+        builder: &mut CircuitBuilder<L, D>,
+        left: Self::Digest,
+        right: Self::Digest,
+    ) -> Self::Digest {
+        let mut input = left.as_bytes().to_vec();
+        input.extend(right.as_bytes());
+        Self::hash(builder, &input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use ethers::types::H256;
+
+    use super::{CircuitHasher, CurtaSha256Hasher, PoseidonHasher};
+    use crate::prelude::*;
+    use crate::utils::hash::sha256;
+
+    /// A toy commitment, hashing each message and then the pair of digests.
+    fn commit<H: CircuitHasher<L, D>, L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        left: &[ByteVariable],
+        right: &[ByteVariable],
+        right_length: U32Variable,
+    ) -> H::Digest {
+        let left = H::hash(builder, left);
+        let right = H::hash_variable(builder, right, right_length);
+        H::hash_pair(builder, left, right)
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_curta_sha256_hasher() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        let left_message = b"plonky2x".to_vec();
+        let right_message = [0xabu8; 70];
+        let right_length = 60;
+
+        let mut builder = DefaultBuilder::new();
+        let left = builder.constant::<BytesVariable<8>>(left_message.clone().try_into().unwrap());
+        let right = builder.constant::<BytesVariable<70>>(right_message);
+        let length = builder.constant::<U32Variable>(right_length as u32);
+        let digest = commit::<CurtaSha256Hasher, _, 2>(&mut builder, &left.0, &right.0, length);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let mut pair = sha256(&left_message).to_vec();
+        pair.extend(sha256(&right_message[..right_length]));
+        assert_eq!(output.read::<Bytes32Variable>(), H256::from(sha256(&pair)));
+    }
+
+    #[test]
+    fn test_poseidon_hasher_ignores_bytes_past_length() {
+        let mut builder = DefaultBuilder::new();
+        let message = builder.read::<BytesVariable<12>>();
+        let length = builder.constant::<U32Variable>(7);
+        let variable_digest = PoseidonHasher::hash_variable(&mut builder, &message.0, length);
+
+        let prefix = builder.read::<BytesVariable<12>>();
+        let prefix_digest = PoseidonHasher::hash_variable(&mut builder, &prefix.0, length);
+        builder.assert_is_equal(variable_digest, prefix_digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BytesVariable<12>>(*b"hello world!");
+        input.write::<BytesVariable<12>>(*b"hello w\0\0\0\0\0");
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_poseidon_hasher_length_out_of_range() {
+        let mut builder = DefaultBuilder::new();
+        let message = builder.constant::<BytesVariable<4>>(*b"abcd");
+        let length = builder.read::<U32Variable>();
+        let digest = PoseidonHasher::hash_variable(&mut builder, &message.0, length);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U32Variable>(5);
+        circuit.prove(&input);
+    }
+}
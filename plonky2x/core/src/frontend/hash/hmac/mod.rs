@@ -0,0 +1,384 @@
+//! HMAC (RFC 2104) and HKDF (RFC 5869) over SHA-256 and SHA-512, computed with the Curta
+//! accelerator.
+//!
+//! The key is padded to the block size of the hash function (and hashed first if it is longer),
+//! after which `HMAC(K, m) = H((K0 ^ opad) || H((K0 ^ ipad) || m))`. The inner hash of the
+//! variable-length methods covers the padded key block followed by the first `length` bytes of
+//! the message.
+
+use plonky2::util::ceil_div_usize;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::hasher::{CircuitHasher, CurtaSha256Hasher, CurtaSha512Hasher};
+use crate::prelude::{ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder, U32Variable};
+
+/// The block size of SHA-256 in bytes.
+const SHA256_BLOCK_SIZE: usize = 64;
+
+/// The block size of SHA-512 in bytes.
+const SHA512_BLOCK_SIZE: usize = 128;
+
+/// The byte xored into the padded key for the inner hash.
+const IPAD: u8 = 0x36;
+
+/// The byte xored into the padded key for the outer hash.
+const OPAD: u8 = 0x5c;
+
+/// A hash function on the first `length` bytes of its input, returning the digest bytes.
+type HashVariableFn<L, const D: usize> =
+    fn(&mut CircuitBuilder<L, D>, &[ByteVariable], U32Variable) -> Vec<ByteVariable>;
+
+fn curta_sha256_bytes<L: PlonkParameters<D>, const D: usize>(
+    builder: &mut CircuitBuilder<L, D>,
+    input: &[ByteVariable],
+    length: U32Variable,
+) -> Vec<ByteVariable> {
+    CurtaSha256Hasher::hash_variable(builder, input, length)
+        .as_bytes()
+        .to_vec()
+}
+
+fn curta_sha512_bytes<L: PlonkParameters<D>, const D: usize>(
+    builder: &mut CircuitBuilder<L, D>,
+    input: &[ByteVariable],
+    length: U32Variable,
+) -> Vec<ByteVariable> {
+    CurtaSha512Hasher::hash_variable(builder, input, length)
+        .0
+        .to_vec()
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Xors every byte of the padded key with the constant `pad`.
+    fn hmac_xor_pad(&mut self, padded_key: &[ByteVariable], pad: u8) -> Vec<ByteVariable> {
+        padded_key
+            .iter()
+            .map(|byte| {
+                // Xoring a constant bit amounts to negating the bits set in `pad`.
+                let mut bits = byte.as_be_bits();
+                for (i, bit) in bits.iter_mut().enumerate() {
+                    if (pad >> (7 - i)) & 1 == 1 {
+                        *bit = self.not(*bit);
+                    }
+                }
+                ByteVariable(bits)
+            })
+            .collect()
+    }
+
+    /// HMAC over `hash` on the first `length` bytes of `message`, where `length` is constrained to
+    /// be at most the length of `message`.
+    fn hmac_variable(
+        &mut self,
+        hash: HashVariableFn<L, D>,
+        block_size: usize,
+        key: &[ByteVariable],
+        message: &[ByteVariable],
+        length: U32Variable,
+    ) -> Vec<ByteVariable> {
+        let max_length = self.constant::<U32Variable>(message.len() as u32);
+        let length_is_valid = self.lte(length, max_length);
+        let true_v = self._true();
+        self.assert_is_equal(length_is_valid, true_v);
+
+        let mut padded_key = if key.len() > block_size {
+            let key_length = self.constant::<U32Variable>(key.len() as u32);
+            hash(self, key, key_length)
+        } else {
+            key.to_vec()
+        };
+        let zero = self.constant::<ByteVariable>(0);
+        padded_key.resize(block_size, zero);
+
+        let mut inner_input = self.hmac_xor_pad(&padded_key, IPAD);
+        inner_input.extend_from_slice(message);
+        let block_length = self.constant::<U32Variable>(block_size as u32);
+        let inner_length = self.add(block_length, length);
+        let inner_digest = hash(self, &inner_input, inner_length);
+
+        let mut outer_input = self.hmac_xor_pad(&padded_key, OPAD);
+        outer_input.extend(inner_digest);
+        let outer_length = self.constant::<U32Variable>(outer_input.len() as u32);
+        hash(self, &outer_input, outer_length)
+    }
+
+    /// HKDF-Expand over `hash`, deriving `output_len` bytes from the pseudorandom key `prk` and
+    /// the context `info`.
+    fn hkdf_expand(
+        &mut self,
+        hash: HashVariableFn<L, D>,
+        block_size: usize,
+        prk: &[ByteVariable],
+        info: &[ByteVariable],
+        output_len: usize,
+    ) -> Vec<ByteVariable> {
+        let num_blocks = ceil_div_usize(output_len, prk.len());
+        assert!(
+            num_blocks <= 255,
+            "HKDF can derive at most 255 blocks of output"
+        );
+
+        let mut output = Vec::with_capacity(num_blocks * prk.len());
+        let mut previous = Vec::new();
+        for i in 1..=num_blocks {
+            let mut message = previous;
+            message.extend_from_slice(info);
+            message.push(self.constant::<ByteVariable>(i as u8));
+            let length = self.constant::<U32Variable>(message.len() as u32);
+            previous = self.hmac_variable(hash, block_size, prk, &message, length);
+            output.extend_from_slice(&previous);
+        }
+        output.truncate(output_len);
+        output
+    }
+
+    /// Computes HMAC-SHA256 of `message` under `key`.
+    pub fn hmac_sha256(
+        &mut self,
+        key: &[ByteVariable],
+        message: &[ByteVariable],
+    ) -> Bytes32Variable {
+        let length = self.constant::<U32Variable>(message.len() as u32);
+        self.hmac_sha256_variable(key, message, length)
+    }
+
+    /// Computes HMAC-SHA256 of the first `length` bytes of `message` under `key`.
+    pub fn hmac_sha256_variable(
+        &mut self,
+        key: &[ByteVariable],
+        message: &[ByteVariable],
+        length: U32Variable,
+    ) -> Bytes32Variable {
+        let digest =
+            self.hmac_variable(curta_sha256_bytes, SHA256_BLOCK_SIZE, key, message, length);
+        Bytes32Variable::from(digest.as_slice())
+    }
+
+    /// Computes HMAC-SHA512 of `message` under `key`.
+    pub fn hmac_sha512(
+        &mut self,
+        key: &[ByteVariable],
+        message: &[ByteVariable],
+    ) -> BytesVariable<64> {
+        let length = self.constant::<U32Variable>(message.len() as u32);
+        self.hmac_sha512_variable(key, message, length)
+    }
+
+    /// Computes HMAC-SHA512 of the first `length` bytes of `message` under `key`.
+    pub fn hmac_sha512_variable(
+        &mut self,
+        key: &[ByteVariable],
+        message: &[ByteVariable],
+        length: U32Variable,
+    ) -> BytesVariable<64> {
+        let digest =
+            self.hmac_variable(curta_sha512_bytes, SHA512_BLOCK_SIZE, key, message, length);
+        BytesVariable(digest.try_into().unwrap())
+    }
+
+    /// HKDF-Extract with SHA-256, returning the pseudorandom key `HMAC-SHA256(salt, ikm)`.
+    pub fn hkdf_sha256_extract(
+        &mut self,
+        salt: &[ByteVariable],
+        ikm: &[ByteVariable],
+    ) -> Bytes32Variable {
+        self.hmac_sha256(salt, ikm)
+    }
+
+    /// HKDF-Expand with SHA-256, deriving `N` bytes of output keying material from `prk`.
+    pub fn hkdf_sha256_expand<const N: usize>(
+        &mut self,
+        prk: Bytes32Variable,
+        info: &[ByteVariable],
+    ) -> BytesVariable<N> {
+        let output = self.hkdf_expand(
+            curta_sha256_bytes,
+            SHA256_BLOCK_SIZE,
+            &prk.as_bytes(),
+            info,
+            N,
+        );
+        BytesVariable(output.try_into().unwrap())
+    }
+
+    /// HKDF with SHA-256, extracting a key from `salt` and `ikm` and expanding it to `N` bytes.
+    pub fn hkdf_sha256<const N: usize>(
+        &mut self,
+        salt: &[ByteVariable],
+        ikm: &[ByteVariable],
+        info: &[ByteVariable],
+    ) -> BytesVariable<N> {
+        let prk = self.hkdf_sha256_extract(salt, ikm);
+        self.hkdf_sha256_expand(prk, info)
+    }
+
+    /// HKDF-Extract with SHA-512, returning the pseudorandom key `HMAC-SHA512(salt, ikm)`.
+    pub fn hkdf_sha512_extract(
+        &mut self,
+        salt: &[ByteVariable],
+        ikm: &[ByteVariable],
+    ) -> BytesVariable<64> {
+        self.hmac_sha512(salt, ikm)
+    }
+
+    /// HKDF-Expand with SHA-512, deriving `N` bytes of output keying material from `prk`.
+    pub fn hkdf_sha512_expand<const N: usize>(
+        &mut self,
+        prk: BytesVariable<64>,
+        info: &[ByteVariable],
+    ) -> BytesVariable<N> {
+        let output = self.hkdf_expand(curta_sha512_bytes, SHA512_BLOCK_SIZE, &prk.0, info, N);
+        BytesVariable(output.try_into().unwrap())
+    }
+
+    /// HKDF with SHA-512, extracting a key from `salt` and `ikm` and expanding it to `N` bytes.
+    pub fn hkdf_sha512<const N: usize>(
+        &mut self,
+        salt: &[ByteVariable],
+        ikm: &[ByteVariable],
+        info: &[ByteVariable],
+    ) -> BytesVariable<N> {
+        let prk = self.hkdf_sha512_extract(salt, ikm);
+        self.hkdf_sha512_expand(prk, info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex::decode;
+
+    use crate::prelude::{Bytes32Variable, BytesVariable, DefaultBuilder, U32Variable};
+    use crate::utils::{self, bytes32};
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_hmac_rfc4231() {
+        utils::setup_logger();
+
+        let mut builder = DefaultBuilder::new();
+
+        // Test case 1.
+        let key = builder.constant::<BytesVariable<20>>([0x0b; 20]);
+        let message = builder.constant::<BytesVariable<8>>(*b"Hi There");
+        let digest = builder.hmac_sha256(&key.0, &message.0);
+        builder.write(digest);
+        let digest = builder.hmac_sha512(&key.0, &message.0);
+        builder.write(digest);
+
+        // Test case 2, with a key shorter than the output.
+        let key = builder.constant::<BytesVariable<4>>(*b"Jefe");
+        let message = builder.constant::<BytesVariable<28>>(*b"what do ya want for nothing?");
+        let digest = builder.hmac_sha256(&key.0, &message.0);
+        builder.write(digest);
+
+        // Test case 6, with a key longer than the block size of both hash functions.
+        let key = builder.constant::<BytesVariable<131>>([0xaa; 131]);
+        let message = builder.constant::<BytesVariable<54>>(
+            *b"Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        let digest = builder.hmac_sha256(&key.0, &message.0);
+        builder.write(digest);
+        let digest = builder.hmac_sha512(&key.0, &message.0);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            bytes32!("0xb0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7")
+        );
+        assert_eq!(
+            output.read::<BytesVariable<64>>().to_vec(),
+            decode("87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854").unwrap()
+        );
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            bytes32!("0x5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            bytes32!("0x60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+        );
+        assert_eq!(
+            output.read::<BytesVariable<64>>().to_vec(),
+            decode("80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598").unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_hmac_variable() {
+        utils::setup_logger();
+
+        const MAX_LEN: usize = 80;
+        let mut message = [0xffu8; MAX_LEN];
+        message[..43].copy_from_slice(b"The quick brown fox jumps over the lazy dog");
+
+        let mut builder = DefaultBuilder::new();
+        let key = builder.constant::<BytesVariable<3>>(*b"key");
+        let message_v = builder.constant::<BytesVariable<MAX_LEN>>(message);
+        let length = builder.read::<U32Variable>();
+        let digest = builder.hmac_sha256_variable(&key.0, &message_v.0, length);
+        builder.write(digest);
+        let digest = builder.hmac_sha512_variable(&key.0, &message_v.0, length);
+        builder.write(digest);
+        let circuit = builder.build();
+
+        let test_cases = [
+            (
+                0,
+                "0x5d5d139563c95b5967b9bd9a8c9b233a9dedb45072794cd232dc1b74832607d0",
+                "84fa5aa0279bbc473267d05a53ea03310a987cecc4c1535ff29b6d76b8f1444a728df3aadb89d4a9a6709e1998f373566e8f824a8ca93b1821f0b69bc2a2f65e",
+            ),
+            (
+                43,
+                "0xf7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+                "b42af09057bac1e2d41708e48a902e09b5ff7f12ab428a4fe86653c73dd248fb82f948a549f7b791a5b41915ee4d1ec3935357e4e2317250d0372afa2ebeeb3a",
+            ),
+        ];
+        for (length, expected_sha256, expected_sha512) in test_cases {
+            let mut input = circuit.input();
+            input.write::<U32Variable>(length);
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<Bytes32Variable>(), bytes32!(expected_sha256));
+            assert_eq!(
+                output.read::<BytesVariable<64>>().to_vec(),
+                decode(expected_sha512).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_hkdf_sha256_rfc5869() {
+        utils::setup_logger();
+
+        // Test case 1.
+        let mut builder = DefaultBuilder::new();
+        let ikm = builder.constant::<BytesVariable<22>>([0x0b; 22]);
+        let salt = builder.constant::<BytesVariable<13>>(core::array::from_fn(|i| i as u8));
+        let info = builder.constant::<BytesVariable<10>>(core::array::from_fn(|i| 0xf0 + i as u8));
+        let prk = builder.hkdf_sha256_extract(&salt.0, &ikm.0);
+        builder.write(prk);
+        let okm = builder.hkdf_sha256_expand::<42>(prk, &info.0);
+        builder.write(okm);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            bytes32!("0x077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")
+        );
+        assert_eq!(
+            output.read::<BytesVariable<42>>().to_vec(),
+            decode("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865").unwrap()
+        );
+    }
+}
@@ -1,68 +1,93 @@
-//! An implementation of the keccak256 hash functions in a plonky2 circuit
+//! An implementation of the keccak256 and SHA-3 hash functions in a plonky2 circuit
 
-use core::marker::PhantomData;
-
-use self::keccak256::Keccak256Generator;
+use self::permutation::KECCAK_SUFFIX;
+use self::sha3::RATE_256;
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::vars::Bytes32Variable;
 use crate::prelude::{ByteVariable, CircuitBuilder, Variable};
 
-pub mod keccak256;
+pub mod permutation;
+pub mod sha3;
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
-    /// WARNING: DO NOT USE IN PRODUCTION, this is unconstrained!
+    /// Executes a keccak256 hash on the given input.
     pub fn keccak256(&mut self, bytes: &[ByteVariable]) -> Bytes32Variable {
-        let generator: Keccak256Generator<L, D> = Keccak256Generator {
-            input: bytes.to_vec(),
-            output: self.init(),
-            length: None,
-            _phantom: PhantomData::<L>,
-        };
-        let output = generator.output;
-        self.add_simple_generator(generator.clone());
-        output
+        let digest = self.keccak_sponge(bytes, RATE_256, KECCAK_SUFFIX, 32);
+        Bytes32Variable::from(digest.as_slice())
     }
 
+    /// Executes a keccak256 hash on the first `length` bytes of the given input, where `length`
+    /// is constrained to be at most `bytes.len()`.
     pub fn keccak256_variable(
         &mut self,
         bytes: &[ByteVariable],
         length: Variable,
     ) -> Bytes32Variable {
-        let generator = Keccak256Generator {
-            input: bytes.to_vec(),
-            output: self.init(),
-            length: Some(length),
-            _phantom: PhantomData::<L>,
-        };
-        self.add_simple_generator(generator.clone());
-        generator.output
+        let digest = self.keccak_sponge_variable(bytes, length, RATE_256, KECCAK_SUFFIX, 32);
+        Bytes32Variable::from(digest.as_slice())
     }
 }
 
 #[cfg(test)]
 mod tests {
-
     use super::*;
     use crate::backend::circuit::DefaultParameters;
-    use crate::prelude::CircuitBuilder;
-    use crate::utils::bytes32;
+    use crate::prelude::{BytesVariable, CircuitBuilder, Field, PlonkParameters};
+    use crate::utils::{self, bytes32};
 
     type L = DefaultParameters;
     const D: usize = 2;
 
     #[test]
     fn test_keccak256() {
-        env_logger::try_init().unwrap_or_default();
+        utils::setup_logger();
 
         let mut builder = CircuitBuilder::<L, D>::new();
         let word = builder.constant::<Bytes32Variable>(bytes32!(
             "0x0000000000000000000000000000000000000000000000000000000000000000"
         ));
         let hash = builder.keccak256(&word.0 .0);
-        builder.watch(&hash, "hi");
+        let expected_hash = builder.constant::<Bytes32Variable>(bytes32!(
+            "0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"
+        ));
+        builder.assert_is_equal(hash, expected_hash);
 
         let circuit = builder.build();
         let input = circuit.input();
-        let (_, _) = circuit.prove(&input);
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_keccak256_variable() {
+        utils::setup_logger();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let mut message = [0u8; 64];
+        message[..3].copy_from_slice(b"abc");
+        let message = builder.constant::<BytesVariable<64>>(message);
+        let length = builder.read::<Variable>();
+        let digest = builder.keccak256_variable(&message.0, length);
+        builder.write(digest);
+        let circuit = builder.build();
+
+        let test_cases = [
+            (
+                0,
+                "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            ),
+            (
+                3,
+                "0x4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+            ),
+        ];
+        for (length, expected_digest) in test_cases {
+            let mut input = circuit.input();
+            input.write::<Variable>(L::Field::from_canonical_usize(length));
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<Bytes32Variable>(), bytes32!(expected_digest));
+        }
     }
 }
@@ -0,0 +1,222 @@
+//! The Keccak-f[1600] permutation and the Keccak sponge in a plonky2 circuit.
+//!
+//! The state is made of 25 lanes of 64 bits, indexed by `x + 5 * y`. Each lane is represented by
+//! its little-endian bits, so that message bytes map to lanes in the order of FIPS 202.
+
+use itertools::Itertools;
+use plonky2::field::types::Field;
+use plonky2::util::ceil_div_usize;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::common::{and_arr, not_arr, xor2_arr, xor3_arr};
+use crate::frontend::vars::{BoolVariable, ByteVariable, CircuitVariable, Variable};
+use crate::prelude::CircuitBuilder;
+
+/// The domain separation suffix of the original Keccak submission, used by `keccak256`.
+pub(crate) const KECCAK_SUFFIX: u8 = 0x01;
+
+/// The domain separation suffix of the SHA-3 hash functions.
+pub(crate) const SHA3_SUFFIX: u8 = 0x06;
+
+/// The domain separation suffix of the SHAKE extendable-output functions.
+pub(crate) const SHAKE_SUFFIX: u8 = 0x1f;
+
+const KECCAK_ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808A,
+    0x8000000080008000,
+    0x000000000000808B,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008A,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000A,
+    0x000000008000808B,
+    0x800000000000008B,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800A,
+    0x800000008000000A,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The rotation offsets of the rho step, indexed by `x + 5 * y`.
+const KECCAK_ROTATIONS: [usize; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+/// A 64-bit lane of the Keccak state, represented by its little-endian bits.
+pub(crate) type LaneVariable = [BoolVariable; 64];
+
+fn rotate_lane(lane: LaneVariable, n: usize) -> LaneVariable {
+    core::array::from_fn(|i| lane[(i + 64 - n) % 64])
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Applies the Keccak-f[1600] permutation to `state`.
+    pub(crate) fn keccak_f(&mut self, state: &mut [LaneVariable; 25]) {
+        for rc in KECCAK_ROUND_CONSTANTS {
+            // Theta.
+            let c: [LaneVariable; 5] = core::array::from_fn(|x| {
+                let c = xor3_arr(state[x], state[x + 5], state[x + 10], self);
+                xor3_arr(c, state[x + 15], state[x + 20], self)
+            });
+            let d: [LaneVariable; 5] = core::array::from_fn(|x| {
+                xor2_arr(c[(x + 4) % 5], rotate_lane(c[(x + 1) % 5], 1), self)
+            });
+            for (i, lane) in state.iter_mut().enumerate() {
+                *lane = xor2_arr(*lane, d[i % 5], self);
+            }
+
+            // Rho and pi.
+            let mut b = *state;
+            for (i, lane) in state.iter().enumerate() {
+                let (x, y) = (i % 5, i / 5);
+                b[y + 5 * ((2 * x + 3 * y) % 5)] = rotate_lane(*lane, KECCAK_ROTATIONS[i]);
+            }
+
+            // Chi.
+            for (i, lane) in state.iter_mut().enumerate() {
+                let (x, y) = (i % 5, i / 5);
+                let not_b1 = not_arr(b[(x + 1) % 5 + 5 * y], self);
+                let not_b1_and_b2 = and_arr(not_b1, b[(x + 2) % 5 + 5 * y], self);
+                *lane = xor2_arr(b[i], not_b1_and_b2, self);
+            }
+
+            // Iota, where xoring a constant bit amounts to negating the set bits.
+            for (i, bit) in state[0].iter_mut().enumerate() {
+                if (rc >> i) & 1 == 1 {
+                    *bit = self.not(*bit);
+                }
+            }
+        }
+    }
+
+    /// Xors a block of `rate` bytes into the state and applies the permutation.
+    fn keccak_absorb(&mut self, state: &mut [LaneVariable; 25], block: &[ByteVariable]) {
+        for (lane, bytes) in state.iter_mut().zip(block.chunks_exact(8)) {
+            let block_lane: LaneVariable = bytes
+                .iter()
+                .flat_map(|byte| byte.as_le_bits())
+                .collect_vec()
+                .try_into()
+                .unwrap();
+            *lane = xor2_arr(*lane, block_lane, self);
+        }
+        self.keccak_f(state);
+    }
+
+    /// Squeezes `output_len` bytes out of the state, applying the permutation between blocks.
+    fn keccak_squeeze(
+        &mut self,
+        state: &mut [LaneVariable; 25],
+        rate: usize,
+        output_len: usize,
+    ) -> Vec<ByteVariable> {
+        let mut output = Vec::with_capacity(output_len);
+        loop {
+            for lane in state[..rate / 8].iter() {
+                output.extend(
+                    lane.chunks_exact(8)
+                        .map(|bits| ByteVariable(core::array::from_fn(|j| bits[7 - j]))),
+                );
+            }
+            if output.len() >= output_len {
+                output.truncate(output_len);
+                return output;
+            }
+            self.keccak_f(state);
+        }
+    }
+
+    /// The Keccak sponge with the given rate in bytes and domain separation suffix, on an input
+    /// whose length is known at circuit build time.
+    pub(crate) fn keccak_sponge(
+        &mut self,
+        input: &[ByteVariable],
+        rate: usize,
+        suffix: u8,
+        output_len: usize,
+    ) -> Vec<ByteVariable> {
+        let mut padded = input.to_vec();
+        padded.push(self.constant::<ByteVariable>(suffix));
+        let zero = self.constant::<ByteVariable>(0);
+        padded.resize(ceil_div_usize(padded.len(), rate) * rate, zero);
+        // The final bit of the padding, which may share its byte with the suffix.
+        let last = padded.len() - 1;
+        padded[last].0[0] = self._true();
+
+        let false_v = self._false();
+        let mut state = [[false_v; 64]; 25];
+        for block in padded.chunks_exact(rate) {
+            self.keccak_absorb(&mut state, block);
+        }
+        self.keccak_squeeze(&mut state, rate, output_len)
+    }
+
+    /// The Keccak sponge on the first `length` bytes of `input`, where `length` is constrained to
+    /// be at most the length of `input`.
+    ///
+    /// The padding is placed with selects, every block that may hold the end of the padding is
+    /// absorbed and the state after the last one is selected before squeezing. The message ends
+    /// at the only index equal to `length`, so reaching the end of `input` without meeting it
+    /// means that `length` is out of range.
+    pub(crate) fn keccak_sponge_variable(
+        &mut self,
+        input: &[ByteVariable],
+        length: Variable,
+        rate: usize,
+        suffix: u8,
+        output_len: usize,
+    ) -> Vec<ByteVariable> {
+        let zero = self.constant::<ByteVariable>(0);
+        let suffix_byte = self.constant::<ByteVariable>(suffix);
+        let false_v = self._false();
+        let mut state = [[false_v; 64]; 25];
+        let mut final_state = state;
+
+        let mut in_message = self._true();
+        for block_index in 0..input.len() / rate + 1 {
+            let mut block = Vec::with_capacity(rate);
+            let mut is_last_block = self._false();
+            for j in 0..rate {
+                let idx = block_index * rate + j;
+                let idx_v = self.constant::<Variable>(L::Field::from_canonical_usize(idx));
+                let at_length = self.is_equal(idx_v, length);
+                let not_at_length = self.not(at_length);
+                in_message = self.and(in_message, not_at_length);
+                is_last_block = self.or(is_last_block, at_length);
+                if idx == input.len() {
+                    self.assert_is_equal(in_message, false_v);
+                }
+
+                let message_byte = match input.get(idx) {
+                    Some(byte) => self.select(in_message, *byte, zero),
+                    None => zero,
+                };
+                let mut byte = self.select(at_length, suffix_byte, message_byte);
+                if j == rate - 1 {
+                    byte.0[0] = self.or(byte.0[0], is_last_block);
+                }
+                block.push(byte);
+            }
+            self.keccak_absorb(&mut state, &block);
+
+            for (final_lane, lane) in final_state.iter_mut().zip(state.iter()) {
+                for (final_bit, bit) in final_lane.iter_mut().zip(lane.iter()) {
+                    *final_bit = self.select(is_last_block, *bit, *final_bit);
+                }
+            }
+        }
+
+        self.keccak_squeeze(&mut final_state, rate, output_len)
+    }
+}
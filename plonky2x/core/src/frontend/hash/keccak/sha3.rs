@@ -0,0 +1,262 @@
+//! The FIPS 202 hash functions (SHA3-256, SHA3-512, SHAKE128 and SHAKE256) and Keccak-512, built
+//! on the constrained Keccak-f[1600] permutation.
+
+use super::permutation::{KECCAK_SUFFIX, SHA3_SUFFIX, SHAKE_SUFFIX};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::vars::{ByteVariable, Bytes32Variable, BytesVariable, Variable};
+use crate::prelude::CircuitBuilder;
+
+/// The rate in bytes of SHA3-256, SHAKE256 and Keccak-256.
+pub(crate) const RATE_256: usize = 136;
+
+/// The rate in bytes of SHA3-512 and Keccak-512.
+const RATE_512: usize = 72;
+
+/// The rate in bytes of SHAKE128.
+const RATE_SHAKE128: usize = 168;
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Executes a SHA3-256 hash on the given input.
+    pub fn sha3_256(&mut self, bytes: &[ByteVariable]) -> Bytes32Variable {
+        let digest = self.keccak_sponge(bytes, RATE_256, SHA3_SUFFIX, 32);
+        Bytes32Variable::from(digest.as_slice())
+    }
+
+    /// Executes a SHA3-256 hash on the first `length` bytes of the given input.
+    pub fn sha3_256_variable(
+        &mut self,
+        bytes: &[ByteVariable],
+        length: Variable,
+    ) -> Bytes32Variable {
+        let digest = self.keccak_sponge_variable(bytes, length, RATE_256, SHA3_SUFFIX, 32);
+        Bytes32Variable::from(digest.as_slice())
+    }
+
+    /// Executes a SHA3-512 hash on the given input.
+    pub fn sha3_512(&mut self, bytes: &[ByteVariable]) -> BytesVariable<64> {
+        let digest = self.keccak_sponge(bytes, RATE_512, SHA3_SUFFIX, 64);
+        BytesVariable(digest.try_into().unwrap())
+    }
+
+    /// Executes a SHA3-512 hash on the first `length` bytes of the given input.
+    pub fn sha3_512_variable(
+        &mut self,
+        bytes: &[ByteVariable],
+        length: Variable,
+    ) -> BytesVariable<64> {
+        let digest = self.keccak_sponge_variable(bytes, length, RATE_512, SHA3_SUFFIX, 64);
+        BytesVariable(digest.try_into().unwrap())
+    }
+
+    /// Executes a Keccak-512 hash (with the padding of the original Keccak submission) on the
+    /// given input.
+    pub fn keccak512(&mut self, bytes: &[ByteVariable]) -> BytesVariable<64> {
+        let digest = self.keccak_sponge(bytes, RATE_512, KECCAK_SUFFIX, 64);
+        BytesVariable(digest.try_into().unwrap())
+    }
+
+    /// Executes a Keccak-512 hash on the first `length` bytes of the given input.
+    pub fn keccak512_variable(
+        &mut self,
+        bytes: &[ByteVariable],
+        length: Variable,
+    ) -> BytesVariable<64> {
+        let digest = self.keccak_sponge_variable(bytes, length, RATE_512, KECCAK_SUFFIX, 64);
+        BytesVariable(digest.try_into().unwrap())
+    }
+
+    /// Computes `N` bytes of SHAKE128 output on the given input.
+    pub fn shake128<const N: usize>(&mut self, bytes: &[ByteVariable]) -> BytesVariable<N> {
+        let output = self.keccak_sponge(bytes, RATE_SHAKE128, SHAKE_SUFFIX, N);
+        BytesVariable(output.try_into().unwrap())
+    }
+
+    /// Computes `N` bytes of SHAKE128 output on the first `length` bytes of the given input.
+    pub fn shake128_variable<const N: usize>(
+        &mut self,
+        bytes: &[ByteVariable],
+        length: Variable,
+    ) -> BytesVariable<N> {
+        let output = self.keccak_sponge_variable(bytes, length, RATE_SHAKE128, SHAKE_SUFFIX, N);
+        BytesVariable(output.try_into().unwrap())
+    }
+
+    /// Computes `N` bytes of SHAKE256 output on the given input.
+    pub fn shake256<const N: usize>(&mut self, bytes: &[ByteVariable]) -> BytesVariable<N> {
+        let output = self.keccak_sponge(bytes, RATE_256, SHAKE_SUFFIX, N);
+        BytesVariable(output.try_into().unwrap())
+    }
+
+    /// Computes `N` bytes of SHAKE256 output on the first `length` bytes of the given input.
+    pub fn shake256_variable<const N: usize>(
+        &mut self,
+        bytes: &[ByteVariable],
+        length: Variable,
+    ) -> BytesVariable<N> {
+        let output = self.keccak_sponge_variable(bytes, length, RATE_256, SHAKE_SUFFIX, N);
+        BytesVariable(output.try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex::decode;
+
+    use crate::backend::circuit::DefaultParameters;
+    use crate::prelude::{
+        Bytes32Variable, BytesVariable, CircuitBuilder, Field, PlonkParameters, Variable,
+    };
+    use crate::utils::{self, bytes32};
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_sha3_256() {
+        utils::setup_logger();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let empty_digest = builder.sha3_256(&[]);
+        let abc = builder.constant::<BytesVariable<3>>(*b"abc");
+        let abc_digest = builder.sha3_256(&abc.0);
+
+        let expected_empty_digest = builder.constant::<Bytes32Variable>(bytes32!(
+            "0xa7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        ));
+        builder.assert_is_equal(empty_digest, expected_empty_digest);
+        let expected_abc_digest = builder.constant::<Bytes32Variable>(bytes32!(
+            "0x3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        ));
+        builder.assert_is_equal(abc_digest, expected_abc_digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha3_256_variable() {
+        utils::setup_logger();
+
+        const MAX_LEN: usize = 200;
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let message = builder.constant::<BytesVariable<MAX_LEN>>(core::array::from_fn(|i| i as u8));
+        let length = builder.read::<Variable>();
+        let digest = builder.sha3_256_variable(&message.0, length);
+        builder.write(digest);
+        let circuit = builder.build();
+
+        // Lengths whose padding ends in the first block, at the end of it, in the second block and
+        // after the full message.
+        let test_cases = [
+            (
+                0,
+                "0xa7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+            ),
+            (
+                135,
+                "0xfded8fd9d6551c601eeb3b7c6bc5e5cfd8aad1d015b7e9aaa9c9b9475231d5e2",
+            ),
+            (
+                136,
+                "0xcf3ccff92480a29160c2d38317c430e14749bfee1788106957dfe73f8c4930e5",
+            ),
+            (
+                150,
+                "0xadaa23ca1ed892ad1cf028cd40ba8ae2bfd3d7df1289c3f2319072106f587a98",
+            ),
+            (
+                MAX_LEN,
+                "0x5f728f63bf5ee48c77f453c0490398fa645b8d4c4e56be9a41cfec344d6ca899",
+            ),
+        ];
+        for (length, expected_digest) in test_cases {
+            let mut input = circuit.input();
+            input.write::<Variable>(L::Field::from_canonical_usize(length));
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+            assert_eq!(output.read::<Bytes32Variable>(), bytes32!(expected_digest));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha3_256_variable_length_out_of_range() {
+        utils::setup_logger();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let message = builder.constant::<BytesVariable<3>>(*b"abc");
+        let length = builder.read::<Variable>();
+        let digest = builder.sha3_256_variable(&message.0, length);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Variable>(L::Field::from_canonical_usize(4));
+        circuit.prove(&input);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha3_512_and_keccak512() {
+        utils::setup_logger();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let abc = builder.constant::<BytesVariable<3>>(*b"abc");
+        let sha3_digest = builder.sha3_512(&abc.0);
+        builder.write(sha3_digest);
+        let keccak_digest = builder.keccak512(&abc.0);
+        builder.write(keccak_digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<BytesVariable<64>>().to_vec(),
+            decode("b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0").unwrap()
+        );
+        assert_eq!(
+            output.read::<BytesVariable<64>>().to_vec(),
+            decode("18587dc2ea106b9a1563e32b3312421ca164c7f1f07bc922a9c83d77cea3a1e5d0c69910739025372dc14ac9642629379540c17e2a65b19d77aa511a9d00bb96").unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_shake() {
+        utils::setup_logger();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let shake128_empty = builder.shake128::<32>(&[]);
+        builder.write(shake128_empty);
+        let abc = builder.constant::<BytesVariable<3>>(*b"abc");
+        let shake256_abc = builder.shake256::<64>(&abc.0);
+        builder.write(shake256_abc);
+        // More output than the rate, which needs a second permutation while squeezing.
+        let shake128_abc = builder.shake128::<200>(&abc.0);
+        builder.write(shake128_abc);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<BytesVariable<32>>().to_vec(),
+            decode("7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26").unwrap()
+        );
+        assert_eq!(
+            output.read::<BytesVariable<64>>().to_vec(),
+            decode("483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739d5a15bef186a5386c75744c0527e1faa9f8726e462a12a4feb06bd8801e751e4").unwrap()
+        );
+        assert_eq!(
+            output.read::<BytesVariable<200>>().to_vec(),
+            decode("5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc844c50af32acd3f2cdd066568706f509bc1bdde58295dae3f891a9a0fca5783789a41f8611214ce612394df286a62d1a2252aa94db9c538956c717dc2bed4f232a0294c857c730aa16067ac1062f1201fb0d377cfb9cde4c63599b27f3462bba4a0ed296c801f9ff7f57302bb3076ee145f97a32ae68e76ab66c48d51675bd49acc29082f5647584e6aa01b3f5af057805f973ff8ecb8b226ac32ada6f01c1fcd4818cb006aa5b4cd").unwrap()
+        );
+    }
+}
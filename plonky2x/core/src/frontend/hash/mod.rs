@@ -1,6 +1,11 @@
 pub mod blake2;
+pub mod blake3;
 pub mod common;
+pub mod continuation;
 pub mod deprecated;
+pub mod hasher;
+pub mod hmac;
 pub mod keccak;
 pub mod poseidon;
+pub mod ripemd160;
 pub mod sha;
@@ -1,3 +1,6 @@
 //! An implementation of the Poseidon hash functions in a plonky2 circuit
 
+pub mod poseidon2;
 pub mod poseidon256;
+pub mod poseidon_bn128;
+pub mod poseidon_bn254;
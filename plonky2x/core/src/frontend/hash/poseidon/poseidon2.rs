@@ -0,0 +1,440 @@
+//! Poseidon2 over the Goldilocks field with a state of 12 elements.
+//!
+//! The round constants are derived with the Grain LFSR as in the reference implementation
+//! (`poseidon2_rust_params.sage`), with the x^7 s-box, 8 full rounds and 22 partial rounds. The
+//! external layer is the 4x4 circulant-based matrix of the paper and the internal layer is
+//! `1 + diag(MATRIX_DIAG_12)`, which only costs one multiplication per element.
+//!
+//! The permutation matches the HorizenLabs reference implementation. In a circuit it is built
+//! from arithmetic gates, as plonky2 has no Poseidon2 gate, so it takes many more gates than
+//! `poseidon_hash`, which uses plonky2's `PoseidonGate`.
+
+use lazy_static::lazy_static;
+use num::BigUint;
+use plonky2::field::extension::Extendable;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::{Field, Field64};
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::Target;
+use plonky2::plonk::circuit_builder::CircuitBuilder as BaseCircuitBuilder;
+
+use super::poseidon256::PoseidonHashOutVariable;
+use super::poseidon_bn254::GrainLfsr;
+use crate::frontend::vars::ArrayVariable;
+use crate::prelude::{CircuitBuilder, CircuitVariable, PlonkParameters, Variable};
+
+/// The number of elements in the state.
+pub const POSEIDON2_WIDTH: usize = 12;
+
+/// The number of elements absorbed by each permutation.
+pub const POSEIDON2_RATE: usize = 8;
+
+const POSEIDON2_FULL_ROUNDS: usize = 8;
+const POSEIDON2_PARTIAL_ROUNDS: usize = 22;
+
+/// The bit size of the Goldilocks field.
+const FIELD_BITS: usize = 64;
+
+/// The diagonal of the internal matrix, minus the identity.
+const MATRIX_DIAG_12: [u64; POSEIDON2_WIDTH] = [
+    0xc3b6c08e23ba9300,
+    0xd84b5de94a324fb6,
+    0x0d0c371c5b35b84f,
+    0x7964f570e7188037,
+    0x5daf18bbd996604b,
+    0x6743bc47b9595257,
+    0x5528b9362c59bb70,
+    0xac45e25b7127b68b,
+    0xa2077d7dfbb606b5,
+    0xf3faac6faee378ae,
+    0x0c6388b51545e883,
+    0xd27dbb6944917b60,
+];
+
+/// The 4x4 matrix from which the external matrix is built.
+const M4: [[u64; 4]; 4] = [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+
+lazy_static! {
+    /// The Poseidon2 parameters, which are expensive to derive.
+    pub static ref POSEIDON2_PARAMS: Poseidon2Params = Poseidon2Params::new();
+}
+
+/// The round constants of Poseidon2 over Goldilocks.
+#[derive(Debug, Clone)]
+pub struct Poseidon2Params {
+    /// The round constants, `POSEIDON2_WIDTH` of them per round. Partial rounds only have a
+    /// constant for the first element, and the others are zero.
+    pub round_constants: Vec<[u64; POSEIDON2_WIDTH]>,
+}
+
+impl Default for Poseidon2Params {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Poseidon2Params {
+    pub fn new() -> Self {
+        let mut lfsr = GrainLfsr::new(
+            FIELD_BITS,
+            POSEIDON2_WIDTH,
+            POSEIDON2_FULL_ROUNDS,
+            POSEIDON2_PARTIAL_ROUNDS,
+        );
+
+        let order = BigUint::from(GoldilocksField::ORDER);
+        let mut next_constant = || loop {
+            let value = lfsr.next_biguint(FIELD_BITS);
+            if value < order {
+                return value.to_u64_digits().first().copied().unwrap_or(0);
+            }
+        };
+
+        // Unlike Poseidon, partial rounds only draw the constant of the first element.
+        let nb_rounds = POSEIDON2_FULL_ROUNDS + POSEIDON2_PARTIAL_ROUNDS;
+        let round_constants = (0..nb_rounds)
+            .map(|round| {
+                let nb_constants = if Self::is_full_round(round) {
+                    POSEIDON2_WIDTH
+                } else {
+                    1
+                };
+                let mut constants = [0; POSEIDON2_WIDTH];
+                for constant in constants.iter_mut().take(nb_constants) {
+                    *constant = next_constant();
+                }
+                constants
+            })
+            .collect();
+
+        Self { round_constants }
+    }
+
+    fn is_full_round(round: usize) -> bool {
+        let half = POSEIDON2_FULL_ROUNDS / 2;
+        round < half || round >= half + POSEIDON2_PARTIAL_ROUNDS
+    }
+
+    fn sbox<F: Field>(x: F) -> F {
+        let x2 = x.square();
+        let x4 = x2.square();
+        x4 * x2 * x
+    }
+
+    fn external_layer<F: Field>(state: &mut [F; POSEIDON2_WIDTH]) {
+        for chunk in state.chunks_exact_mut(4) {
+            let mixed: [F; 4] = core::array::from_fn(|i| {
+                M4[i]
+                    .iter()
+                    .zip(chunk.iter())
+                    .map(|(m, x)| F::from_canonical_u64(*m) * *x)
+                    .sum()
+            });
+            chunk.copy_from_slice(&mixed);
+        }
+        let sums: [F; 4] =
+            core::array::from_fn(|i| (0..POSEIDON2_WIDTH).step_by(4).map(|j| state[i + j]).sum());
+        for (i, x) in state.iter_mut().enumerate() {
+            *x += sums[i % 4];
+        }
+    }
+
+    fn internal_layer<F: Field>(state: &mut [F; POSEIDON2_WIDTH]) {
+        let sum: F = state.iter().copied().sum();
+        for (x, diag) in state.iter_mut().zip(MATRIX_DIAG_12.iter()) {
+            *x = *x * F::from_canonical_u64(*diag) + sum;
+        }
+    }
+
+    /// Applies the Poseidon2 permutation to `state`.
+    pub fn permute<F: RichField>(&self, state: &mut [F; POSEIDON2_WIDTH]) {
+        Self::external_layer(state);
+        for (round, constants) in self.round_constants.iter().enumerate() {
+            if Self::is_full_round(round) {
+                for (x, c) in state.iter_mut().zip(constants.iter()) {
+                    *x = Self::sbox(*x + F::from_canonical_u64(*c));
+                }
+                Self::external_layer(state);
+            } else {
+                state[0] = Self::sbox(state[0] + F::from_canonical_u64(constants[0]));
+                Self::internal_layer(state);
+            }
+        }
+    }
+
+    /// Hashes `inputs` with a sponge in overwrite mode and no padding, like plonky2's
+    /// `hash_n_to_hash_no_pad`.
+    pub fn hash_no_pad<F: RichField>(&self, inputs: &[F]) -> [F; 4] {
+        let mut state = [F::ZERO; POSEIDON2_WIDTH];
+        for chunk in inputs.chunks(POSEIDON2_RATE) {
+            state[..chunk.len()].copy_from_slice(chunk);
+            self.permute(&mut state);
+        }
+        state[..4].try_into().unwrap()
+    }
+}
+
+fn sbox_circuit<F: RichField + Extendable<D>, const D: usize>(
+    api: &mut BaseCircuitBuilder<F, D>,
+    x: Target,
+) -> Target {
+    let x2 = api.square(x);
+    let x4 = api.square(x2);
+    let x6 = api.mul(x4, x2);
+    api.mul(x6, x)
+}
+
+fn external_layer_circuit<F: RichField + Extendable<D>, const D: usize>(
+    api: &mut BaseCircuitBuilder<F, D>,
+    state: &mut [Target; POSEIDON2_WIDTH],
+) {
+    for chunk in state.chunks_exact_mut(4) {
+        let mixed: [Target; 4] = core::array::from_fn(|i| {
+            M4[i]
+                .iter()
+                .zip(chunk.iter())
+                .fold(api.zero(), |acc, (m, x)| {
+                    api.mul_const_add(F::from_canonical_u64(*m), *x, acc)
+                })
+        });
+        chunk.copy_from_slice(&mixed);
+    }
+    let sums: [Target; 4] = core::array::from_fn(|i| {
+        let column = (0..POSEIDON2_WIDTH)
+            .step_by(4)
+            .map(|j| state[i + j])
+            .collect::<Vec<_>>();
+        api.add_many(column)
+    });
+    for (i, x) in state.iter_mut().enumerate() {
+        *x = api.add(*x, sums[i % 4]);
+    }
+}
+
+fn internal_layer_circuit<F: RichField + Extendable<D>, const D: usize>(
+    api: &mut BaseCircuitBuilder<F, D>,
+    state: &mut [Target; POSEIDON2_WIDTH],
+) {
+    let sum = api.add_many(state.iter());
+    for (x, diag) in state.iter_mut().zip(MATRIX_DIAG_12.iter()) {
+        *x = api.mul_const_add(F::from_canonical_u64(*diag), *x, sum);
+    }
+}
+
+/// Applies the Poseidon2 permutation to `state` with the arithmetic gates of a plonky2 builder.
+///
+/// This is shared by the frontend gadget and the in-circuit hasher of
+/// [`Poseidon2GoldilocksConfig`](crate::backend::circuit::poseidon2_config::Poseidon2GoldilocksConfig).
+pub(crate) fn poseidon2_permute_circuit<F: RichField + Extendable<D>, const D: usize>(
+    api: &mut BaseCircuitBuilder<F, D>,
+    params: &Poseidon2Params,
+    mut state: [Target; POSEIDON2_WIDTH],
+) -> [Target; POSEIDON2_WIDTH] {
+    external_layer_circuit(api, &mut state);
+    for (round, constants) in params.round_constants.iter().enumerate() {
+        if Poseidon2Params::is_full_round(round) {
+            for (x, c) in state.iter_mut().zip(constants.iter()) {
+                let x_plus_c = api.add_const(*x, F::from_canonical_u64(*c));
+                *x = sbox_circuit(api, x_plus_c);
+            }
+            external_layer_circuit(api, &mut state);
+        } else {
+            let x_plus_c = api.add_const(state[0], F::from_canonical_u64(constants[0]));
+            state[0] = sbox_circuit(api, x_plus_c);
+            internal_layer_circuit(api, &mut state);
+        }
+    }
+    state
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Applies the Poseidon2 permutation to `state`.
+    pub fn poseidon2_permute(
+        &mut self,
+        params: &Poseidon2Params,
+        state: [Variable; POSEIDON2_WIDTH],
+    ) -> [Variable; POSEIDON2_WIDTH] {
+        poseidon2_permute_circuit(&mut self.api, params, state.map(|x| x.0)).map(Variable)
+    }
+
+    /// Computes the Poseidon2 hash of the given variables with no padding.
+    pub fn poseidon2_hash(&mut self, variables: &[Variable]) -> PoseidonHashOutVariable {
+        let params = &*POSEIDON2_PARAMS;
+        let zero = self.zero::<Variable>();
+        let mut state = [zero; POSEIDON2_WIDTH];
+        for chunk in variables.chunks(POSEIDON2_RATE) {
+            state[..chunk.len()].copy_from_slice(chunk);
+            state = self.poseidon2_permute(params, state);
+        }
+        PoseidonHashOutVariable {
+            elements: ArrayVariable::new(state[..4].to_vec()),
+        }
+    }
+
+    /// Computes the Poseidon2 hash of the concatenation of two digests.
+    pub fn poseidon2_hash_pair(
+        &mut self,
+        left: PoseidonHashOutVariable,
+        right: PoseidonHashOutVariable,
+    ) -> PoseidonHashOutVariable {
+        let mut input = Vec::new();
+        input.extend(left.variables());
+        input.extend(right.variables());
+        self.poseidon2_hash(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::{Field, Sample};
+    use plonky2::hash::hash_types::HashOut;
+
+    use super::{Poseidon2Params, POSEIDON2_WIDTH};
+    use crate::frontend::hash::poseidon::poseidon256::PoseidonHashOutVariable;
+    use crate::prelude::{ArrayVariable, DefaultBuilder, GoldilocksField, Variable};
+    use crate::utils;
+
+    type F = GoldilocksField;
+
+    #[test]
+    fn test_poseidon2_params() {
+        let params = Poseidon2Params::new();
+        assert_eq!(params.round_constants.len(), 30);
+
+        // The first and last full rounds and the first and last partial rounds of the reference
+        // constants (`RC12` in the HorizenLabs implementation).
+        let first_full_round: [u64; POSEIDON2_WIDTH] = [
+            0x13dcf33aba214f46,
+            0x30b3b654a1da6d83,
+            0x1fc634ada6159b56,
+            0x937459964dc03466,
+            0xedd2ef2ca7949924,
+            0xede9affde0e22f68,
+            0x8515b9d6bac9282d,
+            0x6b5c07b4e9e900d8,
+            0x1ec66368838c8a08,
+            0x9042367d80d1fbab,
+            0x400283564a3c3799,
+            0x4a00be0466bca75e,
+        ];
+        let last_full_round: [u64; POSEIDON2_WIDTH] = [
+            0x21dd28d906a62dda,
+            0xf32a46ab5f465b5f,
+            0xbfce13201f3f7e6b,
+            0xf30d2e7adb5304e2,
+            0xecdf4ee4abad48e9,
+            0xf94e82182d395019,
+            0x4ee52e3744d887c5,
+            0xa1341c7cac0083b2,
+            0x2302fb26c30c834a,
+            0xaea3c587273bf7d3,
+            0xf798e24961823ec7,
+            0x962deba3e9a2cd94,
+        ];
+        assert_eq!(params.round_constants[0], first_full_round);
+        assert_eq!(params.round_constants[29], last_full_round);
+        for (round, constant) in [(4, 0x4adf842aa75d4316), (25, 0xf7bb62a8da4c961b)] {
+            let mut expected = [0; POSEIDON2_WIDTH];
+            expected[0] = constant;
+            assert_eq!(params.round_constants[round], expected);
+        }
+
+        // The external matrix is circ(2 * M4, M4, M4) and the internal one 1 + diag(MATRIX_DIAG_12),
+        // so applying the layers to a unit vector gives a column of the matrix.
+        let matrix_diag_12: [u64; POSEIDON2_WIDTH] = [
+            0xc3b6c08e23ba9300,
+            0xd84b5de94a324fb6,
+            0x0d0c371c5b35b84f,
+            0x7964f570e7188037,
+            0x5daf18bbd996604b,
+            0x6743bc47b9595257,
+            0x5528b9362c59bb70,
+            0xac45e25b7127b68b,
+            0xa2077d7dfbb606b5,
+            0xf3faac6faee378ae,
+            0x0c6388b51545e883,
+            0xd27dbb6944917b60,
+        ];
+        let m4 = [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+        for j in 0..POSEIDON2_WIDTH {
+            let mut external = [F::ZERO; POSEIDON2_WIDTH];
+            external[j] = F::ONE;
+            let mut internal = external;
+            Poseidon2Params::external_layer(&mut external);
+            Poseidon2Params::internal_layer(&mut internal);
+            for i in 0..POSEIDON2_WIDTH {
+                let block = if i / 4 == j / 4 { 2 } else { 1 };
+                let expected_external = F::from_canonical_u64(block * m4[i % 4][j % 4]);
+                assert_eq!(external[i], expected_external);
+                let expected_internal = if i == j {
+                    F::from_canonical_u64(matrix_diag_12[i]) + F::ONE
+                } else {
+                    F::ONE
+                };
+                assert_eq!(internal[i], expected_internal);
+            }
+        }
+    }
+
+    #[test]
+    fn test_poseidon2_kat() {
+        // The test vector of the HorizenLabs reference implementation for Goldilocks with a
+        // state of 12 elements.
+        let params = Poseidon2Params::new();
+        assert_eq!(params.round_constants[0][0], 0x13dcf33aba214f46);
+
+        let mut state: [F; POSEIDON2_WIDTH] = core::array::from_fn(F::from_canonical_usize);
+        params.permute(&mut state);
+        let expected = [
+            0x01eaef96bdf1c0c1,
+            0x1f0d2cc525b2540c,
+            0x6282c1dfe1e0358d,
+            0xe780d721f698e1e6,
+            0x280c0b6f753d833b,
+            0x1b942dd5023156ab,
+            0x43f0df3fcccb8398,
+            0xe8e8190585489025,
+            0x56bdbf72f77ada22,
+            0x7911c32bf9dcd705,
+            0xec467926508fbe67,
+            0x6a50450ddf85a6ed,
+        ]
+        .map(F::from_canonical_u64);
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_poseidon2() {
+        utils::setup_logger();
+        let params = Poseidon2Params::new();
+
+        // Two permutations, the second of which only absorbs part of the rate.
+        let inputs = F::rand_vec(11);
+        let expected_digest = params.hash_no_pad(&inputs);
+        let left = F::rand_vec(4);
+        let right = F::rand_vec(4);
+        let expected_pair_digest = params.hash_no_pad(&[left.clone(), right.clone()].concat());
+
+        let mut builder = DefaultBuilder::new();
+        let variables = builder.read::<ArrayVariable<Variable, 11>>();
+        let digest = builder.poseidon2_hash(&variables.as_vec());
+        builder.write(digest);
+        let left_digest = builder.read::<PoseidonHashOutVariable>();
+        let right_digest = builder.read::<PoseidonHashOutVariable>();
+        let pair_digest = builder.poseidon2_hash_pair(left_digest, right_digest);
+        builder.write(pair_digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<Variable, 11>>(inputs);
+        input.write::<PoseidonHashOutVariable>(HashOut::from_vec(left));
+        input.write::<PoseidonHashOutVariable>(HashOut::from_vec(right));
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let digest = output.read::<PoseidonHashOutVariable>();
+        assert_eq!(digest.elements, expected_digest);
+        let pair_digest = output.read::<PoseidonHashOutVariable>();
+        assert_eq!(pair_digest.elements, expected_pair_digest);
+    }
+}
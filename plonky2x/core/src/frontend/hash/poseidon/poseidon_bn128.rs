@@ -0,0 +1,247 @@
+//! The Poseidon BN128 hasher of the wrapper config in a plonky2 circuit, using nonnative
+//! arithmetic over the BN254 scalar field.
+//!
+//! This follows `backend/wrapper/poseidon_bn128.rs` step by step with the same optimized
+//! constants (a state of 4 elements, with sparse matrices in the partial rounds), so the digests
+//! match the ones computed by `PoseidonBN128Hash` and by the gnark/Solidity verifiers of wrapped
+//! proofs.
+
+use ff::PrimeField;
+use num::BigUint;
+use plonky2::field::types::Field;
+use plonky2::util::ceil_div_usize;
+
+use crate::backend::wrapper::poseidon_bn128::{GOLDILOCKS_ELEMENTS, RATE, WIDTH};
+use crate::backend::wrapper::poseidon_bn128_constants::{
+    C_CONSTANTS, M_MATRIX, P_MATRIX, S_CONSTANTS,
+};
+use crate::backend::wrapper::utils::Fr;
+use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+use crate::frontend::num::biguint::BigUintTarget;
+use crate::frontend::num::nonnative::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use crate::frontend::num::u32::gadgets::arithmetic_u32::U32Target;
+use crate::prelude::{CircuitBuilder, PlonkParameters, Variable};
+
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 56;
+
+type PoseidonBn128StateTarget = [NonNativeTarget<Bn254Scalar>; WIDTH];
+
+fn fr_to_bn254_scalar(x: &Fr) -> Bn254Scalar {
+    Bn254Scalar::from_noncanonical_biguint(BigUint::from_bytes_le(x.to_repr().as_ref()))
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Applies the Poseidon BN128 permutation of the wrapper config to `state`.
+    pub fn poseidon_bn128_permute(
+        &mut self,
+        state: &PoseidonBn128StateTarget,
+    ) -> PoseidonBn128StateTarget {
+        let mut state = state.clone();
+        self.poseidon_bn128_ark(&mut state, 0);
+        self.poseidon_bn128_full_rounds(&mut state, true);
+        self.poseidon_bn128_partial_rounds(&mut state);
+        self.poseidon_bn128_full_rounds(&mut state, false);
+        state
+    }
+
+    fn poseidon_bn128_constant(&mut self, x: &Fr) -> NonNativeTarget<Bn254Scalar> {
+        self.api.constant_nonnative(fr_to_bn254_scalar(x))
+    }
+
+    fn poseidon_bn128_ark(&mut self, state: &mut PoseidonBn128StateTarget, it: usize) {
+        for (i, x) in state.iter_mut().enumerate() {
+            let c = self.poseidon_bn128_constant(&C_CONSTANTS[it + i]);
+            *x = self.api.add_nonnative(x, &c);
+        }
+    }
+
+    fn poseidon_bn128_exp5(
+        &mut self,
+        x: &NonNativeTarget<Bn254Scalar>,
+    ) -> NonNativeTarget<Bn254Scalar> {
+        let x2 = self.api.mul_nonnative(x, x);
+        let x4 = self.api.mul_nonnative(&x2, &x2);
+        self.api.mul_nonnative(&x4, x)
+    }
+
+    fn poseidon_bn128_full_rounds(&mut self, state: &mut PoseidonBn128StateTarget, first: bool) {
+        for i in 0..FULL_ROUNDS / 2 - 1 {
+            for x in state.iter_mut() {
+                *x = self.poseidon_bn128_exp5(x);
+            }
+            if first {
+                self.poseidon_bn128_ark(state, (i + 1) * WIDTH);
+            } else {
+                self.poseidon_bn128_ark(
+                    state,
+                    (FULL_ROUNDS / 2 + 1) * WIDTH + PARTIAL_ROUNDS + i * WIDTH,
+                );
+            }
+            self.poseidon_bn128_mix(state, &M_MATRIX);
+        }
+
+        for x in state.iter_mut() {
+            *x = self.poseidon_bn128_exp5(x);
+        }
+        if first {
+            self.poseidon_bn128_ark(state, (FULL_ROUNDS / 2) * WIDTH);
+            self.poseidon_bn128_mix(state, &P_MATRIX);
+        } else {
+            self.poseidon_bn128_mix(state, &M_MATRIX);
+        }
+    }
+
+    fn poseidon_bn128_partial_rounds(&mut self, state: &mut PoseidonBn128StateTarget) {
+        for i in 0..PARTIAL_ROUNDS {
+            let x0 = self.poseidon_bn128_exp5(&state[0]);
+            let c = self.poseidon_bn128_constant(&C_CONSTANTS[(FULL_ROUNDS / 2 + 1) * WIDTH + i]);
+            state[0] = self.api.add_nonnative(&x0, &c);
+
+            let mut products = Vec::with_capacity(WIDTH);
+            for (j, x) in state.iter().enumerate() {
+                let s = self.poseidon_bn128_constant(&S_CONSTANTS[(WIDTH * 2 - 1) * i + j]);
+                products.push(self.api.mul_nonnative(&s, x));
+            }
+            let new_state0 = self.api.add_many_nonnative(&products);
+
+            let x0 = state[0].clone();
+            for (k, x) in state.iter_mut().enumerate().skip(1) {
+                let s =
+                    self.poseidon_bn128_constant(&S_CONSTANTS[(WIDTH * 2 - 1) * i + WIDTH + k - 1]);
+                let product = self.api.mul_nonnative(&x0, &s);
+                *x = self.api.add_nonnative(x, &product);
+            }
+
+            state[0] = new_state0;
+        }
+    }
+
+    fn poseidon_bn128_mix(
+        &mut self,
+        state: &mut PoseidonBn128StateTarget,
+        constant_matrix: &[Vec<Fr>],
+    ) {
+        let mixed = (0..WIDTH)
+            .map(|i| {
+                let products = (0..WIDTH)
+                    .map(|j| {
+                        let m = self.poseidon_bn128_constant(&constant_matrix[j][i]);
+                        self.api.mul_nonnative(&m, &state[j])
+                    })
+                    .collect::<Vec<_>>();
+                self.api.add_many_nonnative(&products)
+            })
+            .collect::<Vec<_>>();
+        state.clone_from_slice(&mixed);
+    }
+
+    /// Packs up to 3 Goldilocks elements into a BN254 scalar, in little-endian order.
+    ///
+    /// Each element is constrained to be canonical, so that the packing is injective.
+    fn poseidon_bn128_pack(&mut self, elements: &[Variable]) -> NonNativeTarget<Bn254Scalar> {
+        assert!(elements.len() <= GOLDILOCKS_ELEMENTS);
+        let zero = self.api.zero();
+        let max_high = self.api.constant(L::Field::from_canonical_u32(u32::MAX));
+        let mut limbs = Vec::new();
+        for element in elements {
+            let (low, high) = self.api.split_low_high(element.0, 32, 64);
+            // The Goldilocks order is 2^64 - 2^32 + 1, so the high limb can only be 2^32 - 1 if the
+            // low limb is zero.
+            let high_is_max = self.api.is_equal(high, max_high);
+            let overflow = self.api.mul(high_is_max.target, low);
+            self.api.connect(overflow, zero);
+            limbs.push(U32Target::from_target_unsafe(low));
+            limbs.push(U32Target::from_target_unsafe(high));
+        }
+        limbs.resize(
+            ceil_div_usize(Bn254Scalar::BITS, 32),
+            U32Target::from_target_unsafe(zero),
+        );
+        self.api.biguint_to_nonnative(&BigUintTarget { limbs })
+    }
+
+    /// Hashes Goldilocks elements like `PoseidonBN128Hash::hash_no_pad`: every 3 elements are
+    /// packed into a BN254 scalar and absorbed 3 scalars at a time.
+    pub fn poseidon_bn128_hash_no_pad(
+        &mut self,
+        inputs: &[Variable],
+    ) -> NonNativeTarget<Bn254Scalar> {
+        let zero = self.api.zero_nonnative();
+        let mut state: PoseidonBn128StateTarget = core::array::from_fn(|_| zero.clone());
+        for rate_chunk in inputs.chunks(RATE * GOLDILOCKS_ELEMENTS) {
+            for (j, bn128_chunk) in rate_chunk.chunks(GOLDILOCKS_ELEMENTS).enumerate() {
+                state[j + 1] = self.poseidon_bn128_pack(bn128_chunk);
+            }
+            state = self.poseidon_bn128_permute(&state);
+        }
+        state[0].clone()
+    }
+
+    /// Combines two digests like `PoseidonBN128Hash::two_to_one`.
+    pub fn poseidon_bn128_two_to_one(
+        &mut self,
+        left: &NonNativeTarget<Bn254Scalar>,
+        right: &NonNativeTarget<Bn254Scalar>,
+    ) -> NonNativeTarget<Bn254Scalar> {
+        let zero = self.api.zero_nonnative();
+        let state = [zero.clone(), zero, left.clone(), right.clone()];
+        self.poseidon_bn128_permute(&state)[0].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use plonky2::field::types::{Field, Sample};
+    use plonky2::plonk::config::{GenericHashOut, Hasher};
+    use plonky2::util::ceil_div_usize;
+
+    use crate::backend::wrapper::plonky2_config::PoseidonBN128Hash;
+    use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+    use crate::frontend::num::nonnative::nonnative::NonNativeTarget;
+    use crate::prelude::{ArrayVariable, DefaultBuilder, Field, GoldilocksField, Variable};
+    use crate::utils;
+
+    type F = GoldilocksField;
+
+    fn hash_out_to_scalar(bytes: &[u8]) -> Bn254Scalar {
+        Bn254Scalar::from_noncanonical_biguint(BigUint::from_bytes_le(bytes))
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_poseidon_bn128() {
+        utils::setup_logger();
+
+        // Two permutations, the second of which only absorbs part of the rate, and an element
+        // close to the Goldilocks order.
+        let mut inputs = F::rand_vec(11);
+        inputs[4] = F::NEG_ONE;
+        let expected_digest = PoseidonBN128Hash::hash_no_pad(&inputs);
+        let expected_pair_digest =
+            <PoseidonBN128Hash as Hasher<F>>::two_to_one(expected_digest, expected_digest);
+
+        let mut builder = DefaultBuilder::new();
+        let variables = builder.read::<ArrayVariable<Variable, 11>>();
+        let digest = builder.poseidon_bn128_hash_no_pad(&variables.as_vec());
+        builder.write(digest.clone());
+        let pair_digest = builder.poseidon_bn128_two_to_one(&digest, &digest);
+        builder.write(pair_digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<ArrayVariable<Variable, 11>>(inputs);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<NonNativeTarget<Bn254Scalar>>(),
+            hash_out_to_scalar(&expected_digest.to_bytes())
+        );
+        assert_eq!(
+            output.read::<NonNativeTarget<Bn254Scalar>>(),
+            hash_out_to_scalar(&expected_pair_digest.to_bytes())
+        );
+    }
+}
@@ -0,0 +1,361 @@
+//! Poseidon over the BN254 scalar field, using the parameters of circomlib.
+//!
+//! The round constants and MDS matrices are derived with the Grain LFSR exactly as in the
+//! reference implementation (`generate_parameters_grain.sage`) that circomlib's constants were
+//! generated with, so digests match `poseidon` from circomlib/circomlibjs (Semaphore, Tornado,
+//! etc.) for any number of inputs between 1 and 16.
+
+use num::{BigUint, One, Zero};
+use plonky2::field::types::Field;
+
+use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+use crate::frontend::num::nonnative::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use crate::prelude::{BoolVariable, CircuitBuilder, PlonkParameters};
+
+/// The number of full rounds, which is the same for every width.
+pub const POSEIDON_BN254_FULL_ROUNDS: usize = 8;
+
+/// The number of partial rounds for widths 2 through 17.
+const POSEIDON_BN254_PARTIAL_ROUNDS: [usize; 16] = [
+    56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+];
+
+/// The bit size of the BN254 scalar field.
+const FIELD_BITS: usize = 254;
+
+/// The Grain LFSR used to derive the Poseidon and Poseidon2 parameters.
+pub(super) struct GrainLfsr {
+    state: [bool; 80],
+}
+
+impl GrainLfsr {
+    pub(super) fn new(
+        field_bits: usize,
+        width: usize,
+        nb_full_rounds: usize,
+        nb_partial_rounds: usize,
+    ) -> Self {
+        // The initial state encodes the field type (prime field), the s-box (x^5), the field size,
+        // the width and the number of rounds, followed by 30 set bits.
+        let mut bits = Vec::with_capacity(80);
+        let mut push = |value: usize, nb_bits: usize| {
+            for i in (0..nb_bits).rev() {
+                bits.push((value >> i) & 1 == 1);
+            }
+        };
+        push(1, 2);
+        push(0, 4);
+        push(field_bits, 12);
+        push(width, 12);
+        push(nb_full_rounds, 10);
+        push(nb_partial_rounds, 10);
+        push((1 << 30) - 1, 30);
+
+        let mut lfsr = Self {
+            state: bits.try_into().unwrap(),
+        };
+        for _ in 0..160 {
+            lfsr.next_bit();
+        }
+        lfsr
+    }
+
+    fn next_bit(&mut self) -> bool {
+        let s = &self.state;
+        let bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+        self.state.rotate_left(1);
+        self.state[79] = bit;
+        bit
+    }
+
+    /// Outputs bits in pairs, keeping the second bit only if the first one is set.
+    fn next_filtered_bit(&mut self) -> bool {
+        loop {
+            let keep = self.next_bit();
+            let bit = self.next_bit();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    pub(super) fn next_biguint(&mut self, nb_bits: usize) -> BigUint {
+        let mut result = BigUint::zero();
+        for _ in 0..nb_bits {
+            result <<= 1;
+            if self.next_filtered_bit() {
+                result += BigUint::one();
+            }
+        }
+        result
+    }
+}
+
+/// The parameters of Poseidon over the BN254 scalar field for a given state width.
+#[derive(Debug, Clone)]
+pub struct PoseidonBn254Params {
+    pub width: usize,
+    pub nb_full_rounds: usize,
+    pub nb_partial_rounds: usize,
+    /// The round constants, `width` of them per round.
+    pub round_constants: Vec<Bn254Scalar>,
+    pub mds: Vec<Vec<Bn254Scalar>>,
+}
+
+impl PoseidonBn254Params {
+    /// Derives the circomlib parameters for a state of `width` elements (i.e. `width - 1` inputs).
+    pub fn new(width: usize) -> Self {
+        assert!(
+            (2..=17).contains(&width),
+            "poseidon bn254 supports widths between 2 and 17"
+        );
+        let nb_full_rounds = POSEIDON_BN254_FULL_ROUNDS;
+        let nb_partial_rounds = POSEIDON_BN254_PARTIAL_ROUNDS[width - 2];
+        let mut lfsr = GrainLfsr::new(FIELD_BITS, width, nb_full_rounds, nb_partial_rounds);
+
+        let order = Bn254Scalar::order();
+        let nb_round_constants = (nb_full_rounds + nb_partial_rounds) * width;
+        let mut round_constants = Vec::with_capacity(nb_round_constants);
+        while round_constants.len() < nb_round_constants {
+            let value = lfsr.next_biguint(FIELD_BITS);
+            if value < order {
+                round_constants.push(Bn254Scalar::from_noncanonical_biguint(value));
+            }
+        }
+
+        // The MDS matrix is the Cauchy matrix 1 / (x_i + y_j).
+        let xs_and_ys = (0..2 * width)
+            .map(|_| Bn254Scalar::from_noncanonical_biguint(lfsr.next_biguint(FIELD_BITS) % &order))
+            .collect::<Vec<_>>();
+        let (xs, ys) = xs_and_ys.split_at(width);
+        let mds = xs
+            .iter()
+            .map(|x| ys.iter().map(|y| (*x + *y).inverse()).collect())
+            .collect();
+
+        Self {
+            width,
+            nb_full_rounds,
+            nb_partial_rounds,
+            round_constants,
+            mds,
+        }
+    }
+
+    fn is_full_round(&self, round: usize) -> bool {
+        let half = self.nb_full_rounds / 2;
+        round < half || round >= half + self.nb_partial_rounds
+    }
+
+    /// Applies the Poseidon permutation to `state`.
+    pub fn permute(&self, state: &mut [Bn254Scalar]) {
+        assert_eq!(state.len(), self.width);
+        let sbox = |x: Bn254Scalar| x.exp_u64(5);
+        for round in 0..self.nb_full_rounds + self.nb_partial_rounds {
+            for (i, x) in state.iter_mut().enumerate() {
+                *x += self.round_constants[round * self.width + i];
+            }
+            if self.is_full_round(round) {
+                state.iter_mut().for_each(|x| *x = sbox(*x));
+            } else {
+                state[0] = sbox(state[0]);
+            }
+            let mixed = self
+                .mds
+                .iter()
+                .map(|row| row.iter().zip(state.iter()).map(|(m, x)| *m * *x).sum())
+                .collect::<Vec<Bn254Scalar>>();
+            state.copy_from_slice(&mixed);
+        }
+    }
+
+    /// Computes the circomlib Poseidon hash of `inputs`, where `inputs.len() == width - 1`.
+    pub fn hash(&self, inputs: &[Bn254Scalar]) -> Bn254Scalar {
+        assert_eq!(inputs.len() + 1, self.width);
+        let mut state = vec![Bn254Scalar::ZERO];
+        state.extend_from_slice(inputs);
+        self.permute(&mut state);
+        state[0]
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Applies the Poseidon-BN254 permutation to `state` using nonnative arithmetic.
+    pub fn poseidon_bn254_permute(
+        &mut self,
+        params: &PoseidonBn254Params,
+        state: &[NonNativeTarget<Bn254Scalar>],
+    ) -> Vec<NonNativeTarget<Bn254Scalar>> {
+        assert_eq!(state.len(), params.width);
+        let mds = params
+            .mds
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|m| self.api.constant_nonnative(*m))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut state = state.to_vec();
+        for round in 0..params.nb_full_rounds + params.nb_partial_rounds {
+            for (i, x) in state.iter_mut().enumerate() {
+                let c = self
+                    .api
+                    .constant_nonnative(params.round_constants[round * params.width + i]);
+                *x = self.api.add_nonnative(x, &c);
+            }
+            let nb_sboxes = if params.is_full_round(round) {
+                params.width
+            } else {
+                1
+            };
+            for x in state.iter_mut().take(nb_sboxes) {
+                let x2 = self.api.mul_nonnative(x, x);
+                let x4 = self.api.mul_nonnative(&x2, &x2);
+                *x = self.api.mul_nonnative(&x4, x);
+            }
+            state = mds
+                .iter()
+                .map(|row| {
+                    let products = row
+                        .iter()
+                        .zip(state.iter())
+                        .map(|(m, x)| self.api.mul_nonnative(m, x))
+                        .collect::<Vec<_>>();
+                    self.api.add_many_nonnative(&products)
+                })
+                .collect();
+        }
+        state
+    }
+
+    /// Computes the circomlib Poseidon hash of between 1 and 16 BN254 scalar field elements.
+    pub fn poseidon_bn254(
+        &mut self,
+        inputs: &[NonNativeTarget<Bn254Scalar>],
+    ) -> NonNativeTarget<Bn254Scalar> {
+        let params = PoseidonBn254Params::new(inputs.len() + 1);
+        let mut state = vec![self.api.zero_nonnative()];
+        state.extend_from_slice(inputs);
+        self.poseidon_bn254_permute(&params, &state)[0].clone()
+    }
+
+    /// Computes the root of a binary Poseidon-BN254 Merkle tree (as used by Semaphore) from a
+    /// leaf, its siblings from the bottom up and the path bits, where a set bit means that the
+    /// current node is the right child.
+    pub fn poseidon_bn254_merkle_root(
+        &mut self,
+        leaf: &NonNativeTarget<Bn254Scalar>,
+        siblings: &[NonNativeTarget<Bn254Scalar>],
+        path_indices: &[BoolVariable],
+    ) -> NonNativeTarget<Bn254Scalar> {
+        assert_eq!(siblings.len(), path_indices.len());
+        let mut node = leaf.clone();
+        for (sibling, is_right) in siblings.iter().zip(path_indices.iter()) {
+            let left = self.api.if_nonnative((*is_right).into(), sibling, &node);
+            let right = self.api.if_nonnative((*is_right).into(), &node, sibling);
+            node = self.poseidon_bn254(&[left, right]);
+        }
+        node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use plonky2::field::types::Field;
+
+    use super::PoseidonBn254Params;
+    use crate::frontend::ecc::babyjubjub::field::bn254_scalar::Bn254Scalar;
+    use crate::frontend::num::nonnative::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+    use crate::prelude::DefaultBuilder;
+    use crate::utils;
+
+    fn fr(value: &str) -> Bn254Scalar {
+        Bn254Scalar::from_noncanonical_biguint(BigUint::parse_bytes(value.as_bytes(), 10).unwrap())
+    }
+
+    #[test]
+    fn test_poseidon_bn254_params() {
+        // The first round constant and MDS entry of circomlib for width 3.
+        let params = PoseidonBn254Params::new(3);
+        assert_eq!(
+            params.round_constants[0],
+            fr("6745197990210204598374042828761989596302876299545964402857411729872131034734")
+        );
+        assert_eq!(
+            params.mds[0][0],
+            fr("7511745149465107256748700652201246547602992235352608707588321460060273774987")
+        );
+    }
+
+    #[test]
+    fn test_poseidon_bn254_native() {
+        // Test vectors from circomlibjs.
+        let hash = PoseidonBn254Params::new(3).hash(&[fr("1"), fr("2")]);
+        assert_eq!(
+            hash,
+            fr("7853200120776062878684798364095072458815029376092732009249414926327459813530")
+        );
+
+        let hash = PoseidonBn254Params::new(5).hash(&[fr("1"), fr("2"), fr("3"), fr("4")]);
+        assert_eq!(
+            hash,
+            fr("18821383157269793795438455681495246036402687001665670618754263018637548127333")
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_poseidon_bn254() {
+        utils::setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        let inputs = [fr("1"), fr("2")]
+            .iter()
+            .map(|x| builder.api.constant_nonnative(*x))
+            .collect::<Vec<_>>();
+        let hash = builder.poseidon_bn254(&inputs);
+        let expected = builder.api.constant_nonnative(fr(
+            "7853200120776062878684798364095072458815029376092732009249414926327459813530",
+        ));
+        builder.api.connect_nonnative(&hash, &expected);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_poseidon_bn254_merkle_root() {
+        utils::setup_logger();
+        let mut builder = DefaultBuilder::new();
+
+        let params = PoseidonBn254Params::new(3);
+        let leaf = fr("42");
+        let siblings = [fr("7"), fr("1337")];
+        let level_1 = params.hash(&[siblings[0], leaf]);
+        let root = params.hash(&[level_1, siblings[1]]);
+
+        let leaf = builder.read::<NonNativeTarget<Bn254Scalar>>();
+        let siblings = (0..2)
+            .map(|_| builder.read::<NonNativeTarget<Bn254Scalar>>())
+            .collect::<Vec<_>>();
+        let path_indices = [builder._true(), builder._false()];
+        let computed_root = builder.poseidon_bn254_merkle_root(&leaf, &siblings, &path_indices);
+        builder.write(computed_root);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<NonNativeTarget<Bn254Scalar>>(fr("42"));
+        input.write::<NonNativeTarget<Bn254Scalar>>(fr("7"));
+        input.write::<NonNativeTarget<Bn254Scalar>>(fr("1337"));
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<NonNativeTarget<Bn254Scalar>>(), root);
+    }
+}
@@ -0,0 +1,283 @@
+//! An implementation of the RIPEMD-160 hash function in a plonky2 circuit, together with the
+//! Bitcoin `hash160` and `hash256` helpers built on top of [`CircuitBuilder::curta_sha256`].
+//!
+//! Only inputs whose length is known at circuit build time are supported, so the padding is
+//! computed out of circuit.
+
+use itertools::Itertools;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::common::{and_arr, not_arr, or_arr, xor2_arr, xor3_arr, WordVariable};
+use crate::frontend::vars::{ByteVariable, Bytes32Variable, BytesVariable, CircuitVariable};
+use crate::prelude::CircuitBuilder;
+
+const BLOCK_SIZE: usize = 64;
+
+const RIPEMD160_IV: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+/// The order in which the message words are read in each round of the left line.
+const R: [[usize; 16]; 5] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8],
+    [3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12],
+    [1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2],
+    [4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13],
+];
+
+/// The order in which the message words are read in each round of the right line.
+const R_PRIME: [[usize; 16]; 5] = [
+    [5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12],
+    [6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2],
+    [15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13],
+    [8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14],
+    [12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11],
+];
+
+/// The left rotation amounts of each step of the left line.
+const S: [[usize; 16]; 5] = [
+    [11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8],
+    [7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12],
+    [11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5],
+    [11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12],
+    [9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6],
+];
+
+/// The left rotation amounts of each step of the right line.
+const S_PRIME: [[usize; 16]; 5] = [
+    [8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6],
+    [9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11],
+    [9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5],
+    [15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8],
+    [8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11],
+];
+
+const K: [u32; 5] = [0x00000000, 0x5A827999, 0x6ED9EBA1, 0x8F1BBCDC, 0xA953FD4E];
+const K_PRIME: [u32; 5] = [0x50A28BE6, 0x5C4DD124, 0x6D703EF3, 0x7A6D76E9, 0x00000000];
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Executes a RIPEMD-160 hash on the given input.
+    pub fn ripemd160(&mut self, input: &[ByteVariable]) -> BytesVariable<20> {
+        let padded_input = self.ripemd160_pad(input);
+
+        let mut h = RIPEMD160_IV.map(|x| self.constant_word(x));
+        for block in padded_input.chunks_exact(BLOCK_SIZE) {
+            let block = self.le_words::<16>(block);
+            h = self.ripemd160_compress(&h, &block);
+        }
+
+        let digest = self.words_to_le_bytes(&h);
+        BytesVariable(digest.try_into().unwrap())
+    }
+
+    /// Computes the Bitcoin `hash160` of the input, i.e. `ripemd160(sha256(input))`.
+    pub fn hash160(&mut self, input: &[ByteVariable]) -> BytesVariable<20> {
+        let sha_digest = self.curta_sha256(input);
+        self.ripemd160(&sha_digest.as_bytes())
+    }
+
+    /// Computes the Bitcoin `hash256` of the input, i.e. `sha256(sha256(input))`.
+    pub fn hash256(&mut self, input: &[ByteVariable]) -> Bytes32Variable {
+        let sha_digest = self.curta_sha256(input);
+        self.curta_sha256(&sha_digest.as_bytes())
+    }
+
+    /// Pads the input with a one bit, zeros and the little-endian 64-bit length in bits, up to a
+    /// multiple of the block size.
+    fn ripemd160_pad(&mut self, input: &[ByteVariable]) -> Vec<ByteVariable> {
+        let bit_length = (input.len() as u64) * 8;
+        let num_zeros = (BLOCK_SIZE - (input.len() + 9) % BLOCK_SIZE) % BLOCK_SIZE;
+
+        let mut padded_input = input.to_vec();
+        padded_input.push(self.constant::<ByteVariable>(0x80));
+        padded_input.extend((0..num_zeros).map(|_| self.constant::<ByteVariable>(0)));
+        padded_input.extend(
+            bit_length
+                .to_le_bytes()
+                .iter()
+                .map(|byte| self.constant::<ByteVariable>(*byte))
+                .collect_vec(),
+        );
+        padded_input
+    }
+
+    /// The RIPEMD-160 compression function, which runs the left and right lines in parallel and
+    /// combines them with the chaining value.
+    fn ripemd160_compress(
+        &mut self,
+        h: &[WordVariable; 5],
+        block: &[WordVariable; 16],
+    ) -> [WordVariable; 5] {
+        let mut left = *h;
+        let mut right = *h;
+        for (j, (k, k_prime)) in K.iter().zip(K_PRIME.iter()).enumerate() {
+            let k = self.constant_word(*k);
+            let k_prime = self.constant_word(*k_prime);
+            for i in 0..16 {
+                self.ripemd160_step(&mut left, j, block[R[j][i]], k, S[j][i]);
+                self.ripemd160_step(
+                    &mut right,
+                    4 - j,
+                    block[R_PRIME[j][i]],
+                    k_prime,
+                    S_PRIME[j][i],
+                );
+            }
+        }
+
+        let [a, b, c, d, e] = left;
+        let [a_prime, b_prime, c_prime, d_prime, e_prime] = right;
+        let h0_plus_b = self.add_arr(h[0], b);
+        let h1_plus_c = self.add_arr(h[1], c);
+        let h2_plus_d = self.add_arr(h[2], d);
+        let h3_plus_e = self.add_arr(h[3], e);
+        let h4_plus_a = self.add_arr(h[4], a);
+        [
+            self.add_arr(h1_plus_c, d_prime),
+            self.add_arr(h2_plus_d, e_prime),
+            self.add_arr(h3_plus_e, a_prime),
+            self.add_arr(h4_plus_a, b_prime),
+            self.add_arr(h0_plus_b, c_prime),
+        ]
+    }
+
+    /// A single step of either line, using the boolean function `f_index`.
+    fn ripemd160_step(
+        &mut self,
+        state: &mut [WordVariable; 5],
+        f_index: usize,
+        x: WordVariable,
+        k: WordVariable,
+        shift: usize,
+    ) {
+        let [a, b, c, d, e] = *state;
+        let f = self.ripemd160_f(f_index, b, c, d);
+        let a_plus_f = self.add_arr(a, f);
+        let x_plus_k = self.add_arr(x, k);
+        let sum = self.add_arr(a_plus_f, x_plus_k);
+        let rotated = self._right_rotate(sum, 32 - shift);
+        let t = self.add_arr(rotated, e);
+        let c_rotated = self._right_rotate(c, 32 - 10);
+        *state = [e, t, b, c_rotated, d];
+    }
+
+    /// The five boolean functions of RIPEMD-160.
+    fn ripemd160_f(
+        &mut self,
+        index: usize,
+        x: WordVariable,
+        y: WordVariable,
+        z: WordVariable,
+    ) -> WordVariable {
+        match index {
+            0 => xor3_arr(x, y, z, self),
+            1 => {
+                let x_and_y = and_arr(x, y, self);
+                let not_x = not_arr(x, self);
+                let not_x_and_z = and_arr(not_x, z, self);
+                or_arr(x_and_y, not_x_and_z, self)
+            }
+            2 => {
+                let not_y = not_arr(y, self);
+                let x_or_not_y = or_arr(x, not_y, self);
+                xor2_arr(x_or_not_y, z, self)
+            }
+            3 => {
+                let x_and_z = and_arr(x, z, self);
+                let not_z = not_arr(z, self);
+                let y_and_not_z = and_arr(y, not_z, self);
+                or_arr(x_and_z, y_and_not_z, self)
+            }
+            4 => {
+                let not_z = not_arr(z, self);
+                let y_or_not_z = or_arr(y, not_z, self);
+                xor2_arr(x, y_or_not_z, self)
+            }
+            _ => panic!("invalid RIPEMD-160 function index {}", index),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use hex::decode;
+
+    use crate::backend::circuit::DefaultParameters;
+    use crate::frontend::vars::Bytes32Variable;
+    use crate::prelude::{BytesVariable, CircuitBuilder};
+    use crate::utils::bytes32;
+
+    type L = DefaultParameters;
+    const D: usize = 2;
+
+    #[test]
+    fn test_ripemd160() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let empty_digest = builder.ripemd160(&[]);
+        builder.write(empty_digest);
+        let abc = builder.constant::<BytesVariable<3>>(*b"abc");
+        let abc_digest = builder.ripemd160(&abc.0);
+        builder.write(abc_digest);
+        // Two blocks, since the padding does not fit after 100 bytes.
+        let msg = builder.constant::<BytesVariable<100>>(core::array::from_fn(|i| i as u8));
+        let msg_digest = builder.ripemd160(&msg.0);
+        builder.write(msg_digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let expected_digests = [
+            "9c1185a5c5e9fc54612808977ee8f548b2258d31",
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc",
+            "8ae5d2e6b1f3a514257f2469b637454931844aeb",
+        ];
+        for expected_digest in expected_digests {
+            let digest = output.read::<BytesVariable<20>>();
+            assert_eq!(digest.to_vec(), decode(expected_digest).unwrap());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_hash160_and_hash256() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        // The compressed public key of the secp256k1 generator.
+        let pubkey = builder.read::<BytesVariable<33>>();
+        let pubkey_hash = builder.hash160(&pubkey.0);
+        builder.write(pubkey_hash);
+        let hello = builder.constant::<BytesVariable<5>>(*b"hello");
+        let hello_hash = builder.hash256(&hello.0);
+        builder.write(hello_hash);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BytesVariable<33>>(
+            decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let pubkey_hash = output.read::<BytesVariable<20>>();
+        assert_eq!(
+            pubkey_hash.to_vec(),
+            decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap()
+        );
+        let hello_hash = output.read::<Bytes32Variable>();
+        assert_eq!(
+            hello_hash,
+            bytes32!("0x9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50")
+        );
+    }
+}
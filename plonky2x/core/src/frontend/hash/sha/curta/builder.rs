@@ -2,7 +2,7 @@ use curta::chip::Chip;
 use curta::plonky2::Plonky2Air;
 
 use super::accelerator::SHAAccelerator;
-use super::digest_hint::SHADigestHint;
+use super::digest_hint::{SHAChainingValueHint, SHADigestHint};
 use super::proof_hint::SHAProofHint;
 use super::request::SHARequest;
 use super::SHA;
@@ -21,22 +21,28 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             .iter()
             .zip(accelerator.sha_responses.iter())
         {
-            let digest_hint = SHADigestHint::<S, CYCLE_LEN>::new();
             let mut input_stream = VariableStream::new();
 
-            match &request {
+            let output_stream = match &request {
                 SHARequest::Fixed(msg) => {
                     let len = self.constant::<Variable>(L::Field::from_canonical_usize(msg.len()));
                     input_stream.write(&len);
                     input_stream.write_slice(msg);
+                    self.hint(input_stream, SHADigestHint::<S, CYCLE_LEN>::new())
                 }
                 SHARequest::Variable(msg, len, _) => {
                     input_stream.write(len);
                     input_stream.write_slice(msg);
+                    self.hint(input_stream, SHADigestHint::<S, CYCLE_LEN>::new())
                 }
-            }
+                SHARequest::Blocks(msg) => {
+                    let len = self.constant::<Variable>(L::Field::from_canonical_usize(msg.len()));
+                    input_stream.write(&len);
+                    input_stream.write_slice(msg);
+                    self.hint(input_stream, SHAChainingValueHint::<S, CYCLE_LEN>::new())
+                }
+            };
 
-            let output_stream = self.hint(input_stream, digest_hint);
             let digest = output_stream.read::<[S::IntVariable; 8]>(self);
             self.assert_is_equal(digest, *response);
         }
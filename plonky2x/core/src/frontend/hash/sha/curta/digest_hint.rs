@@ -45,3 +45,43 @@ impl<S, const CYCLE_LEN: usize> Default for SHADigestHint<S, CYCLE_LEN> {
         Self::new()
     }
 }
+
+/// Computes the chaining value after whole blocks of a message, without padding.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SHAChainingValueHint<S, const CYCLE_LEN: usize> {
+    _marker: PhantomData<S>,
+}
+
+impl<L: PlonkParameters<D>, S: SHA<L, D, CYCLE_LEN>, const D: usize, const CYCLE_LEN: usize>
+    Hint<L, D> for SHAChainingValueHint<S, CYCLE_LEN>
+{
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let length = input_stream.read_value::<Variable>().as_canonical_u64() as usize;
+        let message = input_stream.read_vec::<ByteVariable>(length);
+        let chunks = S::pad(&message);
+
+        // The padded message only differs from the blocks in the trailing padding chunk, so the
+        // blocks are the leading chunks.
+        let num_words = chunks.len() - 16;
+        let mut current_state = S::INITIAL_HASH;
+        for chunk in chunks[..num_words].chunks_exact(16) {
+            let pre_processed = S::pre_process(chunk);
+            current_state = S::process(current_state, &pre_processed);
+        }
+        output_stream.write_value::<[S::IntVariable; 8]>(current_state)
+    }
+}
+
+impl<S, const CYCLE_LEN: usize> SHAChainingValueHint<S, CYCLE_LEN> {
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, const CYCLE_LEN: usize> Default for SHAChainingValueHint<S, CYCLE_LEN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
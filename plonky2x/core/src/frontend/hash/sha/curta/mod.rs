@@ -48,6 +48,12 @@ pub trait SHA<L: PlonkParameters<D>, const D: usize, const CYCLE_LEN: usize>:
         last_chunk: U32Variable,
     ) -> Vec<Self::IntVariable>;
 
+    /// Converts whole blocks of a message to integers, without padding.
+    fn blocks_circuit(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+    ) -> Vec<Self::IntVariable>;
+
     fn value_to_variable(
         builder: &mut CircuitBuilder<L, D>,
         value: <Self::IntRegister as Register>::Value<Variable>,
@@ -81,6 +87,12 @@ pub trait SHA<L: PlonkParameters<D>, const D: usize, const CYCLE_LEN: usize>:
                         Self::pad_circuit_variable_length(builder, input, *length, *last_chunk),
                         *last_chunk,
                     ),
+                    SHARequest::Blocks(input) => {
+                        let chunks = Self::blocks_circuit(builder, input);
+                        let num_chunks =
+                            builder.constant((chunks.len() / 16 - 1).try_into().unwrap());
+                        (chunks, num_chunks)
+                    }
                 };
                 let total_number_of_chunks = padded_chunks.len() / 16;
                 let current_chunk_index_variable = builder
@@ -6,6 +6,7 @@ use crate::prelude::*;
 pub enum SHARequestType {
     Fixed,
     Variable,
+    Blocks,
 }
 
 /// A SHA request.
@@ -15,6 +16,9 @@ pub enum SHARequest {
     Fixed(Vec<ByteVariable>),
     /// A message of variable length, with the actual legnth given by the Variable.
     Variable(Vec<ByteVariable>, U32Variable, U32Variable),
+    /// Whole blocks of a message without padding, whose response is the chaining value after the
+    /// last block.
+    Blocks(Vec<ByteVariable>),
 }
 
 impl SHARequest {
@@ -22,6 +26,7 @@ impl SHARequest {
         match self {
             SHARequest::Fixed(_) => SHARequestType::Fixed,
             SHARequest::Variable(_, _, _) => SHARequestType::Variable,
+            SHARequest::Blocks(_) => SHARequestType::Blocks,
         }
     }
 }
@@ -2,7 +2,9 @@ use core::marker::PhantomData;
 
 use curta::chip::uint::operations::instruction::UintInstruction;
 use curta::chip::AirParameters;
+use curta::machine::hash::sha::algorithm::SHAPure;
 use curta::machine::hash::sha::sha256::SHA256;
+use ethers::types::H256;
 use serde::{Deserialize, Serialize};
 
 use crate::frontend::hash::sha::curta::accelerator::SHAAccelerator;
@@ -59,6 +61,16 @@ impl<L: PlonkParameters<D>, const D: usize> SHA<L, D, 64> for SHA256 {
             .collect()
     }
 
+    fn blocks_circuit(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+    ) -> Vec<Self::IntVariable> {
+        input
+            .chunks_exact(4)
+            .map(|bytes| U32Variable::decode(builder, bytes))
+            .collect()
+    }
+
     fn value_to_variable(
         builder: &mut CircuitBuilder<L, D>,
         value: <Self::IntRegister as curta::chip::register::Register>::Value<Variable>,
@@ -142,6 +154,43 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         digest
     }
 
+    /// Computes the SHA-256 chaining value after the given whole 64-byte blocks of a message,
+    /// encoded like a digest. No padding is applied, so this is the state from which the hash of
+    /// a longer message continues.
+    pub fn curta_sha256_chaining_value(&mut self, blocks: &[ByteVariable]) -> Bytes32Variable {
+        assert_eq!(
+            blocks.len() % 64,
+            0,
+            "blocks length should be a multiple of 64"
+        );
+        if blocks.is_empty() {
+            let initial_hash = SHA256::INITIAL_HASH
+                .iter()
+                .flat_map(|word| word.to_be_bytes())
+                .collect::<Vec<_>>();
+            return self.constant::<Bytes32Variable>(H256::from_slice(&initial_hash));
+        }
+        if self.sha256_accelerator.is_none() {
+            self.sha256_accelerator = Some(SHA256Accelerator {
+                sha_requests: Vec::new(),
+                sha_responses: Vec::new(),
+            });
+        }
+
+        let chaining_value = self.init_unsafe::<Bytes32Variable>();
+        let chaining_value_array = SHA256::digest_to_array(self, chaining_value);
+        let accelerator = self
+            .sha256_accelerator
+            .as_mut()
+            .expect("sha256 accelerator should exist");
+        accelerator
+            .sha_requests
+            .push(SHARequest::Blocks(blocks.to_vec()));
+        accelerator.sha_responses.push(chaining_value_array);
+
+        chaining_value
+    }
+
     pub fn curta_sha256_pair(
         &mut self,
         left: Bytes32Variable,
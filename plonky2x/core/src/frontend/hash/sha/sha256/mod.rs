@@ -1,9 +1,11 @@
 use ::curta::machine::hash::sha::algorithm::SHAPure;
 use ::curta::machine::hash::sha::sha256::SHA256;
 use array_macro::array;
+use ethers::types::H256;
 /// Implementation of sha256
 /// reference: https://github.com/thomdixon/pysha2/blob/master/sha2/sha256.py
 use itertools::Itertools;
+use plonky2::util::ceil_div_usize;
 
 use crate::backend::circuit::PlonkParameters;
 use crate::frontend::builder::CircuitBuilder;
@@ -12,6 +14,7 @@ use crate::frontend::vars::{BoolVariable, ByteVariable, Bytes32Variable, Circuit
 use crate::prelude::U32Variable;
 
 pub mod curta;
+pub mod state;
 
 /// Implements SHA256 implementation for CircuitBuilder
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
@@ -86,6 +89,32 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
                 .collect_vec(),
         );
 
+        self.pad_message_sha256_with_length_bytes(
+            input,
+            input_byte_length,
+            last_chunk,
+            &length_bytes,
+        )
+    }
+
+    /// Pads the first `input_byte_length` bytes of `input` like `pad_message_sha256_variable`, but
+    /// with the given 8 length bytes, so that `input` can be the tail of a longer message.
+    pub(crate) fn pad_message_sha256_with_length_bytes(
+        &mut self,
+        input: &[ByteVariable],
+        input_byte_length: U32Variable,
+        last_chunk: U32Variable,
+        length_bytes: &[ByteVariable],
+    ) -> Vec<ByteVariable> {
+        assert_eq!(length_bytes.len(), 8);
+        let max_number_of_chunks = input.len() / 64;
+        assert_eq!(
+            max_number_of_chunks * 64,
+            input.len(),
+            "input length must be a multiple of 64 bytes"
+        );
+        let zero_byte = self.constant::<ByteVariable>(0x00);
+
         let mut padded_bytes = Vec::new();
 
         let mut message_byte_selector = self.constant::<BoolVariable>(true);
@@ -144,104 +173,107 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     }
 
     fn process_padded_message(&mut self, msg_input: &[ByteVariable]) -> Vec<BoolVariable> {
-        let msg_input_bits = msg_input
-            .iter()
-            .flat_map(|b| b.as_be_bits().to_vec())
-            .collect_vec();
         let mut sha256_hash = self.get_inital_hash();
-        let round_constants = self.get_round_constants();
 
         // Process the input with 512 bit chunks aka 64 byte chunks
-        for chunk_start in (0..msg_input_bits.len()).step_by(512) {
-            let chunk = msg_input_bits[chunk_start..chunk_start + 512].to_vec();
-            let mut u: Vec<BoolVariable> = Vec::new();
-
-            for bit in chunk.iter().take(512) {
-                // 0 .. 16 chunk size * 32 bits7
-                u.push(*bit);
-            }
-            for _ in 512..64 * 32 {
-                // 16 * 8 ... 64 * 8 because of L
-                u.push(self._false());
-            }
-
-            let mut w = self.reshape(u);
-
-            for i in 16..64 {
-                let s0 = xor3_arr(
-                    self._right_rotate(w[i - 15], 7),
-                    self._right_rotate(w[i - 15], 18),
-                    self._shr(w[i - 15], 3),
-                    self,
-                );
-
-                let s1 = xor3_arr(
-                    self._right_rotate(w[i - 2], 17),
-                    self._right_rotate(w[i - 2], 19),
-                    self._shr(w[i - 2], 10),
-                    self,
-                );
+        for chunk in msg_input.chunks_exact(64) {
+            sha256_hash = self.sha256_compress(sha256_hash, chunk);
+        }
 
-                let inter1 = self.add_arr(w[i - 16], s0);
-                let inter2 = self.add_arr(inter1, w[i - 7]);
-                w[i] = self.add_arr(s1, inter2);
-            }
-            let mut a = sha256_hash[0];
-            let mut b = sha256_hash[1];
-            let mut c = sha256_hash[2];
-            let mut d = sha256_hash[3];
-            let mut e = sha256_hash[4];
-            let mut f = sha256_hash[5];
-            let mut g = sha256_hash[6];
-            let mut h = sha256_hash[7];
-
-            for i in 0..64 {
-                let sum1 = xor3_arr(
-                    self._right_rotate(e, 6),
-                    self._right_rotate(e, 11),
-                    self._right_rotate(e, 25),
-                    self,
-                );
-                let ch = xor2_arr(
-                    and_arr(e, f, self),
-                    and_arr(not_arr(e, self), g, self),
-                    self,
-                );
-                let temp1 = self.add_arr(h, sum1);
-                let temp2 = self.add_arr(temp1, ch);
-                let temp3 = self.add_arr(temp2, round_constants[i]);
-                let temp4 = self.add_arr(temp3, w[i]);
-                let final_temp1 = temp4;
-
-                let sum0 = xor3_arr(
-                    self._right_rotate(a, 2),
-                    self._right_rotate(a, 13),
-                    self._right_rotate(a, 22),
-                    self,
-                );
+        sha256_hash.iter().flat_map(|x| x.to_vec()).collect()
+    }
 
-                let maj = xor3_arr(
-                    and_arr(a, b, self),
-                    and_arr(a, c, self),
-                    and_arr(b, c, self),
-                    self,
-                );
-                let final_temp2 = self.add_arr(sum0, maj);
-
-                h = g;
-                g = f;
-                f = e;
-                e = self.add_arr(d, final_temp1);
-                d = c;
-                c = b;
-                b = a;
-                a = self.add_arr(final_temp1, final_temp2);
-            }
+    /// Runs the SHA-256 compression function on a 64 byte chunk.
+    pub(crate) fn sha256_compress(
+        &mut self,
+        sha256_hash: [[BoolVariable; 32]; 8],
+        chunk: &[ByteVariable],
+    ) -> [[BoolVariable; 32]; 8] {
+        let round_constants = self.get_round_constants();
+        let mut u = chunk
+            .iter()
+            .flat_map(|b| b.as_be_bits().to_vec())
+            .collect_vec();
+        assert_eq!(u.len(), 512);
+        for _ in 512..64 * 32 {
+            // 16 * 8 ... 64 * 8 because of L
+            u.push(self._false());
+        }
 
-            sha256_hash = self.zip_add(sha256_hash, [a, b, c, d, e, f, g, h]);
+        let mut w = self.reshape(u);
+
+        for i in 16..64 {
+            let s0 = xor3_arr(
+                self._right_rotate(w[i - 15], 7),
+                self._right_rotate(w[i - 15], 18),
+                self._shr(w[i - 15], 3),
+                self,
+            );
+
+            let s1 = xor3_arr(
+                self._right_rotate(w[i - 2], 17),
+                self._right_rotate(w[i - 2], 19),
+                self._shr(w[i - 2], 10),
+                self,
+            );
+
+            let inter1 = self.add_arr(w[i - 16], s0);
+            let inter2 = self.add_arr(inter1, w[i - 7]);
+            w[i] = self.add_arr(s1, inter2);
+        }
+        let mut a = sha256_hash[0];
+        let mut b = sha256_hash[1];
+        let mut c = sha256_hash[2];
+        let mut d = sha256_hash[3];
+        let mut e = sha256_hash[4];
+        let mut f = sha256_hash[5];
+        let mut g = sha256_hash[6];
+        let mut h = sha256_hash[7];
+
+        for i in 0..64 {
+            let sum1 = xor3_arr(
+                self._right_rotate(e, 6),
+                self._right_rotate(e, 11),
+                self._right_rotate(e, 25),
+                self,
+            );
+            let ch = xor2_arr(
+                and_arr(e, f, self),
+                and_arr(not_arr(e, self), g, self),
+                self,
+            );
+            let temp1 = self.add_arr(h, sum1);
+            let temp2 = self.add_arr(temp1, ch);
+            let temp3 = self.add_arr(temp2, round_constants[i]);
+            let temp4 = self.add_arr(temp3, w[i]);
+            let final_temp1 = temp4;
+
+            let sum0 = xor3_arr(
+                self._right_rotate(a, 2),
+                self._right_rotate(a, 13),
+                self._right_rotate(a, 22),
+                self,
+            );
+
+            let maj = xor3_arr(
+                and_arr(a, b, self),
+                and_arr(a, c, self),
+                and_arr(b, c, self),
+                self,
+            );
+            let final_temp2 = self.add_arr(sum0, maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = self.add_arr(d, final_temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = self.add_arr(final_temp1, final_temp2);
         }
 
-        sha256_hash.iter().flat_map(|x| x.to_vec()).collect()
+        self.zip_add(sha256_hash, [a, b, c, d, e, f, g, h])
     }
 
     pub fn sha256(&mut self, input: &[ByteVariable]) -> Bytes32Variable {
@@ -254,6 +286,53 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         )
     }
 
+    /// Executes a SHA256 hash on the first `length` bytes of `input` with plonky2 gates.
+    ///
+    /// The bytes of `input` past `length` are ignored, and `length` is constrained to be at most
+    /// the length of `input`. Every chunk is compressed and the digest of the chunk holding the end
+    /// of the padding is selected.
+    pub fn sha256_variable(
+        &mut self,
+        input: &[ByteVariable],
+        length: U32Variable,
+    ) -> Bytes32Variable {
+        let max_length = self.constant::<U32Variable>(input.len() as u32);
+        let length_is_valid = self.lte(length, max_length);
+        let true_v = self._true();
+        self.assert_is_equal(length_is_valid, true_v);
+
+        // Leave room for the padding byte and the 8 length bytes after a message of full length.
+        let num_chunks = ceil_div_usize(input.len() + 9, 64);
+        let zero_byte = self.constant::<ByteVariable>(0x00);
+        let mut input = input.to_vec();
+        input.resize(num_chunks * 64, zero_byte);
+
+        // The padding ends in the chunk holding byte `length + 8`, i.e. chunk `(length + 8) / 64`.
+        let length_bytes_len = self.constant::<U32Variable>(8);
+        let padded_length = self.add(length, length_bytes_len);
+        let (last_chunk, _) = self.split_low_bits(padded_length.variable, 6, 32);
+        let last_chunk = U32Variable::from_variables_unsafe(&[last_chunk]);
+
+        let padded = self.pad_message_sha256_variable(&input, length, last_chunk);
+
+        let mut sha256_hash = self.get_inital_hash();
+        let mut digest = self.constant::<Bytes32Variable>(H256::zero());
+        for (i, chunk) in padded.chunks_exact(64).enumerate() {
+            sha256_hash = self.sha256_compress(sha256_hash, chunk);
+
+            let curr_chunk = self.constant::<U32Variable>(i as u32);
+            let is_last_chunk = self.is_equal(curr_chunk, last_chunk);
+            let chunk_digest = Bytes32Variable::from_variables_unsafe(
+                &sha256_hash
+                    .iter()
+                    .flat_map(|x| x.iter().map(|b| b.variable))
+                    .collect_vec(),
+            );
+            digest = self.select(is_last_chunk, chunk_digest, digest);
+        }
+        digest
+    }
+
     pub fn sha256_pair(
         &mut self,
         left: Bytes32Variable,
@@ -265,12 +344,32 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     }
 }
 
+/// Splits a chaining value encoded like a digest into the big-endian bits of its words.
+pub(crate) fn sha256_state_from_bytes(chaining_value: Bytes32Variable) -> [[BoolVariable; 32]; 8] {
+    let bits = chaining_value
+        .as_bytes()
+        .iter()
+        .flat_map(|byte| byte.as_be_bits())
+        .collect_vec();
+    core::array::from_fn(|i| bits[32 * i..32 * (i + 1)].try_into().unwrap())
+}
+
+/// Encodes the words of a chaining value like a digest.
+pub(crate) fn sha256_state_to_bytes(state: &[[BoolVariable; 32]; 8]) -> Bytes32Variable {
+    Bytes32Variable::from_variables_unsafe(
+        &state
+            .iter()
+            .flat_map(|word| word.iter().map(|bit| bit.variable))
+            .collect_vec(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use std::env;
 
     use super::*;
-    use crate::prelude::{ByteVariable, CircuitBuilder, DefaultParameters};
+    use crate::prelude::{ByteVariable, BytesVariable, CircuitBuilder, DefaultParameters};
     use crate::utils::hash::sha256;
 
     type L = DefaultParameters;
@@ -311,10 +410,97 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha256_variable() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        const MAX_LEN: usize = 100;
+        let message = (0..MAX_LEN).map(|i| (i * 7) as u8).collect_vec();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let input = builder.read::<BytesVariable<MAX_LEN>>();
+        let length = builder.read::<U32Variable>();
+        let digest = builder.sha256_variable(&input.0, length);
+        builder.write(digest);
+        let circuit = builder.build();
+
+        // Lengths whose padding ends in the first chunk, at the chunk boundary and in the second.
+        for length in [0, 55, 56, 64, MAX_LEN] {
+            let mut input = circuit.input();
+            input.write::<BytesVariable<MAX_LEN>>(message.clone().try_into().unwrap());
+            input.write::<U32Variable>(length as u32);
+            let (proof, mut output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+
+            let expected_digest = sha256(&message[..length]);
+            assert_eq!(output.read::<Bytes32Variable>(), expected_digest.into());
+        }
+    }
+
     #[test]
     #[should_panic]
     #[cfg_attr(feature = "ci", ignore)]
     fn test_sha256_failure() {
-        todo!();
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        let message = b"plonky2x";
+        let mut wrong_digest = sha256(message);
+        wrong_digest[0] ^= 1;
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let message = builder.constant::<BytesVariable<8>>(*message);
+        let result = builder.sha256(&message.0);
+        let wrong_digest = builder.constant::<Bytes32Variable>(wrong_digest.into());
+        builder.assert_is_equal(result, wrong_digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        circuit.prove(&input);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha256_variable_wrong_length() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        let message = b"plonky2x";
+        let expected_digest = sha256(message);
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let input = builder.constant::<BytesVariable<8>>(*message);
+        let length = builder.read::<U32Variable>();
+        let result = builder.sha256_variable(&input.0, length);
+        let expected_digest = builder.constant::<Bytes32Variable>(expected_digest.into());
+        builder.assert_is_equal(result, expected_digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        // One byte short of the message the digest commits to.
+        input.write::<U32Variable>(7);
+        circuit.prove(&input);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha256_variable_length_exceeds_input() {
+        env::set_var("RUST_LOG", "debug");
+        env_logger::try_init().unwrap_or_default();
+
+        let mut builder = CircuitBuilder::<L, D>::new();
+        let input = builder.constant::<BytesVariable<8>>(*b"plonky2x");
+        let length = builder.read::<U32Variable>();
+        let result = builder.sha256_variable(&input.0, length);
+        builder.write(result);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U32Variable>(9);
+        circuit.prove(&input);
     }
 }
@@ -0,0 +1,295 @@
+//! Incremental SHA-256 hashing.
+//!
+//! A [`Sha256State`] absorbs a message piece by piece, so that gadgets can feed it data coming
+//! from several sources (SSZ chunks, RLP fields, ...) without assembling and padding the message
+//! themselves.
+//!
+//! The chaining value can be read at any point, and is queued on the Curta SHA-256 accelerator as
+//! an `SHARequest::Blocks` request over the whole blocks absorbed so far. The accelerator always
+//! starts from the initial hash value, so these requests cover the message from its start and
+//! only cost rows of the SHA-256 STARK.
+//!
+//! A state can be resumed from a chaining value computed in another circuit with
+//! [`Sha256State::from_chaining_value`]. The blocks following it cannot be sent to the
+//! accelerator, and are compressed with plonky2 gates.
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::sha::sha256::{sha256_state_from_bytes, sha256_state_to_bytes};
+use crate::prelude::{ByteVariable, Bytes32Variable, CircuitBuilder};
+
+/// The block size of SHA-256 in bytes.
+const BLOCK_SIZE: usize = 64;
+
+/// The state of an incremental SHA-256 computation.
+#[derive(Debug, Clone, Default)]
+pub struct Sha256State {
+    /// The chaining value after the first `chained_len` bytes of a resumed computation, or
+    /// `None` for a computation started from the initial hash value.
+    chaining_value: Option<Bytes32Variable>,
+    /// The number of bytes absorbed in `chaining_value`.
+    chained_len: usize,
+    /// The whole blocks absorbed since the initial hash value, or since `chaining_value` and not
+    /// compressed yet.
+    blocks: Vec<ByteVariable>,
+    /// The last chaining value requested from the accelerator and the number of bytes it covers.
+    requested: Option<(usize, Bytes32Variable)>,
+    /// The bytes absorbed after the last whole block.
+    buffer: Vec<ByteVariable>,
+}
+
+impl Sha256State {
+    /// Starts a new SHA-256 computation.
+    pub fn init() -> Self {
+        Self::default()
+    }
+
+    /// Resumes a SHA-256 computation from the chaining value after the first `processed_len`
+    /// bytes of a message, as returned by `chaining_value` and `processed_len`.
+    pub fn from_chaining_value(chaining_value: Bytes32Variable, processed_len: usize) -> Self {
+        assert_eq!(
+            processed_len % BLOCK_SIZE,
+            0,
+            "the chaining value must cover whole blocks"
+        );
+        Self {
+            chaining_value: Some(chaining_value),
+            chained_len: processed_len,
+            blocks: Vec::new(),
+            requested: None,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Absorbs `input` into the state.
+    pub fn update(&mut self, input: &[ByteVariable]) {
+        self.buffer.extend_from_slice(input);
+        let num_whole_bytes = self.buffer.len() - self.buffer.len() % BLOCK_SIZE;
+        self.blocks.extend(self.buffer.drain(..num_whole_bytes));
+    }
+
+    /// The number of bytes absorbed so far.
+    pub fn len(&self) -> usize {
+        self.processed_len() + self.buffer.len()
+    }
+
+    /// Whether no bytes were absorbed yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of bytes covered by the chaining value, i.e. the bytes of the whole blocks
+    /// absorbed so far.
+    pub fn processed_len(&self) -> usize {
+        self.chained_len + self.blocks.len()
+    }
+
+    /// Compresses the pending whole blocks of a resumed computation into its chaining value.
+    fn compress_blocks<L: PlonkParameters<D>, const D: usize>(
+        &mut self,
+        builder: &mut CircuitBuilder<L, D>,
+        chaining_value: Bytes32Variable,
+    ) -> Bytes32Variable {
+        let mut state = sha256_state_from_bytes(chaining_value);
+        for block in self.blocks.chunks_exact(BLOCK_SIZE) {
+            state = builder.sha256_compress(state, block);
+        }
+        let chaining_value = sha256_state_to_bytes(&state);
+        self.chaining_value = Some(chaining_value);
+        self.chained_len += self.blocks.len();
+        self.blocks.clear();
+        chaining_value
+    }
+
+    /// The chaining value after the whole blocks absorbed so far, encoded like a digest.
+    ///
+    /// Together with `processed_len`, this is what another circuit needs to continue the hash.
+    /// The chaining value is requested from the accelerator once for each number of absorbed
+    /// blocks.
+    pub fn chaining_value<L: PlonkParameters<D>, const D: usize>(
+        &mut self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        if let Some(chaining_value) = self.chaining_value {
+            return self.compress_blocks(builder, chaining_value);
+        }
+        match self.requested {
+            Some((len, chaining_value)) if len == self.blocks.len() => chaining_value,
+            _ => {
+                let chaining_value = builder.curta_sha256_chaining_value(&self.blocks);
+                self.requested = Some((self.blocks.len(), chaining_value));
+                chaining_value
+            }
+        }
+    }
+
+    /// Pads the absorbed message and returns its digest.
+    ///
+    /// A message hashed from the initial hash value is sent to the accelerator as a single
+    /// request. A resumed message has its pending blocks and padded tail compressed from the
+    /// chaining value it was resumed from.
+    pub fn finalize<L: PlonkParameters<D>, const D: usize>(
+        mut self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Bytes32Variable {
+        let Some(chaining_value) = self.chaining_value else {
+            let mut message = self.blocks;
+            message.extend(self.buffer);
+            return builder.curta_sha256(&message);
+        };
+
+        let total_len = self.len();
+        let chaining_value = self.compress_blocks(builder, chaining_value);
+
+        let mut tail = self.buffer;
+        tail.push(builder.constant::<ByteVariable>(0x80));
+        while tail.len() % BLOCK_SIZE != BLOCK_SIZE - 8 {
+            tail.push(builder.constant::<ByteVariable>(0));
+        }
+        for byte in (8 * total_len as u64).to_be_bytes() {
+            tail.push(builder.constant::<ByteVariable>(byte));
+        }
+
+        let mut state = sha256_state_from_bytes(chaining_value);
+        for block in tail.chunks_exact(BLOCK_SIZE) {
+            state = builder.sha256_compress(state, block);
+        }
+        sha256_state_to_bytes(&state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::H256;
+    use sha2::compress256;
+    use sha2::digest::generic_array::GenericArray;
+
+    use super::Sha256State;
+    use crate::prelude::{Bytes32Variable, BytesVariable, DefaultBuilder};
+    use crate::utils;
+    use crate::utils::hash::sha256;
+
+    const SHA256_INITIAL_HASH: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    fn state_to_h256(state: [u32; 8]) -> H256 {
+        H256::from_slice(
+            &state
+                .iter()
+                .flat_map(|word| word.to_be_bytes())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha256_state() {
+        utils::setup_logger();
+
+        // Pieces that straddle block boundaries, including an empty one.
+        let first = [0x01u8; 50];
+        let second = [0x02u8; 30];
+        let third = [0x03u8; 90];
+
+        let mut builder = DefaultBuilder::new();
+        let first_v = builder.constant::<BytesVariable<50>>(first);
+        let second_v = builder.constant::<BytesVariable<30>>(second);
+        let third_v = builder.constant::<BytesVariable<90>>(third);
+
+        let mut state = Sha256State::init();
+        let initial_chaining_value = state.chaining_value(&mut builder);
+        builder.write(initial_chaining_value);
+        state.update(&first_v.0);
+        state.update(&[]);
+        state.update(&second_v.0);
+        state.update(&third_v.0);
+        assert_eq!(state.len(), 170);
+        assert_eq!(state.processed_len(), 128);
+        let chaining_value = state.chaining_value(&mut builder);
+        builder.write(chaining_value);
+        let digest = state.finalize(&mut builder);
+        builder.write(digest);
+
+        // Reading chaining values does not change how the digest is computed.
+        let mut unchained_state = Sha256State::init();
+        unchained_state.update(&first_v.0);
+        unchained_state.update(&second_v.0);
+        unchained_state.update(&third_v.0);
+        let unchained_digest = unchained_state.finalize(&mut builder);
+        builder.assert_is_equal(unchained_digest, digest);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let mut message = first.to_vec();
+        message.extend(second);
+        message.extend(third);
+
+        let mut expected_state = SHA256_INITIAL_HASH;
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            state_to_h256(expected_state)
+        );
+        for block in message[..128].chunks_exact(64) {
+            compress256(&mut expected_state, &[*GenericArray::from_slice(block)]);
+        }
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            state_to_h256(expected_state)
+        );
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            H256::from(sha256(&message))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_sha256_state_resume() {
+        utils::setup_logger();
+
+        let message = (0..300).map(|i| i as u8).collect::<Vec<_>>();
+        let mut expected_state = SHA256_INITIAL_HASH;
+        for block in message[..128].chunks_exact(64) {
+            compress256(&mut expected_state, &[*GenericArray::from_slice(block)]);
+        }
+        let chaining_value = state_to_h256(expected_state);
+
+        // The first 128 bytes were absorbed in another circuit, which exposed its chaining value.
+        let mut builder = DefaultBuilder::new();
+        let chaining_value_v = builder.read::<Bytes32Variable>();
+        let rest = builder.constant::<BytesVariable<172>>(message[128..].try_into().unwrap());
+
+        let mut state = Sha256State::from_chaining_value(chaining_value_v, 128);
+        state.update(&rest.0[..100]);
+        assert_eq!(state.processed_len(), 192);
+        let intermediate = state.chaining_value(&mut builder);
+        builder.write(intermediate);
+        state.update(&rest.0[100..]);
+        assert_eq!(state.len(), 300);
+        let digest = state.finalize(&mut builder);
+        builder.write(digest);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(chaining_value);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        compress256(
+            &mut expected_state,
+            &[*GenericArray::from_slice(&message[128..192])],
+        );
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            state_to_h256(expected_state)
+        );
+        assert_eq!(
+            output.read::<Bytes32Variable>(),
+            H256::from(sha256(&message))
+        );
+    }
+}
@@ -60,6 +60,16 @@ impl<L: PlonkParameters<D>, const D: usize> SHA<L, D, 80> for SHA512 {
             .collect()
     }
 
+    fn blocks_circuit(
+        builder: &mut CircuitBuilder<L, D>,
+        input: &[ByteVariable],
+    ) -> Vec<Self::IntVariable> {
+        input
+            .chunks_exact(8)
+            .map(|bytes| U64Variable::decode(builder, bytes))
+            .collect()
+    }
+
     fn value_to_variable(
         builder: &mut CircuitBuilder<L, D>,
         value: <U64Register as curta::chip::register::Register>::Value<Variable>,
@@ -0,0 +1,111 @@
+use ethers::types::{I256, U256};
+use plonky2::hash::hash_types::RichField;
+
+use super::Int;
+use crate::frontend::vars::{EvmVariable, U32Variable};
+use crate::make_int_n;
+use crate::prelude::{
+    Add, BoolVariable, ByteVariable, CircuitBuilder, CircuitVariable, Div, LessThanOrEqual, Mul,
+    Neg, One, PlonkParameters, Rem, Shl, Shr, Sub, U256Variable, Variable, Zero,
+};
+
+impl Int for I256 {
+    type Unsigned = U256;
+
+    fn into_unsigned(self) -> U256 {
+        self.into_raw()
+    }
+
+    fn from_unsigned(value: U256) -> Self {
+        Self::from_raw(value)
+    }
+}
+
+make_int_n!(I256Variable, I256, U256Variable, 8);
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::I256;
+    use rand::Rng;
+
+    use super::I256Variable;
+    use crate::prelude::*;
+
+    fn i256(value: i128) -> I256 {
+        I256::from_dec_str(&value.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_i256_arithmetic() {
+        let mut rng = rand::thread_rng();
+        let a = rng.gen::<i64>() as i128;
+        let b = rng.gen::<i64>() as i128;
+        let shift: usize = rng.gen_range(0..64);
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.constant::<I256Variable>(i256(a));
+        let b_var = builder.constant::<I256Variable>(i256(b));
+        let shift_var = builder.constant::<U32Variable>(shift as u32);
+        let large_shift_var = builder.constant::<U32Variable>(300);
+
+        let results = [
+            (builder.add(a_var, b_var), i256(a + b)),
+            (builder.sub(a_var, b_var), i256(a - b)),
+            (builder.mul(a_var, b_var), i256(a * b)),
+            (builder.neg(a_var), i256(-a)),
+            (builder.div(a_var, b_var), i256(a / b)),
+            (builder.rem(a_var, b_var), i256(a % b)),
+            (a_var.abs(&mut builder), i256(a.abs())),
+            (builder.shr(a_var, shift), i256(a >> shift)),
+            (builder.shr(a_var, shift_var), i256(a >> shift)),
+            (builder.shr(a_var, large_shift_var), i256(a >> 127)),
+        ];
+        for (result, expected_result) in results {
+            let expected_result = builder.constant::<I256Variable>(expected_result);
+            builder.assert_is_equal(result, expected_result);
+        }
+
+        // Arithmetic wraps around the bit width.
+        let max = builder.constant::<I256Variable>(I256::MAX);
+        let one = builder.one::<I256Variable>();
+        let wrapped = builder.add(max, one);
+        let min = builder.constant::<I256Variable>(I256::MIN);
+        builder.assert_is_equal(wrapped, min);
+        let is_negative = wrapped.is_negative(&mut builder);
+        let t = builder._true();
+        builder.assert_is_equal(is_negative, t);
+        let min_lt_max = builder.lt(min, max);
+        builder.assert_is_equal(min_lt_max, t);
+        let max_lt_min = builder.lt(max, min);
+        let f = builder._false();
+        builder.assert_is_equal(max_lt_min, f);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_i256_sign_extension() {
+        let mut builder = DefaultBuilder::new();
+
+        // ABI-encoded `int24` ticks.
+        for (bytes, expected) in [([0xff, 0xff, 0x9c], -100), ([0x00, 0x0d, 0x89], 3465)] {
+            let bytes = builder.constant::<BytesVariable<3>>(bytes);
+            let bits = bytes
+                .0
+                .iter()
+                .flat_map(|b| b.as_be_bits())
+                .collect::<Vec<_>>();
+            let tick = I256Variable::from_be_bits_sign_extended(&bits, &mut builder);
+            let expected = builder.constant::<I256Variable>(i256(expected));
+            builder.assert_is_equal(tick, expected);
+        }
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
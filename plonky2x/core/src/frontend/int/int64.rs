@@ -0,0 +1,127 @@
+use plonky2::hash::hash_types::RichField;
+
+use super::int256::I256Variable;
+use super::Int;
+use crate::frontend::vars::{EvmVariable, U32Variable};
+use crate::make_int_n;
+use crate::prelude::{
+    Add, BoolVariable, ByteVariable, CircuitBuilder, CircuitVariable, Div, LessThanOrEqual, Mul,
+    Neg, One, PlonkParameters, Rem, Shl, Shr, Sub, U64Variable, Variable, Zero,
+};
+
+impl Int for i64 {
+    type Unsigned = u64;
+
+    fn into_unsigned(self) -> u64 {
+        self as u64
+    }
+
+    fn from_unsigned(value: u64) -> Self {
+        value as i64
+    }
+}
+
+make_int_n!(I64Variable, i64, U64Variable, 2);
+
+impl I64Variable {
+    pub fn to_i256<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> I256Variable {
+        let bits = self.to_be_bits(builder);
+        I256Variable::from_be_bits_sign_extended(&bits, builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::I256;
+    use rand::Rng;
+
+    use super::I64Variable;
+    use crate::frontend::int::int256::I256Variable;
+    use crate::frontend::vars::EvmVariable;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_i64_arithmetic() {
+        let mut rng = rand::thread_rng();
+        let a: i64 = rng.gen();
+        let b: i64 = rng.gen();
+        let shift: usize = rng.gen_range(0..64);
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.constant::<I64Variable>(a);
+        let b_var = builder.constant::<I64Variable>(b);
+        let shift_var = builder.constant::<U32Variable>(shift as u32);
+
+        let results = [
+            (builder.add(a_var, b_var), a.wrapping_add(b)),
+            (builder.sub(a_var, b_var), a.wrapping_sub(b)),
+            (builder.mul(a_var, b_var), a.wrapping_mul(b)),
+            (builder.neg(a_var), a.wrapping_neg()),
+            (builder.div(a_var, b_var), a.wrapping_div(b)),
+            (builder.rem(a_var, b_var), a.wrapping_rem(b)),
+            (a_var.abs(&mut builder), a.wrapping_abs()),
+            (builder.shl(a_var, shift), a << shift),
+            (builder.shr(a_var, shift), a >> shift),
+            (builder.shr(a_var, shift_var), a >> shift),
+        ];
+        for (result, expected_result) in results {
+            let expected_result = builder.constant::<I64Variable>(expected_result);
+            builder.assert_is_equal(result, expected_result);
+        }
+
+        let extended = a_var.to_i256(&mut builder);
+        let expected_extended =
+            builder.constant::<I256Variable>(I256::from_dec_str(&a.to_string()).unwrap());
+        builder.assert_is_equal(extended, expected_extended);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_i64_comparison() {
+        let mut rng = rand::thread_rng();
+        let a: i64 = rng.gen();
+        let b: i64 = rng.gen();
+
+        let mut builder = DefaultBuilder::new();
+        let pairs = [
+            (a, b),
+            (b, a),
+            (a, a),
+            (i64::MIN, 0),
+            (-1, 1),
+            (i64::MAX, i64::MIN),
+        ];
+        for (x, y) in pairs {
+            let x_var = builder.constant::<I64Variable>(x);
+            let y_var = builder.constant::<I64Variable>(y);
+            let results = [
+                (builder.lt(x_var, y_var), x < y),
+                (builder.lte(x_var, y_var), x <= y),
+                (builder.gt(x_var, y_var), x > y),
+            ];
+            for (result, expected_result) in results {
+                let expected_result = builder.constant::<BoolVariable>(expected_result);
+                builder.assert_is_equal(result, expected_result);
+            }
+        }
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_i64_evm_value() {
+        let encoded = I64Variable::encode_value::<GoldilocksField>(-2);
+        assert_eq!(encoded, [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]);
+        assert_eq!(I64Variable::decode_value::<GoldilocksField>(&encoded), -2);
+    }
+}
@@ -0,0 +1,276 @@
+#[macro_export]
+macro_rules! make_int_n {
+    ($a:ident, $b:ty, $u:ident, $c:expr) => {
+        /// A signed integer type in two's complement, stored as the unsigned integer with the same
+        /// bits.
+        ///
+        /// `add`, `sub`, `mul`, `neg` and `div` wrap around the bit width.
+        #[derive(Debug, Clone, Copy)]
+        pub struct $a {
+            pub value: $u,
+        }
+
+        impl CircuitVariable for $a {
+            type ValueType<F: RichField> = $b;
+
+            fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> Self {
+                Self {
+                    value: $u::init_unsafe(builder),
+                }
+            }
+
+            fn variables(&self) -> Vec<Variable> {
+                self.value.variables()
+            }
+
+            fn from_variables_unsafe(variables: &[Variable]) -> Self {
+                Self {
+                    value: $u::from_variables_unsafe(variables),
+                }
+            }
+
+            fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+                &self,
+                builder: &mut CircuitBuilder<L, D>,
+            ) {
+                self.value.assert_is_valid(builder)
+            }
+
+            fn nb_elements() -> usize {
+                $u::nb_elements()
+            }
+
+            fn elements<F: RichField>(value: $b) -> Vec<F> {
+                $u::elements::<F>(value.into_unsigned())
+            }
+
+            fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+                <$b as Int>::from_unsigned($u::from_elements::<F>(elements))
+            }
+        }
+
+        impl EvmVariable for $a {
+            fn encode<L: PlonkParameters<D>, const D: usize>(
+                &self,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> Vec<ByteVariable> {
+                self.value.encode(builder)
+            }
+
+            fn decode<L: PlonkParameters<D>, const D: usize>(
+                builder: &mut CircuitBuilder<L, D>,
+                bytes: &[ByteVariable],
+            ) -> Self {
+                Self {
+                    value: $u::decode(builder, bytes),
+                }
+            }
+
+            fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+                $u::encode_value::<F>(value.into_unsigned())
+            }
+
+            fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+                <$b as Int>::from_unsigned($u::decode_value::<F>(bytes))
+            }
+        }
+
+        impl $a {
+            /// Reinterprets the bits of an unsigned variable, like an `as` cast.
+            pub fn from_unsigned(value: $u) -> Self {
+                Self { value }
+            }
+
+            /// Reinterprets the bits as an unsigned variable, like an `as` cast.
+            pub fn to_unsigned(self) -> $u {
+                self.value
+            }
+
+            /// Sign extends the big-endian two's complement `bits` to the width of this type.
+            ///
+            /// This decodes narrower signed values, such as ABI-encoded `int24`s.
+            pub fn from_be_bits_sign_extended<L: PlonkParameters<D>, const D: usize>(
+                bits: &[BoolVariable],
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> Self {
+                assert!(!bits.is_empty() && bits.len() <= 32 * $c);
+                let mut extended = vec![bits[0]; 32 * $c - bits.len()];
+                extended.extend_from_slice(bits);
+                let bytes = extended
+                    .chunks_exact(8)
+                    .map(|byte| ByteVariable(byte.try_into().unwrap()))
+                    .collect::<Vec<_>>();
+                Self::decode(builder, &bytes)
+            }
+
+            /// Whether the value is negative, i.e. whether its most significant bit is set.
+            pub fn is_negative<L: PlonkParameters<D>, const D: usize>(
+                &self,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> BoolVariable {
+                self.value.limbs[$c - 1].to_be_bits(builder)[0]
+            }
+
+            /// The absolute value. As with `wrapping_abs`, the minimum value is its own absolute
+            /// value.
+            pub fn abs<L: PlonkParameters<D>, const D: usize>(
+                self,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> Self {
+                let is_negative = self.is_negative(builder);
+                let negated = builder.neg(self);
+                builder.select(is_negative, negated, self)
+            }
+
+            /// The absolute value as an unsigned integer, which is exact for every value.
+            pub fn unsigned_abs<L: PlonkParameters<D>, const D: usize>(
+                self,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> $u {
+                self.abs(builder).value
+            }
+
+            /// Flips the sign bit, which maps the signed order onto the unsigned order.
+            fn flip_sign_bit<L: PlonkParameters<D>, const D: usize>(
+                self,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> $u {
+                let mut limbs = self.value.limbs;
+                let sign_bit = builder.constant::<U32Variable>(1 << 31);
+                limbs[$c - 1] = builder.add(limbs[$c - 1], sign_bit);
+                $u { limbs }
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> Zero<L, D> for $a {
+            fn zero(builder: &mut CircuitBuilder<L, D>) -> Self {
+                Self {
+                    value: $u::zero(builder),
+                }
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> One<L, D> for $a {
+            fn one(builder: &mut CircuitBuilder<L, D>) -> Self {
+                Self {
+                    value: $u::one(builder),
+                }
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> Add<L, D> for $a {
+            type Output = Self;
+
+            fn add(self, rhs: $a, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                Self {
+                    value: builder.add(self.value, rhs.value),
+                }
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> Sub<L, D> for $a {
+            type Output = Self;
+
+            fn sub(self, rhs: $a, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                Self {
+                    value: builder.sub(self.value, rhs.value),
+                }
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> Mul<L, D> for $a {
+            type Output = Self;
+
+            fn mul(self, rhs: $a, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                // The low half of the product does not depend on the signs.
+                Self {
+                    value: builder.mul(self.value, rhs.value),
+                }
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> Neg<L, D> for $a {
+            type Output = Self;
+
+            fn neg(self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                let zero = builder.zero::<$u>();
+                Self {
+                    value: builder.sub(zero, self.value),
+                }
+            }
+        }
+
+        /// Division rounding towards zero, as in Rust and Solidity.
+        impl<L: PlonkParameters<D>, const D: usize> Div<L, D> for $a {
+            type Output = Self;
+
+            fn div(self, rhs: $a, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                let lhs_is_negative = self.is_negative(builder);
+                let rhs_is_negative = rhs.is_negative(builder);
+                let lhs_abs = self.unsigned_abs(builder);
+                let rhs_abs = rhs.unsigned_abs(builder);
+                let quotient = Self::from_unsigned(builder.div(lhs_abs, rhs_abs));
+                let negated = builder.neg(quotient);
+                let is_negative = builder.xor(lhs_is_negative, rhs_is_negative);
+                builder.select(is_negative, negated, quotient)
+            }
+        }
+
+        /// The remainder of the division rounding towards zero, which has the sign of `self`.
+        impl<L: PlonkParameters<D>, const D: usize> Rem<L, D> for $a {
+            type Output = Self;
+
+            fn rem(self, rhs: $a, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                let lhs_is_negative = self.is_negative(builder);
+                let lhs_abs = self.unsigned_abs(builder);
+                let rhs_abs = rhs.unsigned_abs(builder);
+                let remainder = Self::from_unsigned(builder.rem(lhs_abs, rhs_abs));
+                let negated = builder.neg(remainder);
+                builder.select(lhs_is_negative, negated, remainder)
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> LessThanOrEqual<L, D> for $a {
+            fn lte(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> BoolVariable {
+                let lhs = self.flip_sign_bit(builder);
+                let rhs = rhs.flip_sign_bit(builder);
+                builder.lte(lhs, rhs)
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize, R> Shl<L, D, R> for $a
+        where
+            $u: Shl<L, D, R, Output = $u>,
+        {
+            type Output = Self;
+
+            fn shl(self, rhs: R, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                Self {
+                    value: builder.shl(self.value, rhs),
+                }
+            }
+        }
+
+        /// The arithmetic right shift, which fills the vacated bits with the sign bit.
+        impl<L: PlonkParameters<D>, const D: usize, R> Shr<L, D, R> for $a
+        where
+            $u: Shr<L, D, R, Output = $u>,
+        {
+            type Output = Self;
+
+            fn shr(self, rhs: R, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                // For negative values, `x >> n` is `!(!x >> n)`.
+                let is_negative = self.is_negative(builder);
+                let inverted = builder.not(self.value);
+                let value = builder.select(is_negative, inverted, self.value);
+                let shifted = builder.shr(value, rhs);
+                let inverted = builder.not(shifted);
+                Self {
+                    value: builder.select(is_negative, inverted, shifted),
+                }
+            }
+        }
+    };
+}
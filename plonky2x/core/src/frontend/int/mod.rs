@@ -0,0 +1,23 @@
+//! Signed integer variables in two's complement.
+//!
+//! A signed variable is stored in the limbs of the unsigned variable of the same width, so
+//! addition, subtraction and multiplication are the same operations as for the unsigned types,
+//! while comparisons, division and right shifts take the sign into account.
+
+use core::fmt::Debug;
+
+pub mod int256;
+pub mod int64;
+
+mod int_n;
+
+/// A signed integer value with the same width as an unsigned `Uint` value.
+pub trait Int: Debug + Clone + Copy + Sync + Send + 'static {
+    type Unsigned;
+
+    /// Reinterprets the value as an unsigned value, like an `as` cast.
+    fn into_unsigned(self) -> Self::Unsigned;
+
+    /// Reinterprets an unsigned value as a signed value, like an `as` cast.
+    fn from_unsigned(value: Self::Unsigned) -> Self;
+}
@@ -14,6 +14,9 @@
 //!
 //! Under the hood, we compute each map in a seperate proof and perform the reductions by generating
 //! a proof for each reduction between two proofs until we have a single proof.
+//!
+//! The inputs of each proof are accumulated into a Merkle tree with a [`CircuitHasher`] chosen by
+//! the caller, whose root is checked against the one of the build-time constant inputs.
 
 pub mod generator;
 
@@ -27,12 +30,11 @@ use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
 use plonky2x_derive::CircuitVariable;
 
 use self::generator::MapReduceGenerator;
-use super::hash::poseidon::poseidon256::PoseidonHashOutVariable;
 use crate::backend::circuit::{CircuitBuild, CircuitSerializer};
 use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::hash::hasher::CircuitHasher;
 use crate::frontend::vars::CircuitVariable;
-use crate::prelude::{ArrayVariable, PlonkParameters, Variable};
-use crate::utils::poseidon::mapreduce_merkle_tree_root;
+use crate::prelude::{ArrayVariable, BoolVariable, ByteVariable, PlonkParameters, Variable};
 use crate::utils::proof::ProofWithPublicInputsTargetUtils;
 
 /// The input to the map or reduce circuit.
@@ -51,7 +53,8 @@ struct MapReduceInputVariable<Ctx: CircuitVariable, Input: CircuitVariable, cons
 
 /// The output of the map or reduce circuit.
 #[derive(Debug, Clone, CircuitVariable)]
-struct MapReduceOutputVariable<Ctx: CircuitVariable, Output: CircuitVariable> {
+struct MapReduceOutputVariable<Ctx: CircuitVariable, Output: CircuitVariable, Acc: CircuitVariable>
+{
     /// The context stores a variable passed in from the root circuit. Useful for passing in
     /// small amounts of dynamic data (i.e., a block hash) that is shared among all calls to map
     /// and reduce.
@@ -61,15 +64,33 @@ struct MapReduceOutputVariable<Ctx: CircuitVariable, Output: CircuitVariable> {
     output: Output,
 
     // The accumulator is the hash of the inputs up to this point to the map or reduce function.
-    acc: PoseidonHashOutVariable,
+    acc: Acc,
 }
 
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
-    fn build_map<Ctx, Input, Output, MapFn, const B: usize>(
+    /// Splits the inputs of a map circuit into little-endian bytes, so that they can be hashed
+    /// with a `CircuitHasher`.
+    fn mapreduce_input_bytes<Input: CircuitVariable, const B: usize>(
+        &mut self,
+        inputs: &ArrayVariable<Input, B>,
+    ) -> Vec<ByteVariable> {
+        let mut bytes = Vec::new();
+        for variable in inputs.variables() {
+            let le_bits = self.api.split_le(variable.0, 64);
+            for byte_bits in le_bits.chunks(8) {
+                let be_bits: [BoolVariable; 8] = core::array::from_fn(|i| byte_bits[7 - i].into());
+                bytes.push(ByteVariable(be_bits));
+            }
+        }
+        bytes
+    }
+
+    fn build_map<H, Ctx, Input, Output, MapFn, const B: usize>(
         &mut self,
         map_fn: &MapFn,
     ) -> CircuitBuild<L, D>
     where
+        H: CircuitHasher<L, D>,
         Ctx: CircuitVariable,
         Input: CircuitVariable,
         Output: CircuitVariable,
@@ -88,14 +109,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         let output = map_fn(data.clone().ctx, data.clone().inputs, &mut builder);
 
         // Compute the leaf hash for the input.
-        let input_variables = data
-            .clone()
-            .inputs
-            .as_vec()
-            .iter()
-            .flat_map(|i| i.variables())
-            .collect_vec();
-        let acc = builder.poseidon_hash(&input_variables);
+        let input_bytes = builder.mapreduce_input_bytes(&data.inputs);
+        let acc = H::hash(&mut builder, &input_bytes);
 
         // Write result.
         let result = MapReduceOutputVariable {
@@ -107,12 +122,13 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         builder.build()
     }
 
-    fn build_reduce<Ctx, Output, ReduceFn>(
+    fn build_reduce<H, Ctx, Output, ReduceFn>(
         &mut self,
         child_circuit: &CircuitBuild<L, D>,
         reduce_fn: &ReduceFn,
     ) -> CircuitBuild<L, D>
     where
+        H: CircuitHasher<L, D>,
         Ctx: CircuitVariable,
         Output: CircuitVariable,
         ReduceFn: Fn(Ctx, Output, Output, &mut CircuitBuilder<L, D>) -> Output,
@@ -129,8 +145,10 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         builder.verify_proof::<L>(&proof_right, &verifier_data, &child_circuit.data.common);
 
         // Assert that the contexts match.
-        let input_left = proof_left.read_end_from_pis::<MapReduceOutputVariable<Ctx, Output>>();
-        let input_right = proof_right.read_end_from_pis::<MapReduceOutputVariable<Ctx, Output>>();
+        let input_left =
+            proof_left.read_end_from_pis::<MapReduceOutputVariable<Ctx, Output, H::Digest>>();
+        let input_right =
+            proof_right.read_end_from_pis::<MapReduceOutputVariable<Ctx, Output, H::Digest>>();
         builder.assert_is_equal(input_left.clone().ctx, input_right.clone().ctx);
 
         // Apply the reduce function.
@@ -142,7 +160,11 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         );
 
         // Compute the accumulator hash for the inputs.
-        let acc = builder.poseidon_hash_pair(input_left.clone().acc, input_right.clone().acc);
+        let acc = H::hash_pair(
+            &mut builder,
+            input_left.clone().acc,
+            input_right.clone().acc,
+        );
 
         // Write result.
         let result = MapReduceOutputVariable {
@@ -155,7 +177,9 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         builder.build()
     }
 
-    pub fn mapreduce<Ctx, Input, Output, Serializer, const B: usize, MapFn, ReduceFn>(
+    /// Computes `map_fn` over each chunk of `B` inputs in its own proof and combines the outputs
+    /// pairwise with `reduce_fn`, accumulating the inputs of each proof with the hasher `H`.
+    pub fn mapreduce<H, Ctx, Input, Output, Serializer, const B: usize, MapFn, ReduceFn>(
         &mut self,
         ctx: Ctx,
         inputs: Vec<Input::ValueType<L::Field>>,
@@ -163,6 +187,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         reduce_fn: ReduceFn,
     ) -> Output
     where
+        H: CircuitHasher<L, D>,
         Ctx: CircuitVariable,
         Input: CircuitVariable,
         Output: CircuitVariable,
@@ -180,18 +205,28 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             "inputs.len() / B must be a power of two"
         );
 
-        // Compute the expected inputs accumulator.
-        let expected_acc =
-            self.constant::<PoseidonHashOutVariable>(mapreduce_merkle_tree_root::<L, Input, B, D>(
-                &inputs,
-            ));
+        // Compute the expected inputs accumulator, as the root of the tree of the hashes of the
+        // inputs of each map circuit.
+        let mut accs = Vec::new();
+        for chunk in inputs.chunks(B) {
+            let chunk_inputs = self.constant::<ArrayVariable<Input, B>>(chunk.to_vec());
+            let chunk_bytes = self.mapreduce_input_bytes(&chunk_inputs);
+            accs.push(H::hash(self, &chunk_bytes));
+        }
+        while accs.len() > 1 {
+            accs = accs
+                .chunks_exact(2)
+                .map(|pair| H::hash_pair(self, pair[0].clone(), pair[1].clone()))
+                .collect_vec();
+        }
+        let expected_acc = accs.pop().unwrap();
 
         // The gate and witness generator serializers.
         let gate_serializer = Serializer::gate_registry::<L, D>();
         let generator_serializer = Serializer::generator_registry::<L, D>();
 
         // Build a map circuit which maps from I -> O using the closure `m`.
-        let map_circuit = self.build_map(&map_fn);
+        let map_circuit = self.build_map::<H, Ctx, Input, Output, MapFn, B>(&map_fn);
         debug!("succesfully built map circuit: id={}", map_circuit.id());
 
         // Save map circuit and map circuit input target to build folder.
@@ -210,7 +245,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
                 &reduce_circuits[i - 1]
             };
             let reduce_circuit =
-                self.build_reduce::<Ctx, Output, ReduceFn>(child_circuit, &reduce_fn);
+                self.build_reduce::<H, Ctx, Output, ReduceFn>(child_circuit, &reduce_fn);
             let reduce_circuit_id = reduce_circuit.id();
             let reduce_circuit_path = format!("./build/{}.circuit", reduce_circuit_id);
             reduce_circuit.save(
@@ -245,7 +280,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         );
 
         // Verify the inputs accumulator.
-        let output = final_proof.read_end_from_pis::<MapReduceOutputVariable<Ctx, Output>>();
+        let output =
+            final_proof.read_end_from_pis::<MapReduceOutputVariable<Ctx, Output, H::Digest>>();
         self.assert_is_equal(output.acc, expected_acc);
 
         // Verify the context.
@@ -262,6 +298,7 @@ pub(crate) mod tests {
     use plonky2::field::types::Field;
 
     use crate::backend::circuit::DefaultSerializer;
+    use crate::frontend::hash::hasher::PoseidonHasher;
     use crate::prelude::{CircuitBuilder, DefaultParameters, Variable};
 
     type F = GoldilocksField;
@@ -281,21 +318,22 @@ pub(crate) mod tests {
             F::from_canonical_u64(3),
         ];
 
-        let output = builder.mapreduce::<Variable, Variable, Variable, DefaultSerializer, 2, _, _>(
-            ctx,
-            inputs,
-            |ctx, inputs, builder| {
-                builder.watch(&ctx, "ctx");
-                let constant = builder.constant::<Variable>(F::ONE);
-                let o1 = builder.add(inputs[0], constant);
-                let o2 = builder.add(inputs[1], constant);
-                builder.add(o1, o2)
-            },
-            |ctx, left, right, builder| {
-                builder.watch(&ctx, "ctx");
-                builder.add(left, right)
-            },
-        );
+        let output = builder
+            .mapreduce::<PoseidonHasher, Variable, Variable, Variable, DefaultSerializer, 2, _, _>(
+                ctx,
+                inputs,
+                |ctx, inputs, builder| {
+                    builder.watch(&ctx, "ctx");
+                    let constant = builder.constant::<Variable>(F::ONE);
+                    let o1 = builder.add(inputs[0], constant);
+                    let o2 = builder.add(inputs[1], constant);
+                    builder.add(o1, o2)
+                },
+                |ctx, left, right, builder| {
+                    builder.watch(&ctx, "ctx");
+                    builder.add(left, right)
+                },
+            );
         builder.watch(&output, "output");
         builder.write(output);
 
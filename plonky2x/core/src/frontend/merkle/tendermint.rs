@@ -2,16 +2,21 @@ use itertools::Itertools;
 
 use super::tree::MerkleInclusionProofVariable;
 use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hash::hasher::CircuitHasher;
 use crate::frontend::vars::Bytes32Variable;
 use crate::prelude::{
     ArrayVariable, BoolVariable, ByteVariable, BytesVariable, CircuitBuilder, CircuitVariable,
 };
 
 /// Merkle Tree implementation for the Tendermint spec (follows Comet BFT Simple Merkle Tree spec: https://docs.cometbft.com/main/spec/core/encoding#merkle-trees).
-/// TODO: Create generic interface for Merkle trees to implement.
+///
+/// The tree is generic over the hasher `H`, which is SHA-256 in the spec.
 impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     /// Leaf should already be hashed.
-    pub fn get_root_from_merkle_proof_hashed_leaf<const PROOF_DEPTH: usize>(
+    pub fn get_root_from_merkle_proof_hashed_leaf<
+        H: CircuitHasher<L, D, Digest = Bytes32Variable>,
+        const PROOF_DEPTH: usize,
+    >(
         &mut self,
         proof: &ArrayVariable<Bytes32Variable, PROOF_DEPTH>,
         path_indices: &ArrayVariable<BoolVariable, PROOF_DEPTH>,
@@ -22,29 +27,36 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         for i in 0..PROOF_DEPTH {
             let aunt = proof[i];
             let path_index = path_indices[i];
-            let left_hash_pair = self.inner_hash(&hash_so_far, &aunt);
-            let right_hash_pair = self.inner_hash(&aunt, &hash_so_far);
+            let left_hash_pair = self.inner_hash::<H>(&hash_so_far, &aunt);
+            let right_hash_pair = self.inner_hash::<H>(&aunt, &hash_so_far);
 
             hash_so_far = self.select(path_index, right_hash_pair, left_hash_pair)
         }
         hash_so_far
     }
 
-    pub fn get_root_from_merkle_proof<const PROOF_DEPTH: usize, const LEAF_SIZE_BYTES: usize>(
+    pub fn get_root_from_merkle_proof<
+        H: CircuitHasher<L, D, Digest = Bytes32Variable>,
+        const PROOF_DEPTH: usize,
+        const LEAF_SIZE_BYTES: usize,
+    >(
         &mut self,
         inclusion_proof: &MerkleInclusionProofVariable<PROOF_DEPTH, LEAF_SIZE_BYTES>,
         path_indices: &ArrayVariable<BoolVariable, PROOF_DEPTH>,
     ) -> Bytes32Variable {
-        let hashed_leaf = self.leaf_hash(&inclusion_proof.leaf.0);
+        let hashed_leaf = self.leaf_hash::<H>(&inclusion_proof.leaf.0);
 
-        self.get_root_from_merkle_proof_hashed_leaf::<PROOF_DEPTH>(
+        self.get_root_from_merkle_proof_hashed_leaf::<H, PROOF_DEPTH>(
             &inclusion_proof.proof,
             path_indices,
             hashed_leaf,
         )
     }
 
-    pub fn leaf_hash(&mut self, leaf: &[ByteVariable]) -> Bytes32Variable {
+    pub fn leaf_hash<H: CircuitHasher<L, D, Digest = Bytes32Variable>>(
+        &mut self,
+        leaf: &[ByteVariable],
+    ) -> Bytes32Variable {
         let zero_byte = ByteVariable::constant(self, 0u8);
 
         let mut encoded_leaf = vec![zero_byte];
@@ -53,12 +65,10 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         encoded_leaf.extend(leaf.to_vec());
 
         // Load the output of the hash.
-        // Use curta gadget to generate SHA's.
-        // Note: This can be removed when sha256 interface is fixed.
-        self.curta_sha256(&encoded_leaf)
+        H::hash(self, &encoded_leaf)
     }
 
-    pub fn inner_hash(
+    pub fn inner_hash<H: CircuitHasher<L, D, Digest = Bytes32Variable>>(
         &mut self,
         left: &Bytes32Variable,
         right: &Bytes32Variable,
@@ -77,10 +87,10 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
 
         // Load the output of the hash.
         // Note: Calculate the inner hash as if both validators are enabled.
-        self.curta_sha256(&encoded_leaf)
+        H::hash(self, &encoded_leaf)
     }
 
-    pub fn hash_merkle_layer(
+    pub fn hash_merkle_layer<H: CircuitHasher<L, D, Digest = Bytes32Variable>>(
         &mut self,
         merkle_hashes: Vec<Bytes32Variable>,
         merkle_hash_enabled: Vec<BoolVariable>,
@@ -100,7 +110,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
             let both_nodes_disabled = self.and(first_node_disabled, second_node_disabled);
 
             // Calculuate the inner hash.
-            let inner_hash = self.inner_hash(&merkle_hashes[i], &merkle_hashes[i + 1]);
+            let inner_hash = self.inner_hash::<H>(&merkle_hashes[i], &merkle_hashes[i + 1]);
 
             new_merkle_hashes.push(self.select(both_nodes_enabled, inner_hash, merkle_hashes[i]));
 
@@ -112,17 +122,23 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         (new_merkle_hashes, new_merkle_hash_enabled)
     }
 
-    pub fn hash_leaves<const LEAF_SIZE_BYTES: usize>(
+    pub fn hash_leaves<
+        H: CircuitHasher<L, D, Digest = Bytes32Variable>,
+        const LEAF_SIZE_BYTES: usize,
+    >(
         &mut self,
         leaves: Vec<BytesVariable<LEAF_SIZE_BYTES>>,
     ) -> Vec<Bytes32Variable> {
         leaves
             .iter()
-            .map(|leaf| self.leaf_hash(&leaf.0))
+            .map(|leaf| self.leaf_hash::<H>(&leaf.0))
             .collect_vec()
     }
 
-    pub fn get_root_from_hashed_leaves<const NB_LEAVES: usize>(
+    pub fn get_root_from_hashed_leaves<
+        H: CircuitHasher<L, D, Digest = Bytes32Variable>,
+        const NB_LEAVES: usize,
+    >(
         &mut self,
         leaf_hashes: Vec<Bytes32Variable>,
         leaves_enabled: Vec<BoolVariable>,
@@ -142,7 +158,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         // Hash each layer of nodes to get the root according to the Tendermint spec, starting from the leaves.
         while merkle_layer_size > 1 {
             (current_nodes, current_node_enabled) =
-                self.hash_merkle_layer(current_nodes, current_node_enabled, merkle_layer_size);
+                self.hash_merkle_layer::<H>(current_nodes, current_node_enabled, merkle_layer_size);
             merkle_layer_size /= 2;
         }
 
@@ -150,7 +166,11 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         current_nodes[0]
     }
 
-    pub fn compute_root_from_leaves<const NB_LEAVES: usize, const LEAF_SIZE_BYTES: usize>(
+    pub fn compute_root_from_leaves<
+        H: CircuitHasher<L, D, Digest = Bytes32Variable>,
+        const NB_LEAVES: usize,
+        const LEAF_SIZE_BYTES: usize,
+    >(
         &mut self,
         leaves: Vec<BytesVariable<LEAF_SIZE_BYTES>>,
         leaves_enabled: Vec<BoolVariable>,
@@ -158,8 +178,8 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
         assert!(NB_LEAVES == leaves.len());
         assert!(NB_LEAVES == leaves_enabled.len());
 
-        let hashed_leaves = self.hash_leaves::<LEAF_SIZE_BYTES>(leaves.to_vec());
-        self.get_root_from_hashed_leaves::<NB_LEAVES>(hashed_leaves, leaves_enabled.to_vec())
+        let hashed_leaves = self.hash_leaves::<H, LEAF_SIZE_BYTES>(leaves.to_vec());
+        self.get_root_from_hashed_leaves::<H, NB_LEAVES>(hashed_leaves, leaves_enabled.to_vec())
     }
 }
 
@@ -172,6 +192,7 @@ mod tests {
     use itertools::Itertools;
 
     use crate::backend::circuit::DefaultParameters;
+    use crate::frontend::hash::hasher::CurtaSha256Hasher;
     use crate::frontend::merkle::tree::{InclusionProof, MerkleInclusionProofVariable};
     use crate::prelude::*;
 
@@ -190,7 +211,10 @@ mod tests {
 
         let leaves = builder.read::<ArrayVariable<BytesVariable<48>, 32>>();
         let enabled = builder.read::<ArrayVariable<BoolVariable, 32>>();
-        let root = builder.compute_root_from_leaves::<32, 48>(leaves.as_vec(), enabled.as_vec());
+        let root = builder.compute_root_from_leaves::<CurtaSha256Hasher, 32, 48>(
+            leaves.as_vec(),
+            enabled.as_vec(),
+        );
         builder.write::<Bytes32Variable>(root);
         let circuit = builder.build();
         circuit.test_default_serializers();
@@ -222,7 +246,10 @@ mod tests {
         let proof_variable = builder.read::<MerkleInclusionProofVariable<4, 48>>();
         let path_indices_variable = builder.read::<ArrayVariable<BoolVariable, 4>>();
 
-        let root = builder.get_root_from_merkle_proof(&proof_variable, &path_indices_variable);
+        let root = builder.get_root_from_merkle_proof::<CurtaSha256Hasher, 4, 48>(
+            &proof_variable,
+            &path_indices_variable,
+        );
         builder.write::<Bytes32Variable>(root);
 
         let circuit = builder.build();
@@ -5,10 +5,12 @@ pub mod eth;
 pub mod extension;
 pub mod hash;
 pub mod hint;
+pub mod int;
 pub mod mapreduce;
 pub mod merkle;
 pub mod num;
 pub mod ops;
 pub mod recursion;
+pub mod rsa;
 pub mod uint;
 pub mod vars;
@@ -284,6 +284,9 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBiguint<F, D>
 
         let cmp_rem_b = self.cmp_biguint(&rem, b);
         self.assert_one(cmp_rem_b.target);
+        // `cmp_biguint` only gives `rem <= b`, so also rule out `rem == b`.
+        let rem_is_b = self.is_equal_biguint(&rem, b);
+        self.assert_zero(rem_is_b.target);
 
         (div, rem)
     }
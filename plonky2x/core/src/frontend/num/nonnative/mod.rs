@@ -1,3 +1,4 @@
 #[allow(clippy::module_inception)]
 pub mod nonnative;
 pub mod split_nonnative;
+pub mod variable;
@@ -0,0 +1,305 @@
+//! A nonnative prime field element as a `CircuitVariable`, with its arithmetic exposed through the
+//! operation traits of `CircuitBuilder`.
+//!
+//! Every operation returns a canonical element, i.e. one that is less than the field order, so
+//! `assert_is_equal` and `is_equal` compare field elements. The batched operations sum the
+//! unreduced limbs and reduce once at the end.
+
+use core::marker::PhantomData;
+
+use num::{BigUint, One as NumOne};
+use plonky2::field::types::PrimeField;
+use plonky2::hash::hash_types::RichField;
+use plonky2::util::ceil_div_usize;
+
+use super::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use crate::frontend::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::frontend::num::u32::gadgets::arithmetic_u32::U32Target;
+use crate::prelude::{
+    Add, CircuitBuilder, CircuitVariable, Div, Mul, Neg, One, PlonkParameters, Sub, U32Variable,
+    Variable, Zero,
+};
+
+/// An element of the prime field `FF`, encoded as little-endian u32 limbs.
+#[derive(Debug, Clone)]
+pub struct NonNativeVariable<FF: PrimeField> {
+    pub limbs: Vec<U32Variable>,
+    _marker: PhantomData<FF>,
+}
+
+fn num_limbs<FF: PrimeField>() -> usize {
+    ceil_div_usize(FF::BITS, 32)
+}
+
+impl<FF: PrimeField> NonNativeVariable<FF> {
+    fn from_biguint_target(value: &BigUintTarget) -> Self {
+        assert_eq!(value.num_limbs(), num_limbs::<FF>());
+        Self {
+            limbs: value.limbs.iter().map(|x| U32Variable::from(*x)).collect(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn to_biguint_target(&self) -> BigUintTarget {
+        BigUintTarget {
+            limbs: self.limbs.iter().map(|x| U32Target::from(*x)).collect(),
+        }
+    }
+
+    /// Wraps a `NonNativeTarget` of the raw nonnative gadgets, which must be canonical.
+    pub fn from_target(target: &NonNativeTarget<FF>) -> Self {
+        Self::from_biguint_target(&target.value)
+    }
+
+    /// Converts to a `NonNativeTarget` for the raw nonnative gadgets.
+    pub fn to_target(&self) -> NonNativeTarget<FF> {
+        NonNativeTarget {
+            value: self.to_biguint_target(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<FF: PrimeField> CircuitVariable for NonNativeVariable<FF> {
+    type ValueType<F: RichField> = FF;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            limbs: (0..num_limbs::<FF>())
+                .map(|_| U32Variable::init_unsafe(builder))
+                .collect(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.limbs.iter().map(|x| x.variable).collect()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        assert_eq!(variables.len(), num_limbs::<FF>());
+        Self {
+            limbs: variables
+                .iter()
+                .map(|x| U32Variable::from_variables_unsafe(&[*x]))
+                .collect(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        for limb in self.limbs.iter() {
+            limb.assert_is_valid(builder);
+        }
+        let max = builder
+            .api
+            .constant_biguint(&(FF::order() - BigUint::one()));
+        let is_canonical = builder.api.cmp_biguint(&self.to_biguint_target(), &max);
+        builder.api.assert_one(is_canonical.target);
+    }
+
+    fn nb_elements() -> usize {
+        num_limbs::<FF>()
+    }
+
+    fn elements<F: RichField>(value: FF) -> Vec<F> {
+        let mut limbs = value.to_canonical_biguint().to_u32_digits();
+        limbs.resize(num_limbs::<FF>(), 0);
+        limbs
+            .into_iter()
+            .flat_map(U32Variable::elements::<F>)
+            .collect()
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        let limbs = elements
+            .iter()
+            .map(|x| U32Variable::from_elements(&[*x]))
+            .collect::<Vec<_>>();
+        FF::from_noncanonical_biguint(BigUint::from_slice(&limbs))
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Reduces an unreduced value of any number of limbs modulo the order of `FF`.
+    fn reduce_to_nonnative<FF: PrimeField>(
+        &mut self,
+        value: &BigUintTarget,
+    ) -> NonNativeVariable<FF> {
+        let modulus = self.api.constant_biguint(&FF::order());
+        let remainder = self.api.rem_biguint(value, &modulus);
+        NonNativeVariable::from_biguint_target(&remainder)
+    }
+
+    /// Sums nonnative field elements with a single reduction.
+    pub fn nonnative_add_many<FF: PrimeField>(
+        &mut self,
+        values: &[NonNativeVariable<FF>],
+    ) -> NonNativeVariable<FF> {
+        let mut sum = self.api.zero_biguint();
+        for value in values.iter() {
+            sum = self.api.add_biguint(&sum, &value.to_biguint_target());
+        }
+        self.reduce_to_nonnative(&sum)
+    }
+
+    /// Computes `sum(a[i] * b[i])` over a nonnative field with a single reduction.
+    pub fn nonnative_inner_product<FF: PrimeField>(
+        &mut self,
+        a: &[NonNativeVariable<FF>],
+        b: &[NonNativeVariable<FF>],
+    ) -> NonNativeVariable<FF> {
+        assert_eq!(a.len(), b.len());
+        let mut sum = self.api.zero_biguint();
+        for (x, y) in a.iter().zip(b.iter()) {
+            let product = self
+                .api
+                .mul_biguint(&x.to_biguint_target(), &y.to_biguint_target());
+            sum = self.api.add_biguint(&sum, &product);
+        }
+        self.reduce_to_nonnative(&sum)
+    }
+
+    /// Computes the inverse of a nonzero nonnative field element.
+    pub fn nonnative_inverse<FF: PrimeField>(
+        &mut self,
+        value: NonNativeVariable<FF>,
+    ) -> NonNativeVariable<FF> {
+        // The inverse gadget constrains `value * inverse = 1` but not that the inverse is
+        // canonical.
+        let inverse = self.api.inv_nonnative(&value.to_target());
+        self.reduce_to_nonnative(&inverse.value)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, FF: PrimeField> Zero<L, D> for NonNativeVariable<FF> {
+    fn zero(builder: &mut CircuitBuilder<L, D>) -> Self {
+        builder.constant(FF::ZERO)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, FF: PrimeField> One<L, D> for NonNativeVariable<FF> {
+    fn one(builder: &mut CircuitBuilder<L, D>) -> Self {
+        builder.constant(FF::ONE)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, FF: PrimeField> Add<L, D> for NonNativeVariable<FF> {
+    type Output = Self;
+
+    fn add(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        builder.nonnative_add_many(&[self, rhs])
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, FF: PrimeField> Sub<L, D> for NonNativeVariable<FF> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let negated = builder.neg(rhs);
+        builder.nonnative_add_many(&[self, negated])
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, FF: PrimeField> Neg<L, D> for NonNativeVariable<FF> {
+    type Output = Self;
+
+    fn neg(self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        // The value is canonical, so `order - value` does not underflow, and the reduction maps
+        // `order` to zero.
+        let modulus = builder.api.constant_biguint(&FF::order());
+        let negated = builder.api.sub_biguint(&modulus, &self.to_biguint_target());
+        builder.reduce_to_nonnative(&negated)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, FF: PrimeField> Mul<L, D> for NonNativeVariable<FF> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        builder.nonnative_inner_product(&[self], &[rhs])
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, FF: PrimeField> Div<L, D> for NonNativeVariable<FF> {
+    type Output = Self;
+
+    fn div(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let inverse = builder.nonnative_inverse(rhs);
+        builder.mul(self, inverse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::secp256k1_base::Secp256K1Base;
+    use plonky2::field::types::{PrimeField, Sample};
+
+    use super::NonNativeVariable;
+    use crate::frontend::ecc::ed25519::field::ed25519_base::Ed25519Base;
+    use crate::prelude::*;
+
+    fn test_nonnative_variable<FF: PrimeField>() {
+        let a = FF::rand();
+        let b = FF::rand();
+        let c = FF::rand();
+        let d = FF::rand();
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.read::<NonNativeVariable<FF>>();
+        let b_var = builder.read::<NonNativeVariable<FF>>();
+        let c_var = builder.read::<NonNativeVariable<FF>>();
+        let d_var = builder.read::<NonNativeVariable<FF>>();
+        let results = [
+            builder.add(a_var.clone(), b_var.clone()),
+            builder.sub(a_var.clone(), b_var.clone()),
+            builder.mul(a_var.clone(), b_var.clone()),
+            builder.div(a_var.clone(), b_var.clone()),
+            builder.neg(a_var.clone()),
+            builder.nonnative_add_many(&[a_var.clone(), b_var.clone(), c_var.clone()]),
+            builder.nonnative_inner_product(
+                &[a_var.clone(), b_var.clone()],
+                &[c_var.clone(), d_var.clone()],
+            ),
+        ];
+        for result in results.iter() {
+            builder.write(result.clone());
+        }
+
+        let t = builder._true();
+        let selected = builder.select(t, a_var.clone(), b_var.clone());
+        builder.assert_is_equal(selected, a_var.clone());
+        let zero = builder.zero::<NonNativeVariable<FF>>();
+        let a_minus_a = builder.sub(a_var.clone(), a_var.clone());
+        builder.assert_is_equal(a_minus_a, zero);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<NonNativeVariable<FF>>(a);
+        input.write::<NonNativeVariable<FF>>(b);
+        input.write::<NonNativeVariable<FF>>(c);
+        input.write::<NonNativeVariable<FF>>(d);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let expected_results = [a + b, a - b, a * b, a / b, -a, a + b + c, a * c + b * d];
+        for expected_result in expected_results {
+            assert_eq!(output.read::<NonNativeVariable<FF>>(), expected_result);
+        }
+    }
+
+    #[test]
+    fn test_nonnative_variable_ed25519_base() {
+        test_nonnative_variable::<Ed25519Base>();
+    }
+
+    #[test]
+    fn test_nonnative_variable_secp256k1_base() {
+        test_nonnative_variable::<Secp256K1Base>();
+    }
+}
@@ -126,6 +126,171 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     }
 }
 
+/// The addition operation, which also returns whether the result overflowed.
+///
+/// Types implementing this trait can be used within the `builder.overflowing_add(lhs, rhs)`,
+/// `builder.checked_add(lhs, rhs)` and `builder.wrapping_add(lhs, rhs)` methods.
+pub trait OverflowingAdd<L: PlonkParameters<D>, const D: usize, Rhs = Self> {
+    type Output;
+
+    fn overflowing_add(
+        self,
+        rhs: Rhs,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self::Output, BoolVariable);
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns `lhs + rhs` wrapped around the bit width, along with whether it overflowed.
+    pub fn overflowing_add<Lhs, Rhs>(
+        &mut self,
+        lhs: Lhs,
+        rhs: Rhs,
+    ) -> (<Lhs as OverflowingAdd<L, D, Rhs>>::Output, BoolVariable)
+    where
+        Lhs: OverflowingAdd<L, D, Rhs>,
+    {
+        lhs.overflowing_add(rhs, self)
+    }
+
+    /// Returns `lhs + rhs`, constraining it not to overflow.
+    pub fn checked_add<Lhs, Rhs>(
+        &mut self,
+        lhs: Lhs,
+        rhs: Rhs,
+    ) -> <Lhs as OverflowingAdd<L, D, Rhs>>::Output
+    where
+        Lhs: OverflowingAdd<L, D, Rhs>,
+    {
+        let (result, overflow) = lhs.overflowing_add(rhs, self);
+        let no_overflow = self._false();
+        self.assert_is_equal(overflow, no_overflow);
+        result
+    }
+
+    /// Returns `lhs + rhs` wrapped around the bit width.
+    pub fn wrapping_add<Lhs, Rhs>(
+        &mut self,
+        lhs: Lhs,
+        rhs: Rhs,
+    ) -> <Lhs as OverflowingAdd<L, D, Rhs>>::Output
+    where
+        Lhs: OverflowingAdd<L, D, Rhs>,
+    {
+        lhs.overflowing_add(rhs, self).0
+    }
+}
+
+/// The subtraction operation, which also returns whether the result overflowed.
+///
+/// Types implementing this trait can be used within the `builder.overflowing_sub(lhs, rhs)`,
+/// `builder.checked_sub(lhs, rhs)` and `builder.wrapping_sub(lhs, rhs)` methods.
+pub trait OverflowingSub<L: PlonkParameters<D>, const D: usize, Rhs = Self> {
+    type Output;
+
+    fn overflowing_sub(
+        self,
+        rhs: Rhs,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self::Output, BoolVariable);
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns `lhs - rhs` wrapped around the bit width, along with whether it overflowed.
+    pub fn overflowing_sub<Lhs, Rhs>(
+        &mut self,
+        lhs: Lhs,
+        rhs: Rhs,
+    ) -> (<Lhs as OverflowingSub<L, D, Rhs>>::Output, BoolVariable)
+    where
+        Lhs: OverflowingSub<L, D, Rhs>,
+    {
+        lhs.overflowing_sub(rhs, self)
+    }
+
+    /// Returns `lhs - rhs`, constraining it not to overflow.
+    pub fn checked_sub<Lhs, Rhs>(
+        &mut self,
+        lhs: Lhs,
+        rhs: Rhs,
+    ) -> <Lhs as OverflowingSub<L, D, Rhs>>::Output
+    where
+        Lhs: OverflowingSub<L, D, Rhs>,
+    {
+        let (result, overflow) = lhs.overflowing_sub(rhs, self);
+        let no_overflow = self._false();
+        self.assert_is_equal(overflow, no_overflow);
+        result
+    }
+
+    /// Returns `lhs - rhs` wrapped around the bit width.
+    pub fn wrapping_sub<Lhs, Rhs>(
+        &mut self,
+        lhs: Lhs,
+        rhs: Rhs,
+    ) -> <Lhs as OverflowingSub<L, D, Rhs>>::Output
+    where
+        Lhs: OverflowingSub<L, D, Rhs>,
+    {
+        lhs.overflowing_sub(rhs, self).0
+    }
+}
+
+/// The multiplication operation, which also returns whether the result overflowed.
+///
+/// Types implementing this trait can be used within the `builder.overflowing_mul(lhs, rhs)`,
+/// `builder.checked_mul(lhs, rhs)` and `builder.wrapping_mul(lhs, rhs)` methods.
+pub trait OverflowingMul<L: PlonkParameters<D>, const D: usize, Rhs = Self> {
+    type Output;
+
+    fn overflowing_mul(
+        self,
+        rhs: Rhs,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self::Output, BoolVariable);
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Returns `lhs * rhs` wrapped around the bit width, along with whether it overflowed.
+    pub fn overflowing_mul<Lhs, Rhs>(
+        &mut self,
+        lhs: Lhs,
+        rhs: Rhs,
+    ) -> (<Lhs as OverflowingMul<L, D, Rhs>>::Output, BoolVariable)
+    where
+        Lhs: OverflowingMul<L, D, Rhs>,
+    {
+        lhs.overflowing_mul(rhs, self)
+    }
+
+    /// Returns `lhs * rhs`, constraining it not to overflow.
+    pub fn checked_mul<Lhs, Rhs>(
+        &mut self,
+        lhs: Lhs,
+        rhs: Rhs,
+    ) -> <Lhs as OverflowingMul<L, D, Rhs>>::Output
+    where
+        Lhs: OverflowingMul<L, D, Rhs>,
+    {
+        let (result, overflow) = lhs.overflowing_mul(rhs, self);
+        let no_overflow = self._false();
+        self.assert_is_equal(overflow, no_overflow);
+        result
+    }
+
+    /// Returns `lhs * rhs` wrapped around the bit width.
+    pub fn wrapping_mul<Lhs, Rhs>(
+        &mut self,
+        lhs: Lhs,
+        rhs: Rhs,
+    ) -> <Lhs as OverflowingMul<L, D, Rhs>>::Output
+    where
+        Lhs: OverflowingMul<L, D, Rhs>,
+    {
+        lhs.overflowing_mul(rhs, self).0
+    }
+}
+
 /// A zero element.
 ///
 /// Types implementing this trait can be used via the `builder.zero()` method.
@@ -171,19 +336,17 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     /// The less than operation (<).
     pub fn lt<Lhs, Rhs>(&mut self, lhs: Lhs, rhs: Rhs) -> BoolVariable
     where
-        Lhs: LessThanOrEqual<L, D, Lhs>,
-        Rhs: Sub<L, D, Rhs, Output = Lhs> + One<L, D>,
+        Rhs: LessThanOrEqual<L, D, Lhs>,
     {
-        let one = self.one::<Rhs>();
-        let upper_bound = rhs.sub(one, self);
-        self.lte(lhs, upper_bound)
+        // Comparing with `rhs - 1` would be wrong when `rhs` is the minimum value and wraps.
+        let gte = self.lte(rhs, lhs);
+        self.not(gte)
     }
 
     /// The greater than operation (>).
     pub fn gt<Lhs, Rhs>(&mut self, lhs: Lhs, rhs: Rhs) -> BoolVariable
     where
-        Lhs: Sub<L, D, Lhs, Output = Rhs> + One<L, D>,
-        Rhs: LessThanOrEqual<L, D, Rhs>,
+        Lhs: LessThanOrEqual<L, D, Rhs>,
     {
         self.lt(rhs, lhs)
     }
@@ -191,7 +354,6 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     /// The greater than or equal to operation (>=).
     pub fn gte<Lhs, Rhs>(&mut self, lhs: Lhs, rhs: Rhs) -> BoolVariable
     where
-        Lhs: Sub<L, D, Lhs, Output = Rhs> + One<L, D>,
         Rhs: LessThanOrEqual<L, D, Lhs>,
     {
         self.lte(rhs, lhs)
@@ -200,7 +362,7 @@ impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
     /// The within range operation (lhs <= variable < rhs).
     pub fn within_range<V>(&mut self, variable: V, lhs: V, rhs: V) -> BoolVariable
     where
-        V: LessThanOrEqual<L, D, V> + Sub<L, D, V, Output = V> + One<L, D> + Clone,
+        V: LessThanOrEqual<L, D, V> + Clone,
     {
         let lower_bound_satisfied = self.lte(lhs, variable.clone());
         let upper_bound_satisfied = self.lt(variable, rhs);
@@ -242,6 +404,9 @@ mod tests {
             (10u32, 20u32, false),
             (10u32, 10u32, false),
             (10u32, 5u32, true),
+            (0u32, 10u32, false),
+            (0u32, 0u32, false),
+            (u32::MAX, 0u32, true),
         ];
 
         for test_case in test_cases.iter() {
@@ -271,6 +436,45 @@ mod tests {
             (10u32, 20u32, false),
             (10u32, 10u32, true),
             (10u32, 5u32, true),
+            (0u32, 10u32, false),
+            (0u32, 0u32, true),
+            (u32::MAX, 0u32, true),
+        ];
+
+        for test_case in test_cases.iter() {
+            let mut input = circuit.input();
+            input.write::<U32Variable>(test_case.0);
+            input.write::<U32Variable>(test_case.1);
+            input.write::<BoolVariable>(test_case.2);
+
+            let (proof, output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+        }
+    }
+
+    #[test]
+    fn test_math_lt() {
+        let mut builder = DefaultBuilder::new();
+
+        let v0 = builder.read::<U32Variable>();
+        let v1 = builder.read::<U32Variable>();
+        let result = builder.read::<BoolVariable>();
+        let computed_result = builder.lt(v0, v1);
+        builder.assert_is_equal(result, computed_result);
+
+        let circuit = builder.build();
+
+        // Comparisons against zero and the maximum value used to wrap when computing `rhs - 1`.
+        let test_cases = [
+            (10u32, 20u32, true),
+            (10u32, 10u32, false),
+            (10u32, 5u32, false),
+            (10u32, 0u32, false),
+            (0u32, 0u32, false),
+            (0u32, 1u32, true),
+            (u32::MAX, 0u32, false),
+            (u32::MAX - 1, u32::MAX, true),
+            (u32::MAX, u32::MAX, false),
         ];
 
         for test_case in test_cases.iter() {
@@ -283,4 +487,39 @@ mod tests {
             circuit.verify(&proof, &input, &output);
         }
     }
+
+    #[test]
+    fn test_math_within_range() {
+        let mut builder = DefaultBuilder::new();
+
+        let variable = builder.read::<U32Variable>();
+        let lhs = builder.read::<U32Variable>();
+        let rhs = builder.read::<U32Variable>();
+        let result = builder.read::<BoolVariable>();
+        let computed_result = builder.within_range(variable, lhs, rhs);
+        builder.assert_is_equal(result, computed_result);
+
+        let circuit = builder.build();
+
+        let test_cases = [
+            (5u32, 0u32, 10u32, true),
+            (0u32, 0u32, 10u32, true),
+            (10u32, 0u32, 10u32, false),
+            (0u32, 0u32, 0u32, false),
+            (7u32, 5u32, 0u32, false),
+            (u32::MAX, 0u32, u32::MAX, false),
+            (u32::MAX - 1, 0u32, u32::MAX, true),
+        ];
+
+        for test_case in test_cases.iter() {
+            let mut input = circuit.input();
+            input.write::<U32Variable>(test_case.0);
+            input.write::<U32Variable>(test_case.1);
+            input.write::<U32Variable>(test_case.2);
+            input.write::<BoolVariable>(test_case.3);
+
+            let (proof, output) = circuit.prove(&input);
+            circuit.verify(&proof, &input, &output);
+        }
+    }
 }
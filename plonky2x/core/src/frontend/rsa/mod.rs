@@ -0,0 +1,262 @@
+//! RSA signature verification with SHA-256, following RFC 8017.
+//!
+//! A signature is checked by raising it to the public exponent modulo the key, and matching the
+//! big-endian bytes of the result against the PKCS#1 v1.5 or PSS encoding of the message hash.
+//! The modulus must have exactly `32 * LIMBS` bits, e.g. `RSA2048_LIMBS` for a 2048-bit key.
+
+use crate::frontend::vars::EvmVariable;
+use crate::prelude::{
+    BigUintVariable, ByteVariable, Bytes32Variable, CircuitBuilder, PlonkParameters,
+};
+
+/// The number of limbs of a 2048-bit modulus.
+pub const RSA2048_LIMBS: usize = 64;
+
+/// The number of limbs of a 4096-bit modulus.
+pub const RSA4096_LIMBS: usize = 128;
+
+/// The length of a SHA-256 digest in bytes.
+const HASH_LEN: usize = 32;
+
+/// The DER encoding of the SHA-256 `DigestInfo` that precedes the digest in PKCS#1 v1.5.
+const SHA256_DIGEST_INFO_PREFIX: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes `signature^exponent mod modulus` for a constant public exponent and returns its
+    /// big-endian bytes.
+    ///
+    /// Also constrains the top bit of the modulus to be set and the signature to be less than the
+    /// modulus.
+    fn rsa_encoded_message<const LIMBS: usize>(
+        &mut self,
+        signature: BigUintVariable<LIMBS>,
+        modulus: BigUintVariable<LIMBS>,
+        exponent: u32,
+    ) -> Vec<ByteVariable> {
+        assert!(exponent > 0, "the public exponent must be positive");
+        let t = self._true();
+        let modulus_top_bit = modulus.limbs[LIMBS - 1].to_be_bits(self)[0];
+        self.assert_is_equal(modulus_top_bit, t);
+        let signature_lt_modulus = self.lt(signature, modulus);
+        self.assert_is_equal(signature_lt_modulus, t);
+
+        // Square-and-multiply over the bits of the exponent below its leading one.
+        let mut message = signature;
+        for i in (0..31 - exponent.leading_zeros()).rev() {
+            message = self.mulmod(message, message, modulus);
+            if (exponent >> i) & 1 == 1 {
+                message = self.mulmod(message, signature, modulus);
+            }
+        }
+        message.encode(self)
+    }
+
+    /// Verifies an RSASSA-PKCS1-v1_5 signature of the SHA-256 digest `message_hash`.
+    pub fn verify_rsa_pkcs1v15_sha256<const LIMBS: usize>(
+        &mut self,
+        message_hash: Bytes32Variable,
+        signature: BigUintVariable<LIMBS>,
+        modulus: BigUintVariable<LIMBS>,
+        exponent: u32,
+    ) {
+        let encoded = self.rsa_encoded_message(signature, modulus, exponent);
+
+        // EM = 0x00 || 0x01 || PS || 0x00 || DigestInfo, where PS is at least eight 0xff bytes.
+        let prefix_len = encoded.len() - HASH_LEN;
+        let padding_len = prefix_len - SHA256_DIGEST_INFO_PREFIX.len() - 3;
+        assert!(padding_len >= 8, "the modulus is too short");
+        let mut expected_prefix = vec![0x00, 0x01];
+        expected_prefix.extend(vec![0xff; padding_len]);
+        expected_prefix.push(0x00);
+        expected_prefix.extend(SHA256_DIGEST_INFO_PREFIX);
+
+        for (byte, expected_byte) in encoded.iter().zip(expected_prefix) {
+            let expected_byte = self.constant::<ByteVariable>(expected_byte);
+            self.assert_is_equal(*byte, expected_byte);
+        }
+        for (byte, hash_byte) in encoded[prefix_len..].iter().zip(message_hash.as_bytes()) {
+            self.assert_is_equal(*byte, hash_byte);
+        }
+    }
+
+    /// Verifies an RSASSA-PSS signature of the SHA-256 digest `message_hash`, with MGF1 over
+    /// SHA-256 and a salt of `salt_len` bytes.
+    pub fn verify_rsa_pss_sha256<const LIMBS: usize>(
+        &mut self,
+        message_hash: Bytes32Variable,
+        signature: BigUintVariable<LIMBS>,
+        modulus: BigUintVariable<LIMBS>,
+        exponent: u32,
+        salt_len: usize,
+    ) {
+        let encoded = self.rsa_encoded_message(signature, modulus, exponent);
+        let em_len = encoded.len();
+        assert!(
+            em_len >= HASH_LEN + salt_len + 2,
+            "the modulus is too short"
+        );
+
+        // The encoded message has one bit less than the modulus, so its top bit is zero.
+        let f = self._false();
+        self.assert_is_equal(encoded[0].0[0], f);
+        let trailer = self.constant::<ByteVariable>(0xbc);
+        self.assert_is_equal(encoded[em_len - 1], trailer);
+
+        // EM = maskedDB || H || 0xbc.
+        let db_len = em_len - HASH_LEN - 1;
+        let (masked_db, rest) = encoded.split_at(db_len);
+        let hash = &rest[..HASH_LEN];
+
+        // The mask is MGF1(H), the concatenation of SHA-256(H || counter) for big-endian counters.
+        let mut db_mask = Vec::with_capacity(db_len.div_ceil(HASH_LEN) * HASH_LEN);
+        for counter in 0..db_len.div_ceil(HASH_LEN) as u32 {
+            let mut input = hash.to_vec();
+            for byte in counter.to_be_bytes() {
+                input.push(self.constant::<ByteVariable>(byte));
+            }
+            db_mask.extend(self.curta_sha256(&input).as_bytes());
+        }
+        let mut db = masked_db
+            .iter()
+            .zip(db_mask)
+            .map(|(x, y)| self.xor(*x, y))
+            .collect::<Vec<_>>();
+        db[0].0[0] = f;
+
+        // DB = PS || 0x01 || salt, where PS is all zeros.
+        let ps_len = db_len - salt_len - 1;
+        let zero = self.constant::<ByteVariable>(0x00);
+        for byte in db[..ps_len].iter() {
+            self.assert_is_equal(*byte, zero);
+        }
+        let one = self.constant::<ByteVariable>(0x01);
+        self.assert_is_equal(db[ps_len], one);
+        let salt = &db[ps_len + 1..];
+
+        // H = SHA-256(0x00 * 8 || mHash || salt).
+        let mut m_prime = vec![zero; 8];
+        m_prime.extend(message_hash.as_bytes());
+        m_prime.extend_from_slice(salt);
+        let expected_hash = self.curta_sha256(&m_prime);
+        for (byte, expected_byte) in hash.iter().zip(expected_hash.as_bytes()) {
+            self.assert_is_equal(*byte, expected_byte);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use super::{RSA2048_LIMBS, RSA4096_LIMBS};
+    use crate::prelude::*;
+
+    // A 2048-bit key with public exponent 65537, and signatures of `b"plonky2x rsa test"`.
+    const MODULUS: &str = "bd427a2214dfc9e81bbcbf260fac68f5dad1a5f489e5e1fe4c3339a0b4d8b99f91f0859786275c9cd82b6ed3457596797ed84aa7555fe08fba98f2ad3a6242652e84dfe8dcd1aa072fb8e3de456cbb098dce490c67ee336b6ed5fcfe5b0031f662b5e8b6674429abfbe619a6e73f2295b0af4a486d4b242dc2c84049fb0e607c88e085e3b2c2093e45e6693652459320598a193dd6f0aa7a104188d530777d52433ea13f6a8ad010efe9414724a6f0c45860f72982d992185d1eff80a15261812ced7d70f91d941f5ba241bf84db46af6ea2041251aeaea1210f3c23c591e0e0c0a2ffd635f1bb7cd0db4dd4808a5ac8ae987224caf366a950dc6126b558a0a7";
+    const PKCS1V15_SIGNATURE: &str = "6084da511b3e6257aac6cfd937a643c1f3401456cd8d5709bda142945edec077298de78b234df740d71e03dec54b53e4d8425da0a5861ca2fbe7aae7fda396c1299fb592c743faafbabeba1a429695dcf1b7769eff0a433f002477b23381eb64fdef0a737c17ec3f0a1c63e4a32d3fa5153adfb2091a3ed6616c0b726202ab149c1c9980077b49c3e5e77ab913a789212949ddb545cf07ade294a344dc35560dc51ecd9e4a95970b40906a25eebf50a31548b98735f2a62c1871a66cd0f6e0cd7f2daf525598c6de7dd9b432fc691a5c3a4a5f96765d1862612bc6169da856738b1ea0363aea593d901bf74c8c452a02a7beb68b2f8f27faa4ffae42cd1c7fed";
+    const PSS_SIGNATURE: &str = "40db9de1b7ee2ea0772ff4b9eade2f5a3a29fa4788ffd088aa18de748e5143688f3c930b0304804e65b2f446afc6914ba4cea204db6fd4770bab8c417c39d247917c43e4bbedf555b1ecc5f25df64c4ea91ff8af715f48127f9113201bcc874cfcbb5089c4d6d9f1aa7328b8e288b6556efeca4b3c663feff91530ff34b253dbbeb2dab92de3c294044f85cf63a03bdbd70f11026217e7d7c4253d5f664402d45f2a107b1eb57449e1608a68107ea9ce37db576baf6b59c46efca1ffc3f34526675c13638c67b93e8f1f342358397c367cb9c132a881344f3d4f5ee7130384e7b931c29bc68446e791d4a50a9f5ae1ae8451108d47448e3c0fe24ec7b43a1eda";
+    const MESSAGE_HASH: &str = "0xa8924409f720f65f0aff945076cf81eabef321f2f3459b426011b3e6af0c402c";
+
+    // A 4096-bit key with public exponent 65537, and signatures of the same message.
+    const RSA4096_MODULUS: &str = "a2b042a3e03e66e2a163ba4773d7baa56927f03759067c3571e3a5a327ba13d3bae0c52273923eba334e4edc98cb59089a6af94a7b573275c07765d53863995a29b7b3e554a9dcf7a9051aa7a96615d9a5e6b8f958d1444d215cd39fcb33dab7d0a77ae3f65b9ba6eaa78c5ba897a9c084c9dd970d04c90b06648a48cdd9d2522b81984628483e1c85fe316b0f7f9708ba3196eae2e261b216835904ad24348cfd8faf34cc6f670f35716fecf572772d8edac66543a63caacd5a5040e2626b175c8cf1a46c2eaf27a53e38fd68aa4b5a97136fc2dc2150b39355e1150cdbbfd5e2cfbd6a95d0f04aaf5e919774823f87526a9ae1ac55d596a8469633d185a46d8524c6eeede60a267dde830f371b6516dbe3717f64fbb47b98ae55056c9dc8f72a93462a94b9af7ffed46d3b687d9a1089bdd8cd1e47f5852940497bb89a1bccce3a4125a81d6a019ac90ad6a6e130bcc137dca2d01c1d03e55bee6916166c99f02475f1fbeeff1afd5bebfd83e8baaa2112bd9874871d810b18fe439429075c17c233954166aa670f06fa939e1824b929e9b1543908d81ed0cdfc592b5c6299719f24ad2640ab0218aa98ea15324be63ecc092c7b1d7801b4b460ee4b9093ce20429be87c91c23262a14de0dc483acf73a9b14d99544134beed964674beb3e2733a7d0372870f0972aadcd8a2c49c4eb61fc92cc9462aafc470245e0288ae6b";
+    const RSA4096_PKCS1V15_SIGNATURE: &str = "4769ce37b7c1819fa1c60c60d45fb34fa7ea8372a4fd1c3fccd313cbf189c5f1b66dc7a8c3036d5ebd6e0e008e928e8b844de2562977267b442afd001c441d23142d16a4e35872b2593210862c8e84a3a9494050e19b85f2d9689fbe962f3b2e90af33483c5700cbda95666e96ceb9d1c3de2d6dd7423f669e1077322583cc82ad186c14cdc2a678571b3fad8779ba2cbfd67b91ad167c1d9244e1626dc62a148c6ba98b2cd4ebced2ac65a37b9fb3ded6ab535e65297747685e48c87181d7cb18d6fab40471b8ab5f8ff70564f6bfec6cc5520ea1a20e31cdb3229718e31fa183d758c69df82367b0d47ecf755e266498080f221748a4340c13e875f0cee6433d55612c59115782369d0e3f8faa1758b7509ac0fb9d68291ff8223588a6ae9fb3efa37f5100cb3e3984e761ae6fb610d139ce85c4d5cce0f1bb2443f4827dea099efd26ee7f251a97c5e513128f38ef31c847006bd33e91779acea86ec1cbcf02857e124bfab6e0a94eb16d5160b95e7025020a291eec6490f79359b795ea3d4bf528513f7c89d36170be58de03a452c1c01718e7c852a99756d784c9a3597b80e10cdad260eaa19f17e4dba388c80de17f6d933a852ef3d11f640e6402d8c01965186fd30c73fc3a0b21ef46a2585e5b13cc6979a389b25b052b82c9992aa482c5b415ca8cb03c7b572b95baa79217c22e93f7b3e433583c4f453d847feeaa";
+    const RSA4096_PSS_SIGNATURE: &str = "a0acc1fb1f04bcb5f6e47e4312e2a31a94c41236cedd81442c67c36688399094e7d79f06ec04605f0deb54c108277e1c91236830d6ee6453ef552d2270a1f4d4824521ac84b75e44f881569d785dca655707bb29d1dfd1198678ae1249bf0c19239f0702147773c9d309414078bb695c14fae6ead5e5c27ffbb085ba1c50efa10313b75e800fbd665359018502c7b31de7f3020f7fb87d3d111f033e2811dc2d0bf9c4c8d961522ecf594bd0b8c36529a766468f4eca094968f766f8ad8991fce296d9456968e2f930e67914a8447452fd430abb6ffe5c1e428730f0e0c2cd261b91ce2de8d07043b075574e3aa52db306ac53f624fbdb3818720775b039b88720cb3e6c689666d992bdd15bc9df5bd161b1ca9ca72faffafc3fedd8a866c24da95a8430619184dd461a0b7733f2e7d7ef15fba90f617d7b684b36826d05a33511cbcb2c59abb9f40be1e634110d7e05b9f0de951e4a56cd196cdfe023b19ed135493cc9e2a55c44e0db48fc5467808afd356f0cf7d548bbee7f7202fa8846d7a6858405e6938b0a61e95b77635184aa062f09e88f567d64b0c0a0d80edadfe6e84c0e3b3712016ac1e75b515d1876df0b0941816f8840da9befd0689050faa26f76967afa38f1de37474dc3b55800f49604e927e7850921f7fc308369f872faef06e3556ae1962a4168f7134ee1ae4319fa68e37459abe1c82fb351c7c7df2e";
+
+    fn biguint(hex: &str) -> BigUint {
+        BigUint::parse_bytes(hex.as_bytes(), 16).unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_rsa_pkcs1v15_sha256() {
+        let mut builder = DefaultBuilder::new();
+        let message_hash = builder.read::<Bytes32Variable>();
+        let signature = builder.read::<BigUintVariable<RSA2048_LIMBS>>();
+        let modulus = builder.read::<BigUintVariable<RSA2048_LIMBS>>();
+        builder.verify_rsa_pkcs1v15_sha256(message_hash, signature, modulus, 65537);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(bytes32!(MESSAGE_HASH));
+        input.write::<BigUintVariable<RSA2048_LIMBS>>(biguint(PKCS1V15_SIGNATURE));
+        input.write::<BigUintVariable<RSA2048_LIMBS>>(biguint(MODULUS));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_rsa_pss_sha256() {
+        let mut builder = DefaultBuilder::new();
+        let message_hash = builder.read::<Bytes32Variable>();
+        let signature = builder.read::<BigUintVariable<RSA2048_LIMBS>>();
+        let modulus = builder.read::<BigUintVariable<RSA2048_LIMBS>>();
+        builder.verify_rsa_pss_sha256(message_hash, signature, modulus, 65537, 32);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(bytes32!(MESSAGE_HASH));
+        input.write::<BigUintVariable<RSA2048_LIMBS>>(biguint(PSS_SIGNATURE));
+        input.write::<BigUintVariable<RSA2048_LIMBS>>(biguint(MODULUS));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_rsa4096_pkcs1v15_sha256() {
+        let mut builder = DefaultBuilder::new();
+        let message_hash = builder.read::<Bytes32Variable>();
+        let signature = builder.read::<BigUintVariable<RSA4096_LIMBS>>();
+        let modulus = builder.read::<BigUintVariable<RSA4096_LIMBS>>();
+        builder.verify_rsa_pkcs1v15_sha256(message_hash, signature, modulus, 65537);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(bytes32!(MESSAGE_HASH));
+        input.write::<BigUintVariable<RSA4096_LIMBS>>(biguint(RSA4096_PKCS1V15_SIGNATURE));
+        input.write::<BigUintVariable<RSA4096_LIMBS>>(biguint(RSA4096_MODULUS));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_rsa4096_pss_sha256() {
+        let mut builder = DefaultBuilder::new();
+        let message_hash = builder.read::<Bytes32Variable>();
+        let signature = builder.read::<BigUintVariable<RSA4096_LIMBS>>();
+        let modulus = builder.read::<BigUintVariable<RSA4096_LIMBS>>();
+        builder.verify_rsa_pss_sha256(message_hash, signature, modulus, 65537, 32);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(bytes32!(MESSAGE_HASH));
+        input.write::<BigUintVariable<RSA4096_LIMBS>>(biguint(RSA4096_PSS_SIGNATURE));
+        input.write::<BigUintVariable<RSA4096_LIMBS>>(biguint(RSA4096_MODULUS));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_verify_rsa_pkcs1v15_sha256_wrong_signature() {
+        let mut builder = DefaultBuilder::new();
+        let message_hash = builder.read::<Bytes32Variable>();
+        let signature = builder.read::<BigUintVariable<RSA2048_LIMBS>>();
+        let modulus = builder.read::<BigUintVariable<RSA2048_LIMBS>>();
+        builder.verify_rsa_pkcs1v15_sha256(message_hash, signature, modulus, 65537);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<Bytes32Variable>(bytes32!(MESSAGE_HASH));
+        input.write::<BigUintVariable<RSA2048_LIMBS>>(biguint(PSS_SIGNATURE));
+        input.write::<BigUintVariable<RSA2048_LIMBS>>(biguint(MODULUS));
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
@@ -0,0 +1,419 @@
+use num::BigUint;
+use plonky2::hash::hash_types::RichField;
+
+use super::bitwise::ShiftKind;
+use super::UintVariable;
+use crate::frontend::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::frontend::num::u32::gadgets::arithmetic_u32::U32Target;
+use crate::frontend::vars::{EvmVariable, U32Variable};
+use crate::prelude::{
+    Add, BitAnd, BitOr, BitXor, BoolVariable, ByteVariable, CircuitBuilder, CircuitVariable, Div,
+    LessThanOrEqual, Mul, Not, One, OverflowingAdd, OverflowingMul, OverflowingSub,
+    PlonkParameters, Rem, RotateLeft, RotateRight, Shl, Shr, Sub, Variable, Zero,
+};
+
+/// An unsigned integer of `32 * LIMBS` bits encoded as little-endian u32 limbs.
+///
+/// This is the variable-width counterpart of the fixed-width integer variables, for values such as
+/// RSA moduli. As with them, `add`, `sub` and `mul` wrap around the bit width.
+#[derive(Debug, Clone, Copy)]
+pub struct BigUintVariable<const LIMBS: usize> {
+    pub limbs: [U32Variable; LIMBS],
+}
+
+impl<const LIMBS: usize> BigUintVariable<LIMBS> {
+    fn to_biguint_target(self) -> BigUintTarget {
+        BigUintTarget {
+            limbs: self.limbs.iter().map(|x| U32Target::from(*x)).collect(),
+        }
+    }
+
+    /// Takes the low `LIMBS` limbs of `target`.
+    fn from_biguint_target(target: &BigUintTarget) -> Self {
+        Self {
+            limbs: std::array::from_fn(|i| target.limbs[i].into()),
+        }
+    }
+}
+
+impl<const LIMBS: usize> CircuitVariable for BigUintVariable<LIMBS> {
+    type ValueType<F: RichField> = BigUint;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            limbs: std::array::from_fn(|_| U32Variable::init_unsafe(builder)),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.limbs.iter().map(|x| x.variable).collect()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        assert_eq!(variables.len(), LIMBS);
+        Self {
+            limbs: std::array::from_fn(|i| U32Variable::from_variables_unsafe(&[variables[i]])),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        for limb in self.limbs.iter() {
+            limb.assert_is_valid(builder);
+        }
+    }
+
+    fn nb_elements() -> usize {
+        LIMBS
+    }
+
+    fn elements<F: RichField>(value: BigUint) -> Vec<F> {
+        let mut limbs = value.to_u32_digits();
+        assert!(
+            limbs.len() <= LIMBS,
+            "value does not fit in {} limbs",
+            LIMBS
+        );
+        limbs.resize(LIMBS, 0);
+        limbs
+            .into_iter()
+            .flat_map(U32Variable::elements::<F>)
+            .collect()
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        assert_eq!(elements.len(), LIMBS);
+        let limbs = elements
+            .iter()
+            .map(|x| U32Variable::from_elements(&[*x]))
+            .collect::<Vec<_>>();
+        BigUint::from_slice(&limbs)
+    }
+}
+
+impl<const LIMBS: usize> EvmVariable for BigUintVariable<LIMBS> {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        self.limbs
+            .iter()
+            .rev()
+            .flat_map(|x| x.encode(builder))
+            .collect::<Vec<_>>()
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        assert_eq!(bytes.len(), LIMBS * 4);
+        let mut limbs: [U32Variable; LIMBS] =
+            std::array::from_fn(|i| U32Variable::decode(builder, &bytes[i * 4..(i + 1) * 4]));
+        limbs.reverse();
+        Self { limbs }
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        let bytes = value.to_bytes_be();
+        assert!(
+            bytes.len() <= LIMBS * 4,
+            "value does not fit in {} limbs",
+            LIMBS
+        );
+        let mut padded = vec![0u8; LIMBS * 4 - bytes.len()];
+        padded.extend(bytes);
+        padded
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        BigUint::from_bytes_be(bytes)
+    }
+}
+
+impl<const LIMBS: usize> UintVariable for BigUintVariable<LIMBS> {
+    fn limbs(&self) -> Vec<U32Variable> {
+        self.limbs.to_vec()
+    }
+
+    fn from_limbs(limbs: &[U32Variable]) -> Self {
+        Self {
+            limbs: limbs.try_into().unwrap(),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Zero<L, D>
+    for BigUintVariable<LIMBS>
+{
+    fn zero(builder: &mut CircuitBuilder<L, D>) -> Self {
+        let zero = U32Variable::zero(builder);
+        Self {
+            limbs: [zero; LIMBS],
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> One<L, D>
+    for BigUintVariable<LIMBS>
+{
+    fn one(builder: &mut CircuitBuilder<L, D>) -> Self {
+        let mut value = Self::zero(builder);
+        value.limbs[0] = U32Variable::one(builder);
+        value
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Add<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        builder.overflowing_add(self, rhs).0
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Sub<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        builder.overflowing_sub(self, rhs).0
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Mul<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let product = builder
+            .api
+            .mul_biguint(&self.to_biguint_target(), &rhs.to_biguint_target());
+        Self::from_biguint_target(&product)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Div<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let quotient = builder
+            .api
+            .div_biguint(&self.to_biguint_target(), &rhs.to_biguint_target());
+        Self::from_biguint_target(&quotient)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Rem<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn rem(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let remainder = builder
+            .api
+            .rem_biguint(&self.to_biguint_target(), &rhs.to_biguint_target());
+        Self::from_biguint_target(&remainder)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> OverflowingAdd<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn overflowing_add(
+        self,
+        rhs: Self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self::Output, BoolVariable) {
+        let (limbs, overflow) = builder.overflowing_add_limbs(&self.limbs, &rhs.limbs);
+        (Self::from_limbs(&limbs), overflow)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> OverflowingSub<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn overflowing_sub(
+        self,
+        rhs: Self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self::Output, BoolVariable) {
+        let (limbs, overflow) = builder.overflowing_sub_limbs(&self.limbs, &rhs.limbs);
+        (Self::from_limbs(&limbs), overflow)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> OverflowingMul<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn overflowing_mul(
+        self,
+        rhs: Self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self::Output, BoolVariable) {
+        let (limbs, overflow) = builder.overflowing_mul_limbs(&self.limbs, &rhs.limbs);
+        (Self::from_limbs(&limbs), overflow)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> LessThanOrEqual<L, D>
+    for BigUintVariable<LIMBS>
+{
+    fn lte(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> BoolVariable {
+        builder
+            .api
+            .cmp_biguint(&self.to_biguint_target(), &rhs.to_biguint_target())
+            .into()
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> Not<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn not(self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        Self {
+            limbs: self.limbs.map(|limb| builder.not(limb)),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> BitAnd<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn bitand(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        builder.uint_bitwise(self, rhs, CircuitBuilder::and::<BoolVariable, BoolVariable>)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> BitOr<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn bitor(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        builder.uint_bitwise(self, rhs, CircuitBuilder::or::<BoolVariable, BoolVariable>)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> BitXor<L, D>
+    for BigUintVariable<LIMBS>
+{
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        builder.uint_bitwise(self, rhs, CircuitBuilder::xor::<BoolVariable, BoolVariable>)
+    }
+}
+
+/// Shifts and rotations are by constant amounts only, as the barrel shifter for variable amounts
+/// needs a power of two width.
+macro_rules! impl_biguint_shift {
+    ($trait:ident, $method:ident, $kind:ident) => {
+        impl<L: PlonkParameters<D>, const D: usize, const LIMBS: usize> $trait<L, D, usize>
+            for BigUintVariable<LIMBS>
+        {
+            type Output = Self;
+
+            fn $method(self, rhs: usize, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                builder.uint_shift(self, rhs, ShiftKind::$kind)
+            }
+        }
+    };
+}
+
+impl_biguint_shift!(Shl, shl, Left);
+impl_biguint_shift!(Shr, shr, Right);
+impl_biguint_shift!(RotateLeft, rotate_left, RotateLeft);
+impl_biguint_shift!(RotateRight, rotate_right, RotateRight);
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use rand::Rng;
+
+    use crate::prelude::*;
+
+    const LIMBS: usize = 3;
+
+    fn random_biguint(rng: &mut impl Rng) -> BigUint {
+        BigUint::from_slice(&rng.gen::<[u32; LIMBS]>())
+    }
+
+    #[test]
+    fn test_biguint_variable() {
+        let mut rng = rand::thread_rng();
+        let a = random_biguint(&mut rng);
+        let b = random_biguint(&mut rng) >> rng.gen_range(0..32 * LIMBS);
+        let b = b.max(BigUint::from(1u32));
+        let modulus = BigUint::from(1u32) << (32 * LIMBS);
+        let mask = &modulus - 1u32;
+        let shift = rng.gen_range(0..32 * LIMBS);
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.read::<BigUintVariable<LIMBS>>();
+        let b_var = builder.read::<BigUintVariable<LIMBS>>();
+        let results = [
+            builder.add(a_var, b_var),
+            builder.sub(a_var, b_var),
+            builder.mul(a_var, b_var),
+            builder.div(a_var, b_var),
+            builder.rem(a_var, b_var),
+            builder.and(a_var, b_var),
+            builder.xor(a_var, b_var),
+            builder.not(a_var),
+            builder.shl(a_var, shift),
+            builder.shr(a_var, shift),
+            builder.mulmod(a_var, a_var, b_var),
+        ];
+        for result in results {
+            builder.write(result);
+        }
+        let a_lte_b = builder.lte(a_var, b_var);
+        builder.write(a_lte_b);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<BigUintVariable<LIMBS>>(a.clone());
+        input.write::<BigUintVariable<LIMBS>>(b.clone());
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let expected_results = [
+            (&a + &b) & &mask,
+            (&a + &modulus - &b) & &mask,
+            (&a * &b) & &mask,
+            &a / &b,
+            &a % &b,
+            &a & &b,
+            &a ^ &b,
+            &a ^ &mask,
+            (&a << shift) & &mask,
+            &a >> shift,
+            (&a * &a) % &b,
+        ];
+        for expected_result in expected_results {
+            assert_eq!(output.read::<BigUintVariable<LIMBS>>(), expected_result);
+        }
+        assert_eq!(output.read::<BoolVariable>(), a <= b);
+    }
+}
@@ -0,0 +1,185 @@
+//! Bit counting for the unsigned integer variables.
+//!
+//! The counts are read off the bit decomposition of the value: a running OR over the bits marks
+//! every position at or past the first set bit, and the count is the number of unmarked positions.
+//! As with the methods of `u64`, the counts are `U32Variable`s and the zero value has as many
+//! leading and trailing zeros as the type has bits.
+
+use plonky2::field::types::Field;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::uint::UintVariable;
+use crate::frontend::vars::{BoolVariable, CircuitVariable, U32Variable, Variable};
+use crate::prelude::CircuitBuilder;
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Counts the bits before the first set bit, and returns whether any bit is set.
+    fn count_until_set(&mut self, bits: &[BoolVariable]) -> (Variable, BoolVariable) {
+        let mut seen = self._false();
+        let mut count = self.zero::<Variable>();
+        for bit in bits.iter() {
+            seen = self.or(seen, *bit);
+            let not_seen = self.not(seen);
+            count = self.add(count, not_seen.variable);
+        }
+        (count, seen)
+    }
+
+    /// The number of leading zeros in the binary representation of `value`.
+    pub fn leading_zeros<V: UintVariable>(&mut self, value: V) -> U32Variable {
+        let bits = value.to_be_bits(self);
+        let (count, _) = self.count_until_set(&bits);
+        U32Variable::from_variables_unsafe(&[count])
+    }
+
+    /// The number of trailing zeros in the binary representation of `value`.
+    pub fn trailing_zeros<V: UintVariable>(&mut self, value: V) -> U32Variable {
+        let bits = value.to_le_bits(self);
+        let (count, _) = self.count_until_set(&bits);
+        U32Variable::from_variables_unsafe(&[count])
+    }
+
+    /// The number of bits needed to represent `value`, which is zero for zero.
+    pub fn bit_length<V: UintVariable>(&mut self, value: V) -> U32Variable {
+        let bits = value.to_be_bits(self);
+        let (leading_zeros, _) = self.count_until_set(&bits);
+        let nb_bits = self.constant::<Variable>(L::Field::from_canonical_usize(bits.len()));
+        let bit_length = self.sub(nb_bits, leading_zeros);
+        U32Variable::from_variables_unsafe(&[bit_length])
+    }
+
+    /// Computes `floor(log2(value))`, i.e. the index of the most significant set bit. The value
+    /// must be nonzero.
+    pub fn floor_log2<V: UintVariable>(&mut self, value: V) -> U32Variable {
+        let bits = value.to_be_bits(self);
+        let (leading_zeros, is_nonzero) = self.count_until_set(&bits);
+        let t = self._true();
+        self.assert_is_equal(is_nonzero, t);
+        let max_index = self.constant::<Variable>(L::Field::from_canonical_usize(bits.len() - 1));
+        let log2 = self.sub(max_index, leading_zeros);
+        U32Variable::from_variables_unsafe(&[log2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::U256;
+    use rand::Rng;
+
+    use crate::prelude::*;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bit_counts_small() {
+        const MAX: u32 = 1 << 10;
+
+        let mut builder = DefaultBuilder::new();
+        for _ in 0..MAX {
+            let value = builder.read::<U32Variable>();
+            let leading_zeros = builder.leading_zeros(value);
+            let trailing_zeros = builder.trailing_zeros(value);
+            let bit_length = builder.bit_length(value);
+            builder.write(leading_zeros);
+            builder.write(trailing_zeros);
+            builder.write(bit_length);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for value in 0..MAX {
+            input.write::<U32Variable>(value);
+        }
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for value in 0..MAX {
+            assert_eq!(output.read::<U32Variable>(), value.leading_zeros());
+            assert_eq!(output.read::<U32Variable>(), value.trailing_zeros());
+            assert_eq!(output.read::<U32Variable>(), 32 - value.leading_zeros());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_bit_counts_u64() {
+        // Every bit length, with the values around each power of two.
+        let mut values = vec![0u64, u64::MAX];
+        for i in 0..64 {
+            values.extend([1 << i, (1 << i) - 1, (1 << i) + 1, (1 << i) | (1 << 63)]);
+        }
+
+        let mut builder = DefaultBuilder::new();
+        for _ in values.iter() {
+            let value = builder.read::<U64Variable>();
+            let leading_zeros = builder.leading_zeros(value);
+            let trailing_zeros = builder.trailing_zeros(value);
+            let bit_length = builder.bit_length(value);
+            builder.write(leading_zeros);
+            builder.write(trailing_zeros);
+            builder.write(bit_length);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for value in values.iter() {
+            input.write::<U64Variable>(*value);
+        }
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for value in values {
+            assert_eq!(output.read::<U32Variable>(), value.leading_zeros());
+            assert_eq!(output.read::<U32Variable>(), value.trailing_zeros());
+            assert_eq!(output.read::<U32Variable>(), 64 - value.leading_zeros());
+        }
+    }
+
+    #[test]
+    fn test_floor_log2_u256() {
+        let mut rng = rand::thread_rng();
+        let values = [
+            U256::one(),
+            U256::MAX,
+            U256(rng.gen::<[u64; 4]>()),
+            (U256(rng.gen::<[u64; 4]>()) >> rng.gen_range(0..255)).max(U256::one()),
+            U256::one() << rng.gen_range(0..256),
+        ];
+
+        let mut builder = DefaultBuilder::new();
+        for _ in values.iter() {
+            let value = builder.read::<U256Variable>();
+            let log2 = builder.floor_log2(value);
+            let trailing_zeros = builder.trailing_zeros(value);
+            builder.write(log2);
+            builder.write(trailing_zeros);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for value in values.iter() {
+            input.write::<U256Variable>(*value);
+        }
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for value in values {
+            assert_eq!(output.read::<U32Variable>() as usize, value.bits() - 1);
+            assert_eq!(output.read::<U32Variable>(), value.trailing_zeros());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_floor_log2_zero() {
+        let mut builder = DefaultBuilder::new();
+        let value = builder.read::<U64Variable>();
+        let log2 = builder.floor_log2(value);
+        builder.write(log2);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U64Variable>(0);
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+}
@@ -0,0 +1,190 @@
+//! Bitwise operations shared by the unsigned integer variables.
+//!
+//! The integers are decomposed into their big-endian bits, which are combined or rewired and then
+//! recomposed into limbs. Shifts by at least the bit width give zero, as in the EVM.
+
+use itertools::Itertools;
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::vars::{BoolVariable, ByteVariable, EvmVariable, U32Variable};
+use crate::prelude::CircuitBuilder;
+
+/// The kind of a shift or rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ShiftKind {
+    Left,
+    Right,
+    RotateLeft,
+    RotateRight,
+}
+
+/// Shifts or rotates big-endian bits by a constant amount.
+fn shift_be_bits(
+    bits: &[BoolVariable],
+    shift: usize,
+    kind: ShiftKind,
+    zero: BoolVariable,
+) -> Vec<BoolVariable> {
+    let width = bits.len();
+    (0..width)
+        .map(|i| match kind {
+            ShiftKind::Left if shift < width - i => bits[i + shift],
+            ShiftKind::Right if shift <= i => bits[i - shift],
+            ShiftKind::Left | ShiftKind::Right => zero,
+            ShiftKind::RotateLeft => bits[(i + shift % width) % width],
+            ShiftKind::RotateRight => bits[(i + width - shift % width) % width],
+        })
+        .collect()
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Recomposes an integer variable from its big-endian bits.
+    fn uint_from_be_bits<V: EvmVariable>(&mut self, bits: &[BoolVariable]) -> V {
+        let bytes = bits
+            .chunks_exact(8)
+            .map(|byte| ByteVariable(byte.try_into().unwrap()))
+            .collect_vec();
+        V::decode(self, &bytes)
+    }
+
+    /// Applies a bitwise operation to each pair of bits of `lhs` and `rhs`.
+    pub(crate) fn uint_bitwise<V: EvmVariable>(
+        &mut self,
+        lhs: V,
+        rhs: V,
+        op: fn(&mut Self, BoolVariable, BoolVariable) -> BoolVariable,
+    ) -> V {
+        let lhs_bits = lhs.to_be_bits(self);
+        let rhs_bits = rhs.to_be_bits(self);
+        let bits = lhs_bits
+            .into_iter()
+            .zip(rhs_bits)
+            .map(|(x, y)| op(self, x, y))
+            .collect_vec();
+        self.uint_from_be_bits(&bits)
+    }
+
+    /// Shifts or rotates `value` by a constant amount.
+    pub(crate) fn uint_shift<V: EvmVariable>(
+        &mut self,
+        value: V,
+        shift: usize,
+        kind: ShiftKind,
+    ) -> V {
+        let bits = value.to_be_bits(self);
+        let zero = self._false();
+        let shifted = shift_be_bits(&bits, shift, kind, zero);
+        self.uint_from_be_bits(&shifted)
+    }
+
+    /// Shifts or rotates `value` by the amount in `shift`, with a barrel shifter over the bits of
+    /// the amount.
+    pub(crate) fn uint_shift_variable<V: EvmVariable>(
+        &mut self,
+        value: V,
+        shift: U32Variable,
+        kind: ShiftKind,
+    ) -> V {
+        let mut bits = value.to_be_bits(self);
+        let width = bits.len();
+        assert!(width.is_power_of_two());
+        let log_width = width.trailing_zeros() as usize;
+
+        let zero = self._false();
+        let shift_bits = shift.to_be_bits(self);
+        for (k, shift_bit) in shift_bits.iter().rev().take(log_width).enumerate() {
+            let shifted = shift_be_bits(&bits, 1 << k, kind, zero);
+            bits = bits
+                .into_iter()
+                .zip(shifted)
+                .map(|(x, y)| self.select(*shift_bit, y, x))
+                .collect();
+        }
+
+        // Rotations only depend on the amount modulo the width, while any higher bit of a shift
+        // amount moves every bit out.
+        if matches!(kind, ShiftKind::Left | ShiftKind::Right) {
+            let mut overflow = zero;
+            for shift_bit in shift_bits.iter().take(32 - log_width) {
+                overflow = self.or(overflow, *shift_bit);
+            }
+            bits = bits
+                .into_iter()
+                .map(|x| self.select(overflow, zero, x))
+                .collect();
+        }
+
+        self.uint_from_be_bits(&bits)
+    }
+}
+
+/// Implements the bitwise, shift and rotation traits for an unsigned integer variable.
+macro_rules! impl_uint_bitwise {
+    ($a:ident) => {
+        impl<L: $crate::prelude::PlonkParameters<D>, const D: usize> $crate::prelude::BitAnd<L, D>
+            for $a
+        {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self, builder: &mut $crate::prelude::CircuitBuilder<L, D>) -> Self::Output {
+                builder.uint_bitwise(self, rhs, $crate::prelude::CircuitBuilder::and::<$crate::prelude::BoolVariable, $crate::prelude::BoolVariable>)
+            }
+        }
+
+        impl<L: $crate::prelude::PlonkParameters<D>, const D: usize> $crate::prelude::BitOr<L, D>
+            for $a
+        {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self, builder: &mut $crate::prelude::CircuitBuilder<L, D>) -> Self::Output {
+                builder.uint_bitwise(self, rhs, $crate::prelude::CircuitBuilder::or::<$crate::prelude::BoolVariable, $crate::prelude::BoolVariable>)
+            }
+        }
+
+        impl<L: $crate::prelude::PlonkParameters<D>, const D: usize> $crate::prelude::BitXor<L, D>
+            for $a
+        {
+            type Output = Self;
+
+            fn bitxor(self, rhs: Self, builder: &mut $crate::prelude::CircuitBuilder<L, D>) -> Self::Output {
+                builder.uint_bitwise(self, rhs, $crate::prelude::CircuitBuilder::xor::<$crate::prelude::BoolVariable, $crate::prelude::BoolVariable>)
+            }
+        }
+
+        $crate::frontend::uint::bitwise::impl_uint_bitwise!(@shift $a, Shl, shl, Left);
+        $crate::frontend::uint::bitwise::impl_uint_bitwise!(@shift $a, Shr, shr, Right);
+        $crate::frontend::uint::bitwise::impl_uint_bitwise!(@shift $a, RotateLeft, rotate_left, RotateLeft);
+        $crate::frontend::uint::bitwise::impl_uint_bitwise!(@shift $a, RotateRight, rotate_right, RotateRight);
+    };
+    (@shift $a:ident, $trait:ident, $method:ident, $kind:ident) => {
+        impl<L: $crate::prelude::PlonkParameters<D>, const D: usize>
+            $crate::prelude::$trait<L, D, usize> for $a
+        {
+            type Output = Self;
+
+            fn $method(self, rhs: usize, builder: &mut $crate::prelude::CircuitBuilder<L, D>) -> Self::Output {
+                builder.uint_shift(self, rhs, $crate::frontend::uint::bitwise::ShiftKind::$kind)
+            }
+        }
+
+        impl<L: $crate::prelude::PlonkParameters<D>, const D: usize>
+            $crate::prelude::$trait<L, D, $crate::prelude::U32Variable> for $a
+        {
+            type Output = Self;
+
+            fn $method(
+                self,
+                rhs: $crate::prelude::U32Variable,
+                builder: &mut $crate::prelude::CircuitBuilder<L, D>,
+            ) -> Self::Output {
+                builder.uint_shift_variable(
+                    self,
+                    rhs,
+                    $crate::frontend::uint::bitwise::ShiftKind::$kind,
+                )
+            }
+        }
+    };
+}
+
+pub(crate) use impl_uint_bitwise;
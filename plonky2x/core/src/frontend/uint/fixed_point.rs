@@ -0,0 +1,444 @@
+//! Full-precision `mul_div` and a decimal fixed-point number type on top of it.
+//!
+//! The semantics follow Uniswap's `FullMath`: the product is computed at double width, and the
+//! result must fit in the original width.
+
+use ethers::types::U256;
+use plonky2::hash::hash_types::RichField;
+
+use super::UintVariable;
+use crate::frontend::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::frontend::num::u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::frontend::vars::{EvmVariable, U32Variable};
+use crate::prelude::{
+    Add, BoolVariable, ByteVariable, CircuitBuilder, CircuitVariable, LessThanOrEqual, One,
+    PlonkParameters, Sub, U256Variable, Variable, Zero,
+};
+
+/// The direction to round an inexact result in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+fn to_biguint<V: UintVariable>(value: &V) -> BigUintTarget {
+    BigUintTarget {
+        limbs: value.limbs().into_iter().map(U32Target::from).collect(),
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes `a * b / denominator` with a double width product, as `FullMath.mulDiv` and
+    /// `FullMath.mulDivRoundingUp` do.
+    ///
+    /// The result must fit in `V`, and the denominator must be nonzero, otherwise the circuit is
+    /// unsatisfiable.
+    pub fn mul_div<V: UintVariable>(
+        &mut self,
+        a: V,
+        b: V,
+        denominator: V,
+        rounding: Rounding,
+    ) -> V {
+        let num_limbs = a.limbs().len();
+        let product = self.api.mul_biguint(&to_biguint(&a), &to_biguint(&b));
+        let (quotient, remainder) = self
+            .api
+            .div_rem_biguint(&product, &to_biguint(&denominator));
+        for limb in quotient.limbs[num_limbs..].iter() {
+            self.api.assert_zero_u32(*limb);
+        }
+        let quotient = quotient.limbs[..num_limbs]
+            .iter()
+            .map(|x| U32Variable::from(*x))
+            .collect::<Vec<_>>();
+
+        match rounding {
+            Rounding::Down => V::from_limbs(&quotient),
+            Rounding::Up => {
+                // The sum of the limbs is far below the field order, so it is zero exactly when
+                // the remainder is.
+                let remainder_sum = self.api.add_many(remainder.limbs.iter().map(|x| x.target));
+                let is_exact = self.is_zero(Variable(remainder_sum));
+                let round_up = self.not(is_exact);
+                let mut increment = vec![self.zero::<U32Variable>(); num_limbs];
+                increment[0] = U32Variable::from_variables_unsafe(&[round_up.variable]);
+                let (rounded, overflow) = self.overflowing_add_limbs(&quotient, &increment);
+                let f = self._false();
+                self.assert_is_equal(overflow, f);
+                V::from_limbs(&rounded)
+            }
+        }
+    }
+}
+
+/// A decimal fixed-point number with `DECIMALS` digits after the point, stored as the `U256`
+/// integer `value * 10^DECIMALS`.
+///
+/// Unlike the integer types, `add` and `sub` are checked, so overflows make the circuit
+/// unsatisfiable as they would revert in Solidity. Multiplication, division and square roots
+/// take the rounding direction explicitly.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedPointVariable<const DECIMALS: usize> {
+    pub value: U256Variable,
+}
+
+/// A fixed-point number with 18 decimals, the `WAD` of DeFi protocols.
+pub type WadVariable = FixedPointVariable<18>;
+
+/// A fixed-point number with 27 decimals, the `RAY` of DeFi protocols.
+pub type RayVariable = FixedPointVariable<27>;
+
+impl<const DECIMALS: usize> CircuitVariable for FixedPointVariable<DECIMALS> {
+    type ValueType<F: RichField> = U256;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self {
+            value: U256Variable::init_unsafe(builder),
+        }
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        self.value.variables()
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        Self {
+            value: U256Variable::from_variables_unsafe(variables),
+        }
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        self.value.assert_is_valid(builder)
+    }
+
+    fn nb_elements() -> usize {
+        U256Variable::nb_elements()
+    }
+
+    fn elements<F: RichField>(value: U256) -> Vec<F> {
+        U256Variable::elements::<F>(value)
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        U256Variable::from_elements::<F>(elements)
+    }
+}
+
+impl<const DECIMALS: usize> EvmVariable for FixedPointVariable<DECIMALS> {
+    fn encode<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Vec<ByteVariable> {
+        self.value.encode(builder)
+    }
+
+    fn decode<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        bytes: &[ByteVariable],
+    ) -> Self {
+        Self {
+            value: U256Variable::decode(builder, bytes),
+        }
+    }
+
+    fn encode_value<F: RichField>(value: Self::ValueType<F>) -> Vec<u8> {
+        U256Variable::encode_value::<F>(value)
+    }
+
+    fn decode_value<F: RichField>(bytes: &[u8]) -> Self::ValueType<F> {
+        U256Variable::decode_value::<F>(bytes)
+    }
+}
+
+impl<const DECIMALS: usize> FixedPointVariable<DECIMALS> {
+    /// The raw value of one, `10^DECIMALS`.
+    pub fn scale<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> U256Variable {
+        builder.constant::<U256Variable>(U256::exp10(DECIMALS))
+    }
+
+    /// Wraps a value that is already scaled by `10^DECIMALS`.
+    pub fn from_raw(value: U256Variable) -> Self {
+        Self { value }
+    }
+
+    /// Converts an integer, which must be small enough for the scaled value to fit.
+    pub fn from_integer<L: PlonkParameters<D>, const D: usize>(
+        value: U256Variable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        let scale = Self::scale(builder);
+        Self {
+            value: builder.checked_mul(value, scale),
+        }
+    }
+
+    /// Converts to an integer, rounding the fractional part in the given direction.
+    pub fn to_integer<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rounding: Rounding,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> U256Variable {
+        let one = builder.one::<U256Variable>();
+        let scale = Self::scale(builder);
+        builder.mul_div(self.value, one, scale, rounding)
+    }
+
+    /// Multiplies two numbers, as `mulDiv(a, b, 10^DECIMALS)`.
+    pub fn mul<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rhs: Self,
+        rounding: Rounding,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        let scale = Self::scale(builder);
+        Self {
+            value: builder.mul_div(self.value, rhs.value, scale, rounding),
+        }
+    }
+
+    /// Divides two numbers, as `mulDiv(a, 10^DECIMALS, b)`. The divisor must be nonzero.
+    pub fn div<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rhs: Self,
+        rounding: Rounding,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        let scale = Self::scale(builder);
+        Self {
+            value: builder.mul_div(self.value, scale, rhs.value, rounding),
+        }
+    }
+
+    /// The square root, computed as the integer square root of `value * 10^DECIMALS`.
+    pub fn sqrt<L: PlonkParameters<D>, const D: usize>(
+        self,
+        rounding: Rounding,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        let scale = Self::scale(builder);
+        let scaled = builder
+            .api
+            .mul_biguint(&to_biguint(&self.value), &to_biguint(&scale));
+        let root = builder.isqrt_biguint(&scaled);
+
+        // The root of a 512-bit number has at most 256 bits.
+        let (low, high) = root.limbs.split_at(8);
+        for limb in high {
+            builder.api.assert_zero_u32(*limb);
+        }
+        let root = U256Variable::from_limbs(
+            &low.iter()
+                .map(|x| U32Variable::from(*x))
+                .collect::<Vec<_>>(),
+        );
+
+        let value = match rounding {
+            Rounding::Down => root,
+            Rounding::Up => {
+                let square = builder
+                    .api
+                    .mul_biguint(&to_biguint(&root), &to_biguint(&root));
+                let is_exact = builder.api.is_equal_biguint(&square, &scaled);
+                let round_up = builder.not(BoolVariable::from(is_exact));
+                let mut increment = vec![builder.zero::<U32Variable>(); 8];
+                increment[0] = U32Variable::from_variables_unsafe(&[round_up.variable]);
+                let increment = U256Variable::from_limbs(&increment);
+                builder.checked_add(root, increment)
+            }
+        };
+        Self { value }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const DECIMALS: usize> Zero<L, D>
+    for FixedPointVariable<DECIMALS>
+{
+    fn zero(builder: &mut CircuitBuilder<L, D>) -> Self {
+        Self {
+            value: builder.zero(),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const DECIMALS: usize> One<L, D>
+    for FixedPointVariable<DECIMALS>
+{
+    fn one(builder: &mut CircuitBuilder<L, D>) -> Self {
+        Self {
+            value: Self::scale(builder),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const DECIMALS: usize> Add<L, D>
+    for FixedPointVariable<DECIMALS>
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        Self {
+            value: builder.checked_add(self.value, rhs.value),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const DECIMALS: usize> Sub<L, D>
+    for FixedPointVariable<DECIMALS>
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        Self {
+            value: builder.checked_sub(self.value, rhs.value),
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize, const DECIMALS: usize> LessThanOrEqual<L, D>
+    for FixedPointVariable<DECIMALS>
+{
+    fn lte(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> BoolVariable {
+        builder.lte(self.value, rhs.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{U256, U512};
+    use rand::Rng;
+
+    use super::{Rounding, WadVariable};
+    use crate::prelude::*;
+
+    /// `FullMath.mulDiv` and `FullMath.mulDivRoundingUp`.
+    fn full_math_mul_div(a: U256, b: U256, denominator: U256, rounding: Rounding) -> U256 {
+        let product = a.full_mul(b);
+        let denominator = U512::from(denominator);
+        let mut result = product / denominator;
+        if rounding == Rounding::Up && !(product % denominator).is_zero() {
+            result += U512::one();
+        }
+        U256::try_from(result).unwrap()
+    }
+
+    #[test]
+    fn test_mul_div() {
+        let mut rng = rand::thread_rng();
+        let a = U256(rng.gen::<[u64; 4]>());
+        let b = U256(rng.gen::<[u64; 4]>()) >> 64;
+        // The denominator is large enough for the quotient to fit in 256 bits.
+        let denominator = (U256(rng.gen::<[u64; 4]>()) >> 1) | (U256::one() << 254);
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.read::<U256Variable>();
+        let b_var = builder.read::<U256Variable>();
+        let denominator_var = builder.read::<U256Variable>();
+        let down = builder.mul_div(a_var, b_var, denominator_var, Rounding::Down);
+        let up = builder.mul_div(a_var, b_var, denominator_var, Rounding::Up);
+        builder.write(down);
+        builder.write(up);
+
+        // An exact division is not rounded up.
+        let exact = builder.mul_div(a_var, denominator_var, denominator_var, Rounding::Up);
+        builder.assert_is_equal(exact, a_var);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U256Variable>(a);
+        input.write::<U256Variable>(b);
+        input.write::<U256Variable>(denominator);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(
+            output.read::<U256Variable>(),
+            full_math_mul_div(a, b, denominator, Rounding::Down)
+        );
+        assert_eq!(
+            output.read::<U256Variable>(),
+            full_math_mul_div(a, b, denominator, Rounding::Up)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mul_div_overflow() {
+        let mut builder = DefaultBuilder::new();
+        let max = builder.constant::<U256Variable>(U256::MAX);
+        let two = builder.constant::<U256Variable>(U256::from(2));
+        let one = builder.one::<U256Variable>();
+        builder.mul_div(max, two, one, Rounding::Down);
+
+        let circuit = builder.build();
+        let input = circuit.input();
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    fn test_wad() {
+        let wad = U256::exp10(18);
+        let mut rng = rand::thread_rng();
+        let a = U256::from(rng.gen::<u128>());
+        let b = U256::from(rng.gen::<u64>()) + 1;
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.read::<WadVariable>();
+        let b_var = builder.read::<WadVariable>();
+        let results = [
+            builder.add(a_var, b_var),
+            builder.sub(a_var, b_var),
+            a_var.mul(b_var, Rounding::Down, &mut builder),
+            a_var.mul(b_var, Rounding::Up, &mut builder),
+            a_var.div(b_var, Rounding::Down, &mut builder),
+            a_var.div(b_var, Rounding::Up, &mut builder),
+            a_var.sqrt(Rounding::Down, &mut builder),
+            a_var.sqrt(Rounding::Up, &mut builder),
+        ];
+        for result in results {
+            builder.write(result);
+        }
+        let integer = a_var.to_integer(Rounding::Down, &mut builder);
+        builder.write(integer);
+        let from_integer = WadVariable::from_integer(integer, &mut builder);
+        let lte = builder.lte(from_integer, a_var);
+        builder.write(lte);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<WadVariable>(a);
+        input.write::<WadVariable>(b);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let scaled = a.full_mul(wad);
+        let mut sqrt_up = scaled.integer_sqrt();
+        if sqrt_up * sqrt_up != scaled {
+            sqrt_up += U512::one();
+        }
+        let expected_results = [
+            a + b,
+            a - b,
+            full_math_mul_div(a, b, wad, Rounding::Down),
+            full_math_mul_div(a, b, wad, Rounding::Up),
+            full_math_mul_div(a, wad, b, Rounding::Down),
+            full_math_mul_div(a, wad, b, Rounding::Up),
+            U256::try_from(scaled.integer_sqrt()).unwrap(),
+            U256::try_from(sqrt_up).unwrap(),
+        ];
+        for expected_result in expected_results {
+            assert_eq!(output.read::<WadVariable>(), expected_result);
+        }
+        assert_eq!(output.read::<U256Variable>(), a / wad);
+        assert!(output.read::<BoolVariable>());
+    }
+}
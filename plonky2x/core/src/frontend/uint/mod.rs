@@ -1,8 +1,18 @@
 use core::fmt::Debug;
 
+use crate::frontend::vars::{EvmVariable, U32Variable};
+
+pub mod biguint;
+mod bits;
+mod bitwise;
+pub mod fixed_point;
+pub mod modular;
+mod overflowing;
+pub mod sqrt;
 pub mod uint128;
 pub mod uint256;
 pub mod uint32;
+pub mod uint512;
 pub mod uint64;
 
 mod uint32_n;
@@ -46,3 +56,12 @@ pub trait Uint<const N: usize>: Debug + Clone + Copy + Sync + Send + 'static {
         Self::from_little_endian(&bytes)
     }
 }
+
+/// An unsigned integer variable stored as little-endian u32 limbs.
+pub trait UintVariable: EvmVariable {
+    /// The limbs, starting with the least significant one.
+    fn limbs(&self) -> Vec<U32Variable>;
+
+    /// Builds the variable from exactly as many limbs as it has, least significant first.
+    fn from_limbs(limbs: &[U32Variable]) -> Self;
+}
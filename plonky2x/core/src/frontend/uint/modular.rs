@@ -0,0 +1,320 @@
+//! Modular arithmetic shared by the unsigned integer variables.
+//!
+//! Intermediate results are kept at full width, e.g. the product of two `U256Variable`s has 512
+//! bits, and are reduced with `div_rem_biguint`. As with the `ADDMOD`, `MULMOD` and `MODEXP`
+//! opcodes of the EVM, a zero modulus gives zero.
+
+use itertools::Itertools;
+use num::{BigInt, BigUint, Integer, One, Signed};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::frontend::num::u32::gadgets::arithmetic_u32::U32Target;
+use crate::frontend::num::u32::gadgets::range_check::range_check_u32_circuit;
+use crate::frontend::uint::UintVariable;
+use crate::frontend::vars::{EvmVariable, U32Variable, ValueStream, VariableStream};
+use crate::prelude::CircuitBuilder;
+
+fn to_biguint(limbs: &[U32Variable]) -> BigUintTarget {
+    BigUintTarget {
+        limbs: limbs.iter().map(|x| U32Target::from(*x)).collect(),
+    }
+}
+
+/// Computes the inverse of `a` modulo `modulus`, both little-endian limb arrays of `num_limbs`
+/// limbs, or zero if there is none.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvModHint {
+    num_limbs: usize,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for InvModHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let a = BigInt::from(BigUint::from_slice(
+            &input_stream.read_vec::<U32Variable>(self.num_limbs),
+        ));
+        let modulus = BigInt::from(BigUint::from_slice(
+            &input_stream.read_vec::<U32Variable>(self.num_limbs),
+        ));
+
+        let gcd = a.extended_gcd(&modulus);
+        let mut inverse = if modulus.is_positive() && gcd.gcd.is_one() {
+            gcd.x.mod_floor(&modulus).magnitude().to_u32_digits()
+        } else {
+            Vec::new()
+        };
+        inverse.resize(self.num_limbs, 0);
+        for limb in inverse {
+            output_stream.write_value::<U32Variable>(limb);
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Replaces a zero modulus with one, which reduces every value to zero, so that the division
+    /// in the witness generator is always defined.
+    fn nonzero_modulus<V: UintVariable>(&mut self, modulus: V) -> BigUintTarget {
+        let mut limbs = modulus.limbs();
+        let zero = self.zero::<U32Variable>();
+        let mut is_zero = self._true();
+        for limb in limbs.iter() {
+            let limb_is_zero = self.is_equal(*limb, zero);
+            is_zero = self.and(is_zero, limb_is_zero);
+        }
+        let one = self.one::<U32Variable>();
+        limbs[0] = self.select(is_zero, one, limbs[0]);
+        to_biguint(&limbs)
+    }
+
+    /// Reduces `value` modulo a nonzero modulus, which has as many limbs as `V`.
+    fn reduce_biguint<V: UintVariable>(
+        &mut self,
+        value: &BigUintTarget,
+        modulus: &BigUintTarget,
+    ) -> V {
+        let rem = self.api.rem_biguint(value, modulus);
+        V::from_limbs(&rem.limbs.into_iter().map(U32Variable::from).collect_vec())
+    }
+
+    fn mulmod_biguint<V: UintVariable>(&mut self, a: &V, b: &V, modulus: &BigUintTarget) -> V {
+        let product = self
+            .api
+            .mul_biguint(&to_biguint(&a.limbs()), &to_biguint(&b.limbs()));
+        self.reduce_biguint(&product, modulus)
+    }
+
+    /// Computes `(a + b) % modulus` without wrapping the sum, or zero if the modulus is zero.
+    pub fn addmod<V: UintVariable>(&mut self, a: V, b: V, modulus: V) -> V {
+        let modulus = self.nonzero_modulus(modulus);
+        let sum = self
+            .api
+            .add_biguint(&to_biguint(&a.limbs()), &to_biguint(&b.limbs()));
+        self.reduce_biguint(&sum, &modulus)
+    }
+
+    /// Computes `(a * b) % modulus` without wrapping the product, or zero if the modulus is zero.
+    pub fn mulmod<V: UintVariable>(&mut self, a: V, b: V, modulus: V) -> V {
+        let modulus = self.nonzero_modulus(modulus);
+        self.mulmod_biguint(&a, &b, &modulus)
+    }
+
+    /// Computes `base^exponent % modulus` by square-and-multiply over the bits of `exponent`, or
+    /// zero if the modulus is zero.
+    ///
+    /// The circuit has two modular multiplications per bit of the exponent, so a narrower
+    /// exponent type gives a smaller circuit.
+    pub fn expmod<V: UintVariable, E: EvmVariable>(
+        &mut self,
+        base: V,
+        exponent: E,
+        modulus: V,
+    ) -> V {
+        let modulus = self.nonzero_modulus(modulus);
+        let one = self.one::<U32Variable>();
+        let mut result = self.reduce_biguint::<V>(&to_biguint(&[one]), &modulus);
+        for bit in exponent.to_be_bits(self) {
+            let squared = self.mulmod_biguint(&result, &result, &modulus);
+            let multiplied = self.mulmod_biguint(&squared, &base, &modulus);
+            result = self.select(bit, multiplied, squared);
+        }
+        result
+    }
+
+    /// Computes the inverse of `a` modulo `modulus`, i.e. the `x < modulus` with
+    /// `a * x = 1 (mod modulus)`.
+    ///
+    /// The inverse is witnessed by a hint and checked with a single modular multiplication. The
+    /// circuit is unsatisfiable if `a` has no inverse, which includes `a = 0` and a modulus of zero
+    /// or one.
+    pub fn inv_mod<V: UintVariable>(&mut self, a: V, modulus: V) -> V {
+        let num_limbs = a.limbs().len();
+        let mut input_stream = VariableStream::new();
+        input_stream.write_slice(&a.limbs());
+        input_stream.write_slice(&modulus.limbs());
+        let output_stream = self.hint(input_stream, InvModHint { num_limbs });
+        let inverse_limbs = output_stream.read_vec::<U32Variable>(self, num_limbs);
+        range_check_u32_circuit(
+            &mut self.api,
+            inverse_limbs.iter().map(|x| U32Target::from(*x)).collect(),
+        );
+
+        // inverse < modulus, so that the inverse is unique.
+        let modulus_lte_inverse = self
+            .api
+            .cmp_biguint(&to_biguint(&modulus.limbs()), &to_biguint(&inverse_limbs));
+        self.api.assert_zero(modulus_lte_inverse.target);
+
+        let inverse = V::from_limbs(&inverse_limbs);
+        let product = self.mulmod(a, inverse.clone(), modulus);
+        let mut one_limbs = vec![self.zero::<U32Variable>(); num_limbs];
+        one_limbs[0] = self.one::<U32Variable>();
+        let one = V::from_limbs(&one_limbs);
+        self.assert_is_equal(product, one);
+
+        inverse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{U256, U512};
+    use rand::Rng;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_addmod_mulmod() {
+        let mut rng = rand::thread_rng();
+        let a = U256(rng.gen::<[u64; 4]>());
+        let b = U256(rng.gen::<[u64; 4]>());
+        let modulus = (U256(rng.gen::<[u64; 4]>()) >> rng.gen_range(0..256)).max(U256::one());
+
+        let expected_addmod = (U512::from(a) + U512::from(b)) % U512::from(modulus);
+        let expected_mulmod = a.full_mul(b) % U512::from(modulus);
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.read::<U256Variable>();
+        let b_var = builder.read::<U256Variable>();
+        let modulus_var = builder.read::<U256Variable>();
+        let addmod = builder.addmod(a_var, b_var, modulus_var);
+        let mulmod = builder.mulmod(a_var, b_var, modulus_var);
+        builder.write(addmod);
+        builder.write(mulmod);
+
+        // A zero modulus gives zero.
+        let zero = builder.zero::<U256Variable>();
+        let addmod_zero = builder.addmod(a_var, b_var, zero);
+        let mulmod_zero = builder.mulmod(a_var, b_var, zero);
+        builder.assert_is_equal(addmod_zero, zero);
+        builder.assert_is_equal(mulmod_zero, zero);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U256Variable>(a);
+        input.write::<U256Variable>(b);
+        input.write::<U256Variable>(modulus);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(U512::from(output.read::<U256Variable>()), expected_addmod);
+        assert_eq!(U512::from(output.read::<U256Variable>()), expected_mulmod);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_expmod() {
+        let mut rng = rand::thread_rng();
+        let base = U256(rng.gen::<[u64; 4]>());
+        let exponent = rng.gen::<u32>();
+        let modulus = U256(rng.gen::<[u64; 4]>());
+
+        let mut expected = U512::one();
+        for i in (0..32).rev() {
+            expected = (expected * expected) % U512::from(modulus);
+            if (exponent >> i) & 1 == 1 {
+                expected = (expected * U512::from(base)) % U512::from(modulus);
+            }
+        }
+
+        let mut builder = DefaultBuilder::new();
+        let base_var = builder.read::<U256Variable>();
+        let exponent_var = builder.read::<U32Variable>();
+        let modulus_var = builder.read::<U256Variable>();
+        let result = builder.expmod(base_var, exponent_var, modulus_var);
+        builder.write(result);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U256Variable>(base);
+        input.write::<U32Variable>(exponent);
+        input.write::<U256Variable>(modulus);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(U512::from(output.read::<U256Variable>()), expected);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_inv_mod() {
+        // The Goldilocks prime.
+        const P: u64 = 0xFFFF_FFFF_0000_0001;
+        let a = rand::thread_rng().gen_range(1..P);
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.read::<U64Variable>();
+        let p_var = builder.constant::<U64Variable>(P);
+        let inverse = builder.inv_mod(a_var, p_var);
+        builder.write(inverse);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U64Variable>(a);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let inverse = output.read::<U64Variable>();
+        assert!(inverse < P);
+        assert_eq!((a as u128 * inverse as u128) % P as u128, 1);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_inv_mod_composite() {
+        // The modulus need not be prime: 2^256 - 1 is composite, and 7 is coprime to it.
+        let a = U256::from(7);
+        let modulus = U256::MAX;
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.read::<U256Variable>();
+        let modulus_var = builder.read::<U256Variable>();
+        let inverse = builder.inv_mod(a_var, modulus_var);
+        builder.write(inverse);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U256Variable>(a);
+        input.write::<U256Variable>(modulus);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        let inverse = output.read::<U256Variable>();
+        assert!(inverse < modulus);
+        assert_eq!(a.full_mul(inverse) % U512::from(modulus), U512::one());
+    }
+
+    fn prove_inv_mod(a: u64, modulus: u64) {
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.read::<U64Variable>();
+        let modulus_var = builder.read::<U64Variable>();
+        let inverse = builder.inv_mod(a_var, modulus_var);
+        builder.write(inverse);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U64Variable>(a);
+        input.write::<U64Variable>(modulus);
+        let (proof, output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inv_mod_zero() {
+        prove_inv_mod(0, 0xFFFF_FFFF_0000_0001);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inv_mod_not_coprime() {
+        prove_inv_mod(6, 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inv_mod_zero_modulus() {
+        prove_inv_mod(5, 0);
+    }
+}
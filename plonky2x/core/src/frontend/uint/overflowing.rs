@@ -0,0 +1,72 @@
+//! Limb arithmetic that reports overflow, shared by the unsigned integer variables.
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::frontend::num::u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::frontend::vars::{BoolVariable, CircuitVariable, U32Variable, Variable};
+use crate::prelude::CircuitBuilder;
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Adds two little-endian limb arrays, returning the wrapped sum and the final carry.
+    pub(crate) fn overflowing_add_limbs(
+        &mut self,
+        lhs: &[U32Variable],
+        rhs: &[U32Variable],
+    ) -> (Vec<U32Variable>, BoolVariable) {
+        assert_eq!(lhs.len(), rhs.len());
+        let mut limbs = Vec::with_capacity(lhs.len());
+        let mut carry = self.api.zero_u32();
+        for (a, b) in lhs.iter().zip(rhs.iter()) {
+            let (limb, new_carry) = self.api.add_many_u32(&[carry, (*a).into(), (*b).into()]);
+            limbs.push(limb.into());
+            carry = new_carry;
+        }
+
+        // Each carry is at most one, as the carry into the limb is at most one.
+        let carry = BoolVariable::from_variables_unsafe(&[Variable(carry.target)]);
+        (limbs, carry)
+    }
+
+    /// Subtracts two little-endian limb arrays, returning the wrapped difference and the final
+    /// borrow.
+    pub(crate) fn overflowing_sub_limbs(
+        &mut self,
+        lhs: &[U32Variable],
+        rhs: &[U32Variable],
+    ) -> (Vec<U32Variable>, BoolVariable) {
+        assert_eq!(lhs.len(), rhs.len());
+        let mut limbs = Vec::with_capacity(lhs.len());
+        let mut borrow = self.api.zero_u32();
+        for (a, b) in lhs.iter().zip(rhs.iter()) {
+            let (limb, new_borrow) = self.api.sub_u32((*a).into(), (*b).into(), borrow);
+            limbs.push(limb.into());
+            borrow = new_borrow;
+        }
+
+        // The subtraction gate constrains the borrow to be a bit.
+        let borrow = BoolVariable::from_variables_unsafe(&[Variable(borrow.target)]);
+        (limbs, borrow)
+    }
+
+    /// Multiplies two little-endian limb arrays, returning the low limbs of the product and
+    /// whether any of the high limbs is nonzero.
+    pub(crate) fn overflowing_mul_limbs(
+        &mut self,
+        lhs: &[U32Variable],
+        rhs: &[U32Variable],
+    ) -> (Vec<U32Variable>, BoolVariable) {
+        assert_eq!(lhs.len(), rhs.len());
+        let to_biguint = |limbs: &[U32Variable]| BigUintTarget {
+            limbs: limbs.iter().map(|x| U32Target::from(*x)).collect(),
+        };
+        let product = self.api.mul_biguint(&to_biguint(lhs), &to_biguint(rhs));
+        let (low, high) = product.limbs.split_at(lhs.len());
+
+        // The sum of the high limbs is far below the field order, so it is zero exactly when all
+        // of them are.
+        let high_sum = self.api.add_many(high.iter().map(|x| x.target));
+        let no_overflow = self.is_zero(Variable(high_sum));
+        let overflow = self.not(no_overflow);
+        (low.iter().map(|x| (*x).into()).collect(), overflow)
+    }
+}
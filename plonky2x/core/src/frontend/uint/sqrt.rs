@@ -0,0 +1,151 @@
+//! Integer square roots, witnessed by a hint and checked with a bound on both sides.
+
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::hint::simple::hint::Hint;
+use crate::frontend::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::frontend::num::u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::frontend::num::u32::gadgets::range_check::range_check_u32_circuit;
+use crate::frontend::uint::UintVariable;
+use crate::frontend::vars::{U32Variable, ValueStream, VariableStream};
+use crate::prelude::CircuitBuilder;
+
+/// Computes the integer square root of a little-endian limb array of `num_limbs` limbs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BigUintSqrtHint {
+    num_limbs: usize,
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Hint<L, D> for BigUintSqrtHint {
+    fn hint(&self, input_stream: &mut ValueStream<L, D>, output_stream: &mut ValueStream<L, D>) {
+        let limbs = input_stream.read_vec::<U32Variable>(self.num_limbs);
+        let mut root = BigUint::from_slice(&limbs).sqrt().to_u32_digits();
+        root.resize(self.num_limbs.div_ceil(2), 0);
+        for limb in root {
+            output_stream.write_value::<U32Variable>(limb);
+        }
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes `floor(sqrt(value))`, which has half as many limbs as `value`, rounded up.
+    pub(crate) fn isqrt_biguint(&mut self, value: &BigUintTarget) -> BigUintTarget {
+        let mut input_stream = VariableStream::new();
+        for limb in value.limbs.iter() {
+            input_stream.write(&U32Variable::from(*limb));
+        }
+        let output_stream = self.hint(
+            input_stream,
+            BigUintSqrtHint {
+                num_limbs: value.num_limbs(),
+            },
+        );
+        let root = BigUintTarget {
+            limbs: output_stream
+                .read_vec::<U32Variable>(self, value.num_limbs().div_ceil(2))
+                .into_iter()
+                .map(U32Target::from)
+                .collect(),
+        };
+        range_check_u32_circuit(&mut self.api, root.limbs.clone());
+
+        // root^2 <= value < (root + 1)^2.
+        let square = self.api.mul_biguint(&root, &root);
+        let lower_bound = self.api.cmp_biguint(&square, value);
+        self.api.assert_one(lower_bound.target);
+        let one = self.api.one_u32();
+        let root_plus_one = self
+            .api
+            .add_biguint(&root, &BigUintTarget { limbs: vec![one] });
+        let next_square = self.api.mul_biguint(&root_plus_one, &root_plus_one);
+        let upper_bound = self.api.cmp_biguint(&next_square, value);
+        self.api.assert_zero(upper_bound.target);
+
+        root
+    }
+
+    /// Computes `floor(sqrt(value))`, as in the `integer_squareroot` of the consensus specs.
+    pub fn isqrt<V: UintVariable>(&mut self, value: V) -> V {
+        let value_limbs = value.limbs();
+        let root = self.isqrt_biguint(&BigUintTarget {
+            limbs: value_limbs.iter().map(|x| U32Target::from(*x)).collect(),
+        });
+        let mut limbs = vec![self.zero::<U32Variable>(); value_limbs.len()];
+        for (limb, root_limb) in limbs.iter_mut().zip(root.limbs) {
+            *limb = U32Variable::from(root_limb);
+        }
+        V::from_limbs(&limbs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::U256;
+    use rand::Rng;
+
+    use crate::prelude::*;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_isqrt_small() {
+        const MAX: u32 = 1 << 10;
+
+        let mut builder = DefaultBuilder::new();
+        for _ in 0..MAX {
+            let value = builder.read::<U32Variable>();
+            let root = builder.isqrt(value);
+            builder.write(root);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for value in 0..MAX {
+            input.write::<U32Variable>(value);
+        }
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for value in 0..MAX {
+            let root = output.read::<U32Variable>();
+            assert!(root * root <= value && (root + 1) * (root + 1) > value);
+        }
+    }
+
+    #[test]
+    fn test_isqrt() {
+        let mut rng = rand::thread_rng();
+        let values = [
+            U256::zero(),
+            U256::MAX,
+            U256::from(u64::MAX),
+            U256(rng.gen::<[u64; 4]>()),
+            U256(rng.gen::<[u64; 4]>()) >> rng.gen_range(0..256),
+        ];
+
+        let mut builder = DefaultBuilder::new();
+        for _ in values.iter() {
+            let value = builder.read::<U256Variable>();
+            let root = builder.isqrt(value);
+            builder.write(root);
+        }
+        let value = builder.read::<U64Variable>();
+        let root = builder.isqrt(value);
+        builder.write(root);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for value in values.iter() {
+            input.write::<U256Variable>(*value);
+        }
+        input.write::<U64Variable>(u64::MAX);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for value in values {
+            assert_eq!(output.read::<U256Variable>(), value.integer_sqrt());
+        }
+        assert_eq!(output.read::<U64Variable>(), u32::MAX as u64);
+    }
+}
@@ -8,7 +8,8 @@ use crate::frontend::num::u32::gadgets::arithmetic_u32::U32Target;
 use crate::frontend::vars::{EvmVariable, SSZVariable, U256Variable, U32Variable};
 use crate::prelude::{
     Add, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder,
-    CircuitVariable, Div, LessThanOrEqual, Mul, One, PlonkParameters, Rem, Sub, Variable, Zero,
+    CircuitVariable, Div, LessThanOrEqual, Mul, Not, One, OverflowingAdd, OverflowingMul,
+    OverflowingSub, PlonkParameters, Rem, Sub, Variable, Zero,
 };
 use crate::{make_uint32_n, make_uint32_n_tests};
 
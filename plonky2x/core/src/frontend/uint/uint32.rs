@@ -10,6 +10,7 @@ use crate::frontend::builder::CircuitBuilder;
 use crate::frontend::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
 use crate::frontend::num::u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
 use crate::frontend::num::u32::gadgets::multiple_comparison::list_lte_circuit;
+use crate::frontend::uint::bitwise::impl_uint_bitwise;
 use crate::frontend::vars::{CircuitVariable, EvmVariable, Variable};
 use crate::prelude::*;
 
@@ -206,6 +207,70 @@ impl<L: PlonkParameters<D>, const D: usize> Sub<L, D> for U32Variable {
     }
 }
 
+impl<L: PlonkParameters<D>, const D: usize> OverflowingAdd<L, D> for U32Variable {
+    type Output = Self;
+
+    fn overflowing_add(
+        self,
+        rhs: U32Variable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self::Output, BoolVariable) {
+        let (limbs, overflow) = builder.overflowing_add_limbs(&[self], &[rhs]);
+        (limbs[0], overflow)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> OverflowingSub<L, D> for U32Variable {
+    type Output = Self;
+
+    fn overflowing_sub(
+        self,
+        rhs: U32Variable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self::Output, BoolVariable) {
+        let (limbs, overflow) = builder.overflowing_sub_limbs(&[self], &[rhs]);
+        (limbs[0], overflow)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> OverflowingMul<L, D> for U32Variable {
+    type Output = Self;
+
+    fn overflowing_mul(
+        self,
+        rhs: U32Variable,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> (Self::Output, BoolVariable) {
+        let (limbs, overflow) = builder.overflowing_mul_limbs(&[self], &[rhs]);
+        (limbs[0], overflow)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Not<L, D> for U32Variable {
+    type Output = Self;
+
+    fn not(self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let max = builder.constant::<Variable>(L::Field::from_canonical_u32(u32::MAX));
+        let result = builder.sub(max, self.variable);
+
+        // "u32::MAX - self" is within u32.
+        Self::from_variables_unsafe(&[result])
+    }
+}
+
+impl_uint_bitwise!(U32Variable);
+
+impl UintVariable for U32Variable {
+    fn limbs(&self) -> Vec<U32Variable> {
+        vec![*self]
+    }
+
+    fn from_limbs(limbs: &[U32Variable]) -> Self {
+        assert_eq!(limbs.len(), 1);
+        limbs[0]
+    }
+}
+
 impl U32Variable {
     pub fn to_u64<L: PlonkParameters<D>, const D: usize>(
         &self,
@@ -388,4 +453,115 @@ mod tests {
         let proof = circuit.data.prove(pw).unwrap();
         circuit.data.verify(proof).unwrap();
     }
+
+    #[test]
+    fn test_u32_bitwise() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let mut rng = rand::thread_rng();
+        let operand_a: u32 = rng.gen();
+        let operand_b: u32 = rng.gen();
+        let shift: usize = rng.gen_range(1..32);
+
+        let a = U32Variable::constant(&mut builder, operand_a);
+        let b = U32Variable::constant(&mut builder, operand_b);
+        let shift_var = U32Variable::constant(&mut builder, shift as u32);
+        let large_shift_var = U32Variable::constant(&mut builder, 35);
+
+        let results = [
+            (builder.and(a, b), operand_a & operand_b),
+            (builder.or(a, b), operand_a | operand_b),
+            (builder.xor(a, b), operand_a ^ operand_b),
+            (builder.not(a), !operand_a),
+            (builder.shl(a, shift), operand_a << shift),
+            (builder.shl(a, shift_var), operand_a << shift),
+            (builder.shr(a, shift), operand_a >> shift),
+            (builder.shr(a, shift_var), operand_a >> shift),
+            (
+                builder.rotate_left(a, shift),
+                operand_a.rotate_left(shift as u32),
+            ),
+            (
+                builder.rotate_left(a, shift_var),
+                operand_a.rotate_left(shift as u32),
+            ),
+            (
+                builder.rotate_right(a, shift),
+                operand_a.rotate_right(shift as u32),
+            ),
+            (
+                builder.rotate_right(a, shift_var),
+                operand_a.rotate_right(shift as u32),
+            ),
+            (builder.shl(a, large_shift_var), 0),
+            (builder.shr(a, large_shift_var), 0),
+            (
+                builder.rotate_right(a, large_shift_var),
+                operand_a.rotate_right(35),
+            ),
+        ];
+        for (result, expected_result) in results {
+            let expected_result_var = U32Variable::constant(&mut builder, expected_result);
+            builder.assert_is_equal(result.variable, expected_result_var.variable);
+        }
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    fn test_u32_overflowing() {
+        let mut builder = CircuitBuilder::<L, D>::new();
+
+        let mut rng = rand::thread_rng();
+        let operand_a: u32 = rng.gen();
+        let operand_b: u32 = rng.gen();
+
+        let a = U32Variable::constant(&mut builder, operand_a);
+        let b = U32Variable::constant(&mut builder, operand_b);
+        let max = U32Variable::constant(&mut builder, u32::MAX);
+        let one = builder.one::<U32Variable>();
+
+        let results = [
+            (
+                builder.overflowing_add(a, b),
+                operand_a.overflowing_add(operand_b),
+            ),
+            (
+                builder.overflowing_add(max, one),
+                u32::MAX.overflowing_add(1),
+            ),
+            (
+                builder.overflowing_sub(a, b),
+                operand_a.overflowing_sub(operand_b),
+            ),
+            (
+                builder.overflowing_sub(one, max),
+                1u32.overflowing_sub(u32::MAX),
+            ),
+            (
+                builder.overflowing_mul(a, b),
+                operand_a.overflowing_mul(operand_b),
+            ),
+            (
+                builder.overflowing_mul(max, one),
+                u32::MAX.overflowing_mul(1),
+            ),
+        ];
+        for ((result, overflow), (expected_result, expected_overflow)) in results {
+            let expected_result_var = U32Variable::constant(&mut builder, expected_result);
+            builder.assert_is_equal(result.variable, expected_result_var.variable);
+            let expected_overflow_var = builder.constant::<BoolVariable>(expected_overflow);
+            builder.assert_is_equal(overflow, expected_overflow_var);
+        }
+
+        let circuit = builder.build();
+        let pw = PartialWitness::new();
+
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
 }
@@ -2,6 +2,9 @@
 macro_rules! make_uint32_n {
     ($a:ident, $b:ty, $c:expr) => {
         /// An integer type encoded as little-endian u32 limbs.
+        ///
+        /// `add`, `sub` and `mul` wrap around the bit width. Use the `overflowing_*` methods to
+        /// get the overflow flag and the `checked_*` methods to constrain that there is none.
         #[derive(Debug, Clone, Copy)]
         pub struct $a {
             pub limbs: [U32Variable; $c]
@@ -107,7 +110,13 @@ macro_rules! make_uint32_n {
                     let zero = builder.constant::<ByteVariable>(0);
                     bytes.extend(vec![zero; 32 - bytes.len()]);
                 }
-                Bytes32Variable(BytesVariable::<32>(bytes.try_into().unwrap()))
+                // Values wider than one chunk are packed little-endian into 32-byte chunks, which
+                // are merkleized.
+                let chunks = bytes
+                    .chunks(32)
+                    .map(|chunk| Bytes32Variable(BytesVariable::<32>(chunk.try_into().unwrap())))
+                    .collect::<Vec<_>>();
+                builder.ssz_hash_leafs::<$crate::frontend::hash::hasher::CurtaSha256Hasher>(&chunks)
             }
         }
 
@@ -302,6 +311,69 @@ macro_rules! make_uint32_n {
             }
         }
 
+        impl<L: PlonkParameters<D>, const D: usize> OverflowingAdd<L, D> for $a {
+            type Output = Self;
+
+            fn overflowing_add(
+                self,
+                rhs: $a,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> (Self::Output, BoolVariable) {
+                let (limbs, overflow) = builder.overflowing_add_limbs(&self.limbs, &rhs.limbs);
+                (Self { limbs: limbs.try_into().unwrap() }, overflow)
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> OverflowingSub<L, D> for $a {
+            type Output = Self;
+
+            fn overflowing_sub(
+                self,
+                rhs: $a,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> (Self::Output, BoolVariable) {
+                let (limbs, overflow) = builder.overflowing_sub_limbs(&self.limbs, &rhs.limbs);
+                (Self { limbs: limbs.try_into().unwrap() }, overflow)
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> OverflowingMul<L, D> for $a {
+            type Output = Self;
+
+            fn overflowing_mul(
+                self,
+                rhs: $a,
+                builder: &mut CircuitBuilder<L, D>,
+            ) -> (Self::Output, BoolVariable) {
+                let (limbs, overflow) = builder.overflowing_mul_limbs(&self.limbs, &rhs.limbs);
+                (Self { limbs: limbs.try_into().unwrap() }, overflow)
+            }
+        }
+
+        impl<L: PlonkParameters<D>, const D: usize> Not<L, D> for $a {
+            type Output = Self;
+
+            fn not(self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+                Self {
+                    limbs: self.limbs.map(|limb| builder.not(limb)),
+                }
+            }
+        }
+
+        $crate::frontend::uint::bitwise::impl_uint_bitwise!($a);
+
+        impl $crate::frontend::uint::UintVariable for $a {
+            fn limbs(&self) -> Vec<U32Variable> {
+                self.limbs.to_vec()
+            }
+
+            fn from_limbs(limbs: &[U32Variable]) -> Self {
+                Self {
+                    limbs: limbs.try_into().unwrap(),
+                }
+            }
+        }
+
         impl<L: PlonkParameters<D>, const D: usize> LessThanOrEqual<L, D> for $a {
             fn lte(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> BoolVariable {
                 let mut lte_acc = builder.constant::<BoolVariable>(false);
@@ -427,6 +499,172 @@ macro_rules! make_uint32_n_tests {
                 circuit.data.verify(proof).unwrap();
             }
 
+            #[test]
+            fn test_uint_bitwise() {
+                let mut rng = OsRng;
+
+                let a = <$b as Uint<$c>>::from_u32_limbs(core::array::from_fn(|_| rng.gen()));
+                let b = <$b as Uint<$c>>::from_u32_limbs(core::array::from_fn(|_| rng.gen()));
+
+                let mut builder = CircuitBuilder::<L, D>::new();
+
+                let a_var = $a::constant(&mut builder, a);
+                let b_var = $a::constant(&mut builder, b);
+                let and = builder.and(a_var, b_var);
+                let or = builder.or(a_var, b_var);
+                let xor = builder.xor(a_var, b_var);
+                let not = builder.not(a_var);
+
+                let expected_and = $a::constant(&mut builder, a & b);
+                let expected_or = $a::constant(&mut builder, a | b);
+                let expected_xor = $a::constant(&mut builder, a ^ b);
+                let expected_not = $a::constant(&mut builder, !a);
+                builder.assert_is_equal(and, expected_and);
+                builder.assert_is_equal(or, expected_or);
+                builder.assert_is_equal(xor, expected_xor);
+                builder.assert_is_equal(not, expected_not);
+
+                let circuit = builder.build();
+                let pw = PartialWitness::new();
+
+                let proof = circuit.data.prove(pw).unwrap();
+                circuit.data.verify(proof).unwrap();
+            }
+
+            #[test]
+            fn test_uint_shifts() {
+                let mut rng = OsRng;
+                let bits = 32 * $c;
+
+                let a = <$b as Uint<$c>>::from_u32_limbs(core::array::from_fn(|_| rng.gen()));
+                let shift: usize = rng.gen_range(1..bits);
+                let rotate_left = |x: $b, r: usize| (x << r) | (x >> (bits - r));
+
+                let mut builder = CircuitBuilder::<L, D>::new();
+
+                let a_var = $a::constant(&mut builder, a);
+                let shift_var = builder.constant::<U32Variable>(shift as u32);
+                // Shifts by at least the bit width give zero, while rotations wrap around.
+                let large_shift_var = builder.constant::<U32Variable>((bits + 3) as u32);
+
+                let expected_shl = $a::constant(&mut builder, a << shift);
+                let shl = builder.shl(a_var, shift);
+                builder.assert_is_equal(shl, expected_shl);
+                let shl = builder.shl(a_var, shift_var);
+                builder.assert_is_equal(shl, expected_shl);
+
+                let expected_shr = $a::constant(&mut builder, a >> shift);
+                let shr = builder.shr(a_var, shift);
+                builder.assert_is_equal(shr, expected_shr);
+                let shr = builder.shr(a_var, shift_var);
+                builder.assert_is_equal(shr, expected_shr);
+
+                let expected_rotl = $a::constant(&mut builder, rotate_left(a, shift));
+                let rotl = builder.rotate_left(a_var, shift);
+                builder.assert_is_equal(rotl, expected_rotl);
+                let rotl = builder.rotate_left(a_var, shift_var);
+                builder.assert_is_equal(rotl, expected_rotl);
+
+                let expected_rotr = $a::constant(&mut builder, rotate_left(a, bits - shift));
+                let rotr = builder.rotate_right(a_var, shift);
+                builder.assert_is_equal(rotr, expected_rotr);
+                let rotr = builder.rotate_right(a_var, shift_var);
+                builder.assert_is_equal(rotr, expected_rotr);
+
+                let zero = builder.zero::<$a>();
+                let shl = builder.shl(a_var, large_shift_var);
+                builder.assert_is_equal(shl, zero);
+                let shr = builder.shr(a_var, large_shift_var);
+                builder.assert_is_equal(shr, zero);
+                let expected_rotl = $a::constant(&mut builder, rotate_left(a, 3));
+                let rotl = builder.rotate_left(a_var, large_shift_var);
+                builder.assert_is_equal(rotl, expected_rotl);
+
+                let circuit = builder.build();
+                let pw = PartialWitness::new();
+
+                let proof = circuit.data.prove(pw).unwrap();
+                circuit.data.verify(proof).unwrap();
+            }
+
+            #[test]
+            fn test_uint_overflowing() {
+                let mut rng = OsRng;
+
+                let max = <$b as Uint<$c>>::from_u32_limbs([u32::MAX; $c]);
+                let mut one_limbs = [0; $c];
+                one_limbs[0] = 1;
+                let one = <$b as Uint<$c>>::from_u32_limbs(one_limbs);
+                let a = <$b as Uint<$c>>::from_u32_limbs(core::array::from_fn(|_| rng.gen()));
+                let b = <$b as Uint<$c>>::from_u32_limbs(core::array::from_fn(|_| rng.gen()));
+
+                let mut builder = CircuitBuilder::<L, D>::new();
+
+                let max_var = $a::constant(&mut builder, max);
+                let one_var = $a::constant(&mut builder, one);
+                let a_var = $a::constant(&mut builder, a);
+                let b_var = $a::constant(&mut builder, b);
+
+                let test_cases = [
+                    (builder.overflowing_add(a_var, b_var), a.overflowing_add(b)),
+                    (
+                        builder.overflowing_add(max_var, one_var),
+                        max.overflowing_add(one),
+                    ),
+                    (
+                        builder.overflowing_add(max_var, max_var),
+                        max.overflowing_add(max),
+                    ),
+                    (builder.overflowing_sub(a_var, b_var), a.overflowing_sub(b)),
+                    (builder.overflowing_sub(b_var, a_var), b.overflowing_sub(a)),
+                    (
+                        builder.overflowing_sub(one_var, max_var),
+                        one.overflowing_sub(max),
+                    ),
+                    (builder.overflowing_mul(a_var, b_var), a.overflowing_mul(b)),
+                    (
+                        builder.overflowing_mul(max_var, one_var),
+                        max.overflowing_mul(one),
+                    ),
+                ];
+                for ((result, overflow), (expected_result, expected_overflow)) in test_cases {
+                    let expected_result = $a::constant(&mut builder, expected_result);
+                    builder.assert_is_equal(result, expected_result);
+                    let expected_overflow = builder.constant::<BoolVariable>(expected_overflow);
+                    builder.assert_is_equal(overflow, expected_overflow);
+                }
+
+                let wrapped = builder.wrapping_add(max_var, one_var);
+                let zero = builder.zero::<$a>();
+                builder.assert_is_equal(wrapped, zero);
+                let checked = builder.checked_sub(max_var, one_var);
+                let expected = $a::constant(&mut builder, max.overflowing_sub(one).0);
+                builder.assert_is_equal(checked, expected);
+
+                let circuit = builder.build();
+                let pw = PartialWitness::new();
+
+                let proof = circuit.data.prove(pw).unwrap();
+                circuit.data.verify(proof).unwrap();
+            }
+
+            #[test]
+            #[should_panic]
+            fn test_uint_checked_add_overflow() {
+                let max = <$b as Uint<$c>>::from_u32_limbs([u32::MAX; $c]);
+
+                let mut builder = CircuitBuilder::<L, D>::new();
+                let max_var = $a::constant(&mut builder, max);
+                let one_var = builder.one::<$a>();
+                builder.checked_add(max_var, one_var);
+
+                let circuit = builder.build();
+                let pw = PartialWitness::new();
+
+                let proof = circuit.data.prove(pw).unwrap();
+                circuit.data.verify(proof).unwrap();
+            }
+
             #[test]
             fn test_u256_mul() {
                 const D: usize = 2;
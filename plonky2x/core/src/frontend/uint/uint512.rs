@@ -0,0 +1,178 @@
+use array_macro::array;
+use ethers::types::U512;
+use plonky2::hash::hash_types::RichField;
+
+use super::uint256::U256Variable;
+use super::Uint;
+use crate::frontend::num::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::frontend::num::u32::gadgets::arithmetic_u32::U32Target;
+use crate::frontend::vars::{EvmVariable, SSZVariable, U32Variable};
+use crate::prelude::{
+    Add, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitBuilder,
+    CircuitVariable, Div, LessThanOrEqual, Mul, Not, One, OverflowingAdd, OverflowingMul,
+    OverflowingSub, PlonkParameters, Rem, Sub, Variable, Zero,
+};
+use crate::{make_uint32_n, make_uint32_n_tests};
+
+impl Uint<16> for U512 {
+    fn to_little_endian(&self, bytes: &mut [u8]) {
+        self.to_little_endian(bytes);
+    }
+
+    fn from_little_endian(slice: &[u8]) -> Self {
+        Self::from_little_endian(slice)
+    }
+
+    fn to_big_endian(&self, bytes: &mut [u8]) {
+        self.to_big_endian(bytes);
+    }
+
+    fn from_big_endian(slice: &[u8]) -> Self {
+        Self::from_big_endian(slice)
+    }
+
+    fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        self.overflowing_add(rhs)
+    }
+
+    fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        self.overflowing_sub(rhs)
+    }
+
+    fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        self.overflowing_mul(rhs)
+    }
+}
+
+make_uint32_n!(U512Variable, U512, 16);
+make_uint32_n_tests!(U512Variable, U512, 16);
+
+fn to_biguint(limbs: &[U32Variable]) -> BigUintTarget {
+    BigUintTarget {
+        limbs: limbs.iter().map(|x| U32Target::from(*x)).collect(),
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> CircuitBuilder<L, D> {
+    /// Computes the full 512-bit product of two `U256Variable`s, which cannot overflow.
+    pub fn mul_wide(&mut self, a: U256Variable, b: U256Variable) -> U512Variable {
+        let product = self
+            .api
+            .mul_biguint(&to_biguint(&a.limbs), &to_biguint(&b.limbs));
+        // The product has a final carry limb, which is zero since the product is below 2^512.
+        U512Variable {
+            limbs: array![i => product.limbs[i].into(); 16],
+        }
+    }
+
+    /// Divides a `U512Variable` by a `U256Variable`, returning the quotient and the remainder,
+    /// which is narrowed to a `U256Variable` since it is less than the divisor. As with `div`,
+    /// the divisor must be nonzero.
+    pub fn div_rem_wide(
+        &mut self,
+        a: U512Variable,
+        b: U256Variable,
+    ) -> (U512Variable, U256Variable) {
+        let (quotient, remainder) = self
+            .api
+            .div_rem_biguint(&to_biguint(&a.limbs), &to_biguint(&b.limbs));
+        (
+            U512Variable {
+                limbs: array![i => quotient.limbs[i].into(); 16],
+            },
+            U256Variable {
+                limbs: array![i => remainder.limbs[i].into(); 8],
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::{H256, U512};
+    use rand::Rng;
+    use sha2::{Digest, Sha256};
+
+    use crate::frontend::vars::SSZVariable;
+    use crate::prelude::*;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_u512_hash_tree_root() {
+        let value = U512(rand::thread_rng().gen::<[u64; 8]>());
+
+        // The 64 little-endian bytes form two chunks, which are hashed together.
+        let mut bytes = [0u8; 64];
+        value.to_little_endian(&mut bytes);
+        let expected = H256::from_slice(&Sha256::digest(bytes));
+
+        let mut builder = DefaultBuilder::new();
+        let value_var = builder.read::<U512Variable>();
+        let root = value_var.hash_tree_root(&mut builder);
+        builder.write(root);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U512Variable>(value);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<Bytes32Variable>(), expected);
+    }
+}
+
+#[cfg(test)]
+mod wide_tests {
+    use ethers::types::{U256, U512};
+    use rand::Rng;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_mul_wide_div_rem_wide() {
+        let mut rng = rand::thread_rng();
+        let a = U256(rng.gen::<[u64; 4]>());
+        let b = U256(rng.gen::<[u64; 4]>());
+        let c = U512(rng.gen::<[u64; 8]>());
+        let d = (U256(rng.gen::<[u64; 4]>()) >> rng.gen_range(0..256)).max(U256::one());
+
+        let mut builder = DefaultBuilder::new();
+        let a_var = builder.read::<U256Variable>();
+        let b_var = builder.read::<U256Variable>();
+        let c_var = builder.read::<U512Variable>();
+        let d_var = builder.read::<U256Variable>();
+        let product = builder.mul_wide(a_var, b_var);
+        let max = builder.constant::<U256Variable>(U256::MAX);
+        let max_squared = builder.mul_wide(max, max);
+        let (quotient, remainder) = builder.div_rem_wide(c_var, d_var);
+        builder.write(product);
+        builder.write(max_squared);
+        builder.write(quotient);
+        builder.write(remainder);
+
+        // Dividing the product by a factor gives back the other factor.
+        let zero = builder.zero::<U256Variable>();
+        let one = builder.one::<U256Variable>();
+        let a_is_zero = builder.is_equal(a_var, zero);
+        let divisor = builder.select(a_is_zero, one, a_var);
+        let dividend = builder.mul_wide(divisor, b_var);
+        let (b_again, remainder_again) = builder.div_rem_wide(dividend, divisor);
+        let b_wide = builder.mul_wide(b_var, one);
+        builder.assert_is_equal(b_again, b_wide);
+        builder.assert_is_equal(remainder_again, zero);
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        input.write::<U256Variable>(a);
+        input.write::<U256Variable>(b);
+        input.write::<U512Variable>(c);
+        input.write::<U256Variable>(d);
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        assert_eq!(output.read::<U512Variable>(), a.full_mul(b));
+        assert_eq!(output.read::<U512Variable>(), U256::MAX.full_mul(U256::MAX));
+        assert_eq!(output.read::<U512Variable>(), c / U512::from(d));
+        assert_eq!(U512::from(output.read::<U256Variable>()), c % U512::from(d));
+    }
+}
@@ -0,0 +1,183 @@
+use plonky2::field::types::{Field, PrimeField64};
+use plonky2::hash::hash_types::RichField;
+use serde::{Deserialize, Serialize};
+
+use super::{ByteVariable, CircuitVariable, Variable};
+use crate::backend::circuit::PlonkParameters;
+use crate::frontend::builder::lookup::ByteTable;
+use crate::frontend::builder::CircuitBuilder;
+use crate::frontend::ops::{BitAnd, BitOr, BitXor, Not, Zero};
+
+/// A variable in the circuit representing a byte value as a single field element. Range checks
+/// and bitwise operations go through lookup tables instead of a bit decomposition, which makes it
+/// much cheaper than `ByteVariable` when bytes are mostly moved, compared, xored or packed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CompactByteVariable(pub Variable);
+
+impl CircuitVariable for CompactByteVariable {
+    type ValueType<F: RichField> = u8;
+
+    fn init_unsafe<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> Self {
+        Self(Variable::init_unsafe(builder))
+    }
+
+    fn variables(&self) -> Vec<Variable> {
+        vec![self.0]
+    }
+
+    fn from_variables_unsafe(variables: &[Variable]) -> Self {
+        assert_eq!(variables.len(), 1);
+        Self(variables[0])
+    }
+
+    fn assert_is_valid<L: PlonkParameters<D>, const D: usize>(
+        &self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) {
+        builder.range_check(self.0, 8);
+    }
+
+    fn nb_elements() -> usize {
+        1
+    }
+
+    fn elements<F: RichField>(value: Self::ValueType<F>) -> Vec<F> {
+        vec![F::from_canonical_u8(value)]
+    }
+
+    fn from_elements<F: RichField>(elements: &[F]) -> Self::ValueType<F> {
+        assert_eq!(elements.len(), 1);
+        elements[0].to_canonical_u64() as u8
+    }
+}
+
+impl CompactByteVariable {
+    /// Converts a `ByteVariable` by summing its bits, which needs no range check.
+    pub fn from_byte_variable<L: PlonkParameters<D>, const D: usize>(
+        builder: &mut CircuitBuilder<L, D>,
+        byte: ByteVariable,
+    ) -> Self {
+        Self(byte.to_variable(builder))
+    }
+
+    /// Converts to a `ByteVariable` through a bit decomposition.
+    pub fn to_byte_variable<L: PlonkParameters<D>, const D: usize>(
+        self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> ByteVariable {
+        ByteVariable::from_variable(builder, self.0)
+    }
+
+    /// Splits the byte into its high and low nibbles.
+    pub fn to_nibbles<L: PlonkParameters<D>, const D: usize>(
+        self,
+        builder: &mut CircuitBuilder<L, D>,
+    ) -> [CompactByteVariable; 2] {
+        let high = builder.lookup(ByteTable::HighNibble, self.0 .0);
+        let low = builder
+            .api
+            .mul_const_add(-L::Field::from_canonical_u64(16), high, self.0 .0);
+        [
+            CompactByteVariable(Variable(high)),
+            CompactByteVariable(Variable(low)),
+        ]
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Not<L, D> for CompactByteVariable {
+    type Output = Self;
+
+    fn not(self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        let max = builder.api.constant(L::Field::from_canonical_u8(u8::MAX));
+        Self(Variable(builder.api.sub(max, self.0 .0)))
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> BitAnd<L, D> for CompactByteVariable {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        builder.lookup_byte_pair(ByteTable::And, self, rhs)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> BitOr<L, D> for CompactByteVariable {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        // a | b = a + b - (a & b).
+        let and = builder.lookup_byte_pair(ByteTable::And, self, rhs);
+        let sum = builder.api.add(self.0 .0, rhs.0 .0);
+        Self(Variable(builder.api.sub(sum, and.0 .0)))
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> BitXor<L, D> for CompactByteVariable {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self, builder: &mut CircuitBuilder<L, D>) -> Self::Output {
+        builder.lookup_byte_pair(ByteTable::Xor, self, rhs)
+    }
+}
+
+impl<L: PlonkParameters<D>, const D: usize> Zero<L, D> for CompactByteVariable {
+    fn zero(builder: &mut CircuitBuilder<L, D>) -> Self {
+        Self(builder.zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use crate::prelude::*;
+
+    #[test]
+    #[cfg_attr(feature = "ci", ignore)]
+    fn test_compact_byte_operations() {
+        let mut rng = rand::thread_rng();
+        let pairs = (0..16)
+            .map(|_| (rng.gen::<u8>(), rng.gen::<u8>()))
+            .collect::<Vec<_>>();
+
+        let mut builder = DefaultBuilder::new();
+        for _ in pairs.iter() {
+            let a = builder.read::<CompactByteVariable>();
+            let b = builder.read::<CompactByteVariable>();
+            let results = [
+                builder.not(a),
+                builder.and(a, b),
+                builder.or(a, b),
+                builder.xor(a, b),
+            ];
+            for result in results {
+                builder.write(result);
+            }
+            for nibble in a.to_nibbles(&mut builder) {
+                builder.write(nibble);
+            }
+
+            let byte = a.to_byte_variable(&mut builder);
+            let a_again = CompactByteVariable::from_byte_variable(&mut builder, byte);
+            builder.assert_is_equal(a_again, a);
+        }
+
+        let circuit = builder.build();
+        let mut input = circuit.input();
+        for (a, b) in pairs.iter() {
+            input.write::<CompactByteVariable>(*a);
+            input.write::<CompactByteVariable>(*b);
+        }
+        let (proof, mut output) = circuit.prove(&input);
+        circuit.verify(&proof, &input, &output);
+
+        for (a, b) in pairs {
+            let expected = [!a, a & b, a | b, a ^ b, a >> 4, a & 0xf];
+            for value in expected {
+                assert_eq!(output.read::<CompactByteVariable>(), value);
+            }
+        }
+    }
+}
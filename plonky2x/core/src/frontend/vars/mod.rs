@@ -4,6 +4,7 @@ mod byte;
 mod bytes;
 mod bytes32;
 mod collections;
+mod compact_byte;
 
 mod stream;
 mod variable;
@@ -14,6 +15,7 @@ pub use boolean::*;
 pub use byte::*;
 pub use bytes::*;
 pub use bytes32::*;
+pub use compact_byte::*;
 use itertools::Itertools;
 use plonky2::hash::hash_types::RichField;
 use plonky2::iop::target::Target;
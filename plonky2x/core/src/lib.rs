@@ -27,13 +27,21 @@ pub mod prelude {
     pub use crate::backend::circuit::config::{DefaultParameters, PlonkParameters};
     pub use crate::backend::circuit::{GateRegistry, HintRegistry};
     pub use crate::frontend::builder::{CircuitBuilder, DefaultBuilder};
+    pub use crate::frontend::int::int256::I256Variable;
+    pub use crate::frontend::int::int64::I64Variable;
+    pub use crate::frontend::num::nonnative::variable::NonNativeVariable;
     pub use crate::frontend::ops::*;
+    pub use crate::frontend::uint::biguint::BigUintVariable;
+    pub use crate::frontend::uint::fixed_point::{FixedPointVariable, Rounding};
     pub use crate::frontend::uint::uint128::U128Variable;
     pub use crate::frontend::uint::uint256::U256Variable;
+    pub use crate::frontend::uint::uint512::U512Variable;
     pub use crate::frontend::uint::uint64::U64Variable;
+    pub use crate::frontend::uint::UintVariable;
     pub use crate::frontend::vars::{
         ArrayVariable, BoolVariable, ByteVariable, Bytes32Variable, BytesVariable, CircuitVariable,
-        OutputVariableStream, U32Variable, ValueStream, Variable, VariableStream,
+        CompactByteVariable, OutputVariableStream, U32Variable, ValueStream, Variable,
+        VariableStream,
     };
     pub use crate::utils::{address, bytes, bytes32, hex};
 }